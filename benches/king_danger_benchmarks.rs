@@ -0,0 +1,82 @@
+//! Performance Benchmarks for the king-danger model
+//!
+//! Measures what the model adds to every evaluation, and the search cost with
+//! the model on and with the older enemy-attacker and enemy-hand terms.
+//!
+//! Metrics:
+//! - Danger inputs for both kings, from the start and with pieces in hand
+//! - Fixed-depth search with the model enabled and disabled
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, SamplingMode};
+use shogi_engine::{
+    bitboards::BitboardBoard,
+    evaluation::king_safety::{KingDangerConfig, KingDangerInputs},
+    search::SearchEngine,
+    types::Player,
+};
+use std::time::Duration;
+
+const POSITIONS: [(&str, &str); 2] = [
+    ("start", "lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b - 1"),
+    ("hands", "ln1g3nl/1r1sk1g2/p1pppp1pp/1p4p2/9/2P3P2/PP1PPP2P/2G2S1R1/LNS1KG1NL w BPsp 20"),
+];
+
+/// Benchmark the danger inputs of both kings
+fn benchmark_inputs(c: &mut Criterion) {
+    let mut group = c.benchmark_group("king_danger_inputs");
+    group.measurement_time(Duration::from_secs(3));
+
+    for (name, fen) in POSITIONS {
+        let (board, _, captured) = BitboardBoard::from_fen(fen).unwrap();
+        group.bench_with_input(BenchmarkId::new("both_kings", name), &board, |b, board| {
+            b.iter(|| {
+                for player in [Player::Black, Player::White] {
+                    let king_pos = board.find_king_position(player).unwrap();
+                    black_box(KingDangerInputs::compute(
+                        black_box(board),
+                        king_pos,
+                        player,
+                        black_box(&captured),
+                    ));
+                }
+            })
+        });
+    }
+
+    group.finish();
+}
+
+/// Benchmark a fixed-depth search with the model enabled and disabled
+fn benchmark_search(c: &mut Criterion) {
+    let mut group = c.benchmark_group("king_danger_search");
+    group.measurement_time(Duration::from_secs(4));
+    group.sample_size(10);
+    group.sampling_mode(SamplingMode::Flat);
+
+    let (root, player, captured) = BitboardBoard::from_fen(POSITIONS[1].1).unwrap();
+    for enabled in [false, true] {
+        let label = if enabled { "enabled" } else { "disabled" };
+        group.bench_with_input(BenchmarkId::new("search_at_depth_2", label), &root, |b, root| {
+            b.iter(|| {
+                let mut engine = SearchEngine::new(None, 16);
+                let config = KingDangerConfig { enabled, ..KingDangerConfig::default() };
+                engine.get_evaluator_mut().set_king_danger_config(config);
+                let mut board = root.clone();
+                black_box(engine.search_at_depth(
+                    black_box(&mut board),
+                    black_box(&captured),
+                    black_box(player),
+                    black_box(2),
+                    black_box(60_000u32),
+                    black_box(-10000),
+                    black_box(10000),
+                ))
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_inputs, benchmark_search);
+criterion_main!(benches);
@@ -0,0 +1,111 @@
+//! Performance Benchmarks for NNUE evaluation
+//!
+//! Compares the same work with the NNUE accumulator attached to the board and
+//! without it.
+//!
+//! Metrics:
+//! - Legal move generation, whose legality checks copy the board per move
+//! - Fixed-depth search, classical evaluation vs NNUE
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, SamplingMode};
+use shogi_engine::{
+    bitboards::BitboardBoard,
+    evaluation::nnue::NnueNetwork,
+    moves::MoveGenerator,
+    search::SearchEngine,
+    types::{CapturedPieces, Player},
+};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Small random network; the layer sizes only need to exercise the updates
+fn test_network() -> Arc<NnueNetwork> {
+    Arc::new(NnueNetwork::random(256, 32, 32, 7))
+}
+
+fn board_with_nnue(nnue: bool, captured: &CapturedPieces) -> BitboardBoard {
+    let mut board = BitboardBoard::new();
+    if nnue {
+        board.attach_nnue(test_network(), captured);
+    }
+    board
+}
+
+fn label(nnue: bool) -> &'static str {
+    if nnue {
+        "nnue"
+    } else {
+        "classical"
+    }
+}
+
+/// Benchmark legal move generation with and without an attached accumulator
+fn benchmark_legal_move_generation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("nnue_legal_moves");
+    group.measurement_time(Duration::from_secs(3));
+
+    let captured = CapturedPieces::new();
+    let generator = MoveGenerator::new();
+
+    for nnue in [false, true] {
+        let board = board_with_nnue(nnue, &captured);
+        group.bench_with_input(
+            BenchmarkId::new("generate_legal_moves", label(nnue)),
+            &board,
+            |b, board| {
+                b.iter(|| {
+                    black_box(generator.generate_legal_moves(
+                        black_box(board),
+                        black_box(Player::Black),
+                        black_box(&captured),
+                    ))
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
+/// Benchmark a fixed-depth search with the classical evaluation and with NNUE
+fn benchmark_search(c: &mut Criterion) {
+    let mut group = c.benchmark_group("nnue_search");
+    group.measurement_time(Duration::from_secs(4));
+    group.sample_size(10);
+    group.sampling_mode(SamplingMode::Flat);
+
+    let captured = CapturedPieces::new();
+
+    for nnue in [false, true] {
+        let root = board_with_nnue(nnue, &captured);
+        group.bench_with_input(
+            BenchmarkId::new("search_at_depth_2", label(nnue)),
+            &root,
+            |b, root| {
+                b.iter(|| {
+                    let mut engine = SearchEngine::new(None, 16);
+                    if nnue {
+                        let evaluator = engine.get_evaluator_mut();
+                        evaluator.set_nnue_network(Some(test_network()));
+                        evaluator.set_use_nnue(true);
+                    }
+                    let mut board = root.clone();
+                    black_box(engine.search_at_depth(
+                        black_box(&mut board),
+                        black_box(&captured),
+                        black_box(Player::Black),
+                        black_box(2),
+                        black_box(60_000u32),
+                        black_box(-10000),
+                        black_box(10000),
+                    ))
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_legal_move_generation, benchmark_search);
+criterion_main!(benches);
@@ -9,6 +9,7 @@ use shogi_engine::bitboards::BitboardBoard;
 use shogi_engine::search::move_ordering::{MoveOrdering, OrderingWeights};
 use shogi_engine::search::{ThreadSafeTranspositionTable, ThreadSafetyMode, TranspositionConfig};
 use shogi_engine::types::*;
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
 /// Generate test moves for PV move ordering benchmarks
@@ -89,10 +90,12 @@ fn benchmark_pv_move_retrieval_performance(c: &mut Criterion) {
 
     // Create transposition table and move orderer
     let config = TranspositionConfig::default();
-    let mut tt =
-        ThreadSafeTranspositionTable::with_thread_mode(config, ThreadSafetyMode::SingleThreaded);
+    let tt = Arc::new(RwLock::new(ThreadSafeTranspositionTable::with_thread_mode(
+        config,
+        ThreadSafetyMode::SingleThreaded,
+    )));
     let mut orderer = MoveOrdering::new();
-    orderer.set_transposition_table(&tt);
+    orderer.set_transposition_table(tt.clone());
 
     // Create test position
     let board = BitboardBoard::new();
@@ -154,10 +157,12 @@ fn benchmark_pv_move_storage_performance(c: &mut Criterion) {
 
     // Create transposition table and move orderer
     let config = TranspositionConfig::default();
-    let mut tt =
-        ThreadSafeTranspositionTable::with_thread_mode(config, ThreadSafetyMode::SingleThreaded);
+    let tt = Arc::new(RwLock::new(ThreadSafeTranspositionTable::with_thread_mode(
+        config,
+        ThreadSafetyMode::SingleThreaded,
+    )));
     let mut orderer = MoveOrdering::new();
-    orderer.set_transposition_table(&tt);
+    orderer.set_transposition_table(tt.clone());
 
     // Create test position
     let board = BitboardBoard::new();
@@ -193,10 +198,12 @@ fn benchmark_pv_move_cache_performance(c: &mut Criterion) {
 
     // Create transposition table and move orderer
     let config = TranspositionConfig::default();
-    let mut tt =
-        ThreadSafeTranspositionTable::with_thread_mode(config, ThreadSafetyMode::SingleThreaded);
+    let tt = Arc::new(RwLock::new(ThreadSafeTranspositionTable::with_thread_mode(
+        config,
+        ThreadSafetyMode::SingleThreaded,
+    )));
     let mut orderer = MoveOrdering::new();
-    orderer.set_transposition_table(&tt);
+    orderer.set_transposition_table(tt.clone());
 
     // Create test position
     let board = BitboardBoard::new();
@@ -232,7 +239,7 @@ fn benchmark_pv_move_cache_performance(c: &mut Criterion) {
     group.bench_function("pv_move_cache_misses", |b| {
         b.iter(|| {
             let mut new_orderer = MoveOrdering::new();
-            new_orderer.set_transposition_table(&tt);
+            new_orderer.set_transposition_table(tt.clone());
             criterion::black_box(new_orderer.get_pv_move(&board, &captured_pieces, player, depth));
         })
     });
@@ -247,10 +254,12 @@ fn benchmark_pv_move_ordering_performance(c: &mut Criterion) {
 
     // Create transposition table and move orderer
     let config = TranspositionConfig::default();
-    let mut tt =
-        ThreadSafeTranspositionTable::with_thread_mode(config, ThreadSafetyMode::SingleThreaded);
+    let tt = Arc::new(RwLock::new(ThreadSafeTranspositionTable::with_thread_mode(
+        config,
+        ThreadSafetyMode::SingleThreaded,
+    )));
     let mut orderer = MoveOrdering::new();
-    orderer.set_transposition_table(&tt);
+    orderer.set_transposition_table(tt.clone());
 
     // Create test position
     let board = BitboardBoard::new();
@@ -306,10 +315,12 @@ fn benchmark_pv_move_hit_rate_performance(c: &mut Criterion) {
 
     // Create transposition table and move orderer
     let config = TranspositionConfig::default();
-    let mut tt =
-        ThreadSafeTranspositionTable::with_thread_mode(config, ThreadSafetyMode::SingleThreaded);
+    let tt = Arc::new(RwLock::new(ThreadSafeTranspositionTable::with_thread_mode(
+        config,
+        ThreadSafetyMode::SingleThreaded,
+    )));
     let mut orderer = MoveOrdering::new();
-    orderer.set_transposition_table(&tt);
+    orderer.set_transposition_table(tt.clone());
 
     // Create test position
     let board = BitboardBoard::new();
@@ -352,10 +363,12 @@ fn benchmark_pv_move_memory_efficiency(c: &mut Criterion) {
 
     // Create transposition table and move orderer
     let config = TranspositionConfig::default();
-    let mut tt =
-        ThreadSafeTranspositionTable::with_thread_mode(config, ThreadSafetyMode::SingleThreaded);
+    let tt = Arc::new(RwLock::new(ThreadSafeTranspositionTable::with_thread_mode(
+        config,
+        ThreadSafetyMode::SingleThreaded,
+    )));
     let mut orderer = MoveOrdering::new();
-    orderer.set_transposition_table(&tt);
+    orderer.set_transposition_table(tt.clone());
 
     // Create test position
     let board = BitboardBoard::new();
@@ -400,8 +413,10 @@ fn benchmark_pv_move_configuration_performance(c: &mut Criterion) {
 
     // Create transposition table
     let config = TranspositionConfig::default();
-    let mut tt =
-        ThreadSafeTranspositionTable::with_thread_mode(config, ThreadSafetyMode::SingleThreaded);
+    let tt = Arc::new(RwLock::new(ThreadSafeTranspositionTable::with_thread_mode(
+        config,
+        ThreadSafetyMode::SingleThreaded,
+    )));
 
     // Create test position
     let board = BitboardBoard::new();
@@ -415,7 +430,7 @@ fn benchmark_pv_move_configuration_performance(c: &mut Criterion) {
     // Default PV move weight
     group.bench_function("default_pv_weight", |b| {
         let mut orderer = MoveOrdering::new();
-        orderer.set_transposition_table(&tt);
+        orderer.set_transposition_table(tt.clone());
 
         b.iter(|| {
             criterion::black_box(orderer.order_moves_with_pv(
@@ -432,7 +447,7 @@ fn benchmark_pv_move_configuration_performance(c: &mut Criterion) {
     group.bench_function("high_pv_weight", |b| {
         let custom_weights = OrderingWeights { pv_move_weight: 50000, ..Default::default() };
         let mut orderer = MoveOrdering::with_config(custom_weights);
-        orderer.set_transposition_table(&tt);
+        orderer.set_transposition_table(tt.clone());
 
         b.iter(|| {
             criterion::black_box(orderer.order_moves_with_pv(
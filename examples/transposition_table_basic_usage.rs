@@ -6,6 +6,7 @@
 use shogi_engine::bitboards::*;
 use shogi_engine::search::*;
 use shogi_engine::types::*;
+use std::sync::{Arc, RwLock};
 
 fn main() {
     println!("🎯 Basic Transposition Table Usage Example");
@@ -112,7 +113,7 @@ fn main() {
     // 8. Demonstrate move ordering integration
     println!("\n🎯 Demonstrating move ordering integration...");
     let mut move_orderer = TranspositionMoveOrderer::new();
-    move_orderer.set_transposition_table(&tt);
+    move_orderer.set_transposition_table(Arc::new(RwLock::new(tt)));
 
     // Create sample moves
     let sample_moves = vec![
@@ -6,6 +6,7 @@
 use shogi_engine::bitboards::*;
 use shogi_engine::search::*;
 use shogi_engine::types::*;
+use std::sync::{Arc, RwLock};
 
 fn build_entry(
     score: i32,
@@ -341,14 +342,14 @@ fn integration_best_practices() {
     let config = TranspositionConfig::performance_optimized();
 
     println!("   2. Create transposition table");
-    let tt = ThreadSafeTranspositionTable::new(config);
+    let tt = Arc::new(RwLock::new(ThreadSafeTranspositionTable::new(config)));
 
     println!("   3. Create hash calculator");
     let hash_calc = ShogiHashHandler::new(1000);
 
     println!("   4. Create move orderer and set TT reference");
     let mut move_orderer = TranspositionMoveOrderer::new();
-    move_orderer.set_transposition_table(&tt);
+    move_orderer.set_transposition_table(tt.clone());
 
     println!("   5. Create search engine with integrated components");
     let mut engine = SearchEngine::new(None, 64);
@@ -365,7 +366,7 @@ fn integration_best_practices() {
     println!("   Ensure position representation consistency");
 
     println!("\n3. Monitor integration performance:");
-    let stats = tt.get_stats();
+    let stats = tt.read().unwrap().get_stats();
     println!("   TT hit rate: {:.2}%", stats.hit_rate * 100.0);
     println!("   TT stores: {}", stats.stores);
     println!("   TT replacements: {}", stats.replacements);
@@ -6,6 +6,7 @@
 use shogi_engine::bitboards::*;
 use shogi_engine::search::*;
 use shogi_engine::types::*;
+use std::sync::{Arc, RwLock};
 
 fn build_entry(
     score: i32,
@@ -166,7 +167,7 @@ fn move_ordering_integration() {
     // Create move orderer with transposition table integration
     let mut orderer = TranspositionMoveOrderer::new();
     let tt = ThreadSafeTranspositionTable::new(TranspositionConfig::default());
-    orderer.set_transposition_table(&tt);
+    orderer.set_transposition_table(Arc::new(RwLock::new(tt)));
 
     // Create a board position
     let board = BitboardBoard::new();
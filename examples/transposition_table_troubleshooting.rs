@@ -6,6 +6,7 @@
 use shogi_engine::bitboards::*;
 use shogi_engine::search::*;
 use shogi_engine::types::*;
+use std::sync::{Arc, RwLock};
 
 fn make_entry(hash_key: u64, depth: u8, score: i32) -> TranspositionEntry {
     TranspositionEntry::new(
@@ -305,7 +306,7 @@ fn demonstrate_move_ordering_issues() {
     );
     tt.store(tt_entry);
 
-    orderer.set_transposition_table(&tt);
+    orderer.set_transposition_table(Arc::new(RwLock::new(tt)));
 
     println!("  After proper setup:");
     orderer.reset_stats();
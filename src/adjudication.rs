@@ -0,0 +1,432 @@
+//! Game adjudication
+//!
+//! Shared rules for ending engine games early or by rule: checkmate, a mate
+//! announced by the search, a tablebase result, resignation and draw score
+//! thresholds, the ply limit, sennichite (four-fold repetition, lost by the
+//! side giving perpetual check) and the 27-point entering-king declaration.
+//!
+//! The strength tester, the tuner's `ShogiEngineGamePlayer` and self-play
+//! tooling drive a [`GameAdjudicator`]: call [`GameAdjudicator::check_position`]
+//! for every position reached (including the start), and
+//! [`GameAdjudicator::check_score`] with the score the side to move's search
+//! reported before its move is played. The engine itself uses the resign and
+//! ply-limit settings of [`AdjudicationConfig`] for the `ResignValue`,
+//! `ResignMoves` and `MaxMovesToDraw` options.
+
+use crate::bitboards::BitboardBoard;
+use crate::moves::MoveGenerator;
+use crate::search::zobrist::{RepetitionState, ZobristHasher};
+use crate::tablebase::{MicroTablebase, TablebaseOutcome};
+use crate::types::core::{PieceType, Player};
+use crate::types::{CapturedPieces, GameResult};
+use std::collections::HashMap;
+
+/// Scores at or beyond this magnitude are mates found by the search
+pub const MATE_SCORE_THRESHOLD: i32 = 50_000;
+
+/// Thresholds and rules applied by a [`GameAdjudicator`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdjudicationConfig {
+    /// A side resigns once its score is at or below `-resign_score` for
+    /// `resign_moves` of its consecutive moves; 0 never resigns
+    pub resign_score: i32,
+    pub resign_moves: u32,
+    /// The game is drawn once this many plies have been played; 0 has no
+    /// limit
+    pub max_plies: u32,
+    /// Drawn once both sides report `|score| <= draw_score` for `draw_plies`
+    /// consecutive plies after `draw_min_ply`; 0 plies disables it
+    pub draw_score: i32,
+    pub draw_plies: u32,
+    pub draw_min_ply: u32,
+    /// End the game when the search reports a mate score
+    pub adjudicate_mate: bool,
+    /// End the game on a micro-tablebase win, loss or draw
+    pub use_tablebase: bool,
+    /// Let a side that meets the entering-king conditions declare a win
+    pub allow_declaration: bool,
+}
+
+impl Default for AdjudicationConfig {
+    fn default() -> Self {
+        Self {
+            resign_score: 3000,
+            resign_moves: 3,
+            max_plies: 320,
+            draw_score: 0,
+            draw_plies: 0,
+            draw_min_ply: 0,
+            adjudicate_mate: true,
+            use_tablebase: true,
+            allow_declaration: true,
+        }
+    }
+}
+
+impl AdjudicationConfig {
+    /// Only the rules of the game: checkmate, sennichite, declaration and no
+    /// ply limit
+    pub fn rules_only() -> Self {
+        Self {
+            resign_score: 0,
+            max_plies: 0,
+            adjudicate_mate: false,
+            use_tablebase: false,
+            ..Self::default()
+        }
+    }
+
+    /// Whether a side with `streak` consecutive moves scored at `score`
+    /// should resign
+    pub fn should_resign(&self, score: i32, streak: u32) -> bool {
+        self.resign_score > 0 && score <= -self.resign_score && streak >= self.resign_moves.max(1)
+    }
+}
+
+/// Why a game ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AdjudicationReason {
+    /// The side to move is in check with no legal move
+    Checkmate,
+    /// The side to move has no legal move without being in check (a loss in
+    /// shogi)
+    NoLegalMoves,
+    /// The search found a forced mate
+    MateScore,
+    Tablebase,
+    Resignation,
+    /// Both sides' scores stayed within the draw band
+    ScoreDraw,
+    MaxPlies,
+    /// Four-fold repetition
+    Sennichite,
+    /// Four-fold repetition with one side checking throughout
+    PerpetualCheck,
+    /// Entering-king declaration
+    Declaration,
+}
+
+impl AdjudicationReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Checkmate => "checkmate",
+            Self::NoLegalMoves => "no legal moves",
+            Self::MateScore => "mate score",
+            Self::Tablebase => "tablebase",
+            Self::Resignation => "resignation",
+            Self::ScoreDraw => "score draw",
+            Self::MaxPlies => "max plies",
+            Self::Sennichite => "sennichite",
+            Self::PerpetualCheck => "perpetual check",
+            Self::Declaration => "declaration",
+        }
+    }
+}
+
+/// The end of a game
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Adjudication {
+    /// `None` for a draw
+    pub winner: Option<Player>,
+    pub reason: AdjudicationReason,
+}
+
+impl Adjudication {
+    fn win(winner: Player, reason: AdjudicationReason) -> Self {
+        Self { winner: Some(winner), reason }
+    }
+
+    fn draw(reason: AdjudicationReason) -> Self {
+        Self { winner: None, reason }
+    }
+
+    /// Result from Black's side, as `ShogiEngine::is_game_over` reports it
+    pub fn game_result(&self) -> GameResult {
+        match self.winner {
+            Some(Player::Black) => GameResult::Win,
+            Some(Player::White) => GameResult::Loss,
+            None => GameResult::Draw,
+        }
+    }
+}
+
+/// A position the game passed through
+struct HistoryEntry {
+    hash: u64,
+    to_move: Player,
+    in_check: bool,
+}
+
+/// Tracks one game and decides when it is over
+pub struct GameAdjudicator {
+    config: AdjudicationConfig,
+    hasher: ZobristHasher,
+    tablebase: MicroTablebase,
+    history: Vec<HistoryEntry>,
+    occurrences: HashMap<u64, u32>,
+    /// Consecutive losing scores of Black and White
+    losing_streaks: [u32; 2],
+    drawish_plies: u32,
+}
+
+impl GameAdjudicator {
+    pub fn new(config: AdjudicationConfig) -> Self {
+        Self {
+            config,
+            hasher: ZobristHasher::new(),
+            tablebase: MicroTablebase::new(),
+            history: Vec::new(),
+            occurrences: HashMap::new(),
+            losing_streaks: [0; 2],
+            drawish_plies: 0,
+        }
+    }
+
+    pub fn config(&self) -> &AdjudicationConfig {
+        &self.config
+    }
+
+    /// Plies played since the first recorded position
+    pub fn plies(&self) -> u32 {
+        self.history.len().saturating_sub(1) as u32
+    }
+
+    /// Record a position reached with `to_move` to play and adjudicate it
+    pub fn check_position(
+        &mut self,
+        board: &BitboardBoard,
+        captured_pieces: &CapturedPieces,
+        to_move: Player,
+    ) -> Option<Adjudication> {
+        let hash =
+            self.hasher
+                .hash_position(board, to_move, captured_pieces, RepetitionState::None);
+        let in_check = board.is_king_in_check(to_move, captured_pieces);
+        self.history.push(HistoryEntry { hash, to_move, in_check });
+        let occurrences = self.occurrences.entry(hash).or_insert(0);
+        *occurrences += 1;
+
+        if *occurrences >= 4 {
+            return Some(self.sennichite(hash));
+        }
+        if MoveGenerator::new()
+            .generate_legal_moves(board, to_move, captured_pieces)
+            .is_empty()
+        {
+            let reason = if in_check {
+                AdjudicationReason::Checkmate
+            } else {
+                AdjudicationReason::NoLegalMoves
+            };
+            return Some(Adjudication::win(to_move.opposite(), reason));
+        }
+        if self.config.allow_declaration && can_declare_win(board, captured_pieces, to_move) {
+            return Some(Adjudication::win(to_move, AdjudicationReason::Declaration));
+        }
+        if self.config.use_tablebase {
+            let outcome = self.tablebase.probe(board, to_move, captured_pieces).map(|r| r.outcome);
+            match outcome {
+                Some(TablebaseOutcome::Win) => {
+                    return Some(Adjudication::win(to_move, AdjudicationReason::Tablebase));
+                }
+                Some(TablebaseOutcome::Loss) => {
+                    return Some(Adjudication::win(
+                        to_move.opposite(),
+                        AdjudicationReason::Tablebase,
+                    ));
+                }
+                Some(TablebaseOutcome::Draw) => {
+                    return Some(Adjudication::draw(AdjudicationReason::Tablebase));
+                }
+                Some(TablebaseOutcome::Unknown) | None => {}
+            }
+        }
+        if self.config.max_plies > 0 && self.plies() >= self.config.max_plies {
+            return Some(Adjudication::draw(AdjudicationReason::MaxPlies));
+        }
+        None
+    }
+
+    /// Adjudicate from the score `mover`'s search gave the current position,
+    /// from `mover`'s side
+    pub fn check_score(&mut self, mover: Player, score: i32) -> Option<Adjudication> {
+        if self.config.adjudicate_mate && score.abs() >= MATE_SCORE_THRESHOLD {
+            let winner = if score > 0 { mover } else { mover.opposite() };
+            return Some(Adjudication::win(winner, AdjudicationReason::MateScore));
+        }
+
+        let streak = &mut self.losing_streaks[mover as usize];
+        *streak = if score <= -self.config.resign_score { *streak + 1 } else { 0 };
+        if self.config.should_resign(score, *streak) {
+            return Some(Adjudication::win(mover.opposite(), AdjudicationReason::Resignation));
+        }
+
+        if self.config.draw_plies > 0 && self.plies() >= self.config.draw_min_ply {
+            self.drawish_plies =
+                if score.abs() <= self.config.draw_score { self.drawish_plies + 1 } else { 0 };
+            if self.drawish_plies >= self.config.draw_plies {
+                return Some(Adjudication::draw(AdjudicationReason::ScoreDraw));
+            }
+        }
+        None
+    }
+
+    /// Four-fold repetition of `hash`: a draw, unless one side gave check in
+    /// every position of the cycle, in which case that side loses
+    fn sennichite(&self, hash: u64) -> Adjudication {
+        let start = self.history.iter().position(|entry| entry.hash == hash).unwrap_or(0);
+        let cycle = &self.history[start..];
+        for checked in [Player::Black, Player::White] {
+            let mut positions = cycle.iter().filter(|entry| entry.to_move == checked).peekable();
+            if positions.peek().is_some() && positions.all(|entry| entry.in_check) {
+                return Adjudication::win(checked, AdjudicationReason::PerpetualCheck);
+            }
+        }
+        Adjudication::draw(AdjudicationReason::Sennichite)
+    }
+}
+
+/// Whether `player`, to move, may declare a win by the 27-point rule: king in
+/// the enemy camp and not in check, at least ten other pieces there, and 28
+/// points (Black) or 27 points (White) counting those pieces and the hand,
+/// with rooks and bishops worth 5 and everything else 1
+pub fn can_declare_win(
+    board: &BitboardBoard,
+    captured_pieces: &CapturedPieces,
+    player: Player,
+) -> bool {
+    let in_enemy_camp = |row: u8| match player {
+        Player::Black => row <= 2,
+        Player::White => row >= 6,
+    };
+    let points = |piece_type: PieceType| match piece_type.unpromoted_version().unwrap_or(piece_type)
+    {
+        PieceType::Rook | PieceType::Bishop => 5,
+        _ => 1,
+    };
+
+    let Some(king) = board.find_king_position(player) else {
+        return false;
+    };
+    if !in_enemy_camp(king.row) || board.is_king_in_check(player, captured_pieces) {
+        return false;
+    }
+
+    let (mut count, mut total) = (0, 0);
+    for (position, piece) in board.iter_pieces() {
+        if piece.player == player
+            && piece.piece_type != PieceType::King
+            && in_enemy_camp(position.row)
+        {
+            count += 1;
+            total += points(piece.piece_type);
+        }
+    }
+    let hand = match player {
+        Player::Black => &captured_pieces.black,
+        Player::White => &captured_pieces.white,
+    };
+    total += hand.iter().map(|&piece_type| points(piece_type)).sum::<u32>();
+
+    let required = if player == Player::Black { 28 } else { 27 };
+    count >= 10 && total >= required
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(sfen: &str) -> (BitboardBoard, Player, CapturedPieces) {
+        BitboardBoard::from_fen(sfen).unwrap()
+    }
+
+    #[test]
+    fn test_checkmate_and_max_plies() {
+        let mut adjudicator = GameAdjudicator::new(AdjudicationConfig::rules_only());
+        let (board, player, captured) = position("4k4/4G4/4G4/9/9/9/9/9/4K4 w - 1");
+        assert_eq!(
+            adjudicator.check_position(&board, &captured, player),
+            Some(Adjudication::win(Player::Black, AdjudicationReason::Checkmate))
+        );
+
+        let config = AdjudicationConfig { max_plies: 1, ..AdjudicationConfig::rules_only() };
+        let mut adjudicator = GameAdjudicator::new(config);
+        let (board, player, captured) = position(crate::STARTPOS_SFEN);
+        assert_eq!(adjudicator.check_position(&board, &captured, player), None);
+        let result = adjudicator.check_position(&board, &captured, player.opposite());
+        assert_eq!(result.map(|a| a.reason), Some(AdjudicationReason::MaxPlies));
+        assert_eq!(result.unwrap().game_result(), GameResult::Draw);
+    }
+
+    #[test]
+    fn test_sennichite_and_perpetual_check() {
+        // Plain repetition: the same two positions alternate
+        let mut adjudicator = GameAdjudicator::new(AdjudicationConfig::rules_only());
+        let (board, _, captured) = position(crate::STARTPOS_SFEN);
+        let mut result = None;
+        for ply in 0..8 {
+            let to_move = if ply % 2 == 0 { Player::Black } else { Player::White };
+            result = result.or(adjudicator.check_position(&board, &captured, to_move));
+        }
+        assert_eq!(result, Some(Adjudication::draw(AdjudicationReason::Sennichite)));
+
+        // White is in check every time it is to move: Black loses
+        let mut adjudicator = GameAdjudicator::new(AdjudicationConfig::rules_only());
+        let (checked, _, checked_hand) = position("4k4/9/4R4/9/9/9/9/9/4K4 w - 1");
+        let (quiet, _, quiet_hand) = position("4k4/9/3R5/9/9/9/9/9/4K4 b - 1");
+        let mut result = None;
+        for _ in 0..4 {
+            result = result.or(adjudicator.check_position(&quiet, &quiet_hand, Player::Black));
+            result = result.or(adjudicator.check_position(&checked, &checked_hand, Player::White));
+        }
+        assert_eq!(
+            result,
+            Some(Adjudication::win(Player::White, AdjudicationReason::PerpetualCheck))
+        );
+    }
+
+    #[test]
+    fn test_score_adjudication() {
+        let config = AdjudicationConfig {
+            resign_score: 1000,
+            resign_moves: 2,
+            draw_score: 10,
+            draw_plies: 4,
+            ..AdjudicationConfig::rules_only()
+        };
+        let mut adjudicator = GameAdjudicator::new(config.clone());
+        assert_eq!(adjudicator.check_score(Player::White, -1500), None);
+        assert_eq!(adjudicator.check_score(Player::Black, 1500), None);
+        assert_eq!(
+            adjudicator.check_score(Player::White, -1200),
+            Some(Adjudication::win(Player::Black, AdjudicationReason::Resignation))
+        );
+
+        let mut adjudicator = GameAdjudicator::new(config);
+        for score in [5, -3, 0] {
+            assert_eq!(adjudicator.check_score(Player::Black, score), None);
+        }
+        assert_eq!(
+            adjudicator.check_score(Player::White, 8),
+            Some(Adjudication::draw(AdjudicationReason::ScoreDraw))
+        );
+
+        let mut adjudicator = GameAdjudicator::new(AdjudicationConfig::default());
+        assert_eq!(
+            adjudicator.check_score(Player::Black, -100_000),
+            Some(Adjudication::win(Player::White, AdjudicationReason::MateScore))
+        );
+    }
+
+    #[test]
+    fn test_declaration() {
+        // Black king on 5b with ten pieces in camp: 2 rooks, 2 bishops (20)
+        // plus 6 golds and silvers (6) = 26, and two pawns in hand
+        let (board, _, captured) = position("RRBBK4/GGGSSS3/9/9/9/9/9/9/4k4 b 2P 1");
+        assert!(can_declare_win(&board, &captured, Player::Black));
+        let (board, _, captured) = position("RRBBK4/GGGSSS3/9/9/9/9/9/9/4k4 b P 1");
+        assert!(!can_declare_win(&board, &captured, Player::Black), "27 points is one short");
+        let (board, _, captured) = position(crate::STARTPOS_SFEN);
+        assert!(!can_declare_win(&board, &captured, Player::Black));
+    }
+}
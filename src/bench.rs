@@ -0,0 +1,267 @@
+//! Deterministic benchmark
+//!
+//! Searches the standard benchmark positions to a fixed depth and reports
+//! total nodes, time and NPS. Each position is searched by a fresh search
+//! engine with no opening book, tablebase or time limit, so with one thread
+//! the node count depends only on the search and evaluation code. It serves as
+//! a functional signature: a change meant to be search-neutral must leave the
+//! node count unchanged.
+//!
+//! Run it with `usi-engine bench [depth] [threads] [hash]` or the USI `bench`
+//! command, which takes the same arguments.
+
+use crate::bitboards::BitboardBoard;
+use crate::search::search_engine::{IterativeDeepening, SearchEngine};
+use crate::search::{NullReporter, ParallelSearchConfig};
+use crate::types::search::ParallelOptions;
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Position set searched by the benchmark
+const STANDARD_POSITIONS_JSON: &str =
+    include_str!("../resources/benchmark_positions/standard_positions.json");
+
+pub const DEFAULT_BENCH_DEPTH: u8 = 3;
+pub const DEFAULT_BENCH_THREADS: usize = 1;
+pub const DEFAULT_BENCH_HASH_MB: usize = 16;
+
+/// Time limit large enough that every search ends on depth, never on time
+const BENCH_TIME_LIMIT_MS: u32 = 24 * 60 * 60 * 1000;
+
+/// A benchmark position
+#[derive(Debug, Clone, Deserialize)]
+pub struct BenchPosition {
+    pub name: String,
+    pub fen: String,
+}
+
+/// The built-in benchmark position set
+pub fn standard_positions() -> Vec<BenchPosition> {
+    serde_json::from_str(STANDARD_POSITIONS_JSON)
+        .expect("embedded benchmark positions are valid JSON")
+}
+
+/// Benchmark settings: `bench [depth] [threads] [hash]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchConfig {
+    pub depth: u8,
+    pub threads: usize,
+    pub hash_mb: usize,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            depth: DEFAULT_BENCH_DEPTH,
+            threads: DEFAULT_BENCH_THREADS,
+            hash_mb: DEFAULT_BENCH_HASH_MB,
+        }
+    }
+}
+
+impl BenchConfig {
+    /// Parse the positional `[depth] [threads] [hash]` arguments; missing
+    /// arguments keep their defaults
+    pub fn from_args(args: &[&str]) -> Result<Self, String> {
+        let mut config = Self::default();
+        if args.len() > 3 {
+            return Err(format!(
+                "Too many bench arguments '{}' (expected [depth] [threads] [hash])",
+                args.join(" ")
+            ));
+        }
+        if let Some(depth) = args.first() {
+            config.depth = match depth.parse::<u8>() {
+                Ok(d) if (1..=32).contains(&d) => d,
+                _ => return Err(format!("Invalid bench depth '{}' (expected 1-32)", depth)),
+            };
+        }
+        if let Some(threads) = args.get(1) {
+            config.threads = match threads.parse::<usize>() {
+                Ok(t) if (1..=32).contains(&t) => t,
+                _ => return Err(format!("Invalid bench threads '{}' (expected 1-32)", threads)),
+            };
+        }
+        if let Some(hash) = args.get(2) {
+            config.hash_mb = match hash.parse::<usize>() {
+                Ok(h) if (1..=1024).contains(&h) => h,
+                _ => return Err(format!("Invalid bench hash '{}' (expected 1-1024)", hash)),
+            };
+        }
+        Ok(config)
+    }
+}
+
+/// Result of searching one benchmark position
+#[derive(Debug, Clone)]
+pub struct BenchPositionResult {
+    pub name: String,
+    pub best_move: Option<String>,
+    pub score: i32,
+    pub nodes: u64,
+    pub time_ms: u64,
+}
+
+/// Result of a full benchmark run
+#[derive(Debug, Clone)]
+pub struct BenchReport {
+    pub config: BenchConfig,
+    pub positions: Vec<BenchPositionResult>,
+}
+
+impl BenchReport {
+    /// Total nodes over all positions (the functional signature)
+    pub fn total_nodes(&self) -> u64 {
+        self.positions.iter().map(|p| p.nodes).sum()
+    }
+
+    /// Total search time in milliseconds
+    pub fn total_time_ms(&self) -> u64 {
+        self.positions.iter().map(|p| p.time_ms).sum()
+    }
+
+    /// Nodes per second over the whole run
+    pub fn nps(&self) -> u64 {
+        self.total_nodes() * 1000 / self.total_time_ms().max(1)
+    }
+
+    /// Human-readable report, one line per position followed by the totals
+    pub fn to_lines(&self) -> Vec<String> {
+        let count = self.positions.len();
+        let mut lines: Vec<String> = self
+            .positions
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                format!(
+                    "Position {}/{} ({}): bestmove {} score {} nodes {} time {}",
+                    i + 1,
+                    count,
+                    p.name,
+                    p.best_move.as_deref().unwrap_or("none"),
+                    p.score,
+                    p.nodes,
+                    p.time_ms
+                )
+            })
+            .collect();
+        lines.push("===========================".to_string());
+        lines.push(format!(
+            "Depth {} threads {} hash {}MB",
+            self.config.depth, self.config.threads, self.config.hash_mb
+        ));
+        lines.push(format!("Total time (ms) : {}", self.total_time_ms()));
+        lines.push(format!("Nodes searched  : {}", self.total_nodes()));
+        lines.push(format!("Nodes/second    : {}", self.nps()));
+        if self.config.threads > 1 {
+            lines.push("Note: node counts are only reproducible with 1 thread".to_string());
+        }
+        lines
+    }
+}
+
+/// Search every benchmark position to the configured depth
+pub fn run_bench(config: &BenchConfig) -> BenchReport {
+    let positions = standard_positions()
+        .iter()
+        .map(|position| bench_position(position, config))
+        .collect();
+    BenchReport { config: *config, positions }
+}
+
+fn bench_position(position: &BenchPosition, config: &BenchConfig) -> BenchPositionResult {
+    let Ok((board, player, captured_pieces)) = BitboardBoard::from_fen(&position.fen) else {
+        crate::utils::telemetry::debug_log(&format!(
+            "[BENCH] Skipping '{}': invalid position '{}'",
+            position.name, position.fen
+        ));
+        return BenchPositionResult {
+            name: position.name.clone(),
+            best_move: None,
+            score: 0,
+            nodes: 0,
+            time_ms: 0,
+        };
+    };
+
+    let mut parallel_options = ParallelOptions::default();
+    parallel_options.enable_parallel = config.threads > 1;
+    let parallel_config =
+        ParallelSearchConfig::from_parallel_options(&parallel_options, config.threads);
+
+    let mut search_engine = SearchEngine::new(None, config.hash_mb);
+    let mut searcher = IterativeDeepening::new_with_threads(
+        config.depth,
+        BENCH_TIME_LIMIT_MS,
+        None,
+        config.threads,
+        parallel_config,
+    )
+    .with_reporter(Arc::new(NullReporter));
+
+    // Every iteration resets the per-search counter, so count the nodes of
+    // the whole search from the engine's running total
+    let start_nodes = search_engine.total_nodes_searched();
+    let start = Instant::now();
+    let result = searcher.search(&mut search_engine, &board, &captured_pieces, player);
+    let time_ms = start.elapsed().as_millis() as u64;
+    let nodes = search_engine.total_nodes_searched() - start_nodes;
+
+    BenchPositionResult {
+        name: position.name.clone(),
+        best_move: result.as_ref().map(|(mv, _)| mv.to_usi_string()),
+        score: result.map_or(0, |(_, score)| score),
+        nodes,
+        time_ms,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_standard_positions_parse() {
+        let positions = standard_positions();
+        assert!(!positions.is_empty());
+        for position in &positions {
+            assert!(BitboardBoard::from_fen(&position.fen).is_ok(), "{}", position.name);
+        }
+    }
+
+    #[test]
+    fn test_bench_args() {
+        assert_eq!(BenchConfig::from_args(&[]).unwrap(), BenchConfig::default());
+        let config = BenchConfig::from_args(&["3", "2", "64"]).unwrap();
+        assert_eq!(config, BenchConfig { depth: 3, threads: 2, hash_mb: 64 });
+        assert!(BenchConfig::from_args(&["0"]).is_err());
+        assert!(BenchConfig::from_args(&["3", "x"]).is_err());
+        assert!(BenchConfig::from_args(&["3", "1", "16", "extra"]).is_err());
+    }
+
+    #[test]
+    fn test_bench_node_count_is_reproducible() {
+        let config = BenchConfig { depth: 2, threads: 1, hash_mb: 1 };
+        let first = run_bench(&config);
+        let second = run_bench(&config);
+        assert!(first.total_nodes() > 0);
+        assert_eq!(first.total_nodes(), second.total_nodes());
+    }
+
+    #[test]
+    fn test_bench_counts_every_iteration() {
+        let position = &standard_positions()[0];
+        let config = BenchConfig { depth: 2, threads: 1, hash_mb: 1 };
+        let result = bench_position(position, &config);
+
+        // The per-search counter only holds the last iteration's nodes
+        let (board, player, captured_pieces) = BitboardBoard::from_fen(&position.fen).unwrap();
+        let mut search_engine = SearchEngine::new(None, config.hash_mb);
+        IterativeDeepening::new(config.depth, BENCH_TIME_LIMIT_MS, None)
+            .with_reporter(Arc::new(NullReporter))
+            .search(&mut search_engine, &board, &captured_pieces, player);
+        assert_eq!(result.nodes, search_engine.total_nodes_searched());
+        assert!(result.nodes > search_engine.get_nodes_searched());
+    }
+}
@@ -3,7 +3,17 @@
 //! A command-line tool for analyzing shogi positions with detailed evaluation
 
 use clap::{Parser, Subcommand};
-use shogi_engine::ShogiEngine;
+use shogi_engine::bitboards::BitboardBoard;
+use shogi_engine::evaluation::castles::CastleRecognizer;
+use shogi_engine::evaluation::opening_strategy::classify_opening;
+use shogi_engine::search::search_engine::{IterativeDeepening, SearchEngine};
+use shogi_engine::search::search_reporter::format_usi_score;
+use shogi_engine::search::tree_recorder::{NodeOutcome, SearchTree, TreeRecorderConfig};
+use shogi_engine::search::{NullReporter, ScoreBound, UsiReporter};
+use shogi_engine::types::board::CapturedPieces;
+use shogi_engine::types::core::Player;
+use shogi_engine::{EngineSession, SearchLimits};
+use std::sync::Arc;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -55,6 +65,57 @@ enum Commands {
         #[arg(short, long, default_value_t = 6)]
         depth: u8,
     },
+    /// Search a position and record the explored tree to a file
+    RecordTree {
+        /// SFEN string (defaults to the starting position)
+        #[arg(short, long)]
+        sfen: Option<String>,
+        /// Search depth
+        #[arg(short, long, default_value_t = 4)]
+        depth: u8,
+        /// Output file for the recorded tree
+        #[arg(short, long)]
+        output: String,
+        /// Maximum number of recorded nodes
+        #[arg(long, default_value_t = 200_000)]
+        max_nodes: usize,
+        /// Maximum recorded ply from the root
+        #[arg(long, default_value_t = 16)]
+        max_ply: u8,
+    },
+    /// Explain the static evaluation of a position term by term
+    Eval {
+        /// "startpos" or an SFEN string
+        #[arg(default_value = "startpos")]
+        position: String,
+        /// Print the breakdown as JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+    /// Classify the opening strategy (Static Rook / Ranging Rook family)
+    Strategy {
+        /// "startpos" or an SFEN string
+        #[arg(default_value = "startpos")]
+        position: String,
+    },
+    /// Navigate a recorded search tree
+    InspectTree {
+        /// Recorded tree file
+        file: String,
+        /// Space-separated USI moves from the root to the node to inspect
+        #[arg(short, long, default_value = "")]
+        path: String,
+        /// Iteration depth to inspect (defaults to the last one)
+        #[arg(short, long)]
+        iteration: Option<u8>,
+        /// Only list children with this outcome (e.g. pruned, tt-cutoff),
+        /// or "reduced" / "researched" for LMR decisions
+        #[arg(short, long)]
+        filter: Option<String>,
+        /// Explain why this child move was pruned, reduced or cut off
+        #[arg(short, long)]
+        explain: Option<String>,
+    },
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -67,95 +128,127 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     match &cli.command {
         Some(Commands::Startpos { depth }) => {
-            analyze_starting_position(*depth, cli.verbose)?;
+            analyze_position("startpos", *depth, cli.time_limit, cli.verbose)?;
         }
         Some(Commands::Sfen { sfen, depth }) => {
-            analyze_sfen_position(sfen, *depth, cli.verbose)?;
+            analyze_position(sfen, *depth, cli.time_limit, cli.verbose)?;
         }
         Some(Commands::Compare { positions, depth }) => {
-            compare_positions(positions, *depth, cli.verbose)?;
+            compare_positions(positions, *depth, cli.time_limit)?;
+        }
+        Some(Commands::RecordTree { sfen, depth, output, max_nodes, max_ply }) => {
+            let config = TreeRecorderConfig { max_nodes: *max_nodes, max_ply: *max_ply };
+            record_tree(sfen.as_deref(), *depth, cli.time_limit, output, config)?;
+        }
+        Some(Commands::Eval { position, json }) => {
+            explain_evaluation(position, *json)?;
+        }
+        Some(Commands::Strategy { position }) => {
+            classify_strategy(position)?;
+        }
+        Some(Commands::InspectTree { file, path, iteration, filter, explain }) => {
+            inspect_tree(file, path, *iteration, filter.as_deref(), explain.as_deref())?;
         }
         None => {
-            if let Some(ref position) = cli.position {
-                analyze_sfen_position(position, cli.depth, cli.verbose)?;
-            } else {
-                analyze_starting_position(cli.depth, cli.verbose)?;
-            }
+            let position = cli.position.as_deref().unwrap_or("startpos");
+            analyze_position(position, cli.depth, cli.time_limit, cli.verbose)?;
         }
     }
 
     Ok(())
 }
 
-fn analyze_starting_position(depth: u8, verbose: bool) -> Result<(), Box<dyn std::error::Error>> {
-    let mut engine = ShogiEngine::new();
-
+/// Search a position ("startpos" or an SFEN) and print the result
+fn analyze_position(
+    position: &str,
+    depth: u8,
+    time_limit: u32,
+    verbose: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let session = EngineSession::new();
     if verbose {
-        println!("Analyzing starting position...");
+        session.set_reporter(Arc::new(UsiReporter));
+        println!("Analyzing position: {}", position);
         println!("Search depth: {}", depth);
+    } else {
+        session.set_reporter(Arc::new(NullReporter));
     }
+    session.set_position(position, &[])?;
 
-    let start_time = std::time::Instant::now();
-
-    if let Some(best_move) = engine.get_best_move(depth, 5000, None) {
-        let elapsed = start_time.elapsed();
+    let outcome = session.search(SearchLimits::depth(depth).with_movetime(time_limit));
+    let Some(ref best_move) = outcome.best_move else {
+        println!("No legal moves found!");
+        return Ok(());
+    };
+    let pv: Vec<String> = outcome.pv.iter().map(|mv| mv.to_usi_string()).collect();
 
-        println!("\n=== Analysis Results ===");
-        println!("Best move: {}", best_move.to_usi_string());
-        println!("Search time: {:.2}ms", elapsed.as_millis());
-        println!("Depth: {}", depth);
+    println!("\n=== Analysis Results ===");
+    println!("Best move: {}", best_move.to_usi_string());
+    println!("Score: {}{}", format_usi_score(outcome.score), bound_suffix(outcome.bound));
+    println!("PV: {}", pv.join(" "));
+    println!("Depth: {} (seldepth {})", outcome.depth, outcome.seldepth);
+    println!("Nodes: {}", outcome.nodes);
+    println!("Search time: {}ms", outcome.time_ms);
 
-        if verbose {
+    if verbose {
+        session.with_engine(|engine| {
             println!("\n=== Engine Information ===");
             println!("Debug mode: {}", engine.is_debug_enabled());
             println!("Opening book loaded: {}", engine.is_opening_book_loaded());
-        }
-    } else {
-        println!("No legal moves found!");
+        });
     }
 
     Ok(())
 }
 
-fn analyze_sfen_position(
-    sfen: &str,
-    depth: u8,
-    verbose: bool,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let mut engine = ShogiEngine::new();
-
-    if verbose {
-        println!("Analyzing position: {}", sfen);
-        println!("Search depth: {}", depth);
-        println!("Note: SFEN parsing not yet implemented - using starting position");
+/// Print the evaluator's term-by-term breakdown of a position
+fn explain_evaluation(position: &str, json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let session = EngineSession::new();
+    session.set_position(position, &[])?;
+    let explanation = session.with_engine(|engine| engine.explain_evaluation())?;
+    if json {
+        println!("{}", explanation.to_json()?);
+    } else {
+        println!("{}", explanation.to_table());
     }
+    Ok(())
+}
 
-    let start_time = std::time::Instant::now();
-
-    if let Some(best_move) = engine.get_best_move(depth, 5000, None) {
-        let elapsed = start_time.elapsed();
-
-        println!("\n=== Analysis Results ===");
-        println!("Best move: {}", best_move.to_usi_string());
-        println!("Search time: {:.2}ms", elapsed.as_millis());
-        println!("Depth: {}", depth);
-
-        if verbose {
-            println!("\n=== Engine Information ===");
-            println!("Debug mode: {}", engine.is_debug_enabled());
-            println!("Opening book loaded: {}", engine.is_opening_book_loaded());
-        }
+/// Print the recognised opening strategy and each side's rook and castle
+fn classify_strategy(position: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let (board, _, captured_pieces) = if position == "startpos" {
+        (BitboardBoard::new(), Player::Black, CapturedPieces::new())
     } else {
-        println!("No legal moves found!");
-    }
+        BitboardBoard::from_fen(position).map_err(|e| e.to_string())?
+    };
+    let classification =
+        classify_opening(&board, &captured_pieces).with_castles(&board, &CastleRecognizer::new());
 
+    println!("Strategy: {}", classification);
+    println!("Template: {}", classification.family.template().canonical_name);
+    for player in [Player::Black, Player::White] {
+        let side = classification.side(player);
+        let rook = match (side.rook, side.rook_file) {
+            (Some(placement), Some(file)) => format!("{} (own file {})", placement.name(), file),
+            _ => "none".to_string(),
+        };
+        println!("{:?}: rook {}, castle {}", player, rook, side.castle.unwrap_or("none"));
+    }
     Ok(())
 }
 
+fn bound_suffix(bound: ScoreBound) -> &'static str {
+    match bound {
+        ScoreBound::Exact => "",
+        ScoreBound::Lower => " (lower bound)",
+        ScoreBound::Upper => " (upper bound)",
+    }
+}
+
 fn compare_positions(
     positions: &[String],
     depth: u8,
-    _verbose: bool,
+    time_limit: u32,
 ) -> Result<(), Box<dyn std::error::Error>> {
     if positions.is_empty() {
         return Err("No positions provided for comparison".into());
@@ -168,8 +261,98 @@ fn compare_positions(
         println!("\nPosition {}: {}", i + 1, sfen);
         println!("{}", "-".repeat(50));
 
-        analyze_sfen_position(sfen, depth, false)?;
+        analyze_position(sfen, depth, time_limit, false)?;
     }
 
     Ok(())
 }
+
+fn record_tree(
+    sfen: Option<&str>,
+    depth: u8,
+    time_limit: u32,
+    output: &str,
+    config: TreeRecorderConfig,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (board, player, captured_pieces) = match sfen {
+        Some(sfen) => BitboardBoard::from_fen(sfen).map_err(|e| e.to_string())?,
+        None => (BitboardBoard::new(), Player::Black, CapturedPieces::new()),
+    };
+
+    let mut engine = SearchEngine::new(None, 16);
+    engine.enable_tree_recording(config);
+    let mut searcher = IterativeDeepening::new(depth, time_limit, None);
+    let result = searcher.search(&mut engine, &board, &captured_pieces, player);
+
+    let tree = engine.take_recorded_tree().ok_or("tree recording was not enabled")?;
+    tree.save(output)?;
+
+    if let Some((best_move, score)) = result {
+        println!("Best move: {} (score {})", best_move.to_usi_string(), score);
+    }
+    println!("Recorded {} nodes to {}", tree.nodes.len(), output);
+    Ok(())
+}
+
+fn inspect_tree(
+    file: &str,
+    path: &str,
+    iteration: Option<u8>,
+    filter: Option<&str>,
+    explain: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tree = SearchTree::load(file)?;
+    let pass = match iteration {
+        Some(iteration) => tree.last_pass_of_iteration(iteration),
+        None => tree.last_pass(),
+    }
+    .ok_or("no recorded nodes for the requested iteration")?;
+
+    let moves: Vec<&str> = path.split_whitespace().collect();
+    let node = if moves.is_empty() {
+        None
+    } else {
+        Some(
+            tree.find_path(pass, &moves)
+                .ok_or_else(|| format!("path not found: {}", path))?,
+        )
+    };
+
+    println!("Root: {}", tree.root_sfen);
+    let children = match node {
+        Some(id) => {
+            println!("Node: {}", tree.summary(id));
+            tree.children(id).to_vec()
+        }
+        None => tree.roots(pass),
+    };
+
+    if let Some(mv) = explain {
+        let child = children
+            .iter()
+            .rev()
+            .find(|c| tree.nodes[**c as usize].mv == mv)
+            .ok_or_else(|| format!("move {} was not recorded at this node", mv))?;
+        println!("{}", tree.explain(*child));
+        return Ok(());
+    }
+
+    let outcome_filter = match filter {
+        None | Some("reduced") | Some("researched") => None,
+        Some(name) => {
+            Some(NodeOutcome::from_str(name).ok_or_else(|| format!("unknown filter: {}", name))?)
+        }
+    };
+    for child in children {
+        let record = &tree.nodes[child as usize];
+        let keep = match filter {
+            Some("reduced") => record.reduction > 0,
+            Some("researched") => record.researched,
+            _ => outcome_filter.map_or(true, |outcome| record.outcome == outcome),
+        };
+        if keep {
+            println!("  {}", tree.summary(child));
+        }
+    }
+    Ok(())
+}
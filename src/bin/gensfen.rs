@@ -0,0 +1,173 @@
+//! Self-Play Training Data Generator
+//!
+//! Plays engine self-play games from randomized openings and writes every
+//! recorded quiet position, with its search score, ply and game result, as
+//! packed records for the tuner and the NNUE trainer.
+
+use clap::Parser;
+use shogi_engine::adjudication::AdjudicationConfig;
+use shogi_engine::tuning::packed::PACKED_RECORD_SIZE;
+use shogi_engine::tuning::selfplay::{SelfPlayConfig, SelfPlayGenerator};
+use shogi_engine::SearchLimits;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter};
+use std::path::PathBuf;
+use std::time::Instant;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+#[command(name = "gensfen")]
+#[command(about = "Generate packed training positions from self-play games")]
+struct Cli {
+    /// Output file for packed records
+    #[arg(short, long, value_name = "FILE", default_value = "selfplay.bin")]
+    output: PathBuf,
+
+    /// Append to the output file instead of replacing it
+    #[arg(long)]
+    append: bool,
+
+    /// Number of games to play
+    #[arg(short, long, default_value_t = 100)]
+    games: usize,
+
+    /// Worker threads (default: all cores)
+    #[arg(short, long)]
+    threads: Option<usize>,
+
+    /// Search depth of every move
+    #[arg(short, long)]
+    depth: Option<u8>,
+
+    /// Node limit of every move
+    #[arg(short, long)]
+    nodes: Option<u64>,
+
+    /// Opening book: one USI position per line ("startpos moves ...")
+    #[arg(short, long, value_name = "FILE")]
+    book: Option<PathBuf>,
+
+    /// Fewest random plies after the book line
+    #[arg(long, default_value_t = 8)]
+    random_min: u32,
+
+    /// Most random plies after the book line
+    #[arg(long, default_value_t = 16)]
+    random_max: u32,
+
+    /// Positions before this ply are not recorded
+    #[arg(long, default_value_t = 16)]
+    min_ply: u32,
+
+    /// Positions scored at or beyond this many centipawns are not recorded
+    #[arg(long, default_value_t = 3000)]
+    eval_limit: i32,
+
+    /// Games are drawn after this many plies (0 = no limit)
+    #[arg(long, default_value_t = 320)]
+    max_plies: u32,
+
+    /// Resign once a side's score is at or below minus this value (0 = never)
+    #[arg(long, default_value_t = 3000)]
+    resign_score: i32,
+
+    /// Seed of the openings and searches
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+
+    /// NNUE network to play with
+    #[arg(long, value_name = "FILE")]
+    eval_file: Option<PathBuf>,
+
+    /// Enable verbose output
+    #[arg(short, long)]
+    verbose: bool,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+
+    let limits = match (cli.depth, cli.nodes) {
+        (None, None) => SearchLimits::depth(6),
+        (depth, nodes) => SearchLimits { depth, nodes, ..SearchLimits::default() },
+    };
+    let book_lines = match &cli.book {
+        Some(path) => read_book(path)?,
+        None => Vec::new(),
+    };
+
+    let defaults = SelfPlayConfig::default();
+    let config = SelfPlayConfig {
+        games: cli.games,
+        threads: cli.threads.unwrap_or(defaults.threads),
+        limits,
+        book_lines,
+        random_plies_min: cli.random_min,
+        random_plies_max: cli.random_max,
+        adjudication: AdjudicationConfig {
+            max_plies: cli.max_plies,
+            resign_score: cli.resign_score,
+            ..AdjudicationConfig::default()
+        },
+        min_ply: cli.min_ply,
+        eval_limit: cli.eval_limit,
+        seed: cli.seed,
+        eval_file: cli.eval_file,
+    };
+
+    if cli.verbose {
+        println!("Generating {} games on {} threads", config.games, config.threads);
+        println!("Limits: depth {:?}, nodes {:?}", config.limits.depth, config.limits.nodes);
+        println!("Book lines: {}", config.book_lines.len());
+        println!("Output: {:?}", cli.output);
+    }
+
+    let file = if cli.append {
+        OpenOptions::new().create(true).append(true).open(&cli.output)?
+    } else {
+        File::create(&cli.output)?
+    };
+    let mut writer = BufWriter::new(file);
+
+    let start = Instant::now();
+    let verbose = cli.verbose;
+    let stats = SelfPlayGenerator::new(config).run(&mut writer, |stats| {
+        if verbose {
+            println!(
+                "game {:>6}  positions {:>9}  +{} ={} -{}  ({:.1}s)",
+                stats.games,
+                stats.positions,
+                stats.black_wins,
+                stats.draws,
+                stats.white_wins,
+                start.elapsed().as_secs_f64()
+            );
+        }
+    })?;
+
+    println!(
+        "Wrote {} positions ({} bytes) from {} games in {:.1}s",
+        stats.positions,
+        stats.positions * PACKED_RECORD_SIZE,
+        stats.games,
+        start.elapsed().as_secs_f64()
+    );
+    println!(
+        "Black wins {}, white wins {}, draws {}",
+        stats.black_wins, stats.white_wins, stats.draws
+    );
+    Ok(())
+}
+
+/// Non-empty, non-comment lines of a book file
+fn read_book(path: &PathBuf) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let mut lines = Vec::new();
+    for line in BufReader::new(File::open(path)?).lines() {
+        let line = line?;
+        let line = line.trim();
+        if !line.is_empty() && !line.starts_with('#') {
+            lines.push(line.strip_prefix("position ").unwrap_or(line).to_string());
+        }
+    }
+    Ok(lines)
+}
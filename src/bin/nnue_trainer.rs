@@ -0,0 +1,133 @@
+//! NNUE Trainer
+//!
+//! Trains an NNUE network on the CPU from packed self-play records written by
+//! `gensfen`, and exports it in the format the engine loads with the
+//! `EvalFile` option.
+
+use clap::Parser;
+use shogi_engine::tuning::nnue_trainer::{load_samples, NnueTrainer, NnueTrainerConfig};
+use std::path::PathBuf;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+#[command(name = "nnue-trainer")]
+#[command(about = "Train an NNUE network from packed self-play data")]
+struct Cli {
+    /// Packed training files (repeat for several)
+    #[arg(short, long, value_name = "FILE", required = true)]
+    data: Vec<PathBuf>,
+
+    /// Output network file
+    #[arg(short, long, value_name = "FILE", default_value = "trained.nnue")]
+    output: PathBuf,
+
+    /// Feature transformer width
+    #[arg(long, default_value_t = 128)]
+    l1: usize,
+
+    /// First hidden layer size
+    #[arg(long, default_value_t = 32)]
+    l2: usize,
+
+    /// Second hidden layer size
+    #[arg(long, default_value_t = 32)]
+    l3: usize,
+
+    /// Epochs to train
+    #[arg(short, long, default_value_t = 10)]
+    epochs: usize,
+
+    /// Positions per optimizer step
+    #[arg(short, long, default_value_t = 16384)]
+    batch_size: usize,
+
+    /// Adam learning rate
+    #[arg(long, default_value_t = 0.001)]
+    learning_rate: f64,
+
+    /// Weight of the search score in the target (0 = result only)
+    #[arg(long, default_value_t = 0.75)]
+    lambda: f64,
+
+    /// Centipawns per unit of the win-probability sigmoid
+    #[arg(long, default_value_t = 400.0)]
+    score_scale: f64,
+
+    /// Share of positions held out for validation
+    #[arg(long, default_value_t = 0.05)]
+    validation_split: f64,
+
+    /// Directory for checkpoints
+    #[arg(long, value_name = "DIR")]
+    checkpoint_dir: Option<String>,
+
+    /// Epochs between checkpoints
+    #[arg(long, default_value_t = 1)]
+    checkpoint_every: usize,
+
+    /// Checkpoint (`checkpoint_iter_<n>.json`) to resume from
+    #[arg(long, value_name = "FILE")]
+    resume: Option<PathBuf>,
+
+    /// Worker threads (default: all cores)
+    #[arg(short, long)]
+    threads: Option<usize>,
+
+    /// Seed of the initial weights and shuffling
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+
+    /// Enable verbose output
+    #[arg(short, long)]
+    verbose: bool,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+
+    if let Some(threads) = cli.threads {
+        rayon::ThreadPoolBuilder::new().num_threads(threads).build_global()?;
+    }
+
+    let config = NnueTrainerConfig {
+        layer_sizes: (cli.l1, cli.l2, cli.l3),
+        epochs: cli.epochs,
+        batch_size: cli.batch_size,
+        learning_rate: cli.learning_rate,
+        lambda: cli.lambda,
+        score_scale: cli.score_scale,
+        validation_split: cli.validation_split,
+        checkpoint_path: cli.checkpoint_dir,
+        checkpoint_frequency: cli.checkpoint_every,
+        seed: cli.seed,
+        ..NnueTrainerConfig::default()
+    };
+
+    let samples = load_samples(&cli.data)?;
+    println!("Loaded {} positions from {} files", samples.len(), cli.data.len());
+
+    let mut trainer = match &cli.resume {
+        Some(path) => NnueTrainer::resume(config, path)?,
+        None => NnueTrainer::new(config),
+    };
+    if cli.verbose {
+        println!("Network {}x{}x{}, starting at epoch {}", cli.l1, cli.l2, cli.l3, trainer.epoch());
+    }
+
+    trainer.train(&samples, |report| {
+        let validation = report
+            .validation_loss
+            .map_or_else(String::new, |loss| format!("  validation {loss:.6}"));
+        println!(
+            "epoch {:>4}  training {:.6}{}  ({:.1}s)",
+            report.epoch,
+            report.training_loss,
+            validation,
+            report.elapsed.as_secs_f64()
+        );
+    })?;
+
+    trainer.export(&cli.output)?;
+    println!("Saved network to {:?}", cli.output);
+    Ok(())
+}
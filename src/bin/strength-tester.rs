@@ -4,9 +4,12 @@
 
 use clap::{Parser, Subcommand};
 use shogi_engine::{
-    types::{GameResult, Move},
-    ShogiEngine,
+    adjudication::{Adjudication, AdjudicationConfig, GameAdjudicator},
+    search::{NullReporter, SkillLevel},
+    types::{GameResult, Player},
+    EngineSession, SearchLimits,
 };
+use std::sync::Arc;
 // use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
@@ -55,6 +58,19 @@ enum Commands {
         #[arg(short, long, default_value_t = 20)]
         games: u32,
     },
+    /// Play skill levels against each other and check the measured Elo
+    /// gaps against the calibration curve; fails when a gap is off
+    Calibrate {
+        /// Comma-separated skill levels, weakest first
+        #[arg(long, default_value = "0,5,10,15,20")]
+        levels: String,
+        /// Games per pair of adjacent levels (colours alternate)
+        #[arg(short, long, default_value_t = 20)]
+        games: u32,
+        /// Elo the expected gap may lie outside the measured 95% range
+        #[arg(long, default_value_t = 50)]
+        tolerance: i64,
+    },
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -72,6 +88,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         Some(Commands::Elo { opponent, games }) => {
             estimate_elo(opponent, *games, cli.depth, cli.verbose)?;
         }
+        Some(Commands::Calibrate { levels, games, tolerance }) => {
+            calibrate_skill(levels, *games, *tolerance, cli.depth, cli.verbose)?;
+        }
         None => {
             test_strength(&cli.time_control, cli.games, cli.depth, cli.verbose)?;
         }
@@ -126,69 +145,61 @@ fn test_strength(
 }
 
 fn play_game_direct(depth: u8, verbose: bool) -> Result<GameResult, Box<dyn std::error::Error>> {
-    let mut engine = ShogiEngine::new();
-    let mut move_count = 0;
-    let mut consecutive_passes = 0;
-    let mut last_move: Option<Move> = None;
+    let session = EngineSession::new();
+    session.set_reporter(Arc::new(NullReporter));
+    let mut adjudicator = GameAdjudicator::new(AdjudicationConfig::default());
 
     // Play a game by having engine play against itself
     loop {
-        // Check if game is over
-        if let Some(result) = engine.is_game_over() {
-            if verbose {
-                println!("Game over: {:?}", result);
-            }
+        if let Some(result) = adjudicate_position(&session, &mut adjudicator, verbose) {
             return Ok(result);
         }
 
-        // Get engine's best move
-        if let Some(best_move) = engine.get_best_move(depth, 2000, None) {
-            if verbose && move_count < 10 {
-                println!("Move {}: {}", move_count + 1, best_move.to_usi_string());
-            }
-
-            // Check if same move repeated (possible infinite loop)
-            if last_move.as_ref().map(|m| m.to_usi_string()) == Some(best_move.to_usi_string()) {
-                consecutive_passes += 1;
-                if consecutive_passes >= 3 {
-                    if verbose {
-                        println!("Consecutive repeated moves detected, ending game as draw");
-                    }
-                    return Ok(GameResult::Draw);
-                }
-            } else {
-                consecutive_passes = 0;
-            }
-
-            last_move = Some(best_move.clone());
-
-            // Apply the move to the engine
-            if !engine.apply_move(&best_move) {
-                if verbose {
-                    println!("Failed to apply move: {}, ending game", best_move.to_usi_string());
-                }
-                return Ok(GameResult::Draw);
-            }
-
-            move_count += 1;
+        let mover = session.with_engine(|engine| engine.current_player());
+        let outcome = session.search(SearchLimits::depth(depth).with_movetime(2000));
+        let Some(best_move) = outcome.best_move else {
+            return Ok(session.is_game_over().unwrap_or(GameResult::Draw));
+        };
+        if verbose && adjudicator.plies() < 10 {
+            println!("Move {}: {}", adjudicator.plies() + 1, best_move.to_usi_string());
+        }
+        if let Some(adjudication) = adjudicator.check_score(mover, outcome.score) {
+            return Ok(report_adjudication(adjudication, verbose));
+        }
 
-            // Safety limit to avoid infinite games
-            if move_count >= 200 {
-                if verbose {
-                    println!("Maximum move limit reached, ending as draw");
-                }
-                return Ok(GameResult::Draw);
-            }
-        } else {
-            // No legal moves - game ended
-            if let Some(result) = engine.is_game_over() {
-                return Ok(result);
+        if let Err(e) = session.push_move(&best_move.to_usi_string()) {
+            if verbose {
+                println!("Failed to apply move: {}, ending game", e);
             }
             return Ok(GameResult::Draw);
         }
     }
 }
 
+/// Adjudicate the session's current position
+fn adjudicate_position(
+    session: &EngineSession,
+    adjudicator: &mut GameAdjudicator,
+    verbose: bool,
+) -> Option<GameResult> {
+    session
+        .with_engine(|engine| {
+            adjudicator.check_position(
+                engine.board(),
+                engine.captured_pieces(),
+                engine.current_player(),
+            )
+        })
+        .map(|adjudication| report_adjudication(adjudication, verbose))
+}
+
+fn report_adjudication(adjudication: Adjudication, verbose: bool) -> GameResult {
+    if verbose {
+        println!("Game over by {}: {:?}", adjudication.reason.as_str(), adjudication.winner);
+    }
+    adjudication.game_result()
+}
+
 fn compare_configs(
     config1: &str,
     config2: &str,
@@ -224,3 +235,118 @@ fn estimate_elo(
     println!("\nELO estimation not yet implemented.");
     Ok(())
 }
+
+fn calibrate_skill(
+    levels: &str,
+    games: u32,
+    tolerance: i64,
+    depth: u8,
+    verbose: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let levels: Vec<u8> =
+        levels.split(',').map(|level| level.trim().parse()).collect::<Result<_, _>>()?;
+    if levels.len() < 2 {
+        return Err("calibration needs at least two levels".into());
+    }
+
+    println!("\n=== Skill Level Calibration ===");
+    println!(
+        "{:<10} {:>8} {:>8} {:>12} {:>10} {:>4}",
+        "Levels", "Expected", "Measured", "95% range", "W-D-L", ""
+    );
+    let mut off = Vec::new();
+    for pair in levels.windows(2) {
+        let (weaker, stronger) = (SkillLevel::from_level(pair[0]), SkillLevel::from_level(pair[1]));
+        let (mut wins, mut draws, mut losses) = (0, 0, 0);
+        for game in 0..games {
+            // Alternate colours; the result is counted for the stronger level
+            let stronger_is_black = game % 2 == 0;
+            let (black, white) =
+                if stronger_is_black { (stronger, weaker) } else { (weaker, stronger) };
+            match (play_skill_game(black, white, depth, verbose)?, stronger_is_black) {
+                (GameResult::Draw, _) => draws += 1,
+                (GameResult::Win, true) | (GameResult::Loss, false) => wins += 1,
+                _ => losses += 1,
+            }
+        }
+        let expected = stronger.expected_elo() as i64 - weaker.expected_elo() as i64;
+        let score = (wins as f64 + 0.5 * draws as f64) / games.max(1) as f64;
+        let (low, high) = elo_range(wins, draws, losses);
+        let within = expected >= low - tolerance && expected <= high + tolerance;
+        let levels = format!("{}-{}", pair[0], pair[1]);
+        println!(
+            "{:<10} {:>8} {:>8} {:>12} {:>10} {:>4}",
+            levels,
+            expected,
+            elo_difference(score, games),
+            format!("{}..{}", low, high),
+            format!("{}-{}-{}", wins, draws, losses),
+            if within { "ok" } else { "OFF" }
+        );
+        if !within {
+            off.push(levels);
+        }
+    }
+    println!("===============================");
+    if !off.is_empty() {
+        return Err(format!(
+            "measured Elo gap off the curve by more than {} for levels {}",
+            tolerance,
+            off.join(", ")
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// 95% confidence range of the Elo gap implied by a W-D-L record
+fn elo_range(wins: u32, draws: u32, losses: u32) -> (i64, i64) {
+    let games = wins + draws + losses;
+    let n = games.max(1) as f64;
+    let score = (wins as f64 + 0.5 * draws as f64) / n;
+    let variance = (wins as f64 + 0.25 * draws as f64) / n - score * score;
+    let margin = 1.96 * (variance.max(0.0) / n).sqrt();
+    (elo_difference(score - margin, games), elo_difference(score + margin, games))
+}
+
+/// Elo gap implied by a match score, with the score kept half a game away
+/// from 0 and 1 so sweeps stay finite
+fn elo_difference(score: f64, games: u32) -> i64 {
+    let margin = 0.5 / games.max(1) as f64;
+    let score = score.clamp(margin, 1.0 - margin);
+    (-400.0 * (1.0 / score - 1.0).log10()).round() as i64
+}
+
+/// Play one game between two skill levels; the result is from Black's side
+fn play_skill_game(
+    black: SkillLevel,
+    white: SkillLevel,
+    depth: u8,
+    verbose: bool,
+) -> Result<GameResult, Box<dyn std::error::Error>> {
+    let sessions = [black, white].map(|skill| {
+        let session = EngineSession::new();
+        session.set_reporter(Arc::new(NullReporter));
+        session.with_engine(|engine| engine.set_skill(skill));
+        session
+    });
+    let mut adjudicator = GameAdjudicator::new(AdjudicationConfig::default());
+
+    loop {
+        if let Some(result) = adjudicate_position(&sessions[0], &mut adjudicator, verbose) {
+            return Ok(result);
+        }
+        let to_move = sessions[0].with_engine(|engine| engine.current_player());
+        let mover = &sessions[if to_move == Player::Black { 0 } else { 1 }];
+        let outcome = mover.search(SearchLimits::depth(depth).with_movetime(2000));
+        let Some(best_move) = outcome.best_move else {
+            return Ok(sessions[0].is_game_over().unwrap_or(GameResult::Draw));
+        };
+        if let Some(adjudication) = adjudicator.check_score(to_move, outcome.score) {
+            return Ok(report_adjudication(adjudication, verbose));
+        }
+        for session in &sessions {
+            session.push_move(&best_move.to_usi_string())?;
+        }
+    }
+}
@@ -4,6 +4,8 @@
 //! tuning using various optimization algorithms and validation methods.
 
 use clap::{Parser, Subcommand};
+use shogi_engine::evaluation::king_safety::KingDangerConfig;
+use shogi_engine::evaluation::material::MaterialValueSet;
 use shogi_engine::tuning::{
     data_processor::DataProcessor,
     optimizer::Optimizer,
@@ -22,7 +24,7 @@ use std::time::Instant;
 #[command(name = "tuner")]
 #[command(about = "Automated evaluation tuning for Shogi engine")]
 struct Cli {
-    /// Dataset file path (JSON, KIF, CSA, or PGN format)
+    /// Dataset file path (JSON, packed self-play BIN, KIF, CSA, or PGN format)
     #[arg(short, long, value_name = "FILE")]
     dataset: PathBuf,
 
@@ -71,6 +73,16 @@ struct Cli {
     #[arg(long, value_name = "FILE")]
     initial_weights: Option<PathBuf>,
 
+    /// Also write the tuned hand-count and drop-potential values as a
+    /// material value set (JSON)
+    #[arg(long, value_name = "FILE")]
+    material_output: Option<PathBuf>,
+
+    /// Also write the tuned danger-to-score table as a king-danger
+    /// configuration (JSON)
+    #[arg(long, value_name = "FILE")]
+    king_danger_output: Option<PathBuf>,
+
     /// Subcommand for specific operations
     #[command(subcommand)]
     command: Option<Commands>,
@@ -214,6 +226,26 @@ fn run_tuning(cli: &Cli) -> Result<(), Box<dyn std::error::Error>> {
         println!("Results saved to {:?}", cli.output);
     }
 
+    if let Some(path) = &cli.material_output {
+        let mut value_set = MaterialValueSet::research();
+        value_set.id = "tuned".to_string();
+        value_set.display_name = "Tuned Hand Values".to_string();
+        value_set.apply_tuned_hand_weights(&tuning_results.weights)?;
+        value_set.to_writer(std::fs::File::create(path)?)?;
+        if cli.verbose {
+            println!("Material value set saved to {:?}", path);
+        }
+    }
+
+    if let Some(path) = &cli.king_danger_output {
+        let mut king_danger = KingDangerConfig::default();
+        king_danger.apply_tuned_weights(&tuning_results.weights)?;
+        serde_json::to_writer_pretty(std::fs::File::create(path)?, &king_danger)?;
+        if cli.verbose {
+            println!("King-danger configuration saved to {:?}", path);
+        }
+    }
+
     println!("Tuning completed successfully!");
     println!("Total time: {:.2} seconds", start_time.elapsed().as_secs_f64());
     println!("Final error: {:.6}", optimization_result.final_error);
@@ -449,7 +481,7 @@ fn create_tuning_config(cli: &Cli) -> Result<TuningConfig, Box<dyn std::error::E
 /// Load dataset from file
 fn load_dataset(
     dataset_path: &PathBuf,
-    _data_processor: &DataProcessor,
+    data_processor: &DataProcessor,
 ) -> Result<Vec<shogi_engine::tuning::types::TrainingPosition>, Box<dyn std::error::Error>> {
     use std::fs::File;
     use std::io::BufReader;
@@ -471,6 +503,11 @@ fn load_dataset(
                 serde_json::from_reader(reader)?;
             Ok(positions)
         }
+        "bin" => {
+            // Packed records written by gensfen
+            let path = dataset_path.to_string_lossy();
+            Ok(data_processor.load_packed_training_data(&path)?)
+        }
         "kif" | "csa" | "pgn" => {
             // For now, return an error for unsupported formats
             // In a real implementation, these would be parsed
@@ -535,6 +572,8 @@ mod tests {
             verbose: false,
             progress: false,
             initial_weights: None,
+            material_output: None,
+            king_danger_output: None,
             command: None,
         };
 
@@ -629,6 +668,8 @@ mod tests {
             verbose: false,
             progress: false,
             initial_weights: None,
+            material_output: None,
+            king_danger_output: None,
             command: None,
         };
 
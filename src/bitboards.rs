@@ -1,4 +1,5 @@
 use crate::bitboards::magic::attack_generator::AttackGenerator;
+use crate::evaluation::nnue::{Accumulator, NnueNetwork};
 use crate::search::RepetitionState;
 use crate::types::board::{CapturedPieces, GamePhase};
 use crate::types::core::{Move, Piece, PieceType, Player, Position};
@@ -287,6 +288,9 @@ pub struct BitboardBoard {
     sliding_generator: Option<sliding_moves::SlidingMoveGenerator>,
     side_to_move: Player,
     repetition_state: RepetitionState,
+    /// NNUE accumulator, kept in step with every piece and hand change once
+    /// attached
+    nnue: Option<Box<Accumulator>>,
 }
 
 impl BitboardBoard {
@@ -312,6 +316,31 @@ impl BitboardBoard {
             .filter_map(|(idx, piece)| piece.map(|p| (Position::from_index(idx as u8), p)))
     }
 
+    /// Piece on every square, indexed by `Position::to_index`
+    pub fn squares(&self) -> &[Option<Piece>; 81] {
+        &self.squares
+    }
+
+    /// Attach an NNUE accumulator for the current position; from then on
+    /// every move made or unmade on this board updates it
+    pub fn attach_nnue(&mut self, network: Arc<NnueNetwork>, captured_pieces: &CapturedPieces) {
+        self.nnue = Some(Box::new(Accumulator::new(network, &self.squares, captured_pieces)));
+    }
+
+    pub fn detach_nnue(&mut self) {
+        self.nnue = None;
+    }
+
+    pub fn nnue_accumulator(&self) -> Option<&Accumulator> {
+        self.nnue.as_deref()
+    }
+
+    /// Copy of the board without its NNUE accumulator, for trying moves that
+    /// are never evaluated (legality, checks, exchanges)
+    pub fn clone_without_nnue(&self) -> Self {
+        self.clone_with_nnue(None)
+    }
+
     pub fn new() -> Self {
         let mut board = Self::empty();
         board.setup_initial_position();
@@ -355,6 +384,7 @@ impl BitboardBoard {
             sliding_generator: None,
             side_to_move: Player::Black,
             repetition_state: RepetitionState::None,
+            nnue: None,
         }
     }
 
@@ -414,6 +444,9 @@ impl BitboardBoard {
         }
         set_bit(&mut self.occupied, position);
         self.set_square(position, Some(piece));
+        if let Some(nnue) = self.nnue.as_mut() {
+            nnue.on_place(piece, position, &self.squares);
+        }
     }
 
     pub fn remove_piece(&mut self, position: Position) -> Option<Piece> {
@@ -448,6 +481,9 @@ impl BitboardBoard {
             }
             clear_bit(&mut self.occupied, position);
             self.squares[idx] = None;
+            if let Some(nnue) = self.nnue.as_mut() {
+                nnue.on_remove(piece, position);
+            }
             Some(piece)
         } else {
             None
@@ -489,6 +525,9 @@ impl BitboardBoard {
                 if move_.is_capture {
                     if let Some(cp) = self.remove_piece(move_.to) {
                         captured_piece = Some(cp.unpromoted());
+                        if let Some(nnue) = self.nnue.as_mut() {
+                            nnue.add_hand(move_.player, cp.unpromoted().piece_type);
+                        }
                     }
                 }
                 let final_piece_type = if move_.is_promotion {
@@ -511,6 +550,9 @@ impl BitboardBoard {
             ));
 
             self.place_piece(Piece::new(move_.piece_type, move_.player), move_.to);
+            if let Some(nnue) = self.nnue.as_mut() {
+                nnue.remove_hand(move_.player, move_.piece_type);
+            }
         }
         captured_piece
     }
@@ -536,6 +578,9 @@ impl BitboardBoard {
                 if move_.is_capture {
                     if let Some(cp) = self.remove_piece(move_.to) {
                         captured_piece = Some(cp.unpromoted());
+                        if let Some(nnue) = self.nnue.as_mut() {
+                            nnue.add_hand(move_.player, cp.unpromoted().piece_type);
+                        }
                     }
                 }
                 let final_piece_type = if move_.is_promotion {
@@ -559,6 +604,9 @@ impl BitboardBoard {
             ));
 
             self.place_piece(Piece::new(move_.piece_type, move_.player), move_.to);
+            if let Some(nnue) = self.nnue.as_mut() {
+                nnue.remove_hand(move_.player, move_.piece_type);
+            }
         }
 
         MoveInfo::new(
@@ -606,6 +654,9 @@ impl BitboardBoard {
         // Restore the captured piece if there was one
         if let Some(ref captured_piece) = move_info.captured_piece {
             self.place_piece(captured_piece.clone(), move_info.to);
+            if let Some(nnue) = self.nnue.as_mut() {
+                nnue.remove_hand(move_info.player, captured_piece.piece_type);
+            }
         }
 
         // Restore the moved piece to its original position
@@ -624,6 +675,9 @@ impl BitboardBoard {
             
             // Place the original piece type (before promotion) back at the from position
             self.place_piece(Piece::new(move_info.original_piece_type, move_info.player), from);
+        } else if let Some(nnue) = self.nnue.as_mut() {
+            // A drop: the piece was removed above and goes back to the hand
+            nnue.add_hand(move_info.player, move_info.original_piece_type);
         }
         // If from is None, it was a drop, so we just remove the piece (already
         // done above)
@@ -726,7 +780,7 @@ impl BitboardBoard {
     /// Check if a piece type attacks a square (bitboard-optimized version)
     /// Task 3.0.3.2: Uses precomputed attack tables for non-sliding pieces and
     /// bit scans for sliding pieces
-    fn piece_attacks_square_bitboard(
+    pub(crate) fn piece_attacks_square_bitboard(
         &self,
         piece_type: PieceType,
         from_pos: Position,
@@ -1218,6 +1272,7 @@ impl BitboardBoard {
             sliding_generator: None,
             side_to_move: Player::Black,
             repetition_state: RepetitionState::None,
+            nnue: None,
         })
     }
 
@@ -1625,8 +1680,14 @@ impl BoardTrait for BitboardBoard {
 }
 
 impl Clone for BitboardBoard {
-    /// Task 5.0.5.2: Track board clone operations
     fn clone(&self) -> Self {
+        self.clone_with_nnue(self.nnue.clone())
+    }
+}
+
+impl BitboardBoard {
+    /// Task 5.0.5.2: Track board clone operations
+    fn clone_with_nnue(&self, nnue: Option<Box<Accumulator>>) -> Self {
         BOARD_TELEMETRY.clone_count.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
 
         Self {
@@ -1641,6 +1702,7 @@ impl Clone for BitboardBoard {
             sliding_generator: self.sliding_generator.clone(),
             side_to_move: self.side_to_move,
             repetition_state: self.repetition_state,
+            nnue,
         }
     }
 }
@@ -28,6 +28,9 @@
 //!
 //! ## Loading from a file
 //!
+//! The format follows the extension: `.toml`, `.yaml`/`.yml`, anything else
+//! is read as JSON.
+//!
 //! ```rust,no_run
 //! use shogi_engine::config::EngineConfig;
 //!
@@ -58,9 +61,92 @@ use crate::evaluation::config::TaperedEvalConfig;
 use crate::search::parallel_search::ParallelSearchConfig;
 use crate::search::transposition_table_config::TranspositionTableConfig;
 use crate::types::search::TimeManagementConfig;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
+/// On-disk format of a configuration file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// Pick the format from the file extension, defaulting to JSON
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Self {
+        let extension = path
+            .as_ref()
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("toml") => Self::Toml,
+            Some("yaml") | Some("yml") => Self::Yaml,
+            _ => Self::Json,
+        }
+    }
+
+    /// Parse `content` in this format
+    pub fn parse<T: DeserializeOwned>(self, content: &str) -> std::result::Result<T, String> {
+        match self {
+            Self::Json => serde_json::from_str(content).map_err(|e| e.to_string()),
+            Self::Toml => toml::from_str(content).map_err(|e| e.to_string()),
+            Self::Yaml => serde_yaml::from_str(content).map_err(|e| e.to_string()),
+        }
+    }
+
+    /// Render `value` in this format
+    pub fn render<T: Serialize>(self, value: &T) -> std::result::Result<String, String> {
+        match self {
+            Self::Json => serde_json::to_string_pretty(value).map_err(|e| e.to_string()),
+            Self::Toml => toml::to_string_pretty(value).map_err(|e| e.to_string()),
+            Self::Yaml => serde_yaml::to_string(value).map_err(|e| e.to_string()),
+        }
+    }
+}
+
+/// Read and parse a configuration file, choosing the format by extension
+///
+/// # Errors
+///
+/// Returns `ConfigurationError` if the file cannot be read or parsed.
+pub fn read_config_file<T: DeserializeOwned, P: AsRef<Path>>(path: P) -> Result<T> {
+    let path = path.as_ref();
+    let content = std::fs::read_to_string(path).map_err(|_e| {
+        ShogiEngineError::Configuration(ConfigurationError::file_not_found(
+            path.to_string_lossy().to_string(),
+        ))
+    })?;
+
+    ConfigFormat::from_path(path).parse(&content).map_err(|e| {
+        ShogiEngineError::Configuration(ConfigurationError::parse_error(
+            path.to_string_lossy().to_string(),
+            e,
+        ))
+    })
+}
+
+/// Serialize `value` and write it to `path`, choosing the format by extension
+///
+/// # Errors
+///
+/// Returns `ConfigurationError` if serialization or the write fails.
+pub fn write_config_file<T: Serialize, P: AsRef<Path>>(path: P, value: &T) -> Result<()> {
+    let path = path.as_ref();
+    let content = ConfigFormat::from_path(path).render(value).map_err(|e| {
+        ShogiEngineError::Configuration(ConfigurationError::serialization_failed(e))
+    })?;
+
+    std::fs::write(path, content).map_err(|e| {
+        ShogiEngineError::Configuration(ConfigurationError::serialization_failed(format!(
+            "Failed to write file: {}",
+            e
+        )))
+    })
+}
+
 /// Search configuration
 ///
 /// Configuration for search-related operations including depth limits,
@@ -302,61 +388,37 @@ impl EngineConfig {
         }
     }
 
-    /// Load configuration from a JSON file
+    /// Load configuration from a JSON, TOML or YAML file
     ///
     /// # Task 4.0 (Task 4.22)
     ///
     /// # Arguments
     ///
-    /// * `path` - Path to the JSON configuration file
+    /// * `path` - Path to the configuration file; the extension selects the
+    ///   format (see [`ConfigFormat::from_path`])
     ///
     /// # Errors
     ///
     /// Returns `ConfigurationError` if the file cannot be read or parsed.
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let path = path.as_ref();
-        let content = std::fs::read_to_string(path).map_err(|_e| {
-            ShogiEngineError::Configuration(ConfigurationError::file_not_found(
-                path.to_string_lossy().to_string(),
-            ))
-        })?;
-
-        let config: Self = serde_json::from_str(&content).map_err(|e| {
-            ShogiEngineError::Configuration(ConfigurationError::parse_error(
-                path.to_string_lossy().to_string(),
-                e.to_string(),
-            ))
-        })?;
-
-        Ok(config)
+        read_config_file(path)
     }
 
-    /// Save configuration to a JSON file
+    /// Save configuration to a JSON, TOML or YAML file
     ///
     /// # Task 4.0 (Task 4.23)
     ///
     /// # Arguments
     ///
-    /// * `path` - Path where to save the JSON configuration file
+    /// * `path` - Path where to save the configuration file; the extension
+    ///   selects the format
     ///
     /// # Errors
     ///
     /// Returns `ConfigurationError` if the file cannot be written or
     /// serialization fails.
     pub fn to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
-        let path = path.as_ref();
-        let json = serde_json::to_string_pretty(self).map_err(|e| {
-            ShogiEngineError::Configuration(ConfigurationError::serialization_failed(e.to_string()))
-        })?;
-
-        std::fs::write(path, json).map_err(|e| {
-            ShogiEngineError::Configuration(ConfigurationError::serialization_failed(format!(
-                "Failed to write file: {}",
-                e
-            )))
-        })?;
-
-        Ok(())
+        write_config_file(path, self)
     }
 
     /// Validate the entire configuration
@@ -1,7 +1,8 @@
 // Debug logging utilities for standalone environments
 
+use crate::utils::time::{system_clock, Clock};
 use std::collections::HashMap;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 
 // Global debug flag - set to false to disable debug logging by default
 // Can be enabled via the USI "debug on" command
@@ -122,12 +123,19 @@ macro_rules! trace_log_lazy {
 lazy_static::lazy_static! {
     static ref TIMING_CONTEXT: Mutex<HashMap<String, f64>> = Mutex::new(HashMap::new());
     static ref SEARCH_START_TIME: Mutex<Option<f64>> = Mutex::new(None);
+    static ref TIMING_CLOCK: Mutex<Arc<dyn Clock>> = Mutex::new(system_clock());
+}
+
+/// Use `clock` for timing logs, so they match a virtual search clock
+pub fn set_timing_clock(clock: Arc<dyn Clock>) {
+    if let Ok(mut timing_clock) = TIMING_CLOCK.lock() {
+        *timing_clock = clock;
+    }
 }
 
 /// Get current time in milliseconds
 fn get_current_time_ms() -> f64 {
-    // Delegate to centralized utils::time for consistency
-    crate::utils::time::current_time_ms() as f64
+    TIMING_CLOCK.lock().map_or(0.0, |clock| clock.now_us() as f64 / 1000.0)
 }
 
 /// Enable or disable debug logging
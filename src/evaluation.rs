@@ -22,7 +22,11 @@ use crate::types::evaluation::{
     PST_LANCE_EG_START, PST_LANCE_MG_START, PST_PAWN_EG_START, PST_PAWN_MG_START,
     PST_ROOK_EG_START, PST_ROOK_MG_START, PST_SILVER_EG_START, PST_SILVER_MG_START,
 };
+use crate::types::evaluation::{DROP_POTENTIAL_FEATURES_START, HAND_COUNT_FEATURES_START};
+use crate::types::evaluation::KING_DANGER_FEATURES_START;
+use crate::types::handicap::Handicap;
 use crate::weights::{WeightError, WeightManager};
+use std::sync::Arc;
 
 // Advanced evaluation modules
 pub mod advanced_interpolation;
@@ -32,11 +36,15 @@ pub mod castle_geometry;
 pub mod castles;
 pub mod config;
 pub mod endgame_patterns;
+pub mod entering_king;
+pub mod explain;
 pub mod integration;
 pub mod king_safety;
 pub mod material;
 pub mod material_value_loader;
+pub mod nnue;
 pub mod opening_principles;
+pub mod opening_strategy;
 pub mod patterns;
 pub mod performance;
 pub mod phase_transition;
@@ -78,8 +86,11 @@ pub mod extractors;
 pub mod aggregators;
 use advanced_integration::AdvancedIntegration;
 use eval_cache::{EvaluationCache, MultiLevelCache};
+use explain::{EvalExplanation, EvalTerm};
 use integration::IntegratedEvaluator;
-use king_safety::KingSafetyEvaluator;
+use king_safety::{KingDangerConfig, KingDangerInputs, KingSafetyEvaluator};
+use material::{HAND_PIECE_KINDS, HAND_PIECE_LIMITS, HAND_PIECE_TYPES};
+use nnue::{NnueError, NnueNetwork};
 use statistics::EvaluationTelemetry;
 
 /// Position evaluator for the Shogi engine
@@ -106,6 +117,10 @@ pub struct PositionEvaluator {
     multi_level_cache: Option<MultiLevelCache>,
     // Whether to use cache
     use_cache: bool,
+    // NNUE network loaded from `EvalFile`
+    nnue_network: Option<Arc<NnueNetwork>>,
+    // Use NNUE (vs the classical evaluation) whenever a network is loaded
+    use_nnue: bool,
 }
 
 impl PositionEvaluator {
@@ -122,6 +137,8 @@ impl PositionEvaluator {
             eval_cache: None,
             multi_level_cache: None,
             use_cache: false,
+            nnue_network: None,
+            use_nnue: true,
         }
     }
 
@@ -139,6 +156,8 @@ impl PositionEvaluator {
             eval_cache: None,
             multi_level_cache: None,
             use_cache: false,
+            nnue_network: None,
+            use_nnue: true,
         }
     }
 
@@ -178,6 +197,38 @@ impl PositionEvaluator {
     ) -> Result<(), WeightError> {
         self.weight_manager.load_weights(path)?;
         self.use_tuned_weights = self.weight_manager.is_enabled();
+        // The king-danger entries are the table king safety scores with
+        if let (Some(weights), Some(config)) =
+            (self.weight_manager.weights(), self.king_danger_config())
+        {
+            let mut king_danger = config.clone();
+            if king_danger.apply_tuned_weights(weights).is_ok() {
+                self.set_king_danger_config(king_danger);
+            }
+        }
+        Ok(())
+    }
+
+    /// King-danger model king safety scores with; `None` without the
+    /// integrated evaluator, the only one that uses it
+    pub fn king_danger_config(&self) -> Option<&KingDangerConfig> {
+        self.integrated_evaluator
+            .as_ref()
+            .map(|integrated| &integrated.config().position_features.king_danger)
+    }
+
+    /// Replace the king-danger model, e.g. with one written by the tuner
+    pub fn set_king_danger_config(&mut self, config: KingDangerConfig) {
+        if let Some(integrated) = self.integrated_evaluator.as_mut() {
+            integrated.update_king_danger_config(config);
+        }
+        self.clear_eval_cache();
+    }
+
+    /// Load a king-danger configuration file (`tuner --king-danger-output`)
+    pub fn load_king_danger<P: AsRef<std::path::Path>>(&mut self, path: P) -> Result<(), String> {
+        let config = KingDangerConfig::from_path(path)?;
+        self.set_king_danger_config(config);
         Ok(())
     }
 
@@ -237,11 +288,72 @@ impl PositionEvaluator {
         self.use_integrated_eval && self.integrated_evaluator.is_some()
     }
 
+    /// Load an NNUE network from a file
+    pub fn load_nnue<P: AsRef<std::path::Path>>(&mut self, path: P) -> Result<(), NnueError> {
+        let network = NnueNetwork::load(path)?;
+        self.set_nnue_network(Some(Arc::new(network)));
+        Ok(())
+    }
+
+    /// Replace the NNUE network; `None` unloads it
+    pub fn set_nnue_network(&mut self, network: Option<Arc<NnueNetwork>>) {
+        self.nnue_network = network;
+        self.clear_eval_cache();
+    }
+
+    pub fn nnue_network(&self) -> Option<&Arc<NnueNetwork>> {
+        self.nnue_network.as_ref()
+    }
+
+    /// Switch between NNUE and the classical evaluation
+    pub fn set_use_nnue(&mut self, enabled: bool) {
+        self.use_nnue = enabled;
+        self.clear_eval_cache();
+    }
+
+    /// Whether NNUE is switched on; it only takes effect with a network loaded
+    pub fn use_nnue(&self) -> bool {
+        self.use_nnue
+    }
+
+    /// Check if positions are scored by the NNUE network
+    pub fn is_using_nnue(&self) -> bool {
+        self.use_nnue && self.nnue_network.is_some()
+    }
+
+    /// NNUE score of the position, if NNUE applies to it. A board carrying
+    /// an accumulator is always scored by it: that is how helper threads,
+    /// whose evaluators are not configured, follow the main search.
+    fn evaluate_nnue(
+        &self,
+        board: &BitboardBoard,
+        player: Player,
+        captured_pieces: &CapturedPieces,
+    ) -> Option<i32> {
+        if board.nnue_accumulator().is_some() {
+            return nnue::evaluate_attached(board, player, captured_pieces);
+        }
+        match self.nnue_network {
+            Some(ref network) if self.use_nnue => {
+                nnue::evaluate(network, board, player, captured_pieces)
+            }
+            _ => None,
+        }
+    }
+
     /// Get reference to integrated evaluator
     pub fn get_integrated_evaluator(&self) -> Option<&IntegratedEvaluator> {
         self.integrated_evaluator.as_ref()
     }
 
+    /// Set the starting array of the game
+    pub fn set_handicap(&mut self, handicap: Handicap) {
+        if let Some(ref mut integrated) = self.integrated_evaluator {
+            integrated.set_handicap(handicap);
+        }
+        self.clear_eval_cache();
+    }
+
     /// Get mutable reference to integrated evaluator
     pub fn get_integrated_evaluator_mut(&mut self) -> Option<&mut IntegratedEvaluator> {
         self.integrated_evaluator.as_mut()
@@ -413,6 +525,12 @@ impl PositionEvaluator {
         // Extract development features
         self.extract_development_features(&mut features, board, player);
 
+        // Extract hand count and drop potential features
+        self.extract_hand_features(&mut features, board, player, captured_pieces);
+
+        // Extract king-danger table features
+        self.extract_king_danger_features(&mut features, board, player, captured_pieces);
+
         features
     }
 
@@ -459,6 +577,9 @@ impl PositionEvaluator {
         player: Player,
         captured_pieces: &CapturedPieces,
     ) -> i32 {
+        if let Some(score) = self.evaluate_nnue(board, player, captured_pieces) {
+            return score;
+        }
         // Try cache first (Task 3.1.2: Cache probe before evaluation)
         if self.use_cache {
             if let Some(ref cache) = self.eval_cache {
@@ -514,6 +635,55 @@ impl PositionEvaluator {
         score
     }
 
+    /// Per-term breakdown of the evaluation of a position, bypassing the
+    /// caches. The legacy evaluator is reported as a single term; when an
+    /// NNUE network scores the position, its score is the final one.
+    pub fn explain(
+        &mut self,
+        board: &BitboardBoard,
+        player: Player,
+        captured_pieces: &CapturedPieces,
+    ) -> EvalExplanation {
+        let mut explanation = match self.integrated_evaluator {
+            Some(ref mut integrated) if self.use_integrated_eval => {
+                integrated.explain(board, player, captured_pieces)
+            }
+            _ => {
+                let use_nnue = std::mem::replace(&mut self.use_nnue, false);
+                let score = self.evaluate_with_context(
+                    board,
+                    player,
+                    captured_pieces,
+                    0,
+                    false,
+                    false,
+                    false,
+                    false,
+                );
+                self.use_nnue = use_nnue;
+                let phase = self.calculate_game_phase(board, captured_pieces);
+                let total = TaperedScore::new(score);
+                EvalExplanation {
+                    side_to_move: player,
+                    evaluator: "legacy",
+                    phase,
+                    interpolation: phase_transition::InterpolationMethod::Linear,
+                    terms: vec![EvalTerm::new("legacy", total, phase)],
+                    total,
+                    classical_score: score,
+                    nnue_score: None,
+                    score,
+                }
+            }
+        };
+        if let Some(score) = self.evaluate_nnue(board, player, captured_pieces) {
+            explanation.evaluator = "nnue";
+            explanation.nnue_score = Some(score);
+            explanation.score = score;
+        }
+        explanation
+    }
+
     /// Evaluate using tuned weights if available, otherwise use traditional
     /// evaluation
     pub fn evaluate_with_tuned_weights(
@@ -553,6 +723,9 @@ impl PositionEvaluator {
         has_check: bool,
         is_quiescence: bool,
     ) -> i32 {
+        if let Some(score) = self.evaluate_nnue(board, player, captured_pieces) {
+            return score;
+        }
         // Try cache first (with depth information)
         if self.use_cache && depth > 0 {
             if let Some(ref cache) = self.eval_cache {
@@ -1576,6 +1749,62 @@ impl PositionEvaluator {
         features[MATERIAL_PROMOTED_ROOK_INDEX] = piece_counts[13] as f64;
     }
 
+    /// Extract hand features: for each kind and count n, whether each side
+    /// holds at least n pieces (the weight is the value of the n-th piece),
+    /// then each side's safe drop squares near the enemy king per kind
+    fn extract_hand_features(
+        &self,
+        features: &mut [f64],
+        board: &BitboardBoard,
+        player: Player,
+        captured_pieces: &CapturedPieces,
+    ) {
+        let opponent = player.opposite();
+        let mut index = HAND_COUNT_FEATURES_START;
+        for (kind, piece_type) in HAND_PIECE_TYPES.iter().enumerate() {
+            let ours = captured_pieces.count(*piece_type, player);
+            let theirs = captured_pieces.count(*piece_type, opponent);
+            for nth in 1..=HAND_PIECE_LIMITS[kind] {
+                features[index] =
+                    f64::from(u8::from(ours >= nth)) - f64::from(u8::from(theirs >= nth));
+                index += 1;
+            }
+        }
+
+        let ours = material::safe_king_zone_drops(board, player, captured_pieces);
+        let theirs = material::safe_king_zone_drops(board, opponent, captured_pieces);
+        for kind in 0..HAND_PIECE_KINDS {
+            features[DROP_POTENTIAL_FEATURES_START + kind] =
+                f64::from(ours[kind]) - f64::from(theirs[kind]);
+        }
+    }
+
+    /// Extract king-danger features: the table entry each king's danger falls
+    /// in, positive for the opponent's king (the weight is the penalty)
+    fn extract_king_danger_features(
+        &self,
+        features: &mut [f64],
+        board: &BitboardBoard,
+        player: Player,
+        captured_pieces: &CapturedPieces,
+    ) {
+        let default_config;
+        let config = match self.king_danger_config() {
+            Some(config) => config,
+            None => {
+                default_config = KingDangerConfig::default();
+                &default_config
+            }
+        };
+        for (king_owner, sign) in [(player, -1.0), (player.opposite(), 1.0)] {
+            if let Some(king_pos) = board.find_king_position(king_owner) {
+                let inputs =
+                    KingDangerInputs::compute(board, king_pos, king_owner, captured_pieces);
+                features[KING_DANGER_FEATURES_START + config.danger(&inputs)] += sign;
+            }
+        }
+    }
+
     /// Extract positional features (piece-square table values)
     fn extract_positional_features(
         &self,
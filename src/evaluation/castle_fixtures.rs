@@ -19,6 +19,32 @@ pub enum CastleType {
     Anaguma,
     Yagura,
     Snowroof,
+    SilverCrown,
+    DiamondMino,
+    LeftMino,
+    Boat,
+    Elmo,
+    Nakahara,
+    Bonanza,
+}
+
+impl CastleType {
+    /// Name of the `CastlePattern` expected to recognise this castle.
+    pub fn pattern_name(self) -> &'static str {
+        match self {
+            CastleType::Mino => "Mino",
+            CastleType::Anaguma => "Anaguma",
+            CastleType::Yagura => "Yagura",
+            CastleType::Snowroof => "Snowroof",
+            CastleType::SilverCrown => "Silver Crown",
+            CastleType::DiamondMino => "Diamond Mino",
+            CastleType::LeftMino => "Left Mino",
+            CastleType::Boat => "Boat",
+            CastleType::Elmo => "Elmo",
+            CastleType::Nakahara => "Nakahara",
+            CastleType::Bonanza => "Bonanza",
+        }
+    }
 }
 
 pub struct CastleFixture {
@@ -148,6 +174,224 @@ pub fn castle_fixtures() -> Vec<CastleFixture> {
             player: Player::Black,
             builder: yagura_attacked_mating_net,
         },
+        // Mino family and static-rook castles
+        CastleFixture {
+            name: "silver_crown_canonical_black",
+            theme: CastleFixtureTheme::Canonical,
+            castle_type: CastleType::SilverCrown,
+            player: Player::Black,
+            builder: silver_crown_canonical,
+        },
+        CastleFixture {
+            name: "silver_crown_canonical_white",
+            theme: CastleFixtureTheme::Canonical,
+            castle_type: CastleType::SilverCrown,
+            player: Player::White,
+            builder: silver_crown_canonical,
+        },
+        CastleFixture {
+            name: "silver_crown_mirrored_black",
+            theme: CastleFixtureTheme::Mirrored,
+            castle_type: CastleType::SilverCrown,
+            player: Player::Black,
+            builder: silver_crown_mirrored,
+        },
+        CastleFixture {
+            name: "silver_crown_partial_black",
+            theme: CastleFixtureTheme::Partial,
+            castle_type: CastleType::SilverCrown,
+            player: Player::Black,
+            builder: silver_crown_partial,
+        },
+        CastleFixture {
+            name: "silver_crown_broken_black",
+            theme: CastleFixtureTheme::Broken,
+            castle_type: CastleType::SilverCrown,
+            player: Player::Black,
+            builder: silver_crown_broken,
+        },
+        CastleFixture {
+            name: "diamond_mino_canonical_black",
+            theme: CastleFixtureTheme::Canonical,
+            castle_type: CastleType::DiamondMino,
+            player: Player::Black,
+            builder: diamond_mino_canonical,
+        },
+        CastleFixture {
+            name: "diamond_mino_high_canonical_black",
+            theme: CastleFixtureTheme::Canonical,
+            castle_type: CastleType::DiamondMino,
+            player: Player::Black,
+            builder: diamond_mino_high_canonical,
+        },
+        CastleFixture {
+            name: "diamond_mino_mirrored_black",
+            theme: CastleFixtureTheme::Mirrored,
+            castle_type: CastleType::DiamondMino,
+            player: Player::Black,
+            builder: diamond_mino_mirrored,
+        },
+        CastleFixture {
+            name: "diamond_mino_partial_black",
+            theme: CastleFixtureTheme::Partial,
+            castle_type: CastleType::DiamondMino,
+            player: Player::Black,
+            builder: diamond_mino_partial,
+        },
+        CastleFixture {
+            name: "diamond_mino_broken_black",
+            theme: CastleFixtureTheme::Broken,
+            castle_type: CastleType::DiamondMino,
+            player: Player::Black,
+            builder: diamond_mino_broken,
+        },
+        CastleFixture {
+            name: "left_mino_canonical_black",
+            theme: CastleFixtureTheme::Canonical,
+            castle_type: CastleType::LeftMino,
+            player: Player::Black,
+            builder: left_mino_canonical,
+        },
+        CastleFixture {
+            name: "left_mino_canonical_white",
+            theme: CastleFixtureTheme::Canonical,
+            castle_type: CastleType::LeftMino,
+            player: Player::White,
+            builder: left_mino_canonical,
+        },
+        CastleFixture {
+            name: "left_mino_mirrored_black",
+            theme: CastleFixtureTheme::Mirrored,
+            castle_type: CastleType::LeftMino,
+            player: Player::Black,
+            builder: left_mino_mirrored,
+        },
+        CastleFixture {
+            name: "left_mino_partial_black",
+            theme: CastleFixtureTheme::Partial,
+            castle_type: CastleType::LeftMino,
+            player: Player::Black,
+            builder: left_mino_partial,
+        },
+        CastleFixture {
+            name: "left_mino_broken_black",
+            theme: CastleFixtureTheme::Broken,
+            castle_type: CastleType::LeftMino,
+            player: Player::Black,
+            builder: left_mino_broken,
+        },
+        CastleFixture {
+            name: "boat_canonical_black",
+            theme: CastleFixtureTheme::Canonical,
+            castle_type: CastleType::Boat,
+            player: Player::Black,
+            builder: boat_canonical,
+        },
+        CastleFixture {
+            name: "boat_mirrored_black",
+            theme: CastleFixtureTheme::Mirrored,
+            castle_type: CastleType::Boat,
+            player: Player::Black,
+            builder: boat_mirrored,
+        },
+        CastleFixture {
+            name: "boat_partial_black",
+            theme: CastleFixtureTheme::Partial,
+            castle_type: CastleType::Boat,
+            player: Player::Black,
+            builder: boat_partial,
+        },
+        CastleFixture {
+            name: "boat_broken_black",
+            theme: CastleFixtureTheme::Broken,
+            castle_type: CastleType::Boat,
+            player: Player::Black,
+            builder: boat_broken,
+        },
+        CastleFixture {
+            name: "elmo_canonical_black",
+            theme: CastleFixtureTheme::Canonical,
+            castle_type: CastleType::Elmo,
+            player: Player::Black,
+            builder: elmo_canonical,
+        },
+        CastleFixture {
+            name: "elmo_mirrored_black",
+            theme: CastleFixtureTheme::Mirrored,
+            castle_type: CastleType::Elmo,
+            player: Player::Black,
+            builder: elmo_mirrored,
+        },
+        CastleFixture {
+            name: "elmo_partial_black",
+            theme: CastleFixtureTheme::Partial,
+            castle_type: CastleType::Elmo,
+            player: Player::Black,
+            builder: elmo_partial,
+        },
+        CastleFixture {
+            name: "elmo_broken_black",
+            theme: CastleFixtureTheme::Broken,
+            castle_type: CastleType::Elmo,
+            player: Player::Black,
+            builder: elmo_broken,
+        },
+        CastleFixture {
+            name: "nakahara_canonical_black",
+            theme: CastleFixtureTheme::Canonical,
+            castle_type: CastleType::Nakahara,
+            player: Player::Black,
+            builder: nakahara_canonical,
+        },
+        CastleFixture {
+            name: "nakahara_mirrored_black",
+            theme: CastleFixtureTheme::Mirrored,
+            castle_type: CastleType::Nakahara,
+            player: Player::Black,
+            builder: nakahara_mirrored,
+        },
+        CastleFixture {
+            name: "nakahara_partial_black",
+            theme: CastleFixtureTheme::Partial,
+            castle_type: CastleType::Nakahara,
+            player: Player::Black,
+            builder: nakahara_partial,
+        },
+        CastleFixture {
+            name: "nakahara_broken_black",
+            theme: CastleFixtureTheme::Broken,
+            castle_type: CastleType::Nakahara,
+            player: Player::Black,
+            builder: nakahara_broken,
+        },
+        CastleFixture {
+            name: "bonanza_canonical_black",
+            theme: CastleFixtureTheme::Canonical,
+            castle_type: CastleType::Bonanza,
+            player: Player::Black,
+            builder: bonanza_canonical,
+        },
+        CastleFixture {
+            name: "bonanza_mirrored_black",
+            theme: CastleFixtureTheme::Mirrored,
+            castle_type: CastleType::Bonanza,
+            player: Player::Black,
+            builder: bonanza_mirrored,
+        },
+        CastleFixture {
+            name: "bonanza_partial_black",
+            theme: CastleFixtureTheme::Partial,
+            castle_type: CastleType::Bonanza,
+            player: Player::Black,
+            builder: bonanza_partial,
+        },
+        CastleFixture {
+            name: "bonanza_broken_black",
+            theme: CastleFixtureTheme::Broken,
+            castle_type: CastleType::Bonanza,
+            player: Player::Black,
+            builder: bonanza_broken,
+        },
     ]
 }
 
@@ -413,3 +657,333 @@ fn yagura_attacked_mating_net(player: Player) -> (BitboardBoard, Position) {
 
     (board, king_pos)
 }
+
+/// Build a castle from Black's point of view: `king` is Black's (row, col) and
+/// each piece is a `RelativeOffset` from it. White gets the rotated position and
+/// `mirror` swaps the castle onto the other wing.
+fn castle_board(
+    player: Player,
+    king: (u8, u8),
+    pieces: &[(i8, i8, PieceType)],
+    mirror: bool,
+) -> (BitboardBoard, Position) {
+    let (row, col) = if mirror { (king.0, 8 - king.1) } else { king };
+    let king_pos = match player {
+        Player::Black => Position::new(row, col),
+        Player::White => Position::new(8 - row, 8 - col),
+    };
+
+    let mut board = BitboardBoard::empty();
+    board.place_piece(Piece::new(PieceType::King, player), king_pos);
+    for &(rank, file, piece_type) in pieces {
+        let offset = RelativeOffset::new(rank, file);
+        let offset = if mirror { offset.mirrored() } else { offset };
+        place_relative(&mut board, player, king_pos, offset, piece_type);
+    }
+
+    (board, king_pos)
+}
+
+// Silver Crown: king 2八, silver 2七, golds 3八/4七
+const SILVER_CROWN_KING: (u8, u8) = (7, 7);
+
+const SILVER_CROWN: &[(i8, i8, PieceType)] = &[
+    (-1, 0, PieceType::Silver),
+    (0, -1, PieceType::Gold),
+    (-1, -2, PieceType::Gold),
+    (-2, 0, PieceType::Pawn),
+    (-2, -1, PieceType::Pawn),
+    (-2, 1, PieceType::Pawn),
+    (1, 0, PieceType::Knight),
+    (1, 1, PieceType::Lance),
+];
+
+const SILVER_CROWN_PARTIAL: &[(i8, i8, PieceType)] = &[
+    (-1, 0, PieceType::Silver),
+    (0, -1, PieceType::Gold),
+    (-2, 0, PieceType::Pawn),
+    (1, 0, PieceType::Knight),
+    (1, 1, PieceType::Lance),
+];
+
+const SILVER_CROWN_BROKEN: &[(i8, i8, PieceType)] =
+    &[(0, -1, PieceType::Gold), (-2, -1, PieceType::Pawn), (1, 1, PieceType::Lance)];
+
+// Diamond Mino: king 2八, silvers 3八/4七, golds 4九/5八
+const DIAMOND_MINO_KING: (u8, u8) = (7, 7);
+
+const DIAMOND_MINO: &[(i8, i8, PieceType)] = &[
+    (0, -1, PieceType::Silver),
+    (-1, -2, PieceType::Silver),
+    (1, -2, PieceType::Gold),
+    (0, -3, PieceType::Gold),
+    (-1, 0, PieceType::Pawn),
+    (-2, -1, PieceType::Pawn),
+    (-2, -2, PieceType::Pawn),
+    (1, 0, PieceType::Knight),
+    (1, 1, PieceType::Lance),
+];
+
+// High Mino (Takamino): the gold on 4七 instead of the second silver
+const HIGH_MINO: &[(i8, i8, PieceType)] = &[
+    (0, -1, PieceType::Silver),
+    (-1, -2, PieceType::Gold),
+    (1, -2, PieceType::Gold),
+    (-1, 0, PieceType::Pawn),
+    (-2, -1, PieceType::Pawn),
+    (-2, -2, PieceType::Pawn),
+    (1, 0, PieceType::Knight),
+    (1, 1, PieceType::Lance),
+];
+
+const DIAMOND_MINO_PARTIAL: &[(i8, i8, PieceType)] = &[
+    (0, -1, PieceType::Silver),
+    (-1, -2, PieceType::Silver),
+    (1, -2, PieceType::Gold),
+    (-1, 0, PieceType::Pawn),
+    (1, 0, PieceType::Knight),
+    (1, 1, PieceType::Lance),
+];
+
+const DIAMOND_MINO_BROKEN: &[(i8, i8, PieceType)] =
+    &[(1, -2, PieceType::Gold), (-2, -2, PieceType::Pawn), (1, 1, PieceType::Lance)];
+
+// Left Mino: king 8八, silver 7八, golds 6九/5八
+const LEFT_MINO_KING: (u8, u8) = (7, 1);
+
+const LEFT_MINO: &[(i8, i8, PieceType)] = &[
+    (0, 1, PieceType::Silver),
+    (1, 2, PieceType::Gold),
+    (0, 3, PieceType::Gold),
+    (-1, 0, PieceType::Pawn),
+    (-1, -1, PieceType::Pawn),
+    (-2, 1, PieceType::Pawn),
+    (1, 0, PieceType::Knight),
+    (1, -1, PieceType::Lance),
+];
+
+const LEFT_MINO_PARTIAL: &[(i8, i8, PieceType)] = &[
+    (0, 1, PieceType::Silver),
+    (1, 2, PieceType::Gold),
+    (-1, 0, PieceType::Pawn),
+    (1, 0, PieceType::Knight),
+    (1, -1, PieceType::Lance),
+];
+
+const LEFT_MINO_BROKEN: &[(i8, i8, PieceType)] =
+    &[(1, 2, PieceType::Gold), (-1, -1, PieceType::Pawn), (1, -1, PieceType::Lance)];
+
+// Boat: king 7八, silver 6八, golds 6九/5八
+const BOAT_KING: (u8, u8) = (7, 2);
+
+const BOAT: &[(i8, i8, PieceType)] = &[
+    (0, 1, PieceType::Silver),
+    (1, 1, PieceType::Gold),
+    (0, 2, PieceType::Gold),
+    (-1, 1, PieceType::Pawn),
+    (-1, 0, PieceType::Pawn),
+    (-1, -1, PieceType::Pawn),
+    (1, -1, PieceType::Knight),
+    (1, -2, PieceType::Lance),
+];
+
+const BOAT_PARTIAL: &[(i8, i8, PieceType)] = &[
+    (0, 1, PieceType::Silver),
+    (1, 1, PieceType::Gold),
+    (-1, 0, PieceType::Pawn),
+    (1, -1, PieceType::Knight),
+    (1, -2, PieceType::Lance),
+];
+
+const BOAT_BROKEN: &[(i8, i8, PieceType)] =
+    &[(1, 1, PieceType::Gold), (-1, -1, PieceType::Pawn), (1, -2, PieceType::Lance)];
+
+// Elmo: king 7九, gold 7八, silver 7七, bishop 8八
+const ELMO_KING: (u8, u8) = (8, 2);
+
+const ELMO: &[(i8, i8, PieceType)] = &[
+    (-1, 0, PieceType::Gold),
+    (-2, 0, PieceType::Silver),
+    (-1, -1, PieceType::Bishop),
+    (-1, 2, PieceType::Gold),
+    (-2, -1, PieceType::Pawn),
+    (-2, 1, PieceType::Pawn),
+    (0, -1, PieceType::Knight),
+    (0, -2, PieceType::Lance),
+];
+
+const ELMO_PARTIAL: &[(i8, i8, PieceType)] = &[
+    (-1, 0, PieceType::Gold),
+    (-2, 0, PieceType::Silver),
+    (-1, -1, PieceType::Bishop),
+    (0, -2, PieceType::Lance),
+];
+
+const ELMO_BROKEN: &[(i8, i8, PieceType)] =
+    &[(-1, -1, PieceType::Bishop), (-2, -1, PieceType::Pawn), (0, -2, PieceType::Lance)];
+
+// Nakahara: king 6九, gold 7八, silver 7九, gold 5八
+const NAKAHARA_KING: (u8, u8) = (8, 3);
+
+const NAKAHARA: &[(i8, i8, PieceType)] = &[
+    (-1, -1, PieceType::Gold),
+    (0, -1, PieceType::Silver),
+    (-1, 1, PieceType::Gold),
+    (-2, -1, PieceType::Pawn),
+    (-2, 0, PieceType::Pawn),
+    (-2, 1, PieceType::Pawn),
+    (0, -2, PieceType::Knight),
+    (0, -3, PieceType::Lance),
+];
+
+const NAKAHARA_PARTIAL: &[(i8, i8, PieceType)] = &[
+    (-1, -1, PieceType::Gold),
+    (0, -1, PieceType::Silver),
+    (-2, 0, PieceType::Pawn),
+    (0, -2, PieceType::Knight),
+    (0, -3, PieceType::Lance),
+];
+
+const NAKAHARA_BROKEN: &[(i8, i8, PieceType)] =
+    &[(-1, 1, PieceType::Gold), (-2, 1, PieceType::Pawn), (0, -3, PieceType::Lance)];
+
+// Bonanza: king 6八 between golds 5八/7八, silver 7七
+const BONANZA_KING: (u8, u8) = (7, 3);
+
+const BONANZA: &[(i8, i8, PieceType)] = &[
+    (0, -1, PieceType::Gold),
+    (0, 1, PieceType::Gold),
+    (-1, -1, PieceType::Silver),
+    (-1, 0, PieceType::Pawn),
+    (-1, 1, PieceType::Pawn),
+    (-2, -1, PieceType::Pawn),
+    (1, -2, PieceType::Knight),
+    (1, -3, PieceType::Lance),
+];
+
+const BONANZA_PARTIAL: &[(i8, i8, PieceType)] = &[
+    (0, -1, PieceType::Gold),
+    (0, 1, PieceType::Gold),
+    (-1, -1, PieceType::Silver),
+    (1, -2, PieceType::Knight),
+    (1, -3, PieceType::Lance),
+];
+
+const BONANZA_BROKEN: &[(i8, i8, PieceType)] =
+    &[(0, 1, PieceType::Gold), (-1, 1, PieceType::Pawn), (1, -3, PieceType::Lance)];
+
+fn silver_crown_canonical(player: Player) -> (BitboardBoard, Position) {
+    castle_board(player, SILVER_CROWN_KING, SILVER_CROWN, false)
+}
+
+fn silver_crown_mirrored(player: Player) -> (BitboardBoard, Position) {
+    castle_board(player, SILVER_CROWN_KING, SILVER_CROWN, true)
+}
+
+fn silver_crown_partial(player: Player) -> (BitboardBoard, Position) {
+    castle_board(player, SILVER_CROWN_KING, SILVER_CROWN_PARTIAL, false)
+}
+
+fn silver_crown_broken(player: Player) -> (BitboardBoard, Position) {
+    castle_board(player, SILVER_CROWN_KING, SILVER_CROWN_BROKEN, false)
+}
+
+fn diamond_mino_canonical(player: Player) -> (BitboardBoard, Position) {
+    castle_board(player, DIAMOND_MINO_KING, DIAMOND_MINO, false)
+}
+
+fn diamond_mino_high_canonical(player: Player) -> (BitboardBoard, Position) {
+    castle_board(player, DIAMOND_MINO_KING, HIGH_MINO, false)
+}
+
+fn diamond_mino_mirrored(player: Player) -> (BitboardBoard, Position) {
+    castle_board(player, DIAMOND_MINO_KING, DIAMOND_MINO, true)
+}
+
+fn diamond_mino_partial(player: Player) -> (BitboardBoard, Position) {
+    castle_board(player, DIAMOND_MINO_KING, DIAMOND_MINO_PARTIAL, false)
+}
+
+fn diamond_mino_broken(player: Player) -> (BitboardBoard, Position) {
+    castle_board(player, DIAMOND_MINO_KING, DIAMOND_MINO_BROKEN, false)
+}
+
+fn left_mino_canonical(player: Player) -> (BitboardBoard, Position) {
+    castle_board(player, LEFT_MINO_KING, LEFT_MINO, false)
+}
+
+fn left_mino_mirrored(player: Player) -> (BitboardBoard, Position) {
+    castle_board(player, LEFT_MINO_KING, LEFT_MINO, true)
+}
+
+fn left_mino_partial(player: Player) -> (BitboardBoard, Position) {
+    castle_board(player, LEFT_MINO_KING, LEFT_MINO_PARTIAL, false)
+}
+
+fn left_mino_broken(player: Player) -> (BitboardBoard, Position) {
+    castle_board(player, LEFT_MINO_KING, LEFT_MINO_BROKEN, false)
+}
+
+fn boat_canonical(player: Player) -> (BitboardBoard, Position) {
+    castle_board(player, BOAT_KING, BOAT, false)
+}
+
+fn boat_mirrored(player: Player) -> (BitboardBoard, Position) {
+    castle_board(player, BOAT_KING, BOAT, true)
+}
+
+fn boat_partial(player: Player) -> (BitboardBoard, Position) {
+    castle_board(player, BOAT_KING, BOAT_PARTIAL, false)
+}
+
+fn boat_broken(player: Player) -> (BitboardBoard, Position) {
+    castle_board(player, BOAT_KING, BOAT_BROKEN, false)
+}
+
+fn elmo_canonical(player: Player) -> (BitboardBoard, Position) {
+    castle_board(player, ELMO_KING, ELMO, false)
+}
+
+fn elmo_mirrored(player: Player) -> (BitboardBoard, Position) {
+    castle_board(player, ELMO_KING, ELMO, true)
+}
+
+fn elmo_partial(player: Player) -> (BitboardBoard, Position) {
+    castle_board(player, ELMO_KING, ELMO_PARTIAL, false)
+}
+
+fn elmo_broken(player: Player) -> (BitboardBoard, Position) {
+    castle_board(player, ELMO_KING, ELMO_BROKEN, false)
+}
+
+fn nakahara_canonical(player: Player) -> (BitboardBoard, Position) {
+    castle_board(player, NAKAHARA_KING, NAKAHARA, false)
+}
+
+fn nakahara_mirrored(player: Player) -> (BitboardBoard, Position) {
+    castle_board(player, NAKAHARA_KING, NAKAHARA, true)
+}
+
+fn nakahara_partial(player: Player) -> (BitboardBoard, Position) {
+    castle_board(player, NAKAHARA_KING, NAKAHARA_PARTIAL, false)
+}
+
+fn nakahara_broken(player: Player) -> (BitboardBoard, Position) {
+    castle_board(player, NAKAHARA_KING, NAKAHARA_BROKEN, false)
+}
+
+fn bonanza_canonical(player: Player) -> (BitboardBoard, Position) {
+    castle_board(player, BONANZA_KING, BONANZA, false)
+}
+
+fn bonanza_mirrored(player: Player) -> (BitboardBoard, Position) {
+    castle_board(player, BONANZA_KING, BONANZA, true)
+}
+
+fn bonanza_partial(player: Player) -> (BitboardBoard, Position) {
+    castle_board(player, BONANZA_KING, BONANZA_PARTIAL, false)
+}
+
+fn bonanza_broken(player: Player) -> (BitboardBoard, Position) {
+    castle_board(player, BONANZA_KING, BONANZA_BROKEN, false)
+}
@@ -176,6 +176,9 @@ pub struct CastleRecognizer {
     early_termination_threshold: f32,
     /// Enable symmetry-aware caching (mirrored positions share cache)
     enable_symmetry: bool,
+    /// Pattern squares outside the king zone and buffer ring; hashed into the
+    /// cache key so that variants reaching further out do not collide
+    outer_offsets: Vec<RelativeOffset>,
 }
 
 #[derive(Debug, Clone)]
@@ -215,12 +218,37 @@ impl CastleRecognizer {
         let mut stats = CastleCacheStats::default();
         stats.max_size = cache_size;
 
+        let patterns = vec![
+            get_mino_castle(),
+            get_anaguma_castle(),
+            get_yagura_castle(),
+            get_silver_crown_castle(),
+            get_diamond_mino_castle(),
+            get_left_mino_castle(),
+            get_boat_castle(),
+            get_elmo_castle(),
+            get_nakahara_castle(),
+            get_bonanza_castle(),
+        ];
+
+        let mut outer_offsets: Vec<RelativeOffset> = Vec::new();
+        for piece in patterns.iter().flat_map(|p| &p.variants).flat_map(|v| &v.pieces) {
+            let offset = piece.offset;
+            if !KING_ZONE_RING.contains(&offset)
+                && !BUFFER_RING.contains(&offset)
+                && !outer_offsets.contains(&offset)
+            {
+                outer_offsets.push(offset);
+            }
+        }
+
         Self {
-            patterns: vec![get_mino_castle(), get_anaguma_castle(), get_yagura_castle()],
+            patterns,
             pattern_cache: RefCell::new(cache),
             cache_stats: RefCell::new(stats),
             early_termination_threshold: 0.8,
             enable_symmetry: true,
+            outer_offsets,
         }
     }
 
@@ -284,7 +312,8 @@ impl CastleRecognizer {
         for offset in &KING_ZONE_RING {
             if let Some(pos) = offset.to_absolute(king_pos, player) {
                 if let Some(piece) = board.get_piece(pos) {
-                    let piece_hash = (piece.piece_type as u8 as u64) << (piece.player as u8 * 4);
+                    let piece_hash =
+                        (piece.piece_type as u8 as u64 + 1) << (piece.player as u8 * 4);
                     hash ^= piece_hash
                         .wrapping_mul(pos.row as u64 + 1)
                         .wrapping_mul(pos.col as u64 + 1);
@@ -297,7 +326,21 @@ impl CastleRecognizer {
             if let Some(pos) = offset.to_absolute(king_pos, player) {
                 if let Some(piece) = board.get_piece(pos) {
                     if piece.player == player {
-                        let piece_hash = (piece.piece_type as u8 as u64) << 8;
+                        let piece_hash = (piece.piece_type as u8 as u64 + 1) << 8;
+                        hash ^= piece_hash
+                            .wrapping_mul(pos.row as u64 + 1)
+                            .wrapping_mul(pos.col as u64 + 1);
+                    }
+                }
+            }
+        }
+
+        // Hash the remaining squares the castle patterns look at
+        for offset in &self.outer_offsets {
+            if let Some(pos) = offset.to_absolute(king_pos, player) {
+                if let Some(piece) = board.get_piece(pos) {
+                    if piece.player == player {
+                        let piece_hash = (piece.piece_type as u8 as u64 + 1) << 16;
                         hash ^= piece_hash
                             .wrapping_mul(pos.row as u64 + 1)
                             .wrapping_mul(pos.col as u64 + 1);
@@ -384,14 +427,13 @@ impl CastleRecognizer {
         }
 
         let mut best_entry: Option<CachedEvaluation> = None;
+        let zone_metrics = ZoneMetrics::evaluate(board, player, king_pos);
 
         for (pattern_index, pattern) in self.patterns.iter().enumerate() {
             for (variant_index, variant) in pattern.variants.iter().enumerate() {
                 let totals = VariantTotals::from_variant(variant);
                 let stats = self.analyze_variant(board, player, king_pos, variant);
 
-                let zone_metrics = ZoneMetrics::evaluate(board, player, king_pos);
-
                 let base_quality = self.calculate_match_quality(
                     stats.matches,
                     totals.total_pieces,
@@ -917,7 +959,7 @@ mod tests {
     #[test]
     fn test_castle_recognizer_creation() {
         let recognizer = CastleRecognizer::new();
-        assert_eq!(recognizer.patterns.len(), 3);
+        assert_eq!(recognizer.patterns.len(), 10);
     }
 
     #[test]
@@ -1266,6 +1308,28 @@ mod tests {
                                      // (different keys)
     }
 
+    #[test]
+    fn test_cache_key_covers_pawns_and_outer_pattern_squares() {
+        let recognizer = CastleRecognizer::new();
+        let king_pos = Position::new(7, 7);
+        let mut board = BitboardBoard::empty();
+        board.place_piece(Piece::new(PieceType::King, Player::Black), king_pos);
+        board.place_piece(Piece::new(PieceType::Silver, Player::Black), Position::new(7, 6));
+        recognizer.evaluate_castle(&board, Player::Black, king_pos);
+
+        // A pawn in the buffer ring changes the key
+        board.place_piece(Piece::new(PieceType::Pawn, Player::Black), Position::new(5, 6));
+        recognizer.evaluate_castle(&board, Player::Black, king_pos);
+
+        // So does the Diamond Mino gold on 5八, outside both rings
+        board.place_piece(Piece::new(PieceType::Gold, Player::Black), Position::new(7, 4));
+        recognizer.evaluate_castle(&board, Player::Black, king_pos);
+
+        let stats = recognizer.get_cache_stats();
+        assert_eq!(stats.misses, 3);
+        assert_eq!(stats.hits, 0);
+    }
+
     #[test]
     fn test_cache_hit_and_miss() {
         let recognizer = CastleRecognizer::new();
@@ -27,6 +27,7 @@
 //! ```
 
 use crate::evaluation::advanced_interpolation::AdvancedInterpolationConfig;
+use crate::evaluation::king_safety::KingDangerConfig;
 use crate::evaluation::material::MaterialEvaluationConfig;
 use crate::evaluation::phase_transition::{InterpolationMethod, PhaseTransitionConfig};
 use crate::evaluation::position_features::PositionFeatureConfig;
@@ -595,6 +596,7 @@ impl TaperedEvalConfig {
                 enable_mobility: false, // Expensive, disable for speed
                 enable_center_control: true,
                 enable_development: true,
+                king_danger: KingDangerConfig::default(),
             },
             base: TaperedEvaluationConfig::performance_optimized(),
             weights: EvaluationWeights::default(),
@@ -629,6 +631,7 @@ impl TaperedEvalConfig {
                 enable_mobility: true,
                 enable_center_control: true,
                 enable_development: true,
+                king_danger: KingDangerConfig::default(),
             },
             base: TaperedEvaluationConfig::default(),
             weights: EvaluationWeights {
@@ -673,6 +676,7 @@ impl TaperedEvalConfig {
                 enable_mobility: false,
                 enable_center_control: true,
                 enable_development: false,
+                king_danger: KingDangerConfig::default(),
             },
             base: TaperedEvaluationConfig::memory_optimized(),
             weights: EvaluationWeights::default(),
@@ -0,0 +1,324 @@
+//! Entering-King (入玉) Evaluation
+//!
+//! Once both kings head for the opponent's promotion zone, mating attacks stop
+//! working and the game is decided by impasse points instead: rooks and
+//! bishops (promoted or not) count 5, every other piece 1, kings nothing. The
+//! usual king-safety and material terms then say little about who is winning.
+//!
+//! This module scores such positions with a dedicated model:
+//! - King advancement toward, and entry into, the enemy camp
+//! - Pieces in the enemy camp (a declaration needs ten besides the king)
+//! - Impasse points, with bonuses for reaching the 24-point draw line and the
+//!   declaration targets (28 points for Black, 27 for White)
+//! - Pieces blocking the opponent king's path into our own camp
+//!
+//! [`EnteringKingEvaluator::entering_factor`] measures how far both kings
+//! have advanced; the integrated evaluator blends the regular tapered total
+//! toward this model by that factor before the usual phase interpolation.
+
+use crate::bitboards::BitboardBoard;
+use crate::types::board::CapturedPieces;
+use crate::types::core::{PieceType, Player, Position};
+use crate::types::evaluation::TaperedScore;
+use serde::{Deserialize, Serialize};
+
+/// Points each side needs to avoid losing under the 24-point rule
+pub const IMPASSE_DRAW_POINTS: i32 = 24;
+
+/// Pieces other than the king that must stand in the enemy camp to declare
+pub const DECLARATION_CAMP_PIECES: u8 = 10;
+
+/// Points needed to declare a win under the 27-point rule
+pub fn declaration_points_target(player: Player) -> i32 {
+    match player {
+        Player::Black => 28,
+        Player::White => 27,
+    }
+}
+
+/// Configuration of the entering-king model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EnteringKingConfig {
+    /// Blend toward this model when both kings advance
+    pub enabled: bool,
+    /// Ranks both kings must have advanced before the model starts to blend in
+    pub activation_start_ranks: u8,
+    /// Ranks both kings must have advanced for the model to replace the
+    /// regular evaluation entirely (6 = inside the promotion zone)
+    pub activation_full_ranks: u8,
+    /// Per rank the king has advanced from its own back rank
+    pub king_advance_per_rank: TaperedScore,
+    /// Once the king stands in the enemy camp
+    pub king_in_camp_bonus: TaperedScore,
+    /// Per piece in the enemy camp, counting at most the ten a declaration
+    /// needs
+    pub camp_piece_bonus: TaperedScore,
+    /// Per impasse point on the board and in hand
+    pub point_value: TaperedScore,
+    /// Once the side has the 24 points that avoid an impasse loss
+    pub draw_points_bonus: TaperedScore,
+    /// Once the side meets every declaration condition
+    pub declaration_bonus: TaperedScore,
+    /// Per piece in our camp standing in the opponent king's path, counting at
+    /// most `max_blockers`
+    pub blocker_bonus: TaperedScore,
+    pub max_blockers: u8,
+}
+
+impl Default for EnteringKingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            activation_start_ranks: 3,
+            activation_full_ranks: 6,
+            king_advance_per_rank: TaperedScore::new_tapered(10, 15),
+            king_in_camp_bonus: TaperedScore::new_tapered(150, 200),
+            camp_piece_bonus: TaperedScore::new_tapered(20, 25),
+            point_value: TaperedScore::new_tapered(90, 110),
+            draw_points_bonus: TaperedScore::new(300),
+            declaration_bonus: TaperedScore::new(600),
+            blocker_bonus: TaperedScore::new_tapered(25, 30),
+            max_blockers: 6,
+        }
+    }
+}
+
+/// Per-side quantities the model is computed from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct EnteringKingInputs {
+    /// Ranks the king has advanced from its own back rank (0-8)
+    pub king_progress: u8,
+    /// Pieces other than the king in the enemy camp
+    pub camp_pieces: u8,
+    /// Impasse points of every piece on the board and in hand
+    pub points: i32,
+    /// Points counted for a declaration: pieces in the enemy camp and in hand
+    pub declaration_points: i32,
+    /// Pieces in our own camp on or beside the opponent king's file, ahead of
+    /// it
+    pub blockers: u8,
+}
+
+impl EnteringKingInputs {
+    pub fn compute(
+        board: &BitboardBoard,
+        player: Player,
+        captured_pieces: &CapturedPieces,
+    ) -> Self {
+        let mut inputs = Self {
+            king_progress: board.find_king_position(player).map_or(0, |pos| progress(player, pos)),
+            points: board.count_impasse_points(player, captured_pieces),
+            ..Self::default()
+        };
+        let opponent_king = board.find_king_position(player.opposite());
+
+        for (pos, piece) in board.iter_pieces() {
+            if piece.player != player || piece.piece_type == PieceType::King {
+                continue;
+            }
+            if in_enemy_camp(player, pos) {
+                inputs.camp_pieces += 1;
+                inputs.declaration_points += point_value(piece.piece_type);
+            }
+            if let Some(king_pos) = opponent_king {
+                if blocks_entry(player, pos, king_pos) {
+                    inputs.blockers += 1;
+                }
+            }
+        }
+
+        let hand = match player {
+            Player::Black => &captured_pieces.black,
+            Player::White => &captured_pieces.white,
+        };
+        inputs.declaration_points +=
+            hand.iter().map(|&piece_type| point_value(piece_type)).sum::<i32>();
+        inputs
+    }
+
+    pub fn king_in_camp(&self) -> bool {
+        self.king_progress >= 6
+    }
+
+    /// Whether the side could declare a win (ignoring whether it is in check)
+    pub fn can_declare(&self, player: Player) -> bool {
+        self.king_in_camp()
+            && self.camp_pieces >= DECLARATION_CAMP_PIECES
+            && self.declaration_points >= declaration_points_target(player)
+    }
+}
+
+/// Entering-king evaluator
+#[derive(Debug, Clone, Default)]
+pub struct EnteringKingEvaluator {
+    config: EnteringKingConfig,
+}
+
+impl EnteringKingEvaluator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_config(config: EnteringKingConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn config(&self) -> &EnteringKingConfig {
+        &self.config
+    }
+
+    /// How far the position is into an entering-king phase, from 0.0 (at
+    /// least one king has not left home) to 1.0 (both kings have advanced
+    /// `activation_full_ranks`)
+    pub fn entering_factor(&self, board: &BitboardBoard) -> f32 {
+        if !self.config.enabled {
+            return 0.0;
+        }
+        let (Some(black), Some(white)) =
+            (board.find_king_position(Player::Black), board.find_king_position(Player::White))
+        else {
+            return 0.0;
+        };
+        let advanced = progress(Player::Black, black).min(progress(Player::White, white)) as f32;
+        let start = self.config.activation_start_ranks as f32;
+        let full = (self.config.activation_full_ranks as f32).max(start + 1.0);
+        ((advanced - start) / (full - start)).clamp(0.0, 1.0)
+    }
+
+    /// Score of the model from `player`'s point of view
+    pub fn evaluate(
+        &self,
+        board: &BitboardBoard,
+        player: Player,
+        captured_pieces: &CapturedPieces,
+    ) -> TaperedScore {
+        self.evaluate_side(board, player, captured_pieces)
+            - self.evaluate_side(board, player.opposite(), captured_pieces)
+    }
+
+    fn evaluate_side(
+        &self,
+        board: &BitboardBoard,
+        player: Player,
+        captured_pieces: &CapturedPieces,
+    ) -> TaperedScore {
+        let config = &self.config;
+        let inputs = EnteringKingInputs::compute(board, player, captured_pieces);
+
+        let mut score = config.king_advance_per_rank * inputs.king_progress as f32
+            + config.camp_piece_bonus * inputs.camp_pieces.min(DECLARATION_CAMP_PIECES) as f32
+            + config.point_value * inputs.points as f32
+            + config.blocker_bonus * inputs.blockers.min(config.max_blockers) as f32;
+        if inputs.king_in_camp() {
+            score += config.king_in_camp_bonus;
+        }
+        if inputs.points >= IMPASSE_DRAW_POINTS {
+            score += config.draw_points_bonus;
+        }
+        if inputs.can_declare(player) {
+            score += config.declaration_bonus;
+        }
+        score
+    }
+}
+
+/// Impasse points of a piece: 5 for rooks and bishops, promoted or not
+fn point_value(piece_type: PieceType) -> i32 {
+    match piece_type {
+        PieceType::Rook
+        | PieceType::PromotedRook
+        | PieceType::Bishop
+        | PieceType::PromotedBishop => 5,
+        PieceType::King => 0,
+        _ => 1,
+    }
+}
+
+/// Ranks advanced from the player's own back rank
+fn progress(player: Player, pos: Position) -> u8 {
+    match player {
+        Player::Black => 8 - pos.row,
+        Player::White => pos.row,
+    }
+}
+
+fn in_enemy_camp(player: Player, pos: Position) -> bool {
+    progress(player, pos) >= 6
+}
+
+/// A piece of `player` in its own camp, within one file of the opponent king
+/// and between that king and `player`'s back rank
+fn blocks_entry(player: Player, pos: Position, opponent_king: Position) -> bool {
+    let in_own_camp = progress(player, pos) <= 2;
+    let ahead_of_king = progress(player, pos) < progress(player, opponent_king);
+    in_own_camp && ahead_of_king && pos.col.abs_diff(opponent_king.col) <= 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Both kings in the enemy camp; Black meets every declaration condition
+    /// (ten pieces in camp, 28 points), White has 26 points and nine pieces
+    const DECLARATION: &str = "+P+P+P+P+P+P+P+P+P/+R3K4/9/9/9/9/9/4k4/+p+p+p+p+p+p+p+r+b b \
+                               B2G2S2NL2P2g2s2n3l 150";
+
+    fn position(sfen: &str) -> (BitboardBoard, Player, CapturedPieces) {
+        BitboardBoard::from_fen(sfen).unwrap()
+    }
+
+    #[test]
+    fn test_entering_factor_follows_the_less_advanced_king() {
+        let evaluator = EnteringKingEvaluator::new();
+        assert_eq!(evaluator.entering_factor(&BitboardBoard::new()), 0.0);
+
+        let (board, _, _) = position(DECLARATION);
+        assert_eq!(evaluator.entering_factor(&board), 1.0);
+
+        // Both kings on the fifth rank: four ranks advanced
+        let (board, _, _) = position("9/9/9/9/4K4/4k4/9/9/9 b - 1");
+        assert!((evaluator.entering_factor(&board) - 1.0 / 3.0).abs() < 1e-6);
+
+        let disabled = EnteringKingEvaluator::with_config(EnteringKingConfig {
+            enabled: false,
+            ..EnteringKingConfig::default()
+        });
+        let (board, _, _) = position(DECLARATION);
+        assert_eq!(disabled.entering_factor(&board), 0.0);
+    }
+
+    #[test]
+    fn test_declaration_inputs() {
+        let (board, _, captured) = position(DECLARATION);
+        let black = EnteringKingInputs::compute(&board, Player::Black, &captured);
+        assert_eq!(black.camp_pieces, 10);
+        assert_eq!(black.points, 28);
+        assert_eq!(black.declaration_points, 28);
+        assert!(black.can_declare(Player::Black));
+
+        let white = EnteringKingInputs::compute(&board, Player::White, &captured);
+        assert_eq!(white.camp_pieces, 9);
+        assert_eq!(white.points, 26);
+        assert!(!white.can_declare(Player::White));
+
+        let evaluator = EnteringKingEvaluator::new();
+        let score = evaluator.evaluate(&board, Player::Black, &captured);
+        assert!(score.mg > 0 && score.eg > 0);
+        assert_eq!(evaluator.evaluate(&board, Player::White, &captured), -score);
+    }
+
+    #[test]
+    fn test_blocking_the_opponent_king_is_rewarded() {
+        let evaluator = EnteringKingEvaluator::new();
+        // White's king on 5五 heading for Black's camp
+        let (open, player, captured) = position("9/9/9/9/4k4/9/9/9/4K4 w G 1");
+        let (blocked, _, _) = position("9/9/9/9/4k4/9/9/4G4/4K4 w - 1");
+        let none = CapturedPieces::new();
+        assert_eq!(EnteringKingInputs::compute(&blocked, Player::Black, &none).blockers, 1);
+
+        let open_score = evaluator.evaluate(&open, player, &captured);
+        let blocked_score = evaluator.evaluate(&blocked, player, &none);
+        assert!(blocked_score.mg < open_score.mg);
+    }
+}
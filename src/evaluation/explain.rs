@@ -0,0 +1,225 @@
+//! Evaluation Explanation
+//!
+//! Per-term breakdown of the evaluation of a single position, for debugging
+//! and tuning. [`IntegratedEvaluator::explain`] records every weighted term
+//! of the classical evaluation as an [`EvalTerm`] with its middlegame and
+//! endgame parts, the value it contributes at the position's phase, and any
+//! sub-terms (board and hand material, the position features, ...). The top
+//! level terms add up exactly to the tapered total, which the phase
+//! interpolation turns into the final score.
+//!
+//! [`IntegratedEvaluator::explain`]: super::integration::IntegratedEvaluator::explain
+
+use crate::evaluation::phase_transition::InterpolationMethod;
+use crate::types::core::Player;
+use crate::types::evaluation::{TaperedScore, GAME_PHASE_MAX};
+use serde::Serialize;
+use std::fmt::Write;
+
+/// One named term of an evaluation
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EvalTerm {
+    pub name: String,
+    /// Middlegame part, after weighting
+    pub mg: i32,
+    /// Endgame part, after weighting
+    pub eg: i32,
+    /// Linear interpolation of `mg` and `eg` at the position's phase
+    pub value: i32,
+    /// Extra information, such as the recognized castle
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Parts the term is made of
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<EvalTerm>,
+}
+
+impl EvalTerm {
+    pub fn new(name: &str, score: TaperedScore, phase: i32) -> Self {
+        Self {
+            name: name.to_string(),
+            mg: score.mg,
+            eg: score.eg,
+            value: score.interpolate(phase),
+            detail: None,
+            children: Vec::new(),
+        }
+    }
+
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    pub fn with_children(mut self, children: Vec<EvalTerm>) -> Self {
+        self.children = children;
+        self
+    }
+
+    pub fn score(&self) -> TaperedScore {
+        TaperedScore { mg: self.mg, eg: self.eg }
+    }
+}
+
+/// Full breakdown of the evaluation of one position
+#[derive(Debug, Clone, Serialize)]
+pub struct EvalExplanation {
+    /// Scores are from this side's point of view
+    pub side_to_move: Player,
+    /// "classical", "nnue" or "legacy"
+    pub evaluator: &'static str,
+    /// Game phase, from 0 (bare kings) to `GAME_PHASE_MAX` (opening)
+    pub phase: i32,
+    /// How the tapered total is turned into the final score
+    pub interpolation: InterpolationMethod,
+    /// Top-level classical terms in evaluation order
+    pub terms: Vec<EvalTerm>,
+    /// Sum of the top-level terms
+    pub total: TaperedScore,
+    /// Interpolated classical score
+    pub classical_score: i32,
+    /// Network score, when an NNUE network evaluates the position
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nnue_score: Option<i32>,
+    /// Score the search uses
+    pub score: i32,
+}
+
+impl EvalExplanation {
+    /// Find a term by name, searching sub-terms depth first
+    pub fn term(&self, name: &str) -> Option<&EvalTerm> {
+        fn find<'a>(terms: &'a [EvalTerm], name: &str) -> Option<&'a EvalTerm> {
+            terms.iter().find_map(|term| {
+                if term.name == name {
+                    Some(term)
+                } else {
+                    find(&term.children, name)
+                }
+            })
+        }
+        find(&self.terms, name)
+    }
+
+    /// Fixed-width table, one line per term with sub-terms indented
+    pub fn to_table(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "Evaluation for {:?} ({}), phase {}/{}",
+            self.side_to_move, self.evaluator, self.phase, GAME_PHASE_MAX
+        );
+        let _ = writeln!(out, "{:<24} {:>8} {:>8} {:>8}  detail", "term", "mg", "eg", "value");
+        for term in &self.terms {
+            write_term(&mut out, term, 0);
+        }
+        let _ = writeln!(
+            out,
+            "{:<24} {:>8} {:>8} {:>8}",
+            "total",
+            self.total.mg,
+            self.total.eg,
+            self.total.interpolate(self.phase)
+        );
+        let _ = writeln!(
+            out,
+            "{:<24} {:>26}  {:?}",
+            "phase interpolation", self.classical_score, self.interpolation
+        );
+        if let Some(nnue_score) = self.nnue_score {
+            let _ = writeln!(out, "{:<24} {:>26}", "nnue", nnue_score);
+        }
+        let _ = write!(out, "{:<24} {:>26}", "final", self.score);
+        out
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+fn write_term(out: &mut String, term: &EvalTerm, depth: usize) {
+    let name = format!("{}{}", "  ".repeat(depth), term.name);
+    let _ = write!(out, "{:<24} {:>8} {:>8} {:>8}", name, term.mg, term.eg, term.value);
+    if let Some(detail) = &term.detail {
+        let _ = write!(out, "  {}", detail);
+    }
+    out.push('\n');
+    for child in &term.children {
+        write_term(out, child, depth + 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitboards::BitboardBoard;
+    use crate::evaluation::integration::IntegratedEvaluator;
+    use crate::evaluation::PositionEvaluator;
+
+    const MIDGAME: &str =
+        "ln1g1k1nl/1r1s2gb1/p1ppppspp/1p4p2/9/2P1P4/PPBP1PPPP/2G1SS1R1/LN2KG1NL b P 21";
+
+    #[test]
+    fn test_terms_add_up_to_the_score() {
+        let (board, player, captured) = BitboardBoard::from_fen(MIDGAME).unwrap();
+        let mut evaluator = IntegratedEvaluator::new();
+        let explanation = evaluator.explain(&board, player, &captured);
+
+        let mut total = TaperedScore::default();
+        for term in &explanation.terms {
+            total += term.score();
+            if !term.children.is_empty() {
+                let mut children = TaperedScore::default();
+                for child in &term.children {
+                    children += child.score();
+                }
+                assert_eq!(children, term.score(), "children of {}", term.name);
+            }
+        }
+        assert_eq!(total, explanation.total);
+        assert_eq!(explanation.evaluator, "classical");
+        assert_eq!(explanation.score, explanation.classical_score);
+
+        let hand = explanation.term("hand").unwrap();
+        assert!(hand.mg > 0, "black holds a pawn in hand");
+        assert!(explanation.term("castle").unwrap().detail.is_some());
+    }
+
+    #[test]
+    fn test_explanation_matches_evaluate() {
+        let (board, player, captured) = BitboardBoard::from_fen(MIDGAME).unwrap();
+        let mut evaluator = PositionEvaluator::new();
+        let explanation = evaluator.explain(&board, player, &captured);
+        assert_eq!(explanation.score, evaluator.evaluate(&board, player, &captured));
+
+        let table = explanation.to_table();
+        assert!(table.contains("king_safety"));
+        assert!(table.lines().all(|line| line == line.trim_end()));
+        let json: serde_json::Value =
+            serde_json::from_str(&explanation.to_json().unwrap()).unwrap();
+        assert_eq!(json["score"], explanation.score);
+        assert_eq!(json["terms"][0]["name"], "material");
+    }
+
+    #[test]
+    fn test_entering_king_blend_is_a_term() {
+        // Both kings deep in the enemy camp
+        let (board, player, captured) = BitboardBoard::from_fen(
+            "+P+P+P+P+P+P+P+P+P/+R3K4/9/9/9/9/9/4k4/+p+p+p+p+p+p+p+r+b b B2G2S2NL2P2g2s2n3l 150",
+        )
+        .unwrap();
+        let mut evaluator = IntegratedEvaluator::new();
+        let explanation = evaluator.explain(&board, player, &captured);
+
+        let entering = explanation.term("entering_king").unwrap();
+        assert_eq!(entering.detail.as_deref(), Some("blend 1.00"));
+        assert!(explanation.term("impasse_model").unwrap().value > 0);
+        let total =
+            explanation.terms.iter().fold(TaperedScore::default(), |acc, t| acc + t.score());
+        assert_eq!(total, explanation.total);
+
+        let midgame = BitboardBoard::from_fen(MIDGAME).unwrap();
+        let explanation = evaluator.explain(&midgame.0, midgame.1, &midgame.2);
+        assert!(explanation.term("entering_king").is_none());
+    }
+}
@@ -244,8 +244,12 @@ use crate::evaluation::{
     component_coordinator::{ComponentContributionTracker, ComponentCoordination},
     config::EvaluationWeights,
     endgame_patterns::EndgamePatternEvaluator,
+    entering_king::{EnteringKingConfig, EnteringKingEvaluator},
+    explain::{EvalExplanation, EvalTerm},
+    king_safety::KingDangerConfig,
     material::{MaterialEvaluationConfig, MaterialEvaluationStats, MaterialEvaluator},
     opening_principles::OpeningPrincipleEvaluator,
+    opening_strategy::classify_opening,
     performance::OptimizedEvaluator,
     phase_transition::PhaseTransition,
     piece_square_tables::PieceSquareTables,
@@ -259,7 +263,8 @@ use crate::evaluation::{
 // use crate::tuning::OptimizationMethod; // Unused
 use crate::types::board::CapturedPieces;
 use crate::types::core::{PieceType, Player, Position};
-use crate::types::evaluation::TaperedScore;
+use crate::types::evaluation::{TaperedScore, GAME_PHASE_MAX, PIECE_PHASE_VALUES};
+use crate::types::handicap::Handicap;
 // use serde::{Deserialize, Serialize}; // Unused
 pub use crate::evaluation::weight_tuning::{
     ConvergenceReason, TuningConfig, TuningPosition, TuningPositionSet, TuningResult,
@@ -329,6 +334,8 @@ pub struct IntegratedEvaluator {
     positional_patterns: PositionalPatternAnalyzer,
     /// Castle pattern recognizer (Task 17.0 - Task 1.0)
     castle_recognizer: CastleRecognizer,
+    /// Impasse-point model for entering-king phases
+    entering_king: EnteringKingEvaluator,
     /// Optimized evaluator (for performance mode)
     // Note: Pattern caching is handled per-module. Individual pattern recognizers
     // (CastleRecognizer, TacticalPatternRecognizer, etc.) maintain their own internal
@@ -345,6 +352,10 @@ pub struct IntegratedEvaluator {
     eval_cache: HashMap<u64, CachedEvaluation>,
     /// Phase history for phase-aware validation (Task 20.0 - Task 5.14)
     phase_history: Vec<i32>,
+    /// Phase of the pieces removed by the game's handicap
+    handicap_phase: i32,
+    /// Terms recorded by the evaluation, while `explain` runs it
+    explain_terms: Option<Vec<EvalTerm>>,
 }
 
 impl IntegratedEvaluator {
@@ -417,12 +428,15 @@ impl IntegratedEvaluator {
             tactical_patterns: TacticalPatternRecognizer::with_config(config.tactical.clone()),
             positional_patterns: PositionalPatternAnalyzer::new(),
             castle_recognizer: CastleRecognizer::new(),
+            entering_king: EnteringKingEvaluator::with_config(config.entering_king.clone()),
             optimized_eval,
             statistics: EvaluationStatistics::new(),
             telemetry: None,
             phase_cache: HashMap::new(),
             eval_cache: HashMap::new(),
             phase_history: Vec::new(), // Task 20.0 - Task 5.14
+            handicap_phase: 0,
+            explain_terms: None,
         };
 
         evaluator
@@ -511,6 +525,40 @@ impl IntegratedEvaluator {
         result
     }
 
+    /// Evaluate without the cache, recording every weighted term
+    pub fn explain(
+        &mut self,
+        board: &BitboardBoard,
+        player: Player,
+        captured_pieces: &CapturedPieces,
+    ) -> EvalExplanation {
+        self.explain_terms = Some(Vec::new());
+        let result = self.evaluate_standard(board, player, captured_pieces, None);
+        let terms = self.explain_terms.take().unwrap_or_default();
+        let mut total = TaperedScore::default();
+        for term in &terms {
+            total += term.score();
+        }
+        EvalExplanation {
+            side_to_move: player,
+            evaluator: "classical",
+            phase: result.phase,
+            interpolation: self.phase_transition.config().default_method,
+            terms,
+            total,
+            classical_score: result.score,
+            nnue_score: None,
+            score: result.score,
+        }
+    }
+
+    /// Add a term to the explanation being recorded, if any
+    fn record_term(&mut self, term: impl FnOnce() -> EvalTerm) {
+        if let Some(terms) = self.explain_terms.as_mut() {
+            terms.push(term());
+        }
+    }
+
     /// Update statistics from an evaluation result
     ///
     /// This method should be called after `evaluate()` if statistics tracking
@@ -563,8 +611,9 @@ impl IntegratedEvaluator {
         move_count: Option<u32>,
     ) -> EvaluationResult {
         let stats_enabled = self.statistics.is_enabled();
-        // Calculate phase
-        let phase = self.calculate_phase_cached(board, captured_pieces);
+        // Calculate phase; a handicap opening still counts as an opening
+        let phase = (self.calculate_phase_cached(board, captured_pieces) + self.handicap_phase)
+            .min(GAME_PHASE_MAX);
 
         // Track component scores for result
         let mut component_scores = HashMap::new();
@@ -578,6 +627,16 @@ impl IntegratedEvaluator {
             temp_config.apply_phase_scaling(&mut weights, phase);
         }
 
+        // Adapt weights to the recognised opening strategy until the endgame
+        if self.config.enable_strategy_weights
+            && phase >= self.config.phase_boundaries.endgame_threshold
+        {
+            classify_opening(board, captured_pieces)
+                .family
+                .weight_multipliers()
+                .apply(&mut weights);
+        }
+
         // Clamp weights to valid range (0.0-10.0) if needed
         weights.material_weight = weights.material_weight.clamp(0.0, 10.0);
         weights.position_weight = weights.position_weight.clamp(0.0, 10.0);
@@ -604,8 +663,18 @@ impl IntegratedEvaluator {
 
         // Material
         if self.config.components.material {
-            let material_score =
-                self.material_eval.evaluate_material(board, player, captured_pieces);
+            let (board_material, hand_material) =
+                self.material_eval.evaluate_material_parts(board, player, captured_pieces);
+            let drop_potential =
+                self.material_eval.evaluate_drop_potential(board, player, captured_pieces);
+            let material_score = board_material + hand_material + drop_potential;
+            self.record_term(|| {
+                EvalTerm::new("material", material_score, phase).with_children(vec![
+                    EvalTerm::new("board", board_material, phase),
+                    EvalTerm::new("hand", hand_material, phase),
+                    EvalTerm::new("drop_potential", drop_potential, phase),
+                ])
+            });
 
             // Task 5.0 - Task 5.5a, 5.5b: Validate zero scores from enabled components
             if self.config.enable_component_validation && material_score == TaperedScore::default()
@@ -638,6 +707,7 @@ impl IntegratedEvaluator {
             }
 
             total += pst_score;
+            self.record_term(|| EvalTerm::new("pst", pst_score, phase));
             pst_telemetry = Some(telemetry);
             if stats_enabled {
                 component_scores.insert("pst".to_string(), pst_score);
@@ -783,6 +853,15 @@ impl IntegratedEvaluator {
                 position_feature_stats_snapshot = Some(self.position_features.stats().clone());
             }
             self.position_features.end_evaluation();
+            self.record_term(|| {
+                EvalTerm::new("position_features", pf_total, phase).with_children(vec![
+                    EvalTerm::new("king_safety", king_safety_weighted, phase),
+                    EvalTerm::new("pawn_structure", pawn_weighted, phase),
+                    EvalTerm::new("mobility", mobility_weighted, phase),
+                    EvalTerm::new("center_control", center_weighted, phase),
+                    EvalTerm::new("development", dev_weighted, phase),
+                ])
+            });
 
             // Track position_features aggregate contribution for telemetry
             if stats_enabled {
@@ -822,6 +901,7 @@ impl IntegratedEvaluator {
             opening_score = opening_score * coordination.opening_fade_factor;
 
             total += opening_score;
+            self.record_term(|| EvalTerm::new("opening_principles", opening_score, phase));
         }
 
         // Endgame patterns (if in endgame)
@@ -854,6 +934,7 @@ impl IntegratedEvaluator {
                 }
 
                 total += endgame_score;
+                self.record_term(|| EvalTerm::new("endgame", endgame_score, phase));
             }
         }
 
@@ -886,6 +967,9 @@ impl IntegratedEvaluator {
             }
 
             total += tactical_score * weights.tactical_weight;
+            self.record_term(|| {
+                EvalTerm::new("tactics", tactical_score * weights.tactical_weight, phase)
+            });
             // Track contribution for telemetry
             if stats_enabled {
                 let tactical_interp =
@@ -952,6 +1036,9 @@ impl IntegratedEvaluator {
             }
 
             total += positional_score * weights.positional_weight;
+            self.record_term(|| {
+                EvalTerm::new("positional", positional_score * weights.positional_weight, phase)
+            });
             // Track contribution for telemetry
             if stats_enabled {
                 let positional_interp =
@@ -968,6 +1055,7 @@ impl IntegratedEvaluator {
         // castle formation patterns. These are complementary and should both be
         // enabled for comprehensive king safety evaluation.
         if self.config.components.castle_patterns {
+            let mut castle_detail = None;
             let castle_score = {
                 // Find king position for castle evaluation
                 if let Some(king_pos) = board.find_king_position(player) {
@@ -975,6 +1063,12 @@ impl IntegratedEvaluator {
                     if stats_enabled {
                         castle_cache_stats = Some(self.castle_recognizer.get_cache_stats());
                     }
+                    if self.explain_terms.is_some() {
+                        castle_detail = Some(match eval.matched_pattern {
+                            Some(name) => format!("{} (quality {:.2})", name, eval.quality),
+                            None => "no castle".to_string(),
+                        });
+                    }
                     eval.score()
                 } else {
                     TaperedScore::default()
@@ -1001,6 +1095,13 @@ impl IntegratedEvaluator {
             }
 
             total += castle_score * weights.castle_weight;
+            self.record_term(|| {
+                let term = EvalTerm::new("castle", castle_score * weights.castle_weight, phase);
+                match castle_detail {
+                    Some(detail) => term.with_detail(detail),
+                    None => term,
+                }
+            });
             // Track contribution for telemetry
             if stats_enabled {
                 let castle_interp =
@@ -1009,6 +1110,30 @@ impl IntegratedEvaluator {
             }
         }
 
+        // Entering-king phases: as both kings advance, blend the total toward
+        // the impasse-point model; the blend is tapered like every other term
+        let entering_factor = self.entering_king.entering_factor(board);
+        if entering_factor > 0.0 {
+            let model = self.entering_king.evaluate(board, player, captured_pieces);
+            let blended = total * (1.0 - entering_factor) + model * entering_factor;
+            self.record_term(|| {
+                EvalTerm::new("entering_king", blended - total, phase)
+                    .with_detail(format!("blend {:.2}", entering_factor))
+                    .with_children(vec![
+                        EvalTerm::new("impasse_model", model * entering_factor, phase),
+                        EvalTerm::new(
+                            "regular_terms",
+                            blended - total - model * entering_factor,
+                            phase,
+                        ),
+                    ])
+            });
+            total = blended;
+            if stats_enabled {
+                component_scores.insert("entering_king".to_string(), model);
+            }
+        }
+
         // Interpolate to final score
         let final_score = self.phase_transition.interpolate_default(total, phase);
 
@@ -1378,6 +1503,21 @@ impl IntegratedEvaluator {
         hash
     }
 
+    /// Set the starting array of the game. The phase of the pieces the
+    /// handicap removed is added back when computing the game phase.
+    pub fn set_handicap(&mut self, handicap: Handicap) {
+        let removed_phase: i32 = handicap
+            .removed_pieces()
+            .iter()
+            .filter_map(|piece_type| {
+                PIECE_PHASE_VALUES.iter().find(|(pt, _)| pt == piece_type).map(|(_, value)| *value)
+            })
+            .sum();
+        // The even array is worth 30 phase units
+        self.handicap_phase = removed_phase * GAME_PHASE_MAX / 30;
+        self.clear_caches();
+    }
+
     /// Clear all caches
     pub fn clear_caches(&mut self) {
         self.phase_cache.clear();
@@ -1443,6 +1583,13 @@ impl IntegratedEvaluator {
         self.set_config(updated);
     }
 
+    /// Update the king-danger model used by king safety.
+    pub fn update_king_danger_config(&mut self, king_danger: KingDangerConfig) {
+        let mut updated = self.config.clone();
+        updated.position_features.king_danger = king_danger;
+        self.set_config(updated);
+    }
+
     /// Retrieve material evaluation statistics.
     pub fn material_statistics(&self) -> MaterialEvaluationStats {
         self.material_eval.stats().clone()
@@ -1474,6 +1621,7 @@ impl IntegratedEvaluator {
             self.tactical_patterns.set_config(config.tactical.clone());
         }
 
+        self.entering_king = EnteringKingEvaluator::with_config(config.entering_king.clone());
         self.weights = config.weights.clone();
 
         let pst_tables = match PieceSquareTableLoader::load(&config.pst) {
@@ -1578,11 +1726,17 @@ pub struct IntegratedEvaluationConfig {
     pub position_features: PositionFeatureConfig,
     /// Tactical pattern configuration
     pub tactical: TacticalConfig,
+    /// Entering-king (impasse) model configuration
+    pub entering_king: EnteringKingConfig,
     /// Evaluation weights for combining features
     pub weights: EvaluationWeights,
     /// Enable phase-dependent weight scaling (default: false for backward
     /// compatibility)
     pub enable_phase_dependent_weights: bool,
+    /// Scale weights by the opening strategy recognised on the board
+    /// (see `opening_strategy::OpeningFamily::weight_multipliers`; default:
+    /// false)
+    pub enable_strategy_weights: bool,
     /// Threshold for logging large weight contributions in centipawns (default:
     /// 1000.0)
     pub weight_contribution_threshold: f32,
@@ -1634,8 +1788,10 @@ impl Default for IntegratedEvaluationConfig {
             pst: PieceSquareTableConfig::default(),
             position_features: PositionFeatureConfig::default(),
             tactical: TacticalConfig::default(),
+            entering_king: EnteringKingConfig::default(),
             weights: EvaluationWeights::default(),
             enable_phase_dependent_weights: false,
+            enable_strategy_weights: false,
             weight_contribution_threshold: 1000.0,
             large_contribution_threshold: 0.20,
             enable_component_validation: false,
@@ -2062,6 +2218,20 @@ mod tests {
         assert!(evaluator.phase_cache.len() > 0);
     }
 
+    #[test]
+    fn test_handicap_opening_phase() {
+        let mut evaluator = IntegratedEvaluator::new();
+        let (board, player, captured_pieces) =
+            BitboardBoard::from_fen(Handicap::TenPiece.sfen()).unwrap();
+
+        let unadjusted = evaluator.evaluate(&board, player, &captured_pieces);
+        assert!(unadjusted.phase < GAME_PHASE_MAX);
+
+        evaluator.set_handicap(Handicap::TenPiece);
+        let adjusted = evaluator.evaluate(&board, player, &captured_pieces);
+        assert_eq!(adjusted.phase, GAME_PHASE_MAX);
+    }
+
     #[test]
     fn test_clear_caches() {
         let mut evaluator = IntegratedEvaluator::new();
@@ -1,9 +1,15 @@
 use crate::bitboards::*;
 use crate::evaluation::attacks::{AttackAnalyzer, ThreatEvaluator};
 use crate::evaluation::castles::{CastleCacheStats, CastleRecognizer};
+use crate::evaluation::material::{legal_drop_squares, HAND_PIECE_KINDS, HAND_PIECE_TYPES};
 use crate::evaluation::storm_tracking::StormState;
+use crate::search::board_trait::BoardTrait;
+use crate::types::board::CapturedPieces;
 use crate::types::core::{PieceType, Player, Position};
-use crate::types::evaluation::{KingSafetyConfig, TaperedScore};
+use crate::types::evaluation::{
+    KingSafetyConfig, TaperedScore, KING_DANGER_FEATURES, KING_DANGER_FEATURES_START,
+};
+use crate::types::{is_bit_set, set_bit, Bitboard};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -923,6 +929,319 @@ impl Default for KingSafetyEvaluator {
     }
 }
 
+// ============================================================================
+// King danger
+// ============================================================================
+
+/// Number of entries in the danger-to-score table
+pub const KING_DANGER_TABLE_SIZE: usize = KING_DANGER_FEATURES;
+
+/// Configuration of the king-danger model
+///
+/// Everything the attacker has against a king (control of the squares around
+/// it, pieces close to it, checks on the board and checks it can drop from
+/// hand) is summed into integer danger units. The units index a non-linear
+/// table, so threats that would each be harmless on their own add up to a
+/// much larger penalty once they come together.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KingDangerConfig {
+    /// Score the king with this model instead of the separate enemy-attacker
+    /// and enemy-hand terms
+    pub enabled: bool,
+    /// Per square next to the king the attacker controls
+    pub attacked_square_weight: i32,
+    /// Per attacked square next to the king that no other defender covers
+    pub undefended_square_weight: i32,
+    /// Per attacking piece in the 5x5 zone around the king
+    pub zone_attacker_weight: i32,
+    /// Per defending piece next to the king, subtracted
+    pub defender_weight: i32,
+    /// Per square the king can safely step to, subtracted
+    pub escape_square_weight: i32,
+    /// Added when the king has no safe square to step to
+    pub no_escape_weight: i32,
+    /// Per checking move by a piece on the board, counting at most four
+    pub board_check_weight: i32,
+    /// Per piece kind in hand (pawn to rook) that can be dropped with check on
+    /// a safe square
+    pub drop_check_weights: [i32; HAND_PIECE_KINDS],
+    /// Per piece of each kind in the attacker's hand, counting at most two
+    pub hand_weights: [i32; HAND_PIECE_KINDS],
+    /// Penalty by danger units; the last entry applies to anything larger
+    pub danger_table: Vec<TaperedScore>,
+}
+
+impl Default for KingDangerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            attacked_square_weight: 3,
+            undefended_square_weight: 3,
+            zone_attacker_weight: 2,
+            defender_weight: 2,
+            escape_square_weight: 1,
+            no_escape_weight: 6,
+            board_check_weight: 3,
+            drop_check_weights: [3, 3, 4, 5, 6, 6, 7],
+            hand_weights: [1, 1, 1, 2, 2, 2, 3],
+            danger_table: Self::default_danger_table(),
+        }
+    }
+}
+
+impl KingDangerConfig {
+    /// Quadratic in the danger units, endgame half the middlegame, capped
+    pub fn default_danger_table() -> Vec<TaperedScore> {
+        (0..KING_DANGER_TABLE_SIZE as i32)
+            .map(|units| {
+                let mg = (units * units / 2 + 2 * units).min(900);
+                TaperedScore::new_tapered(mg, mg / 2)
+            })
+            .collect()
+    }
+
+    /// Danger units for the given inputs, clamped to the table
+    ///
+    /// Defenders and escape squares only offset the pressure from pieces on
+    /// the board; pieces in hand stay a threat wherever the king stands.
+    pub fn danger(&self, inputs: &KingDangerInputs) -> usize {
+        let mut board_units = self.attacked_square_weight * i32::from(inputs.attacked_squares)
+            + self.undefended_square_weight * i32::from(inputs.undefended_squares)
+            + self.zone_attacker_weight * i32::from(inputs.zone_attackers)
+            - self.defender_weight * i32::from(inputs.defenders)
+            - self.escape_square_weight * i32::from(inputs.escape_squares)
+            + self.board_check_weight * i32::from(inputs.board_checks.min(4));
+        if inputs.escape_squares == 0 {
+            board_units += self.no_escape_weight;
+        }
+        let mut hand_units = 0;
+        for kind in 0..HAND_PIECE_KINDS {
+            if inputs.drop_checks[kind] > 0 {
+                hand_units += self.drop_check_weights[kind];
+            }
+            hand_units += self.hand_weights[kind] * i32::from(inputs.hand[kind].min(2));
+        }
+        let units = board_units.max(0) + hand_units;
+        let last = self.danger_table.len().saturating_sub(1);
+        (units as usize).min(last)
+    }
+
+    /// Penalty for a king at the given danger
+    pub fn score(&self, danger: usize) -> TaperedScore {
+        self.danger_table
+            .get(danger.min(self.danger_table.len().saturating_sub(1)))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Penalty for the king of `player` on `king_pos`
+    pub fn evaluate(
+        &self,
+        board: &BitboardBoard,
+        king_pos: Position,
+        player: Player,
+        captured_pieces: &CapturedPieces,
+    ) -> TaperedScore {
+        let inputs = KingDangerInputs::compute(board, king_pos, player, captured_pieces);
+        self.score(self.danger(&inputs))
+    }
+
+    /// Load a configuration written by `tuner --king-danger-output`
+    pub fn from_path<P: AsRef<std::path::Path>>(path: P) -> Result<Self, String> {
+        crate::config::read_config_file(path).map_err(|e| e.to_string())
+    }
+
+    /// Take the danger table from tuned weights laid out as in
+    /// `get_evaluation_features`. The features are untapered, so the tuned
+    /// weight becomes the middlegame penalty and the endgame keeps the
+    /// default table's half of it.
+    pub fn apply_tuned_weights(&mut self, weights: &[f64]) -> Result<(), String> {
+        let end = KING_DANGER_FEATURES_START + KING_DANGER_TABLE_SIZE;
+        if weights.len() < end {
+            return Err(format!("expected at least {} weights, got {}", end, weights.len()));
+        }
+        self.danger_table = weights[KING_DANGER_FEATURES_START..end]
+            .iter()
+            .map(|&weight| {
+                let mg = weight.round() as i32;
+                TaperedScore::new_tapered(mg, mg / 2)
+            })
+            .collect();
+        Ok(())
+    }
+}
+
+/// What the attacker has against one king
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct KingDangerInputs {
+    /// Squares next to the king the attacker controls
+    pub attacked_squares: u8,
+    /// Attacked squares next to the king covered by no defender but the king
+    pub undefended_squares: u8,
+    /// Attacking pieces in the 5x5 zone around the king
+    pub zone_attackers: u8,
+    /// Defending pieces next to the king
+    pub defenders: u8,
+    /// Squares the king can step to without being captured
+    pub escape_squares: u8,
+    /// Moves by attacking pieces on the board that give check, without
+    /// promotions
+    pub board_checks: u8,
+    /// Safe, legal squares where each kind in hand (pawn to rook) would be
+    /// dropped with check; pawn-drop mate is not detected
+    pub drop_checks: [u8; HAND_PIECE_KINDS],
+    /// Pieces of each kind in the attacker's hand
+    pub hand: [u8; HAND_PIECE_KINDS],
+}
+
+impl KingDangerInputs {
+    /// Inputs for the king of `player` on `king_pos`
+    pub fn compute(
+        board: &BitboardBoard,
+        king_pos: Position,
+        player: Player,
+        captured_pieces: &CapturedPieces,
+    ) -> Self {
+        let attacker = player.opposite();
+        let mut inputs = Self::default();
+        let attacker_occupied = board.get_occupied_bitboard_for_player(attacker);
+        // Squares the attacker controls, and those the defender's pieces
+        // other than the king cover
+        let mut attacked = EMPTY_BITBOARD;
+        let mut covered = EMPTY_BITBOARD;
+        let mut board_checks = 0;
+        // A piece checks from wherever the same piece of the defender on the
+        // king's square would attack; computed once per piece type
+        let mut checking_squares: [Option<Bitboard>; PieceType::COUNT] = [None; PieceType::COUNT];
+        let mut checking = |piece_type: PieceType| {
+            *checking_squares[piece_type.as_index()]
+                .get_or_insert_with(|| piece_attacks(board, piece_type, player, king_pos))
+        };
+        for (pos, piece) in board.iter_pieces() {
+            let attacks = piece_attacks(board, piece.piece_type, piece.player, pos);
+            if piece.player == player {
+                if piece.piece_type != PieceType::King {
+                    covered |= attacks;
+                }
+                continue;
+            }
+            attacked |= attacks;
+            if piece.piece_type == PieceType::King {
+                continue;
+            }
+            if pos.row.abs_diff(king_pos.row) <= 2 && pos.col.abs_diff(king_pos.col) <= 2 {
+                inputs.zone_attackers += 1;
+            }
+            board_checks +=
+                (attacks & checking(piece.piece_type) & !attacker_occupied).count_ones();
+        }
+        inputs.board_checks = board_checks.min(u32::from(u8::MAX)) as u8;
+
+        let king_moves = piece_attacks(board, PieceType::King, player, king_pos);
+        for idx in bits(king_moves) {
+            let pos = Position::from_index(idx);
+            let occupant = board.get_piece(pos);
+            if occupant.is_some_and(|piece| piece.player == player) {
+                inputs.defenders += 1;
+            }
+            if is_bit_set(attacked, pos) {
+                inputs.attacked_squares += 1;
+                if !is_bit_set(covered, pos) {
+                    inputs.undefended_squares += 1;
+                }
+            } else if occupant.is_none_or(|piece| piece.player == attacker) {
+                inputs.escape_squares += 1;
+            }
+        }
+
+        for (kind, piece_type) in HAND_PIECE_TYPES.iter().enumerate() {
+            inputs.hand[kind] = captured_pieces.count(*piece_type, attacker) as u8;
+        }
+        if inputs.hand.iter().any(|&count| count > 0) {
+            // Safe for the dropped piece: not defended, or supported
+            let safe = !(covered | king_moves) | attacked;
+            let legal = legal_drop_squares(board, attacker);
+            for (kind, &piece_type) in HAND_PIECE_TYPES.iter().enumerate() {
+                if inputs.hand[kind] > 0 {
+                    let squares = checking(piece_type) & legal[kind] & safe;
+                    inputs.drop_checks[kind] = squares.count_ones() as u8;
+                }
+            }
+        }
+        inputs
+    }
+}
+
+/// Squares a `piece_type` of `player` on `from` attacks, sliders stopped by
+/// the first piece in their way.
+///
+/// Slider attacks come from the board's magic/ray-cast lookup. The step
+/// pieces are generated from offsets, since the precomputed step tables do
+/// not follow this board's row orientation.
+fn piece_attacks(
+    board: &BitboardBoard,
+    piece_type: PieceType,
+    player: Player,
+    from: Position,
+) -> Bitboard {
+    let steps = |directions: &[(i8, i8)]| step_attacks(player, from, directions);
+    match piece_type {
+        PieceType::Pawn => steps(&[(1, 0)]),
+        PieceType::Lance => {
+            board.get_attack_pattern(from, PieceType::Rook) & file_ahead(player, from)
+        }
+        PieceType::Knight => steps(&KNIGHT_STEPS),
+        PieceType::Silver => steps(&SILVER_STEPS),
+        PieceType::Gold
+        | PieceType::PromotedPawn
+        | PieceType::PromotedLance
+        | PieceType::PromotedKnight
+        | PieceType::PromotedSilver => steps(&GOLD_STEPS),
+        PieceType::King => steps(&ORTHOGONALS) | steps(&DIAGONALS),
+        PieceType::Bishop => board.get_attack_pattern(from, PieceType::Bishop),
+        PieceType::Rook => board.get_attack_pattern(from, PieceType::Rook),
+        PieceType::PromotedBishop => {
+            board.get_attack_pattern(from, PieceType::Bishop) | steps(&ORTHOGONALS)
+        }
+        PieceType::PromotedRook => {
+            board.get_attack_pattern(from, PieceType::Rook) | steps(&DIAGONALS)
+        }
+    }
+}
+
+// Directions as (forward, sideways) steps for the piece's owner
+const ORTHOGONALS: [(i8, i8); 4] = [(1, 0), (-1, 0), (0, -1), (0, 1)];
+const DIAGONALS: [(i8, i8); 4] = [(1, -1), (1, 1), (-1, -1), (-1, 1)];
+const GOLD_STEPS: [(i8, i8); 6] = [(1, -1), (1, 0), (1, 1), (0, -1), (0, 1), (-1, 0)];
+const SILVER_STEPS: [(i8, i8); 5] = [(1, -1), (1, 0), (1, 1), (-1, -1), (-1, 1)];
+const KNIGHT_STEPS: [(i8, i8); 2] = [(2, -1), (2, 1)];
+
+/// Squares one step from `from` in each direction of `player`'s
+fn step_attacks(player: Player, from: Position, directions: &[(i8, i8)]) -> Bitboard {
+    // Rows decrease toward White's side, where Black moves
+    let forward = if player == Player::Black { -1 } else { 1 };
+    let mut attacks = EMPTY_BITBOARD;
+    for &(ahead, side) in directions {
+        let row = from.row as i8 + ahead * forward;
+        let col = from.col as i8 + side;
+        if (0..9).contains(&row) && (0..9).contains(&col) {
+            set_bit(&mut attacks, Position::new(row as u8, col as u8));
+        }
+    }
+    attacks
+}
+
+/// Squares on the file of `from` ahead of it for `player`
+fn file_ahead(player: Player, from: Position) -> Bitboard {
+    let rows = if player == Player::Black { 0..from.row } else { from.row + 1..9 };
+    let mut ahead = EMPTY_BITBOARD;
+    for row in rows {
+        set_bit(&mut ahead, Position::new(row, from.col));
+    }
+    ahead
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1262,4 +1581,126 @@ mod tests {
         // In practice, debug logging would be verified through integration
         // tests that check log output
     }
+
+    #[test]
+    fn test_king_danger_counts_drop_checks() {
+        let mut board = BitboardBoard::empty();
+        let king_pos = Position::new(8, 4);
+        board.place_piece(Piece::new(PieceType::King, Player::Black), king_pos);
+        board.place_piece(Piece::new(PieceType::Gold, Player::Black), Position::new(8, 3));
+        board.place_piece(Piece::new(PieceType::Silver, Player::White), Position::new(6, 4));
+
+        let empty_hand = CapturedPieces::new();
+        let without = KingDangerInputs::compute(&board, king_pos, Player::Black, &empty_hand);
+        assert_eq!(without.drop_checks, [0; HAND_PIECE_KINDS]);
+        assert_eq!(without.board_checks, 3, "the silver checks from all three squares ahead");
+        assert_eq!(without.zone_attackers, 1);
+        assert_eq!(without.defenders, 1);
+
+        let mut captured = CapturedPieces::new();
+        captured.add_piece(PieceType::Gold, Player::White);
+        let with = KingDangerInputs::compute(&board, king_pos, Player::Black, &captured);
+        let gold = PieceType::Gold.as_index();
+        assert_eq!(with.hand[gold], 1);
+        assert_eq!(with.drop_checks[gold], 3, "drops the silver supports are safe");
+
+        let config = KingDangerConfig::default();
+        assert!(config.danger(&with) > config.danger(&without));
+        assert!(
+            config.evaluate(&board, king_pos, Player::Black, &captured).mg
+                > config.evaluate(&board, king_pos, Player::Black, &empty_hand).mg
+        );
+    }
+
+    #[test]
+    fn test_start_position_has_no_king_danger() {
+        let board = BitboardBoard::new();
+        let king_pos = board.find_king_position(Player::Black).unwrap();
+        let inputs =
+            KingDangerInputs::compute(&board, king_pos, Player::Black, &CapturedPieces::new());
+        assert_eq!(inputs.attacked_squares, 0);
+        assert_eq!(inputs.defenders, 2);
+        assert_eq!(inputs.escape_squares, 3);
+
+        let config = KingDangerConfig::default();
+        assert_eq!(config.danger(&inputs), 0);
+        assert_eq!(config.score(0), TaperedScore::default());
+    }
+
+    #[test]
+    fn test_king_danger_table_is_non_linear() {
+        let mut config = KingDangerConfig::default();
+        let table = &config.danger_table;
+        assert_eq!(table.len(), KING_DANGER_TABLE_SIZE);
+        for units in 2..table.len() {
+            let step = table[units].mg - table[units - 1].mg;
+            let previous = table[units - 1].mg - table[units - 2].mg;
+            assert!(step >= previous || table[units].mg == 900);
+        }
+        assert_eq!(config.score(10_000), *table.last().unwrap());
+
+        let mut weights = vec![0.0; crate::types::evaluation::NUM_EVAL_FEATURES];
+        for units in 0..KING_DANGER_TABLE_SIZE {
+            weights[KING_DANGER_FEATURES_START + units] = units as f64 * 10.0;
+        }
+        config.apply_tuned_weights(&weights).unwrap();
+        assert_eq!(config.score(5), TaperedScore::new_tapered(50, 25));
+        assert!(config.apply_tuned_weights(&weights[..KING_DANGER_FEATURES_START]).is_err());
+    }
+
+    #[test]
+    fn test_evaluator_uses_loaded_king_danger() {
+        use crate::evaluation::PositionEvaluator;
+
+        let mut tuned = KingDangerConfig::default();
+        tuned.drop_check_weights = [0; HAND_PIECE_KINDS];
+        tuned.hand_weights = [0; HAND_PIECE_KINDS];
+        tuned.danger_table =
+            (0..KING_DANGER_TABLE_SIZE as i32).map(|units| TaperedScore::new(7 * units)).collect();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("king_danger.json");
+        std::fs::write(&path, serde_json::to_string_pretty(&tuned).unwrap()).unwrap();
+
+        let mut evaluator = PositionEvaluator::new();
+        evaluator.load_king_danger(&path).unwrap();
+        assert_eq!(evaluator.king_danger_config(), Some(&tuned));
+
+        // Tuning features index the table with the loaded weights
+        let mut board = BitboardBoard::empty();
+        let king_pos = Position::new(8, 4);
+        board.place_piece(Piece::new(PieceType::King, Player::Black), king_pos);
+        board.place_piece(Piece::new(PieceType::Silver, Player::White), Position::new(6, 4));
+        let mut captured = CapturedPieces::new();
+        captured.add_piece(PieceType::Gold, Player::White);
+        let inputs = KingDangerInputs::compute(&board, king_pos, Player::Black, &captured);
+        assert_ne!(tuned.danger(&inputs), KingDangerConfig::default().danger(&inputs));
+        let features = evaluator.get_evaluation_features(&board, Player::White, &captured);
+        assert_eq!(features[KING_DANGER_FEATURES_START + tuned.danger(&inputs)], 1.0);
+
+        assert!(evaluator.load_king_danger(dir.path().join("missing.json")).is_err());
+    }
+
+    #[test]
+    fn test_piece_attacks_respect_orientation_and_blockers() {
+        let mut board = BitboardBoard::empty();
+        let target = Position::new(8, 4);
+        let reaches = |board: &BitboardBoard, piece_type, player, row, col| {
+            is_bit_set(piece_attacks(board, piece_type, player, Position::new(row, col)), target)
+        };
+        assert!(reaches(&board, PieceType::Knight, Player::White, 6, 3));
+        assert!(!reaches(&board, PieceType::Knight, Player::Black, 6, 3));
+        assert!(reaches(&board, PieceType::Lance, Player::White, 2, 4));
+        assert!(!reaches(&board, PieceType::Lance, Player::Black, 2, 4));
+        assert!(reaches(&board, PieceType::Pawn, Player::White, 7, 4));
+        assert!(!reaches(&board, PieceType::Pawn, Player::Black, 7, 4));
+
+        board.place_piece(Piece::new(PieceType::Pawn, Player::Black), Position::new(5, 4));
+        assert!(!reaches(&board, PieceType::Lance, Player::White, 2, 4));
+        assert!(reaches(&board, PieceType::Bishop, Player::White, 5, 1));
+        assert!(!reaches(&board, PieceType::Silver, Player::White, 8, 3));
+        assert!(reaches(&board, PieceType::Gold, Player::White, 8, 3));
+        assert!(reaches(&board, PieceType::PromotedBishop, Player::Black, 8, 3));
+        assert!(!reaches(&board, PieceType::PromotedBishop, Player::Black, 6, 4));
+        assert!(reaches(&board, PieceType::PromotedRook, Player::Black, 7, 3));
+    }
 }
@@ -26,11 +26,14 @@
 //! let score = evaluator.evaluate_material(&board, Player::Black, &captured_pieces);
 //! ```
 
-use crate::bitboards::BitboardBoard;
+use crate::bitboards::{bits, get_file_mask, get_rank_mask, BitboardBoard};
 use crate::evaluation::material_value_loader::MaterialValueLoader;
 use crate::types::board::CapturedPieces;
 use crate::types::core::{PieceType, Player, Position};
-use crate::types::evaluation::TaperedScore;
+use crate::types::evaluation::{
+    TaperedScore, DROP_POTENTIAL_FEATURES_START, HAND_COUNT_FEATURES_START,
+};
+use crate::types::{is_bit_set, Bitboard};
 use crate::utils::telemetry::debug_log;
 use serde::{Deserialize, Serialize};
 use serde_json;
@@ -65,8 +68,8 @@ const ALL_PIECE_TYPES: [PieceType; PieceType::COUNT] = [
     PieceType::PromotedRook,
 ];
 
-#[cfg(feature = "material_fast_loop")]
-const HAND_PIECE_TYPES: [PieceType; 7] = [
+/// Piece kinds that can be held in hand, in hand-table order
+pub const HAND_PIECE_TYPES: [PieceType; HAND_PIECE_KINDS] = [
     PieceType::Pawn,
     PieceType::Lance,
     PieceType::Knight,
@@ -76,6 +79,12 @@ const HAND_PIECE_TYPES: [PieceType; 7] = [
     PieceType::Rook,
 ];
 
+/// Number of piece kinds that can be held in hand
+pub const HAND_PIECE_KINDS: usize = 7;
+
+/// Most pieces of each kind a hand can hold
+pub const HAND_PIECE_LIMITS: [usize; HAND_PIECE_KINDS] = [18, 4, 4, 4, 4, 2, 2];
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MaterialValueSet {
     pub id: String,
@@ -92,6 +101,14 @@ pub struct MaterialValueSet {
     pub board_values: [TaperedScore; PieceType::COUNT],
     #[serde(default)]
     pub hand_values: [Option<TaperedScore>; PieceType::COUNT],
+    /// Value of the 1st, 2nd, ... piece of each kind in hand (pawn to rook);
+    /// counts past the end of a table repeat its last entry. Without tables
+    /// every piece in hand is worth `hand_value`.
+    #[serde(default)]
+    pub hand_count_values: Option<[Vec<TaperedScore>; HAND_PIECE_KINDS]>,
+    /// Bonus per safe drop square in the enemy king zone, for each kind held
+    #[serde(default)]
+    pub drop_potential: [TaperedScore; HAND_PIECE_KINDS],
 }
 
 impl MaterialValueSet {
@@ -105,6 +122,7 @@ impl MaterialValueSet {
             Self::legacy_research_board(),
             Self::legacy_research_hand(),
         )
+        .with_default_hand_tables()
     }
 
     pub fn classic() -> Self {
@@ -117,6 +135,32 @@ impl MaterialValueSet {
             Self::legacy_classic_board(),
             Self::legacy_classic_hand(),
         )
+        .with_default_hand_tables()
+    }
+
+    /// Add diminishing count tables scaled from the flat hand values, and
+    /// the default drop-potential bonuses
+    fn with_default_hand_tables(mut self) -> Self {
+        const PERCENT: [&[i32]; HAND_PIECE_KINDS] = [
+            &[100, 95, 85, 75, 65, 60, 55],
+            &[100, 95, 90, 85],
+            &[100, 95, 90, 85],
+            &[100, 95, 90, 85],
+            &[100, 95, 85, 80],
+            &[100, 90],
+            &[100, 90],
+        ];
+        let tables = std::array::from_fn(|kind| {
+            let value = self.hand_value(HAND_PIECE_TYPES[kind]);
+            PERCENT[kind]
+                .iter()
+                .map(|percent| ts!(value.mg * percent / 100, value.eg * percent / 100))
+                .collect()
+        });
+        self.hand_count_values = Some(tables);
+        self.drop_potential =
+            [ts!(4, 2), ts!(6, 4), ts!(8, 4), ts!(10, 6), ts!(10, 6), ts!(8, 6), ts!(10, 8)];
+        self
     }
 
     fn from_legacy(
@@ -137,6 +181,8 @@ impl MaterialValueSet {
             last_updated: last_updated.map(|s| s.to_string()),
             board_values,
             hand_values,
+            hand_count_values: None,
+            drop_potential: [TaperedScore::default(); HAND_PIECE_KINDS],
         }
     }
 
@@ -150,6 +196,55 @@ impl MaterialValueSet {
         self.hand_values[piece_type.as_index()].unwrap_or_else(|| self.board_value(piece_type))
     }
 
+    /// Value of the `nth` (1-based) piece of a kind in hand
+    pub fn hand_value_nth(&self, piece_type: PieceType, nth: usize) -> TaperedScore {
+        let table = self
+            .hand_count_values
+            .as_ref()
+            .and_then(|tables| tables.get(piece_type.as_index()))
+            .filter(|table| !table.is_empty());
+        match table {
+            Some(table) => table[nth.clamp(1, table.len()) - 1],
+            None => self.hand_value(piece_type),
+        }
+    }
+
+    /// Value of holding `count` pieces of a kind
+    pub fn hand_value_for_count(&self, piece_type: PieceType, count: usize) -> TaperedScore {
+        let mut total = TaperedScore::default();
+        for nth in 1..=count {
+            total += self.hand_value_nth(piece_type, nth);
+        }
+        total
+    }
+
+    /// Replace the count tables and drop-potential bonuses with tuned weights
+    /// laid out as in the tuner's feature vector (see
+    /// `HAND_COUNT_FEATURES_START`); the weights apply to both phases
+    pub fn apply_tuned_hand_weights(&mut self, weights: &[f64]) -> Result<(), String> {
+        if weights.len() < DROP_POTENTIAL_FEATURES_START + HAND_PIECE_KINDS {
+            return Err(format!(
+                "expected at least {} weights, got {}",
+                DROP_POTENTIAL_FEATURES_START + HAND_PIECE_KINDS,
+                weights.len()
+            ));
+        }
+        let mut start = HAND_COUNT_FEATURES_START;
+        let tables = std::array::from_fn(|kind| {
+            let table = weights[start..start + HAND_PIECE_LIMITS[kind]]
+                .iter()
+                .map(|&weight| TaperedScore::new(weight.round() as i32))
+                .collect();
+            start += HAND_PIECE_LIMITS[kind];
+            table
+        });
+        self.hand_count_values = Some(tables);
+        self.drop_potential = std::array::from_fn(|kind| {
+            TaperedScore::new(weights[DROP_POTENTIAL_FEATURES_START + kind].round() as i32)
+        });
+        Ok(())
+    }
+
     pub fn validate(&self) -> Result<(), String> {
         for idx in 0..PieceType::COUNT {
             let piece = PieceType::from_u8(idx as u8);
@@ -161,6 +256,20 @@ impl MaterialValueSet {
                 ));
             }
         }
+        if let Some(tables) = &self.hand_count_values {
+            for (kind, table) in tables.iter().enumerate() {
+                if table.len() > HAND_PIECE_LIMITS[kind] {
+                    return Err(format!(
+                        "Hand count table for {:?} has {} entries, at most {} pieces can be held \
+                         in value set {}",
+                        HAND_PIECE_TYPES[kind],
+                        table.len(),
+                        HAND_PIECE_LIMITS[kind],
+                        self.id
+                    ));
+                }
+            }
+        }
         Ok(())
     }
 
@@ -464,23 +573,40 @@ impl MaterialEvaluator {
         player: Player,
         captured_pieces: &CapturedPieces,
     ) -> TaperedScore {
+        let (board_score, hand_score) =
+            self.evaluate_material_parts(board, player, captured_pieces);
+        board_score + hand_score
+    }
+
+    /// Material split into (pieces on board, pieces in hand); the hand part
+    /// is zero when `include_hand_pieces` is off
+    pub fn evaluate_material_parts(
+        &mut self,
+        board: &BitboardBoard,
+        player: Player,
+        captured_pieces: &CapturedPieces,
+    ) -> (TaperedScore, TaperedScore) {
         self.stats.register_value_set(&self.value_set);
         let mut contribution = MaterialContribution::default();
-        let mut score = TaperedScore::default();
 
         // Evaluate pieces on board
-        score += self.evaluate_board_material(board, player, &mut contribution);
+        let board_score = self.evaluate_board_material(board, player, &mut contribution);
 
         // Evaluate captured pieces (pieces in hand)
-        if self.config.include_hand_pieces {
-            score += self.evaluate_hand_material(captured_pieces, player, &mut contribution);
-        }
+        let hand_score = if self.config.include_hand_pieces {
+            self.evaluate_hand_material(captured_pieces, player, &mut contribution)
+        } else {
+            TaperedScore::default()
+        };
 
         self.stats.record_contribution(&contribution);
-        score
+        (board_score, hand_score)
     }
 
     /// Compute a tapered score delta for incremental updates.
+    ///
+    /// Hand pieces are valued at their flat `hand_value`, ignoring the count
+    /// tables, since a delta does not know how many pieces are already held.
     pub fn evaluate_delta(&self, delta: &MaterialDelta) -> TaperedScore {
         let mut score = TaperedScore::default();
         for idx in 0..PieceType::COUNT {
@@ -672,6 +798,11 @@ impl MaterialEvaluator {
         player: Player,
         contribution: &mut MaterialContribution,
     ) -> TaperedScore {
+        // Count tables value each piece by how many of its kind are held
+        if self.value_set.hand_count_values.is_some() {
+            return self.evaluate_hand_material_counted(captured_pieces, player, contribution);
+        }
+
         #[cfg(feature = "simd")]
         {
             // Check runtime flag before using SIMD
@@ -796,6 +927,54 @@ impl MaterialEvaluator {
         score
     }
 
+    /// Hand material from the count tables: the n-th piece of a kind is
+    /// worth the n-th entry of its table
+    fn evaluate_hand_material_counted(
+        &self,
+        captured_pieces: &CapturedPieces,
+        player: Player,
+        contribution: &mut MaterialContribution,
+    ) -> TaperedScore {
+        let mut score = TaperedScore::default();
+        for piece_type in HAND_PIECE_TYPES {
+            for (side, is_player) in [(player, true), (player.opposite(), false)] {
+                for nth in 1..=captured_pieces.count(piece_type, side) {
+                    let value = self.value_set.hand_value_nth(piece_type, nth);
+                    contribution.add_hand(piece_type, value, is_player);
+                    if is_player {
+                        score += value;
+                    } else {
+                        score -= value;
+                    }
+                }
+            }
+        }
+        score
+    }
+
+    /// Drop potential of both hands from `player`'s point of view: for each
+    /// kind held, its `drop_potential` bonus per safe drop square in the
+    /// enemy king zone (see [`safe_king_zone_drops`])
+    pub fn evaluate_drop_potential(
+        &self,
+        board: &BitboardBoard,
+        player: Player,
+        captured_pieces: &CapturedPieces,
+    ) -> TaperedScore {
+        let mut score = TaperedScore::default();
+        if !self.config.include_hand_pieces || !self.config.enable_drop_potential {
+            return score;
+        }
+        for (side, sign) in [(player, 1), (player.opposite(), -1)] {
+            let squares = safe_king_zone_drops(board, side, captured_pieces);
+            for (bonus, count) in self.value_set.drop_potential.iter().zip(squares) {
+                let count = count as i32 * sign;
+                score += ts!(bonus.mg * count, bonus.eg * count);
+            }
+        }
+        score
+    }
+
     /// Get tapered value for a piece on the board
     ///
     /// Returns a TaperedScore with separate mg/eg values
@@ -899,6 +1078,9 @@ pub struct MaterialEvaluationConfig {
     /// Enable optimized fast-loop traversal for board/hand evaluation
     #[serde(default)]
     pub enable_fast_loop: bool,
+    /// Score safe drop squares near the enemy king for pieces in hand
+    #[serde(default = "default_drop_potential_enabled")]
+    pub enable_drop_potential: bool,
     /// Enable SIMD-optimized material evaluation
     ///
     /// Only effective when the `simd` feature is enabled at compile time.
@@ -907,6 +1089,10 @@ pub struct MaterialEvaluationConfig {
     pub enable_simd: bool,
 }
 
+fn default_drop_potential_enabled() -> bool {
+    true
+}
+
 #[cfg(feature = "simd")]
 fn default_simd_enabled() -> bool {
     true // Default to enabled when SIMD feature is available
@@ -919,12 +1105,89 @@ impl Default for MaterialEvaluationConfig {
             use_research_values: true,
             values_path: None,
             enable_fast_loop: false,
+            enable_drop_potential: true,
             #[cfg(feature = "simd")]
             enable_simd: true, // Default to enabled when SIMD feature is available
         }
     }
 }
 
+/// Safe drop squares in the enemy king zone for each kind `player` holds,
+/// in [`HAND_PIECE_TYPES`] order (zero for kinds not in hand)
+///
+/// The zone is the eight squares around the enemy king plus the three squares
+/// two ranks in front of it. A square counts for a kind when the kind may be
+/// dropped there (no second pawn on a file, no pawn, lance or knight without
+/// a further move) and it is safe: empty and either not attacked by the
+/// defender or supported by one of `player`'s pieces.
+pub fn safe_king_zone_drops(
+    board: &BitboardBoard,
+    player: Player,
+    captured_pieces: &CapturedPieces,
+) -> [u32; HAND_PIECE_KINDS] {
+    let mut counts = [0; HAND_PIECE_KINDS];
+    let held = HAND_PIECE_TYPES.map(|piece_type| captured_pieces.count(piece_type, player) > 0);
+    if !held.contains(&true) {
+        return counts;
+    }
+    let defender = player.opposite();
+    let Some(king) = board.find_king_position(defender) else {
+        return counts;
+    };
+
+    let legal = legal_drop_squares(board, player);
+
+    // Ranks grow toward Black's side, where Black's attack comes from
+    let forward: i8 = if player == Player::Black { 1 } else { -1 };
+    let offsets = (-1..=1)
+        .flat_map(|dr| (-1..=1).map(move |dc| (dr, dc)))
+        .filter(|&offset| offset != (0, 0))
+        .chain((-1..=1).map(|dc| (2 * forward, dc)));
+    for (dr, dc) in offsets {
+        let row = king.row as i8 + dr;
+        let col = king.col as i8 + dc;
+        if !(0..9).contains(&row) || !(0..9).contains(&col) {
+            continue;
+        }
+        let pos = Position::new(row as u8, col as u8);
+        if board.get_piece(pos).is_some()
+            || (board.is_square_attacked_by(pos, defender)
+                && !board.is_square_attacked_by(pos, player))
+        {
+            continue;
+        }
+        for kind in 0..HAND_PIECE_KINDS {
+            if held[kind] && is_bit_set(legal[kind], pos) {
+                counts[kind] += 1;
+            }
+        }
+    }
+    counts
+}
+
+/// Empty squares where `player` may drop each kind, in [`HAND_PIECE_TYPES`]
+/// order: never a second pawn on a file, and no pawn, lance or knight where
+/// it would have no further move
+pub fn legal_drop_squares(board: &BitboardBoard, player: Player) -> [Bitboard; HAND_PIECE_KINDS] {
+    let board_squares = Bitboard::from_u128((1u128 << 81) - 1);
+    let empty = !board.get_occupied_bitboard() & board_squares;
+    // Pawns and lances need a rank in front of them, knights two
+    let (last_rank, second_last_rank) = if player == Player::Black { (0, 1) } else { (8, 7) };
+    let one_ahead = empty & !get_rank_mask(last_rank);
+    let two_ahead = one_ahead & !get_rank_mask(second_last_rank);
+    let player_idx = if player == Player::Black { 0 } else { 1 };
+    let mut pawn_squares = one_ahead;
+    for idx in bits(board.get_pieces()[player_idx][PieceType::Pawn.to_u8() as usize]) {
+        pawn_squares &= !get_file_mask(idx % 9);
+    }
+    HAND_PIECE_TYPES.map(|piece_type| match piece_type {
+        PieceType::Pawn => pawn_squares,
+        PieceType::Lance => one_ahead,
+        PieceType::Knight => two_ahead,
+        _ => empty,
+    })
+}
+
 /// Statistics for monitoring material evaluation
 #[derive(Debug, Clone)]
 pub struct MaterialEvaluationStats {
@@ -1477,4 +1740,116 @@ mod tests {
         assert_eq!(black_score.mg, -white_score.mg);
         assert_eq!(black_score.eg, -white_score.eg);
     }
+
+    #[test]
+    fn test_hand_count_tables_diminish() {
+        let value_set = MaterialValueSet::research();
+        let first = value_set.hand_value_nth(PieceType::Pawn, 1);
+        let fifth = value_set.hand_value_nth(PieceType::Pawn, 5);
+        assert_eq!(first, value_set.hand_value(PieceType::Pawn));
+        assert!(fifth.mg < first.mg && fifth.eg < first.eg);
+        // Counts past the table repeat its last entry
+        assert_eq!(
+            value_set.hand_value_nth(PieceType::Pawn, 18),
+            value_set.hand_value_nth(PieceType::Pawn, 7)
+        );
+
+        let mut captured = CapturedPieces::new();
+        for _ in 0..5 {
+            captured.add_piece(PieceType::Pawn, Player::Black);
+        }
+        let mut evaluator = MaterialEvaluator::new();
+        let (_, hand) =
+            evaluator.evaluate_material_parts(&BitboardBoard::empty(), Player::Black, &captured);
+        assert_eq!(hand, value_set.hand_value_for_count(PieceType::Pawn, 5));
+        assert!(hand.mg < first.mg * 5);
+    }
+
+    #[test]
+    fn test_value_set_without_count_tables_uses_flat_values() {
+        let mut flat = MaterialValueSet::classic();
+        flat.hand_count_values = None;
+        let mut json = Vec::new();
+        flat.to_writer(&mut json).expect("write material value set");
+        let loaded = MaterialValueSet::from_reader(json.as_slice()).unwrap();
+        assert!(loaded.hand_count_values.is_none());
+        let gold = loaded.hand_value(PieceType::Gold);
+        assert_eq!(loaded.hand_value_for_count(PieceType::Gold, 3), gold + gold + gold);
+    }
+
+    #[test]
+    fn test_safe_king_zone_drops() {
+        let mut board = BitboardBoard::empty();
+        board.place_piece(Piece::new(PieceType::King, Player::White), Position::new(0, 4));
+        board.place_piece(Piece::new(PieceType::King, Player::Black), Position::new(8, 4));
+        let mut captured = CapturedPieces::new();
+        captured.add_piece(PieceType::Gold, Player::Black);
+        captured.add_piece(PieceType::Pawn, Player::Black);
+
+        // Only the three squares two ranks in front of the king are safe
+        let drops = safe_king_zone_drops(&board, Player::Black, &captured);
+        assert_eq!(drops, [3, 0, 0, 0, 3, 0, 0]);
+
+        // A silver supports the squares next to the king, and a pawn on the
+        // fifth file rules out pawn drops there
+        board.place_piece(Piece::new(PieceType::Silver, Player::Black), Position::new(2, 4));
+        board.place_piece(Piece::new(PieceType::Pawn, Player::Black), Position::new(6, 4));
+        let drops = safe_king_zone_drops(&board, Player::Black, &captured);
+        assert_eq!(drops[PieceType::Gold.as_index()], 5);
+        assert_eq!(drops[PieceType::Pawn.as_index()], 4);
+
+        // White holds nothing, so it has no drop potential
+        assert_eq!(safe_king_zone_drops(&board, Player::White, &captured), [0; 7]);
+
+        let evaluator = MaterialEvaluator::new();
+        let black = evaluator.evaluate_drop_potential(&board, Player::Black, &captured);
+        let white = evaluator.evaluate_drop_potential(&board, Player::White, &captured);
+        assert!(black.mg > 0);
+        assert_eq!(black, -white);
+    }
+
+    #[test]
+    fn test_legal_drop_squares() {
+        let mut board = BitboardBoard::empty();
+        board.place_piece(Piece::new(PieceType::Pawn, Player::Black), Position::new(6, 4));
+        board.place_piece(Piece::new(PieceType::Gold, Player::White), Position::new(3, 3));
+        let count = |squares: Bitboard| squares.count_ones();
+
+        let black = legal_drop_squares(&board, Player::Black);
+        assert_eq!(count(black[PieceType::Gold.as_index()]), 79);
+        assert_eq!(count(black[PieceType::Lance.as_index()]), 79 - 9);
+        assert_eq!(count(black[PieceType::Knight.as_index()]), 79 - 18);
+        // Not on the last rank, nor the pawn's file
+        assert_eq!(count(black[PieceType::Pawn.as_index()]), 8 * 8 - 1);
+        assert!(!is_bit_set(black[PieceType::Lance.as_index()], Position::new(0, 0)));
+
+        let white = legal_drop_squares(&board, Player::White);
+        assert!(is_bit_set(white[PieceType::Pawn.as_index()], Position::new(0, 4)));
+        assert!(!is_bit_set(white[PieceType::Knight.as_index()], Position::new(7, 0)));
+    }
+
+    #[test]
+    fn test_apply_tuned_hand_weights() {
+        assert_eq!(
+            HAND_PIECE_LIMITS.iter().sum::<usize>(),
+            crate::types::evaluation::HAND_COUNT_FEATURES
+        );
+
+        let mut weights = vec![0.0; crate::types::evaluation::NUM_EVAL_FEATURES];
+        weights[HAND_COUNT_FEATURES_START] = 120.4;
+        weights[HAND_COUNT_FEATURES_START + 1] = 90.0;
+        // First lance follows the 18 pawn entries
+        weights[HAND_COUNT_FEATURES_START + 18] = 310.0;
+        weights[DROP_POTENTIAL_FEATURES_START + 4] = 12.0;
+
+        let mut value_set = MaterialValueSet::research();
+        value_set.apply_tuned_hand_weights(&weights).unwrap();
+        assert_eq!(value_set.hand_value_nth(PieceType::Pawn, 1), TaperedScore::new(120));
+        assert_eq!(value_set.hand_value_nth(PieceType::Pawn, 2), TaperedScore::new(90));
+        assert_eq!(value_set.hand_value_nth(PieceType::Lance, 1), TaperedScore::new(310));
+        assert_eq!(value_set.drop_potential[PieceType::Gold.as_index()], TaperedScore::new(12));
+        assert!(value_set.validate().is_ok());
+
+        assert!(value_set.apply_tuned_hand_weights(&weights[..100]).is_err());
+    }
 }
@@ -0,0 +1,315 @@
+//! Incrementally updated feature transformer output.
+//!
+//! The board owns an [`Accumulator`] once NNUE is attached and reports every
+//! piece placed or removed and every hand change to it, so an evaluation
+//! only runs the small hidden layers. A king move changes every feature of
+//! its own perspective, so that perspective is rebuilt from the board.
+
+use super::features::{
+    active_features, board_feature, hand_feature, hand_index, player_index, HandCounts, NUM_SQUARES,
+};
+use super::network::NnueNetwork;
+use crate::types::board::CapturedPieces;
+use crate::types::core::{Piece, PieceType, Player, Position};
+use std::sync::Arc;
+
+const PERSPECTIVES: [Player; 2] = [Player::Black, Player::White];
+
+/// Feature transformer output of both perspectives
+#[derive(Debug, Clone)]
+pub struct Accumulator {
+    network: Arc<NnueNetwork>,
+    values: [Vec<i16>; 2],
+    /// King square of each perspective; `None` while the king is off the
+    /// board, which leaves that half stale until the king is placed
+    king: [Option<u8>; 2],
+    hands: HandCounts,
+}
+
+impl Accumulator {
+    /// Accumulator of the position with `squares` and `captured` in hand
+    pub fn new(
+        network: Arc<NnueNetwork>,
+        squares: &[Option<Piece>; NUM_SQUARES],
+        captured: &CapturedPieces,
+    ) -> Self {
+        let bias = network.ft_bias().to_vec();
+        let mut accumulator = Self {
+            network,
+            values: [bias.clone(), bias],
+            king: [None; 2],
+            hands: hand_counts(captured),
+        };
+        for perspective in PERSPECTIVES {
+            accumulator.king[player_index(perspective)] = find_king(squares, perspective);
+            accumulator.refresh(perspective, squares);
+        }
+        accumulator
+    }
+
+    pub fn network(&self) -> &Arc<NnueNetwork> {
+        &self.network
+    }
+
+    /// Hand counts the accumulator was last told about
+    pub fn hands(&self) -> &HandCounts {
+        &self.hands
+    }
+
+    /// Rebuild one perspective from scratch
+    pub fn refresh(&mut self, perspective: Player, squares: &[Option<Piece>; NUM_SQUARES]) {
+        let side = player_index(perspective);
+        let Some(king) = self.king[side] else {
+            return;
+        };
+        let mut values = self.network.ft_bias().to_vec();
+        for feature in active_features(perspective, king as usize, squares, &self.hands) {
+            add(&mut values, self.network.ft_row(feature));
+        }
+        self.values[side] = values;
+    }
+
+    /// `piece` was placed on `position`; `squares` already holds it
+    pub fn on_place(
+        &mut self,
+        piece: Piece,
+        position: Position,
+        squares: &[Option<Piece>; NUM_SQUARES],
+    ) {
+        if piece.piece_type == PieceType::King {
+            self.king[player_index(piece.player)] = Some(position.to_index());
+            self.refresh(piece.player, squares);
+            return;
+        }
+        self.update_board(piece, position, add);
+    }
+
+    /// `piece` was removed from `position`
+    pub fn on_remove(&mut self, piece: Piece, position: Position) {
+        if piece.piece_type == PieceType::King {
+            self.king[player_index(piece.player)] = None;
+            return;
+        }
+        self.update_board(piece, position, sub);
+    }
+
+    /// `owner` gained a `piece_type` in hand
+    pub fn add_hand(&mut self, owner: Player, piece_type: PieceType) {
+        let Some(hand) = hand_index(piece_type) else {
+            return;
+        };
+        let count = &mut self.hands[player_index(owner)][hand];
+        *count = count.saturating_add(1);
+        let count = *count;
+        self.update_hand(owner, hand, count, add);
+    }
+
+    /// `owner` lost a `piece_type` from hand
+    pub fn remove_hand(&mut self, owner: Player, piece_type: PieceType) {
+        let Some(hand) = hand_index(piece_type) else {
+            return;
+        };
+        let count = self.hands[player_index(owner)][hand];
+        if count == 0 {
+            return;
+        }
+        self.update_hand(owner, hand, count, sub);
+        self.hands[player_index(owner)][hand] = count - 1;
+    }
+
+    /// Bring the hand features in line with `captured`
+    pub fn sync_hands(&mut self, captured: &CapturedPieces) {
+        let target = hand_counts(captured);
+        for owner in PERSPECTIVES {
+            for (hand, &piece_type) in super::features::HAND_PIECE_TYPES.iter().enumerate() {
+                let wanted = target[player_index(owner)][hand];
+                while self.hands[player_index(owner)][hand] < wanted {
+                    self.add_hand(owner, piece_type);
+                }
+                while self.hands[player_index(owner)][hand] > wanted {
+                    self.remove_hand(owner, piece_type);
+                }
+            }
+        }
+    }
+
+    /// Score in centipawns for `side_to_move`; `None` without both kings
+    pub fn evaluate(&self, side_to_move: Player) -> Option<i32> {
+        if self.king.iter().any(Option::is_none) {
+            return None;
+        }
+        let us = player_index(side_to_move);
+        Some(self.network.evaluate(&self.values[us], &self.values[1 - us]))
+    }
+
+    /// Raw transformer output of one perspective
+    pub fn values(&self, perspective: Player) -> &[i16] {
+        &self.values[player_index(perspective)]
+    }
+
+    fn update_board(&mut self, piece: Piece, position: Position, apply: fn(&mut [i16], &[i16])) {
+        for perspective in PERSPECTIVES {
+            let side = player_index(perspective);
+            let Some(king) = self.king[side] else {
+                continue;
+            };
+            if let Some(feature) = board_feature(perspective, king as usize, piece, position) {
+                apply(&mut self.values[side], self.network.ft_row(feature));
+            }
+        }
+    }
+
+    fn update_hand(
+        &mut self,
+        owner: Player,
+        hand: usize,
+        count: u8,
+        apply: fn(&mut [i16], &[i16]),
+    ) {
+        for perspective in PERSPECTIVES {
+            let side = player_index(perspective);
+            let Some(king) = self.king[side] else {
+                continue;
+            };
+            if let Some(feature) = hand_feature(perspective, king as usize, owner, hand, count) {
+                apply(&mut self.values[side], self.network.ft_row(feature));
+            }
+        }
+    }
+}
+
+/// Hand counts of `captured`
+pub fn hand_counts(captured: &CapturedPieces) -> HandCounts {
+    let mut counts = [[0u8; 7]; 2];
+    for owner in PERSPECTIVES {
+        let pieces = match owner {
+            Player::Black => &captured.black,
+            Player::White => &captured.white,
+        };
+        for &piece_type in pieces {
+            if let Some(hand) = hand_index(piece_type) {
+                let count = &mut counts[player_index(owner)][hand];
+                *count = count.saturating_add(1);
+            }
+        }
+    }
+    counts
+}
+
+fn find_king(squares: &[Option<Piece>; NUM_SQUARES], player: Player) -> Option<u8> {
+    squares
+        .iter()
+        .position(|piece| {
+            matches!(piece, Some(piece) if piece.piece_type == PieceType::King
+                && piece.player == player)
+        })
+        .map(|square| square as u8)
+}
+
+#[inline]
+fn add(acc: &mut [i16], row: &[i16]) {
+    #[cfg(feature = "simd")]
+    if super::simd::add_assign(acc, row) {
+        return;
+    }
+    for (value, &weight) in acc.iter_mut().zip(row) {
+        *value = value.wrapping_add(weight);
+    }
+}
+
+#[inline]
+fn sub(acc: &mut [i16], row: &[i16]) {
+    #[cfg(feature = "simd")]
+    if super::simd::sub_assign(acc, row) {
+        return;
+    }
+    for (value, &weight) in acc.iter_mut().zip(row) {
+        *value = value.wrapping_sub(weight);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitboards::BitboardBoard;
+    use crate::moves::MoveGenerator;
+
+    fn network() -> Arc<NnueNetwork> {
+        Arc::new(NnueNetwork::random(16, 8, 8, 11))
+    }
+
+    fn assert_matches_refresh(board: &BitboardBoard, captured: &CapturedPieces) {
+        let incremental = board.nnue_accumulator().expect("accumulator attached");
+        let mut fresh_board = board.clone();
+        fresh_board.attach_nnue(Arc::clone(incremental.network()), captured);
+        let fresh = fresh_board.nnue_accumulator().unwrap();
+        for perspective in PERSPECTIVES {
+            assert_eq!(incremental.values(perspective), fresh.values(perspective));
+        }
+        assert_eq!(incremental.hands(), &hand_counts(captured));
+    }
+
+    #[test]
+    fn test_incremental_updates_match_refresh() {
+        let sfen = "l2g3nl/2s1k1gs1/p1npppbpp/1r4p2/1pP6/P3P1P2/1P1P1PNPP/1BG1GS1R1/LN2K3L b \
+                    SPp 1";
+        let (mut board, mut player, mut captured) = BitboardBoard::from_fen(sfen).unwrap();
+        board.attach_nnue(network(), &captured);
+        let generator = MoveGenerator::new();
+
+        // Walk a deterministic line, unmaking every few moves, covering
+        // captures, drops, promotions and king moves
+        let mut history = Vec::new();
+        for ply in 0..40 {
+            let moves = generator.generate_legal_moves(&board, player, &captured);
+            if moves.is_empty() {
+                break;
+            }
+            let pick = moves
+                .iter()
+                .position(|mv| mv.is_capture || mv.from.is_none() || mv.is_promotion)
+                .filter(|_| ply % 3 != 2)
+                .unwrap_or(ply * 7 % moves.len());
+            let mv = moves[pick].clone();
+            let captured_before = captured.clone();
+            let info = board.make_move_with_info(&mv);
+            if let Some(piece) = info.captured_piece {
+                captured.add_piece(piece.piece_type, player);
+            }
+            if mv.from.is_none() {
+                captured.remove_piece(mv.piece_type, player);
+            }
+            assert_matches_refresh(&board, &captured);
+            history.push((info, captured_before, player));
+            player = player.opposite();
+
+            if ply % 5 == 4 {
+                let (info, captured_before, mover) = history.pop().unwrap();
+                board.unmake_move(&info);
+                captured = captured_before;
+                player = mover;
+                assert_matches_refresh(&board, &captured);
+            }
+        }
+        while let Some((info, captured_before, _)) = history.pop() {
+            board.unmake_move(&info);
+            captured = captured_before;
+            assert_matches_refresh(&board, &captured);
+        }
+    }
+
+    #[test]
+    fn test_sync_hands() {
+        let board = BitboardBoard::new();
+        let mut captured = CapturedPieces::new();
+        let mut accumulator = Accumulator::new(network(), board.squares(), &captured);
+        captured.add_piece(PieceType::Pawn, Player::Black);
+        captured.add_piece(PieceType::Pawn, Player::Black);
+        captured.add_piece(PieceType::Rook, Player::White);
+        accumulator.sync_hands(&captured);
+        let fresh = Accumulator::new(network(), board.squares(), &captured);
+        assert_eq!(accumulator.values(Player::Black), fresh.values(Player::Black));
+        assert_eq!(accumulator.values(Player::White), fresh.values(Player::White));
+        assert!(accumulator.evaluate(Player::Black).is_some());
+    }
+}
@@ -0,0 +1,203 @@
+//! HalfKP-style input features.
+//!
+//! Each perspective (the side whose king the features are relative to) sees
+//! one feature per non-king piece on the board, indexed by its own king's
+//! square, the piece kind, whether the piece is its own or the opponent's, and
+//! the piece's square. Pieces in hand add one feature per piece held: the
+//! third pawn in hand is a different input from the first, so the network can
+//! learn diminishing value per extra piece.
+//!
+//! White's perspective is rotated by 180 degrees and has friend and foe
+//! swapped, so both sides share the same weights.
+
+use crate::types::core::{Piece, PieceType, Player, Position};
+
+/// Squares on the board
+pub const NUM_SQUARES: usize = 81;
+
+/// Piece kinds that appear as board features: every piece type but the king
+pub const BOARD_PIECE_KINDS: usize = 13;
+
+/// Board features per king square: kind x (own, opponent) x square
+pub const BOARD_FEATURES: usize = 2 * BOARD_PIECE_KINDS * NUM_SQUARES;
+
+/// Piece types that can be held in hand, in feature order
+pub const HAND_PIECE_TYPES: [PieceType; 7] = [
+    PieceType::Pawn,
+    PieceType::Lance,
+    PieceType::Knight,
+    PieceType::Silver,
+    PieceType::Gold,
+    PieceType::Bishop,
+    PieceType::Rook,
+];
+
+/// Most pieces of each hand type one side can hold
+pub const HAND_MAX: [u8; 7] = [18, 4, 4, 4, 4, 2, 2];
+
+/// First hand slot of each hand type
+const HAND_OFFSET: [usize; 7] = [0, 18, 22, 26, 30, 34, 36];
+
+/// Hand slots per side, one per piece that can be held
+pub const HAND_SLOTS: usize = 38;
+
+/// Features per king square
+pub const FEATURES_PER_KING: usize = BOARD_FEATURES + 2 * HAND_SLOTS;
+
+/// Size of the input layer
+pub const NUM_FEATURES: usize = NUM_SQUARES * FEATURES_PER_KING;
+
+/// Hand piece counts of both sides, indexed by player and hand type
+pub type HandCounts = [[u8; 7]; 2];
+
+/// Index of a player in per-perspective arrays
+#[inline]
+pub fn player_index(player: Player) -> usize {
+    match player {
+        Player::Black => 0,
+        Player::White => 1,
+    }
+}
+
+/// Square index as seen from `perspective`
+#[inline]
+fn orient(perspective: Player, square: usize) -> usize {
+    match perspective {
+        Player::Black => square,
+        Player::White => NUM_SQUARES - 1 - square,
+    }
+}
+
+/// Board feature kind of a piece type, `None` for the king
+#[inline]
+fn board_kind(piece_type: PieceType) -> Option<usize> {
+    let king = PieceType::King.as_index();
+    let index = piece_type.as_index();
+    if index == king {
+        None
+    } else {
+        Some(if index > king { index - 1 } else { index })
+    }
+}
+
+/// Hand feature index of a piece type, `None` for types that are never held
+#[inline]
+pub fn hand_index(piece_type: PieceType) -> Option<usize> {
+    HAND_PIECE_TYPES.iter().position(|&held| held == piece_type)
+}
+
+/// Feature of `piece` on `square` for `perspective`, whose king is on
+/// `king_square`; `None` for kings
+#[inline]
+pub fn board_feature(
+    perspective: Player,
+    king_square: usize,
+    piece: Piece,
+    square: Position,
+) -> Option<usize> {
+    let kind = board_kind(piece.piece_type)?;
+    let side = usize::from(piece.player != perspective);
+    Some(
+        orient(perspective, king_square) * FEATURES_PER_KING
+            + (side * BOARD_PIECE_KINDS + kind) * NUM_SQUARES
+            + orient(perspective, square.to_index() as usize),
+    )
+}
+
+/// Feature of the `count`-th piece of hand type `hand` held by `owner`, for
+/// `perspective` with its king on `king_square`
+#[inline]
+pub fn hand_feature(
+    perspective: Player,
+    king_square: usize,
+    owner: Player,
+    hand: usize,
+    count: u8,
+) -> Option<usize> {
+    if count == 0 || count > HAND_MAX[hand] {
+        return None;
+    }
+    let side = usize::from(owner != perspective);
+    Some(
+        orient(perspective, king_square) * FEATURES_PER_KING
+            + BOARD_FEATURES
+            + side * HAND_SLOTS
+            + HAND_OFFSET[hand]
+            + usize::from(count - 1),
+    )
+}
+
+/// Every active feature of `perspective`, whose king is on `king_square`
+pub fn active_features(
+    perspective: Player,
+    king_square: usize,
+    squares: &[Option<Piece>; NUM_SQUARES],
+    hands: &HandCounts,
+) -> Vec<usize> {
+    let mut features = Vec::with_capacity(64);
+    for (index, piece) in squares.iter().enumerate() {
+        if let Some(piece) = piece {
+            let square = Position::from_index(index as u8);
+            features.extend(board_feature(perspective, king_square, *piece, square));
+        }
+    }
+    for owner in [Player::Black, Player::White] {
+        for (hand, &count) in hands[player_index(owner)].iter().enumerate() {
+            for held in 1..=count {
+                features.extend(hand_feature(perspective, king_square, owner, hand, held));
+            }
+        }
+    }
+    features
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feature_indices_are_distinct_and_in_range() {
+        let mut seen = std::collections::HashSet::new();
+        let king_square = 40;
+        for player in [Player::Black, Player::White] {
+            for piece_type in (0..PieceType::COUNT as u8).map(PieceType::from_u8) {
+                for square in 0..NUM_SQUARES as u8 {
+                    let piece = Piece::new(piece_type, player);
+                    let position = Position::from_index(square);
+                    if let Some(feature) =
+                        board_feature(Player::Black, king_square, piece, position)
+                    {
+                        assert!(feature < NUM_FEATURES);
+                        assert!(seen.insert(feature));
+                    }
+                }
+            }
+            for hand in 0..HAND_PIECE_TYPES.len() {
+                for count in 1..=HAND_MAX[hand] {
+                    let feature =
+                        hand_feature(Player::Black, king_square, player, hand, count).unwrap();
+                    assert!(feature < NUM_FEATURES);
+                    assert!(seen.insert(feature));
+                }
+            }
+        }
+        assert_eq!(seen.len(), FEATURES_PER_KING);
+    }
+
+    #[test]
+    fn test_perspectives_mirror() {
+        // A black pawn seen by Black equals the rotated white pawn seen by White
+        let black_pawn = Piece::new(PieceType::Pawn, Player::Black);
+        let white_pawn = Piece::new(PieceType::Pawn, Player::White);
+        let square = Position::from_index(60);
+        let rotated = Position::from_index(80 - 60);
+        assert_eq!(
+            board_feature(Player::Black, 76, black_pawn, square),
+            board_feature(Player::White, 80 - 76, white_pawn, rotated)
+        );
+        assert_eq!(
+            hand_feature(Player::Black, 76, Player::White, 0, 2),
+            hand_feature(Player::White, 4, Player::Black, 0, 2)
+        );
+    }
+}
@@ -0,0 +1,142 @@
+//! NNUE evaluation.
+//!
+//! A HalfKP network ([`NnueNetwork`]) scores positions from king-relative
+//! piece-square and hand-piece features ([`features`]). The feature
+//! transformer output lives in an [`Accumulator`] attached to the
+//! [`BitboardBoard`], which updates it on every `make_move` and
+//! `unmake_move`, so the search only pays for the small hidden layers.
+//!
+//! Networks are loaded with the `EvalFile` USI option and switched on with
+//! `UseNNUE`; without a network the classical evaluator is used.
+
+pub mod accumulator;
+pub mod features;
+pub mod network;
+#[cfg(feature = "simd")]
+pub mod simd;
+
+pub use accumulator::{hand_counts, Accumulator};
+pub use network::{NnueError, NnueNetwork};
+
+use crate::bitboards::BitboardBoard;
+use crate::types::board::CapturedPieces;
+use crate::types::core::Player;
+use std::sync::Arc;
+
+/// Score of the board's attached accumulator for `player`; `None` when no
+/// accumulator is attached or a king is missing
+pub fn evaluate_attached(
+    board: &BitboardBoard,
+    player: Player,
+    captured_pieces: &CapturedPieces,
+) -> Option<i32> {
+    let accumulator = board.nnue_accumulator()?;
+    if *accumulator.hands() == hand_counts(captured_pieces) {
+        return accumulator.evaluate(player);
+    }
+    // Callers that edit the hand without making a move leave the hand
+    // features behind; score a corrected copy
+    let mut synced = accumulator.clone();
+    synced.sync_hands(captured_pieces);
+    synced.evaluate(player)
+}
+
+/// Score of the position for `player` under `network`, using the board's
+/// accumulator when it belongs to `network` and a full refresh otherwise
+pub fn evaluate(
+    network: &Arc<NnueNetwork>,
+    board: &BitboardBoard,
+    player: Player,
+    captured_pieces: &CapturedPieces,
+) -> Option<i32> {
+    match board.nnue_accumulator() {
+        Some(accumulator) if Arc::ptr_eq(accumulator.network(), network) => {
+            evaluate_attached(board, player, captured_pieces)
+        }
+        _ => {
+            Accumulator::new(Arc::clone(network), board.squares(), captured_pieces).evaluate(player)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attached_and_refreshed_scores_agree() {
+        let network = Arc::new(NnueNetwork::random(16, 8, 8, 5));
+        let (mut board, player, mut captured) = BitboardBoard::from_fen(
+            "lnsgk2nl/1r4gs1/p1pppp1pp/1p4p2/7P1/2P6/PP1PPPP1P/1SG4R1/LN2KGSNL b Bb 1",
+        )
+        .unwrap();
+        let refreshed = evaluate(&network, &board, player, &captured).unwrap();
+        board.attach_nnue(Arc::clone(&network), &captured);
+        assert_eq!(evaluate_attached(&board, player, &captured), Some(refreshed));
+        assert_eq!(evaluate(&network, &board, player, &captured), Some(refreshed));
+
+        // A hand edited behind the board's back is still scored correctly
+        captured.add_piece(crate::types::core::PieceType::Pawn, Player::White);
+        let expected = evaluate(&Arc::new((*network).clone()), &board, player, &captured);
+        assert_eq!(evaluate_attached(&board, player, &captured), expected);
+    }
+
+    #[test]
+    fn test_probe_copies_skip_the_accumulator() {
+        let network = Arc::new(NnueNetwork::random(16, 8, 8, 3));
+        let captured = CapturedPieces::new();
+        let mut board = BitboardBoard::new();
+        board.attach_nnue(network, &captured);
+
+        assert!(board.clone().nnue_accumulator().is_some());
+        let probe = board.clone_without_nnue();
+        assert!(probe.nnue_accumulator().is_none());
+        assert_eq!(probe.squares(), board.squares());
+
+        // Legal moves are the same with or without the accumulator
+        let generator = crate::moves::MoveGenerator::new();
+        assert_eq!(
+            generator.generate_legal_moves(&board, Player::Black, &captured).len(),
+            generator.generate_legal_moves(&probe, Player::Black, &captured).len()
+        );
+    }
+
+    #[test]
+    fn test_evaluator_switch() {
+        use crate::evaluation::PositionEvaluator;
+
+        let network = Arc::new(NnueNetwork::random(16, 8, 8, 9));
+        let board = BitboardBoard::new();
+        let captured = CapturedPieces::new();
+        let mut evaluator = PositionEvaluator::new();
+        let classical = evaluator.evaluate(&board, Player::Black, &captured);
+
+        evaluator.set_nnue_network(Some(Arc::clone(&network)));
+        assert!(evaluator.is_using_nnue());
+        let nnue_score = evaluate(&network, &board, Player::Black, &captured).unwrap();
+        assert_eq!(evaluator.evaluate(&board, Player::Black, &captured), nnue_score);
+
+        evaluator.set_use_nnue(false);
+        assert!(!evaluator.is_using_nnue());
+        assert_eq!(evaluator.evaluate(&board, Player::Black, &captured), classical);
+    }
+
+    #[test]
+    fn test_search_with_nnue() {
+        use crate::search::NullReporter;
+        use crate::{SearchLimits, ShogiEngine};
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("small.nnue");
+        NnueNetwork::random(16, 8, 8, 2).save(&path).unwrap();
+
+        let mut engine = ShogiEngine::new();
+        engine.opening_book =
+            Arc::new(std::sync::Mutex::new(crate::opening_book::OpeningBook::new()));
+        engine.set_search_reporter(Arc::new(NullReporter));
+        engine.load_eval_file(path.to_str().unwrap()).unwrap();
+        assert!(engine.is_using_nnue());
+        let outcome = engine.search_with_limits(&SearchLimits::depth(2), None);
+        assert!(outcome.best_move.is_some());
+    }
+}
@@ -0,0 +1,438 @@
+//! Quantised NNUE network and its file format.
+//!
+//! The network is `features -> 2 x L1 -> L2 -> L3 -> 1`. The feature
+//! transformer is int16 and is kept up to date incrementally by
+//! [`super::Accumulator`]; the two perspective halves are concatenated side
+//! to move first, clipped to `0..=QA` and fed through int8 hidden layers.
+//!
+//! Quantisation, for a float network with the same shape:
+//!
+//! - feature transformer weights and biases are scaled by [`QA`]
+//! - hidden and output weights are scaled by [`QW`], their biases by
+//!   `QA * QW`; hidden sums are shifted right by [`WEIGHT_SHIFT`] and
+//!   clipped back to `0..=QA`
+//! - the output sum divided by [`OUTPUT_SCALE`] is the score in centipawns,
+//!   so one float output unit is `QA * QW / OUTPUT_SCALE` centipawns
+//!
+//! The file is little endian: magic, version, the three layer sizes as
+//! `u32`, then every layer's biases followed by its weights, then a
+//! checksum of everything after the header.
+
+use super::features::NUM_FEATURES;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+/// NNUE file format version for compatibility checking
+pub const NNUE_FILE_VERSION: u32 = 1;
+
+/// Magic number for NNUE file identification
+pub const NNUE_FILE_MAGIC: &[u8] = b"SHOGI_NNUE_HKPV1";
+
+/// Activation scale: a clipped activation of 1.0 is stored as `QA`
+pub const QA: i32 = 127;
+
+/// Scale of hidden and output layer weights
+pub const QW: i32 = 1 << WEIGHT_SHIFT;
+
+/// Right shift that brings a hidden layer sum back to activation scale
+pub const WEIGHT_SHIFT: u32 = 6;
+
+/// Divisor turning the output sum into centipawns
+pub const OUTPUT_SCALE: i32 = 16;
+
+/// Largest layer size accepted from a file
+pub(crate) const MAX_LAYER_SIZE: u32 = 4096;
+
+/// Errors that can occur while loading or saving a network
+#[derive(Debug, thiserror::Error)]
+pub enum NnueError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid magic number in NNUE file")]
+    InvalidMagic,
+
+    #[error(
+        "Version mismatch: file version {file_version}, supported version {supported_version}"
+    )]
+    VersionMismatch { file_version: u32, supported_version: u32 },
+
+    #[error("Invalid layer sizes {l1}x{l2}x{l3}")]
+    InvalidLayerSizes { l1: u32, l2: u32, l3: u32 },
+
+    #[error("Checksum mismatch: file checksum {file_checksum}, calculated {calculated_checksum}")]
+    ChecksumMismatch { file_checksum: u64, calculated_checksum: u64 },
+}
+
+/// A quantised HalfKP network
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NnueNetwork {
+    l1: usize,
+    l2: usize,
+    l3: usize,
+    ft_bias: Vec<i16>,
+    /// `NUM_FEATURES` rows of `l1` weights
+    ft_weights: Vec<i16>,
+    l2_bias: Vec<i32>,
+    /// `l2` rows of `2 * l1` weights
+    l2_weights: Vec<i8>,
+    l3_bias: Vec<i32>,
+    /// `l3` rows of `l2` weights
+    l3_weights: Vec<i8>,
+    out_bias: i32,
+    out_weights: Vec<i8>,
+}
+
+impl NnueNetwork {
+    /// Build a network from its quantised layers; `None` if the layer
+    /// lengths do not fit the sizes
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_parts(
+        (l1, l2, l3): (usize, usize, usize),
+        ft_bias: Vec<i16>,
+        ft_weights: Vec<i16>,
+        l2_bias: Vec<i32>,
+        l2_weights: Vec<i8>,
+        l3_bias: Vec<i32>,
+        l3_weights: Vec<i8>,
+        out_bias: i32,
+        out_weights: Vec<i8>,
+    ) -> Option<Self> {
+        let fits = l1 > 0
+            && l2 > 0
+            && l3 > 0
+            && ft_bias.len() == l1
+            && ft_weights.len() == NUM_FEATURES * l1
+            && l2_bias.len() == l2
+            && l2_weights.len() == l2 * 2 * l1
+            && l3_bias.len() == l3
+            && l3_weights.len() == l3 * l2
+            && out_weights.len() == l3;
+        fits.then_some(Self {
+            l1,
+            l2,
+            l3,
+            ft_bias,
+            ft_weights,
+            l2_bias,
+            l2_weights,
+            l3_bias,
+            l3_weights,
+            out_bias,
+            out_weights,
+        })
+    }
+
+    /// Network with small random weights, for tests and as a training seed
+    pub fn random(l1: usize, l2: usize, l3: usize, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut i16s = |len: usize, range: i16| -> Vec<i16> {
+            (0..len).map(|_| rng.gen_range(-range..=range)).collect()
+        };
+        let ft_bias = i16s(l1, 32);
+        let ft_weights = i16s(NUM_FEATURES * l1, 16);
+        let mut i8s =
+            |len: usize| -> Vec<i8> { (0..len).map(|_| rng.gen_range(-32..=32)).collect() };
+        let l2_weights = i8s(l2 * 2 * l1);
+        let l3_weights = i8s(l3 * l2);
+        let out_weights = i8s(l3);
+        Self {
+            l1,
+            l2,
+            l3,
+            ft_bias,
+            ft_weights,
+            l2_bias: vec![0; l2],
+            l2_weights,
+            l3_bias: vec![0; l3],
+            l3_weights,
+            out_bias: 0,
+            out_weights,
+        }
+    }
+
+    /// Width of one accumulator half
+    pub fn l1_size(&self) -> usize {
+        self.l1
+    }
+
+    /// Layer sizes `(l1, l2, l3)`
+    pub fn layer_sizes(&self) -> (usize, usize, usize) {
+        (self.l1, self.l2, self.l3)
+    }
+
+    /// Feature transformer bias, the accumulator of an empty position
+    pub(crate) fn ft_bias(&self) -> &[i16] {
+        &self.ft_bias
+    }
+
+    /// Feature transformer weights of one input feature
+    #[inline]
+    pub(crate) fn ft_row(&self, feature: usize) -> &[i16] {
+        &self.ft_weights[feature * self.l1..(feature + 1) * self.l1]
+    }
+
+    /// Score in centipawns for the side whose accumulator half is `us`
+    pub fn evaluate(&self, us: &[i16], them: &[i16]) -> i32 {
+        debug_assert_eq!(us.len(), self.l1);
+        debug_assert_eq!(them.len(), self.l1);
+        let mut input = vec![0u8; 2 * self.l1];
+        clip(us, &mut input[..self.l1]);
+        clip(them, &mut input[self.l1..]);
+        let mut hidden1 = vec![0u8; self.l2];
+        affine_clipped(&self.l2_weights, &self.l2_bias, &input, &mut hidden1);
+        let mut hidden2 = vec![0u8; self.l3];
+        affine_clipped(&self.l3_weights, &self.l3_bias, &hidden1, &mut hidden2);
+        (self.out_bias + dot(&hidden2, &self.out_weights)) / OUTPUT_SCALE
+    }
+
+    /// Load a network from `path`
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, NnueError> {
+        Self::read_from(&mut BufReader::new(File::open(path)?))
+    }
+
+    /// Save the network to `path`
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), NnueError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_to(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    pub fn read_from<R: Read>(reader: &mut R) -> Result<Self, NnueError> {
+        let mut magic = [0u8; 16];
+        reader.read_exact(&mut magic)?;
+        if magic != NNUE_FILE_MAGIC {
+            return Err(NnueError::InvalidMagic);
+        }
+        let version = read_u32(reader)?;
+        if version != NNUE_FILE_VERSION {
+            return Err(NnueError::VersionMismatch {
+                file_version: version,
+                supported_version: NNUE_FILE_VERSION,
+            });
+        }
+        let (l1, l2, l3) = (read_u32(reader)?, read_u32(reader)?, read_u32(reader)?);
+        let valid = |size: u32| size > 0 && size <= MAX_LAYER_SIZE;
+        if !(valid(l1) && valid(l2) && valid(l3)) {
+            return Err(NnueError::InvalidLayerSizes { l1, l2, l3 });
+        }
+        let (l1, l2, l3) = (l1 as usize, l2 as usize, l3 as usize);
+
+        let mut checksum = Checksum::default();
+        let ft_bias = read_values(reader, l1, &mut checksum, i16::from_le_bytes)?;
+        let ft_weights = read_values(reader, NUM_FEATURES * l1, &mut checksum, i16::from_le_bytes)?;
+        let l2_bias = read_values(reader, l2, &mut checksum, i32::from_le_bytes)?;
+        let l2_weights = read_values(reader, l2 * 2 * l1, &mut checksum, i8::from_le_bytes)?;
+        let l3_bias = read_values(reader, l3, &mut checksum, i32::from_le_bytes)?;
+        let l3_weights = read_values(reader, l3 * l2, &mut checksum, i8::from_le_bytes)?;
+        let out_bias = read_values(reader, 1, &mut checksum, i32::from_le_bytes)?[0];
+        let out_weights = read_values(reader, l3, &mut checksum, i8::from_le_bytes)?;
+
+        let mut stored = [0u8; 8];
+        reader.read_exact(&mut stored)?;
+        let file_checksum = u64::from_le_bytes(stored);
+        if file_checksum != checksum.0 {
+            return Err(NnueError::ChecksumMismatch {
+                file_checksum,
+                calculated_checksum: checksum.0,
+            });
+        }
+
+        Ok(Self {
+            l1,
+            l2,
+            l3,
+            ft_bias,
+            ft_weights,
+            l2_bias,
+            l2_weights,
+            l3_bias,
+            l3_weights,
+            out_bias,
+            out_weights,
+        })
+    }
+
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<(), NnueError> {
+        writer.write_all(NNUE_FILE_MAGIC)?;
+        writer.write_all(&NNUE_FILE_VERSION.to_le_bytes())?;
+        for size in [self.l1, self.l2, self.l3] {
+            writer.write_all(&(size as u32).to_le_bytes())?;
+        }
+        let mut checksum = Checksum::default();
+        write_values(writer, &self.ft_bias, &mut checksum, i16::to_le_bytes)?;
+        write_values(writer, &self.ft_weights, &mut checksum, i16::to_le_bytes)?;
+        write_values(writer, &self.l2_bias, &mut checksum, i32::to_le_bytes)?;
+        write_values(writer, &self.l2_weights, &mut checksum, i8::to_le_bytes)?;
+        write_values(writer, &self.l3_bias, &mut checksum, i32::to_le_bytes)?;
+        write_values(writer, &self.l3_weights, &mut checksum, i8::to_le_bytes)?;
+        write_values(writer, &[self.out_bias], &mut checksum, i32::to_le_bytes)?;
+        write_values(writer, &self.out_weights, &mut checksum, i8::to_le_bytes)?;
+        writer.write_all(&checksum.0.to_le_bytes())?;
+        Ok(())
+    }
+}
+
+/// FNV-1a over the layer bytes
+pub(crate) struct Checksum(pub(crate) u64);
+
+impl Default for Checksum {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Checksum {
+    pub(crate) fn update(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+pub(crate) fn read_u32<R: Read>(reader: &mut R) -> Result<u32, NnueError> {
+    let mut bytes = [0u8; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+pub(crate) fn read_values<R: Read, T, const N: usize>(
+    reader: &mut R,
+    count: usize,
+    checksum: &mut Checksum,
+    decode: fn([u8; N]) -> T,
+) -> Result<Vec<T>, NnueError> {
+    let mut bytes = vec![0u8; count * N];
+    reader.read_exact(&mut bytes)?;
+    checksum.update(&bytes);
+    Ok(bytes.chunks_exact(N).map(|chunk| decode(chunk.try_into().unwrap())).collect())
+}
+
+pub(crate) fn write_values<W: Write, T: Copy, const N: usize>(
+    writer: &mut W,
+    values: &[T],
+    checksum: &mut Checksum,
+    encode: fn(T) -> [u8; N],
+) -> Result<(), NnueError> {
+    let mut bytes = Vec::with_capacity(values.len() * N);
+    for &value in values {
+        bytes.extend_from_slice(&encode(value));
+    }
+    checksum.update(&bytes);
+    writer.write_all(&bytes)?;
+    Ok(())
+}
+
+/// Clipped ReLU from accumulator scale to activations
+#[inline]
+fn clip(values: &[i16], out: &mut [u8]) {
+    for (out, &value) in out.iter_mut().zip(values) {
+        *out = value.clamp(0, QA as i16) as u8;
+    }
+}
+
+/// `out = clip((weights * input + bias) >> WEIGHT_SHIFT)`
+#[inline]
+fn affine_clipped(weights: &[i8], bias: &[i32], input: &[u8], out: &mut [u8]) {
+    for (row, (out, &bias)) in weights.chunks_exact(input.len()).zip(out.iter_mut().zip(bias)) {
+        let sum = (bias + dot(input, row)) >> WEIGHT_SHIFT;
+        *out = sum.clamp(0, QA) as u8;
+    }
+}
+
+#[inline]
+fn dot(input: &[u8], weights: &[i8]) -> i32 {
+    #[cfg(feature = "simd")]
+    if let Some(sum) = super::simd::dot(input, weights) {
+        return sum;
+    }
+    dot_scalar(input, weights)
+}
+
+#[inline]
+pub(crate) fn dot_scalar(input: &[u8], weights: &[i8]) -> i32 {
+    input.iter().zip(weights).map(|(&x, &w)| i32::from(x) * i32::from(w)).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn small_network() -> NnueNetwork {
+        NnueNetwork::random(8, 4, 4, 3)
+    }
+
+    #[test]
+    fn test_file_round_trip() {
+        let network = small_network();
+        let mut bytes = Vec::new();
+        network.write_to(&mut bytes).unwrap();
+        let loaded = NnueNetwork::read_from(&mut bytes.as_slice()).unwrap();
+        assert_eq!(loaded, network);
+    }
+
+    #[test]
+    fn test_corrupt_files_are_rejected() {
+        let mut bytes = Vec::new();
+        small_network().write_to(&mut bytes).unwrap();
+
+        let mut bad_magic = bytes.clone();
+        bad_magic[0] ^= 1;
+        assert!(matches!(
+            NnueNetwork::read_from(&mut bad_magic.as_slice()),
+            Err(NnueError::InvalidMagic)
+        ));
+
+        let mut bad_weight = bytes.clone();
+        let middle = bad_weight.len() / 2;
+        bad_weight[middle] ^= 1;
+        assert!(matches!(
+            NnueNetwork::read_from(&mut bad_weight.as_slice()),
+            Err(NnueError::ChecksumMismatch { .. })
+        ));
+
+        let truncated = &bytes[..bytes.len() - 4];
+        assert!(matches!(NnueNetwork::read_from(&mut &truncated[..]), Err(NnueError::Io(_))));
+    }
+
+    #[test]
+    fn test_output_scale() {
+        let (l1, l2, l3) = (2, 2, 2);
+        let network = NnueNetwork::from_parts(
+            (l1, l2, l3),
+            vec![0; l1],
+            vec![0; NUM_FEATURES * l1],
+            vec![0; l2],
+            vec![0; l2 * 2 * l1],
+            vec![0; l3],
+            vec![0; l3 * l2],
+            100 * OUTPUT_SCALE,
+            vec![0; l3],
+        )
+        .unwrap();
+        assert_eq!(network.evaluate(&[50, -50], &[0, 300]), 100);
+        assert!(NnueNetwork::from_parts(
+            (l1, l2, l3),
+            vec![0; l1],
+            Vec::new(),
+            vec![0; l2],
+            vec![0; l2 * 2 * l1],
+            vec![0; l3],
+            vec![0; l3 * l2],
+            0,
+            vec![0; l3],
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_simd_dot_matches_scalar() {
+        let input: Vec<u8> = (0..100u32).map(|i| (i * 37 % 128) as u8).collect();
+        let weights: Vec<i8> = (0..100u32).map(|i| (i * 91 % 256) as u8 as i8).collect();
+        assert_eq!(dot(&input, &weights), dot_scalar(&input, &weights));
+    }
+}
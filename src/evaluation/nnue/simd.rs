@@ -0,0 +1,198 @@
+//! AVX2 and NEON kernels for the NNUE accumulator and hidden layers.
+//!
+//! Each kernel returns `false` when the CPU lacks the instructions, and the
+//! caller falls back to the scalar loop. Results match the scalar code bit
+//! for bit: accumulator arithmetic wraps like `wrapping_add`, and the dot
+//! product of clipped activations (0..=127) with int8 weights cannot
+//! saturate the intermediate 16-bit sums.
+
+/// `acc[i] += row[i]` with wrapping
+#[inline]
+pub fn add_assign(acc: &mut [i16], row: &[i16]) -> bool {
+    debug_assert_eq!(acc.len(), row.len());
+    #[cfg(target_arch = "x86_64")]
+    {
+        if crate::bitboards::platform_detection::get_platform_capabilities().should_use_avx2() {
+            unsafe { x86::add_assign_avx2(acc, row) };
+            return true;
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        unsafe { neon::add_assign(acc, row) };
+        return true;
+    }
+    #[allow(unreachable_code)]
+    false
+}
+
+/// `acc[i] -= row[i]` with wrapping
+#[inline]
+pub fn sub_assign(acc: &mut [i16], row: &[i16]) -> bool {
+    debug_assert_eq!(acc.len(), row.len());
+    #[cfg(target_arch = "x86_64")]
+    {
+        if crate::bitboards::platform_detection::get_platform_capabilities().should_use_avx2() {
+            unsafe { x86::sub_assign_avx2(acc, row) };
+            return true;
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        unsafe { neon::sub_assign(acc, row) };
+        return true;
+    }
+    #[allow(unreachable_code)]
+    false
+}
+
+/// Dot product of clipped activations with one row of int8 weights
+#[inline]
+pub fn dot(input: &[u8], weights: &[i8]) -> Option<i32> {
+    debug_assert_eq!(input.len(), weights.len());
+    #[cfg(target_arch = "x86_64")]
+    {
+        if crate::bitboards::platform_detection::get_platform_capabilities().should_use_avx2() {
+            return Some(unsafe { x86::dot_avx2(input, weights) });
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        return Some(unsafe { neon::dot(input, weights) });
+    }
+    #[allow(unreachable_code)]
+    None
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use std::arch::x86_64::*;
+
+    const LANES_16: usize = 16;
+    const LANES_8: usize = 32;
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn add_assign_avx2(acc: &mut [i16], row: &[i16]) {
+        let chunks = acc.len() / LANES_16;
+        for chunk in 0..chunks {
+            let a = acc.as_mut_ptr().add(chunk * LANES_16) as *mut __m256i;
+            let r = row.as_ptr().add(chunk * LANES_16) as *const __m256i;
+            _mm256_storeu_si256(a, _mm256_add_epi16(_mm256_loadu_si256(a), _mm256_loadu_si256(r)));
+        }
+        for i in chunks * LANES_16..acc.len() {
+            acc[i] = acc[i].wrapping_add(row[i]);
+        }
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn sub_assign_avx2(acc: &mut [i16], row: &[i16]) {
+        let chunks = acc.len() / LANES_16;
+        for chunk in 0..chunks {
+            let a = acc.as_mut_ptr().add(chunk * LANES_16) as *mut __m256i;
+            let r = row.as_ptr().add(chunk * LANES_16) as *const __m256i;
+            _mm256_storeu_si256(a, _mm256_sub_epi16(_mm256_loadu_si256(a), _mm256_loadu_si256(r)));
+        }
+        for i in chunks * LANES_16..acc.len() {
+            acc[i] = acc[i].wrapping_sub(row[i]);
+        }
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn dot_avx2(input: &[u8], weights: &[i8]) -> i32 {
+        let chunks = input.len() / LANES_8;
+        let ones = _mm256_set1_epi16(1);
+        let mut sum = _mm256_setzero_si256();
+        for chunk in 0..chunks {
+            let x = _mm256_loadu_si256(input.as_ptr().add(chunk * LANES_8) as *const __m256i);
+            let w = _mm256_loadu_si256(weights.as_ptr().add(chunk * LANES_8) as *const __m256i);
+            // Pairwise u8 x i8 products fit in i16 because inputs are at most 127
+            let products = _mm256_maddubs_epi16(x, w);
+            sum = _mm256_add_epi32(sum, _mm256_madd_epi16(products, ones));
+        }
+        let mut lanes = [0i32; 8];
+        _mm256_storeu_si256(lanes.as_mut_ptr() as *mut __m256i, sum);
+        let mut total: i32 = lanes.iter().sum();
+        for i in chunks * LANES_8..input.len() {
+            total += i32::from(input[i]) * i32::from(weights[i]);
+        }
+        total
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod neon {
+    use std::arch::aarch64::*;
+
+    const LANES_16: usize = 8;
+    const LANES_8: usize = 8;
+
+    pub unsafe fn add_assign(acc: &mut [i16], row: &[i16]) {
+        let chunks = acc.len() / LANES_16;
+        for chunk in 0..chunks {
+            let a = acc.as_mut_ptr().add(chunk * LANES_16);
+            let r = row.as_ptr().add(chunk * LANES_16);
+            vst1q_s16(a, vaddq_s16(vld1q_s16(a), vld1q_s16(r)));
+        }
+        for i in chunks * LANES_16..acc.len() {
+            acc[i] = acc[i].wrapping_add(row[i]);
+        }
+    }
+
+    pub unsafe fn sub_assign(acc: &mut [i16], row: &[i16]) {
+        let chunks = acc.len() / LANES_16;
+        for chunk in 0..chunks {
+            let a = acc.as_mut_ptr().add(chunk * LANES_16);
+            let r = row.as_ptr().add(chunk * LANES_16);
+            vst1q_s16(a, vsubq_s16(vld1q_s16(a), vld1q_s16(r)));
+        }
+        for i in chunks * LANES_16..acc.len() {
+            acc[i] = acc[i].wrapping_sub(row[i]);
+        }
+    }
+
+    pub unsafe fn dot(input: &[u8], weights: &[i8]) -> i32 {
+        let chunks = input.len() / LANES_8;
+        let mut sum = vdupq_n_s32(0);
+        for chunk in 0..chunks {
+            // Activations are at most 127, so they can be read as i8
+            let x = vld1_s8(input.as_ptr().add(chunk * LANES_8) as *const i8);
+            let w = vld1_s8(weights.as_ptr().add(chunk * LANES_8));
+            sum = vpadalq_s16(sum, vmull_s8(x, w));
+        }
+        let mut total = vaddvq_s32(sum);
+        for i in chunks * LANES_8..input.len() {
+            total += i32::from(input[i]) * i32::from(weights[i]);
+        }
+        total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kernels_match_scalar() {
+        let row: Vec<i16> = (0..37).map(|i| 30_000 - i * 1_000).collect();
+        let mut acc: Vec<i16> = (0..37).map(|i| i * 900).collect();
+        let mut expected = acc.clone();
+        if add_assign(&mut acc, &row) {
+            for (value, &weight) in expected.iter_mut().zip(&row) {
+                *value = value.wrapping_add(weight);
+            }
+            assert_eq!(acc, expected);
+        }
+        if sub_assign(&mut acc, &row) {
+            for (value, &weight) in expected.iter_mut().zip(&row) {
+                *value = value.wrapping_sub(weight);
+            }
+            assert_eq!(acc, expected);
+        }
+
+        let input: Vec<u8> = (0..70u32).map(|i| (i * 37 % 128) as u8).collect();
+        let weights: Vec<i8> = (0..70u32).map(|i| (i * 91 % 256) as u8 as i8).collect();
+        if let Some(sum) = dot(&input, &weights) {
+            assert_eq!(sum, super::super::network::dot_scalar(&input, &weights));
+        }
+    }
+}
@@ -0,0 +1,520 @@
+//! Opening Strategy Classification
+//!
+//! Recognises the strategy actually being played from the board instead of
+//! relying on the free-text `opening_name` carried by book moves. Each side's
+//! rook file places it in the Static Rook (Ibisha) or one of the Ranging Rook
+//! (Furibisha) families; rook-pawn exchanges, the bishop exchange, the side
+//! pawn capture and the Yagura shell then separate the double Static Rook
+//! openings.
+//!
+//! Files and ranks are always counted from the side's own point of view, so a
+//! White rook on 2八 (Black's notation) is on White's own second file.
+//!
+//! The classification feeds strategy-aware evaluation weights, opening book
+//! tagging (see `OpeningBook::strategy_distribution`) and the analyzer's
+//! `strategy` subcommand.
+
+use crate::bitboards::BitboardBoard;
+use crate::evaluation::castles::CastleRecognizer;
+use crate::evaluation::config::EvaluationWeights;
+use crate::opening_book::templates::{self, OpeningTemplate};
+use crate::types::board::CapturedPieces;
+use crate::types::core::{PieceType, Player, Position};
+use std::fmt;
+
+/// Where a side has placed its rook
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RookPlacement {
+    /// Rook on its own files 1-4 (Ibisha)
+    StaticRook,
+    /// Rook on the fifth file (Nakabisha)
+    Central,
+    /// Rook on its own sixth file (Shikenbisha)
+    FourthFile,
+    /// Rook on its own seventh file (Sankenbisha)
+    ThirdFile,
+    /// Rook on its own eighth or ninth file, facing the enemy rook (Mukaibisha)
+    Opposing,
+}
+
+impl RookPlacement {
+    /// Classify a rook standing on the side's own `file` (1-9)
+    pub fn from_own_file(file: u8) -> Self {
+        match file {
+            0..=4 => RookPlacement::StaticRook,
+            5 => RookPlacement::Central,
+            6 => RookPlacement::FourthFile,
+            7 => RookPlacement::ThirdFile,
+            _ => RookPlacement::Opposing,
+        }
+    }
+
+    pub fn is_ranging(self) -> bool {
+        self != RookPlacement::StaticRook
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            RookPlacement::StaticRook => "Ibisha",
+            RookPlacement::Central => "Nakabisha",
+            RookPlacement::FourthFile => "Shikenbisha",
+            RookPlacement::ThirdFile => "Sankenbisha",
+            RookPlacement::Opposing => "Mukaibisha",
+        }
+    }
+}
+
+/// Opening family of the whole position
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OpeningFamily {
+    /// Both rooks and rook pawns still at home; nothing committed yet
+    Undetermined,
+    /// Double Static Rook without a more specific signature
+    Ibisha,
+    /// Double Wing Attack: both rook pawns pushed or exchanged, a bishop
+    /// diagonal still closed
+    Aigakari,
+    /// Bishop Exchange: both bishops in hand
+    Kakugawari,
+    /// Side Pawn Capture: rook pawns exchanged and the enemy side pawn taken
+    Yokofudori,
+    /// Yagura shell (gold and silver stacked on the seventh file)
+    Yagura,
+    /// Central Rook against Static Rook
+    Nakabisha,
+    /// Central Rook with the centre pawn on the fifth rank and the bishop
+    /// diagonal open
+    GokigenNakabisha,
+    /// Fourth File Rook against Static Rook
+    Shikenbisha,
+    /// Third File Rook against Static Rook
+    Sankenbisha,
+    /// Opposing Rook against Static Rook
+    Mukaibisha,
+    /// Double Ranging Rook
+    AiFuribisha,
+}
+
+impl OpeningFamily {
+    pub fn name(self) -> &'static str {
+        match self {
+            OpeningFamily::Undetermined => "Undetermined",
+            OpeningFamily::Ibisha => "Ibisha",
+            OpeningFamily::Aigakari => "Aigakari",
+            OpeningFamily::Kakugawari => "Kakugawari",
+            OpeningFamily::Yokofudori => "Yokofudori",
+            OpeningFamily::Yagura => "Yagura",
+            OpeningFamily::Nakabisha => "Nakabisha",
+            OpeningFamily::GokigenNakabisha => "Gokigen Nakabisha",
+            OpeningFamily::Shikenbisha => "Shikenbisha",
+            OpeningFamily::Sankenbisha => "Sankenbisha",
+            OpeningFamily::Mukaibisha => "Mukaibisha",
+            OpeningFamily::AiFuribisha => "Ai-furibisha",
+        }
+    }
+
+    /// True for every family where at least one rook has ranged
+    pub fn is_ranging(self) -> bool {
+        matches!(
+            self,
+            OpeningFamily::Nakabisha
+                | OpeningFamily::GokigenNakabisha
+                | OpeningFamily::Shikenbisha
+                | OpeningFamily::Sankenbisha
+                | OpeningFamily::Mukaibisha
+                | OpeningFamily::AiFuribisha
+        )
+    }
+
+    /// Opening-book template for this family (Static Rook when undetermined)
+    pub fn template(self) -> &'static OpeningTemplate {
+        templates::find_template_for_opening(self.name())
+            .unwrap_or_else(templates::default_template)
+    }
+
+    /// Evaluation weight multipliers that suit the family's typical plans
+    pub fn weight_multipliers(self) -> StrategyWeightMultipliers {
+        let base = StrategyWeightMultipliers::default();
+        match self {
+            OpeningFamily::Undetermined | OpeningFamily::Ibisha => base,
+            // Rook-file fights: piece activity and development decide
+            OpeningFamily::Aigakari => {
+                StrategyWeightMultipliers { mobility: 1.1, development: 1.1, ..base }
+            }
+            // Bishop drops into the camp make king safety more pressing
+            OpeningFamily::Kakugawari => {
+                StrategyWeightMultipliers { king_safety: 1.15, development: 1.1, ..base }
+            }
+            // Open positions with thin castles
+            OpeningFamily::Yokofudori => {
+                StrategyWeightMultipliers { king_safety: 1.2, mobility: 1.15, castle: 0.85, ..base }
+            }
+            OpeningFamily::Yagura => {
+                StrategyWeightMultipliers { castle: 1.1, pawn_structure: 1.1, ..base }
+            }
+            // Static vs Ranging: castles are built fully before the fight
+            OpeningFamily::Nakabisha
+            | OpeningFamily::Shikenbisha
+            | OpeningFamily::Sankenbisha
+            | OpeningFamily::Mukaibisha => {
+                StrategyWeightMultipliers { king_safety: 1.1, castle: 1.2, ..base }
+            }
+            OpeningFamily::GokigenNakabisha => {
+                StrategyWeightMultipliers { king_safety: 1.1, castle: 1.1, mobility: 1.1, ..base }
+            }
+            OpeningFamily::AiFuribisha => {
+                StrategyWeightMultipliers { castle: 1.1, pawn_structure: 1.1, ..base }
+            }
+        }
+    }
+}
+
+impl fmt::Display for OpeningFamily {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Multipliers applied to `EvaluationWeights` for a recognised strategy
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StrategyWeightMultipliers {
+    pub king_safety: f32,
+    pub castle: f32,
+    pub mobility: f32,
+    pub development: f32,
+    pub pawn_structure: f32,
+}
+
+impl Default for StrategyWeightMultipliers {
+    fn default() -> Self {
+        Self { king_safety: 1.0, castle: 1.0, mobility: 1.0, development: 1.0, pawn_structure: 1.0 }
+    }
+}
+
+impl StrategyWeightMultipliers {
+    pub fn apply(&self, weights: &mut EvaluationWeights) {
+        weights.king_safety_weight *= self.king_safety;
+        weights.castle_weight *= self.castle;
+        weights.mobility_weight *= self.mobility;
+        weights.development_weight *= self.development;
+        weights.pawn_structure_weight *= self.pawn_structure;
+    }
+}
+
+/// Strategy features of one side
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SideStrategy {
+    /// Rook family, `None` when the side has no unpromoted rook on the board
+    pub rook: Option<RookPlacement>,
+    /// Own file (1-9) of the rook used for `rook`
+    pub rook_file: Option<u8>,
+    /// Rook pawn no longer on its starting square
+    pub rook_pawn_advanced: bool,
+    /// Neither side has a pawn left on this side's rook-pawn file
+    pub rook_pawn_exchanged: bool,
+    /// Bishop diagonal opened by moving the own seventh-file pawn
+    pub bishop_diagonal_open: bool,
+    /// Own fifth-file pawn on the fifth rank or beyond
+    pub centre_pawn_advanced: bool,
+    /// Gold on 7八 under a silver on 7七 and a pawn on 7六 (own view)
+    pub yagura_shell: bool,
+    /// Castle recognised by `OpeningClassification::with_castles`
+    pub castle: Option<&'static str>,
+}
+
+/// Result of classifying a position
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpeningClassification {
+    pub family: OpeningFamily,
+    /// Side playing the ranging rook against a static rook, if any
+    pub ranging_player: Option<Player>,
+    pub black: SideStrategy,
+    pub white: SideStrategy,
+}
+
+impl OpeningClassification {
+    pub fn side(&self, player: Player) -> &SideStrategy {
+        match player {
+            Player::Black => &self.black,
+            Player::White => &self.white,
+        }
+    }
+
+    /// Fill in each side's recognised castle. A Yagura castle upgrades an
+    /// otherwise plain double Static Rook classification to `Yagura`.
+    pub fn with_castles(mut self, board: &BitboardBoard, castles: &CastleRecognizer) -> Self {
+        for player in [Player::Black, Player::White] {
+            let castle = board.find_king_position(player).and_then(|king_pos| {
+                castles.evaluate_castle(board, player, king_pos).matched_pattern
+            });
+            match player {
+                Player::Black => self.black.castle = castle,
+                Player::White => self.white.castle = castle,
+            }
+        }
+
+        if matches!(self.family, OpeningFamily::Undetermined | OpeningFamily::Ibisha)
+            && (self.black.castle == Some("Yagura") || self.white.castle == Some("Yagura"))
+        {
+            self.family = OpeningFamily::Yagura;
+        }
+        self
+    }
+}
+
+impl fmt::Display for OpeningClassification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.family)?;
+        if let Some(player) = self.ranging_player {
+            write!(f, " ({:?} ranging)", player)?;
+        }
+        Ok(())
+    }
+}
+
+/// Classify the opening strategy of a position
+pub fn classify_opening(board: &BitboardBoard, captured: &CapturedPieces) -> OpeningClassification {
+    let black = side_strategy(board, Player::Black);
+    let white = side_strategy(board, Player::White);
+
+    let (family, ranging_player) = match (black.rook, white.rook) {
+        (Some(b), Some(w)) if b.is_ranging() && w.is_ranging() => {
+            (OpeningFamily::AiFuribisha, None)
+        }
+        (Some(b), _) if b.is_ranging() => (ranging_family(b, &black), Some(Player::Black)),
+        (_, Some(w)) if w.is_ranging() => (ranging_family(w, &white), Some(Player::White)),
+        (Some(_), Some(_)) => (static_family(board, captured, &black, &white), None),
+        _ => (OpeningFamily::Undetermined, None),
+    };
+
+    OpeningClassification { family, ranging_player, black, white }
+}
+
+fn ranging_family(rook: RookPlacement, side: &SideStrategy) -> OpeningFamily {
+    match rook {
+        RookPlacement::Central if side.centre_pawn_advanced && side.bishop_diagonal_open => {
+            OpeningFamily::GokigenNakabisha
+        }
+        RookPlacement::Central => OpeningFamily::Nakabisha,
+        RookPlacement::FourthFile => OpeningFamily::Shikenbisha,
+        RookPlacement::ThirdFile => OpeningFamily::Sankenbisha,
+        RookPlacement::Opposing => OpeningFamily::Mukaibisha,
+        RookPlacement::StaticRook => OpeningFamily::Ibisha,
+    }
+}
+
+fn static_family(
+    board: &BitboardBoard,
+    captured: &CapturedPieces,
+    black: &SideStrategy,
+    white: &SideStrategy,
+) -> OpeningFamily {
+    let both_exchanged = black.rook_pawn_exchanged && white.rook_pawn_exchanged;
+    if both_exchanged
+        && (side_pawn_taken(board, Player::Black) || side_pawn_taken(board, Player::White))
+    {
+        return OpeningFamily::Yokofudori;
+    }
+
+    let bishops_in_hand = captured.count(PieceType::Bishop, Player::Black) > 0
+        && captured.count(PieceType::Bishop, Player::White) > 0;
+    if bishops_in_hand {
+        return OpeningFamily::Kakugawari;
+    }
+
+    if black.yagura_shell || white.yagura_shell {
+        return OpeningFamily::Yagura;
+    }
+
+    let wings_pushed = (black.rook_pawn_advanced || black.rook_pawn_exchanged)
+        && (white.rook_pawn_advanced || white.rook_pawn_exchanged);
+    if wings_pushed && !(black.bishop_diagonal_open && white.bishop_diagonal_open) {
+        return OpeningFamily::Aigakari;
+    }
+
+    let rooks_home = black.rook_file == Some(2) && white.rook_file == Some(2);
+    if rooks_home && !black.rook_pawn_advanced && !white.rook_pawn_advanced {
+        OpeningFamily::Undetermined
+    } else {
+        OpeningFamily::Ibisha
+    }
+}
+
+fn side_strategy(board: &BitboardBoard, player: Player) -> SideStrategy {
+    let rook_pos = find_rook(board, player);
+    let rook_file = rook_pos.map(|pos| own_file(player, pos));
+
+    let rook_pawn = own_pawn_rank(board, player, 2);
+    let opponent_rook_pawn = own_pawn_rank(board, player.opposite(), 8);
+    let bishop_pawn = own_pawn_rank(board, player, 7);
+    let centre_pawn = own_pawn_rank(board, player, 5);
+
+    SideStrategy {
+        rook: rook_file.map(RookPlacement::from_own_file),
+        rook_file,
+        rook_pawn_advanced: rook_pawn != Some(7),
+        rook_pawn_exchanged: rook_pawn.is_none() && opponent_rook_pawn.is_none(),
+        bishop_diagonal_open: bishop_pawn != Some(7),
+        centre_pawn_advanced: centre_pawn.is_some_and(|rank| rank <= 5),
+        yagura_shell: bishop_pawn == Some(6)
+            && has_own_piece(board, player, 7, 7, PieceType::Silver)
+            && has_own_piece(board, player, 7, 8, PieceType::Gold),
+        castle: None,
+    }
+}
+
+/// Prefer a rook outside the enemy camp; a rook dropped there later in the
+/// game says nothing about the opening.
+fn find_rook(board: &BitboardBoard, player: Player) -> Option<Position> {
+    let mut fallback = None;
+    for row in 0..9 {
+        for col in 0..9 {
+            let pos = Position::new(row, col);
+            if let Some(piece) = board.get_piece(pos) {
+                if piece.player == player && piece.piece_type == PieceType::Rook {
+                    if own_rank(player, pos) >= 4 {
+                        return Some(pos);
+                    }
+                    fallback.get_or_insert(pos);
+                }
+            }
+        }
+    }
+    fallback
+}
+
+/// Yokofudori signature for `player`: the opponent's pawn on our third file
+/// is gone while our own third-file pawn is still there.
+fn side_pawn_taken(board: &BitboardBoard, player: Player) -> bool {
+    own_pawn_rank(board, player, 3).is_some()
+        && own_pawn_rank(board, player.opposite(), 7).is_none()
+}
+
+/// Own rank (1-9) of `player`'s pawn on their own `file`, if any
+fn own_pawn_rank(board: &BitboardBoard, player: Player, file: u8) -> Option<u8> {
+    let col = board_col(player, file);
+    (0..9).map(|row| Position::new(row, col)).find_map(|pos| {
+        board
+            .get_piece(pos)
+            .filter(|piece| piece.player == player && piece.piece_type == PieceType::Pawn)
+            .map(|_| own_rank(player, pos))
+    })
+}
+
+fn has_own_piece(
+    board: &BitboardBoard,
+    player: Player,
+    file: u8,
+    rank: u8,
+    piece_type: PieceType,
+) -> bool {
+    let row = match player {
+        Player::Black => rank - 1,
+        Player::White => 9 - rank,
+    };
+    board
+        .get_piece(Position::new(row, board_col(player, file)))
+        .is_some_and(|piece| piece.player == player && piece.piece_type == piece_type)
+}
+
+fn board_col(player: Player, file: u8) -> u8 {
+    match player {
+        Player::Black => 9 - file,
+        Player::White => file - 1,
+    }
+}
+
+fn own_file(player: Player, pos: Position) -> u8 {
+    match player {
+        Player::Black => 9 - pos.col,
+        Player::White => pos.col + 1,
+    }
+}
+
+fn own_rank(player: Player, pos: Position) -> u8 {
+    match player {
+        Player::Black => pos.row + 1,
+        Player::White => 9 - pos.row,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn classify(sfen: &str) -> OpeningClassification {
+        let (board, _, captured) = BitboardBoard::from_fen(sfen).unwrap();
+        classify_opening(&board, &captured)
+    }
+
+    #[test]
+    fn test_start_position_is_undetermined() {
+        let (board, captured) = (BitboardBoard::new(), CapturedPieces::new());
+        let classification = classify_opening(&board, &captured);
+        assert_eq!(classification.family, OpeningFamily::Undetermined);
+        assert_eq!(classification.black.rook, Some(RookPlacement::StaticRook));
+        assert_eq!(classification.white.rook_file, Some(2));
+    }
+
+    #[test]
+    fn test_ranging_rook_files() {
+        let shikenbisha =
+            classify("lnsgkgsnl/1r5b1/ppppppppp/9/9/2P6/PP1PPPPPP/1B1R5/LNSGKGSNL w - 4");
+        assert_eq!(shikenbisha.family, OpeningFamily::Shikenbisha);
+        assert_eq!(shikenbisha.ranging_player, Some(Player::Black));
+
+        // White's rook on its own seventh file (3二 in Black's notation)
+        let sankenbisha =
+            classify("lnsgkgsnl/6rb1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b - 3");
+        assert_eq!(sankenbisha.family, OpeningFamily::Sankenbisha);
+        assert_eq!(sankenbisha.ranging_player, Some(Player::White));
+
+        let ai_furibisha =
+            classify("lnsgkgsnl/5r1b1/ppppppppp/9/9/2P6/PPBPPPPPP/1R7/LNSGKGSNL b - 5");
+        assert_eq!(ai_furibisha.family, OpeningFamily::AiFuribisha);
+        assert_eq!(ai_furibisha.black.rook, Some(RookPlacement::Opposing));
+        assert_eq!(ai_furibisha.white.rook, Some(RookPlacement::FourthFile));
+    }
+
+    #[test]
+    fn test_gokigen_needs_open_bishop_diagonal() {
+        let gokigen =
+            classify("lnsgkgsnl/1r5b1/ppppppppp/9/4P4/2P6/PP1P1PPPP/1B2R4/LNSGKGSNL w - 6");
+        assert_eq!(gokigen.family, OpeningFamily::GokigenNakabisha);
+
+        let closed = classify("lnsgkgsnl/1r5b1/ppppppppp/9/4P4/9/PPPP1PPPP/1B2R4/LNSGKGSNL w - 6");
+        assert_eq!(closed.family, OpeningFamily::Nakabisha);
+    }
+
+    #[test]
+    fn test_double_static_rook_families() {
+        let kakugawari =
+            classify("lnsgkgsnl/1r7/pppppp1pp/6p2/9/2P6/PP1PPPPPP/7R1/LNSGKGSNL b Bb 5");
+        assert_eq!(kakugawari.family, OpeningFamily::Kakugawari);
+
+        let aigakari =
+            classify("lnsgkgsnl/1r5b1/p1ppppppp/9/1p5P1/9/PPPPPPP1P/1B5R1/LNSGKGSNL b - 9");
+        assert_eq!(aigakari.family, OpeningFamily::Aigakari);
+
+        let yokofudori =
+            classify("lnsgkgsnl/1r5b1/p1pppp2p/6R2/9/2P6/P2PPPP1P/1B7/LNSGKGSNL w 3P2p 10");
+        assert_eq!(yokofudori.family, OpeningFamily::Yokofudori);
+        assert!(yokofudori.black.rook_pawn_exchanged && yokofudori.white.rook_pawn_exchanged);
+
+        let yagura = classify("lnsgkgsnl/1r5b1/ppppppppp/9/9/2P6/PPSPPPPPP/1BG4R1/LN2KGSNL w - 8");
+        assert_eq!(yagura.family, OpeningFamily::Yagura);
+        assert!(yagura.black.yagura_shell);
+    }
+
+    #[test]
+    fn test_family_templates_and_weights() {
+        assert_eq!(OpeningFamily::Shikenbisha.template().canonical_name, "Ranging Rook");
+        assert_eq!(OpeningFamily::Kakugawari.template().canonical_name, "Static Rook");
+        assert_eq!(OpeningFamily::Undetermined.template().canonical_name, "Static Rook");
+
+        let mut weights = EvaluationWeights::default();
+        let castle_before = weights.castle_weight;
+        OpeningFamily::Mukaibisha.weight_multipliers().apply(&mut weights);
+        assert!(weights.castle_weight > castle_before);
+    }
+}
@@ -0,0 +1,93 @@
+//! Boat castle (Funagakoi)
+//!
+//! The quick static-rook castle: king on 7八 beside a silver on 6八 and a gold
+//! on 6九. It is thin on top, so the score is modest and the pattern is
+//! flexible about the supporting pieces.
+
+use crate::evaluation::castle_geometry::{
+    CastlePieceClass, CastlePieceDescriptor, CastlePieceRole, RelativeOffset,
+};
+use crate::evaluation::castles::{
+    mirror_descriptors, CastlePattern, CastleVariant, GOLD_FAMILY, PAWN_WALL_FAMILY, SILVER_FAMILY,
+};
+use crate::types::evaluation::TaperedScore;
+
+/// King on 7八: silver 6八, golds 6九 and 5八, pawns 8七/7七/6七.
+fn base_shell() -> Vec<CastlePieceDescriptor> {
+    vec![
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(SILVER_FAMILY),
+            RelativeOffset::new(0, 1),
+            true,
+            10,
+            CastlePieceRole::PrimaryDefender,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(GOLD_FAMILY),
+            RelativeOffset::new(1, 1),
+            true,
+            9,
+            CastlePieceRole::PrimaryDefender,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(GOLD_FAMILY),
+            RelativeOffset::new(0, 2),
+            false,
+            7,
+            CastlePieceRole::SecondaryDefender,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(PAWN_WALL_FAMILY),
+            RelativeOffset::new(-1, 1),
+            false,
+            6,
+            CastlePieceRole::PawnShield,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(PAWN_WALL_FAMILY),
+            RelativeOffset::new(-1, 0),
+            false,
+            5,
+            CastlePieceRole::PawnShield,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(PAWN_WALL_FAMILY),
+            RelativeOffset::new(-1, -1),
+            false,
+            5,
+            CastlePieceRole::PawnShield,
+        ),
+    ]
+}
+
+pub fn get_boat_castle() -> CastlePattern {
+    let base = base_shell();
+
+    let mut variants = Vec::new();
+    variants.push(CastleVariant::from_descriptors("left-base", &base));
+    variants.push(CastleVariant::from_descriptors("right-base", &mirror_descriptors(&base)));
+
+    CastlePattern {
+        name: "Boat",
+        variants,
+        score: TaperedScore::new_tapered(110, 40),
+        flexibility: 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_boat_castle_variants() {
+        let pattern = get_boat_castle();
+        assert_eq!(pattern.name, "Boat");
+        assert_eq!(pattern.variants.len(), 2);
+
+        for variant in &pattern.variants {
+            let required = variant.pieces.iter().filter(|piece| piece.required).count();
+            assert!(required >= 2);
+        }
+    }
+}
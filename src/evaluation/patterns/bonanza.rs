@@ -0,0 +1,92 @@
+//! Bonanza castle
+//!
+//! Named after the engine that played it: the king stays on 6八 between golds
+//! on 5八 and 7八, with a silver on 7七 covering the head of the king.
+
+use crate::evaluation::castle_geometry::{
+    CastlePieceClass, CastlePieceDescriptor, CastlePieceRole, RelativeOffset,
+};
+use crate::evaluation::castles::{
+    mirror_descriptors, CastlePattern, CastleVariant, GOLD_FAMILY, PAWN_WALL_FAMILY, SILVER_FAMILY,
+};
+use crate::types::evaluation::TaperedScore;
+
+/// King on 6八: golds 7八 and 5八, silver 7七, pawns 7六/6七/5七.
+fn base_shell() -> Vec<CastlePieceDescriptor> {
+    vec![
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(GOLD_FAMILY),
+            RelativeOffset::new(0, -1),
+            true,
+            10,
+            CastlePieceRole::PrimaryDefender,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(GOLD_FAMILY),
+            RelativeOffset::new(0, 1),
+            true,
+            10,
+            CastlePieceRole::PrimaryDefender,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(SILVER_FAMILY),
+            RelativeOffset::new(-1, -1),
+            true,
+            8,
+            CastlePieceRole::PrimaryDefender,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(PAWN_WALL_FAMILY),
+            RelativeOffset::new(-1, 0),
+            false,
+            6,
+            CastlePieceRole::PawnShield,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(PAWN_WALL_FAMILY),
+            RelativeOffset::new(-1, 1),
+            false,
+            5,
+            CastlePieceRole::PawnShield,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(PAWN_WALL_FAMILY),
+            RelativeOffset::new(-2, -1),
+            false,
+            5,
+            CastlePieceRole::PawnShield,
+        ),
+    ]
+}
+
+pub fn get_bonanza_castle() -> CastlePattern {
+    let base = base_shell();
+
+    let mut variants = Vec::new();
+    variants.push(CastleVariant::from_descriptors("left-base", &base));
+    variants.push(CastleVariant::from_descriptors("right-base", &mirror_descriptors(&base)));
+
+    CastlePattern {
+        name: "Bonanza",
+        variants,
+        score: TaperedScore::new_tapered(140, 60),
+        flexibility: 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bonanza_castle_variants() {
+        let pattern = get_bonanza_castle();
+        assert_eq!(pattern.name, "Bonanza");
+        assert_eq!(pattern.variants.len(), 2);
+
+        for variant in &pattern.variants {
+            let required = variant.pieces.iter().filter(|piece| piece.required).count();
+            assert!(required >= 3);
+        }
+    }
+}
@@ -0,0 +1,151 @@
+//! Diamond Mino and High Mino (Takamino)
+//!
+//! Both grow out of the plain Mino: the gold on 5八 climbs to 4七 to form High
+//! Mino, and a second silver on 4七 turns the four generals around 4八 into the
+//! diamond shape.
+
+use crate::evaluation::castle_geometry::{
+    CastlePieceClass, CastlePieceDescriptor, CastlePieceRole, RelativeOffset,
+};
+use crate::evaluation::castles::{
+    mirror_descriptors, CastlePattern, CastleVariant, GOLD_FAMILY, PAWN_WALL_FAMILY, SILVER_FAMILY,
+};
+use crate::types::evaluation::TaperedScore;
+
+/// King on 2八: silvers 3八 and 4七, golds 4九 and 5八, pawns 2七/3六/4六.
+fn diamond_shell() -> Vec<CastlePieceDescriptor> {
+    vec![
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(SILVER_FAMILY),
+            RelativeOffset::new(0, -1),
+            true,
+            10,
+            CastlePieceRole::PrimaryDefender,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(SILVER_FAMILY),
+            RelativeOffset::new(-1, -2),
+            true,
+            9,
+            CastlePieceRole::PrimaryDefender,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(GOLD_FAMILY),
+            RelativeOffset::new(1, -2),
+            true,
+            9,
+            CastlePieceRole::PrimaryDefender,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(GOLD_FAMILY),
+            RelativeOffset::new(0, -3),
+            true,
+            8,
+            CastlePieceRole::PrimaryDefender,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(PAWN_WALL_FAMILY),
+            RelativeOffset::new(-1, 0),
+            false,
+            6,
+            CastlePieceRole::PawnShield,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(PAWN_WALL_FAMILY),
+            RelativeOffset::new(-2, -1),
+            false,
+            6,
+            CastlePieceRole::PawnShield,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(PAWN_WALL_FAMILY),
+            RelativeOffset::new(-2, -2),
+            false,
+            6,
+            CastlePieceRole::PawnShield,
+        ),
+    ]
+}
+
+/// King on 2八: silver 3八, golds 4七 and 4九, pawns 2七/3六/4六.
+fn high_shell() -> Vec<CastlePieceDescriptor> {
+    vec![
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(SILVER_FAMILY),
+            RelativeOffset::new(0, -1),
+            true,
+            10,
+            CastlePieceRole::PrimaryDefender,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(GOLD_FAMILY),
+            RelativeOffset::new(-1, -2),
+            true,
+            9,
+            CastlePieceRole::PrimaryDefender,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(GOLD_FAMILY),
+            RelativeOffset::new(1, -2),
+            true,
+            9,
+            CastlePieceRole::PrimaryDefender,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(PAWN_WALL_FAMILY),
+            RelativeOffset::new(-1, 0),
+            false,
+            6,
+            CastlePieceRole::PawnShield,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(PAWN_WALL_FAMILY),
+            RelativeOffset::new(-2, -1),
+            false,
+            6,
+            CastlePieceRole::PawnShield,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(PAWN_WALL_FAMILY),
+            RelativeOffset::new(-2, -2),
+            false,
+            6,
+            CastlePieceRole::PawnShield,
+        ),
+    ]
+}
+
+pub fn get_diamond_mino_castle() -> CastlePattern {
+    let diamond = diamond_shell();
+    let high = high_shell();
+
+    let mut variants = Vec::new();
+    variants.push(CastleVariant::from_descriptors("right-diamond", &diamond));
+    variants.push(CastleVariant::from_descriptors("left-diamond", &mirror_descriptors(&diamond)));
+    variants.push(CastleVariant::from_descriptors("right-high", &high));
+    variants.push(CastleVariant::from_descriptors("left-high", &mirror_descriptors(&high)));
+
+    CastlePattern {
+        name: "Diamond Mino",
+        variants,
+        score: TaperedScore::new_tapered(210, 70),
+        flexibility: 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diamond_mino_castle_variants() {
+        let pattern = get_diamond_mino_castle();
+        assert_eq!(pattern.name, "Diamond Mino");
+        assert_eq!(pattern.variants.len(), 4);
+
+        for variant in &pattern.variants {
+            let required = variant.pieces.iter().filter(|piece| piece.required).count();
+            assert!(required >= 3);
+        }
+    }
+}
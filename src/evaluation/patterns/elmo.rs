@@ -0,0 +1,101 @@
+//! Elmo castle
+//!
+//! A static-rook castle popularised by engine play: the king drops to 7九
+//! behind a vertical gold on 7八 and silver on 7七, with the bishop still on 8八.
+
+use crate::evaluation::castle_geometry::{
+    exact, CastlePieceClass, CastlePieceDescriptor, CastlePieceRole, RelativeOffset,
+};
+use crate::evaluation::castles::{
+    mirror_descriptors, CastlePattern, CastleVariant, GOLD_FAMILY, KNIGHT_FAMILY, PAWN_WALL_FAMILY,
+    SILVER_FAMILY,
+};
+use crate::types::core::PieceType;
+use crate::types::evaluation::TaperedScore;
+
+/// King on 7九: gold 7八, silver 7七, bishop 8八, gold 5八, pawns 8七/6七.
+fn base_shell() -> Vec<CastlePieceDescriptor> {
+    vec![
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(GOLD_FAMILY),
+            RelativeOffset::new(-1, 0),
+            true,
+            10,
+            CastlePieceRole::PrimaryDefender,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(SILVER_FAMILY),
+            RelativeOffset::new(-2, 0),
+            true,
+            9,
+            CastlePieceRole::PrimaryDefender,
+        ),
+        CastlePieceDescriptor::new(
+            exact(PieceType::Bishop),
+            RelativeOffset::new(-1, -1),
+            false,
+            5,
+            CastlePieceRole::SecondaryDefender,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(GOLD_FAMILY),
+            RelativeOffset::new(-1, 2),
+            false,
+            7,
+            CastlePieceRole::SecondaryDefender,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(PAWN_WALL_FAMILY),
+            RelativeOffset::new(-2, -1),
+            false,
+            6,
+            CastlePieceRole::PawnShield,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(PAWN_WALL_FAMILY),
+            RelativeOffset::new(-2, 1),
+            false,
+            6,
+            CastlePieceRole::PawnShield,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(KNIGHT_FAMILY),
+            RelativeOffset::new(0, -1),
+            false,
+            4,
+            CastlePieceRole::Buffer,
+        ),
+    ]
+}
+
+pub fn get_elmo_castle() -> CastlePattern {
+    let base = base_shell();
+
+    let mut variants = Vec::new();
+    variants.push(CastleVariant::from_descriptors("left-base", &base));
+    variants.push(CastleVariant::from_descriptors("right-base", &mirror_descriptors(&base)));
+
+    CastlePattern {
+        name: "Elmo",
+        variants,
+        score: TaperedScore::new_tapered(190, 60),
+        flexibility: 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_elmo_castle_variants() {
+        let pattern = get_elmo_castle();
+        assert_eq!(pattern.name, "Elmo");
+        assert_eq!(pattern.variants.len(), 2);
+
+        for variant in &pattern.variants {
+            let required = variant.pieces.iter().filter(|piece| piece.required).count();
+            assert!(required >= 2);
+        }
+    }
+}
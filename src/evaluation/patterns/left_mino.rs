@@ -0,0 +1,145 @@
+//! Left Mino
+//!
+//! The static-rook answer to a ranging rook: the Mino shell built on the left
+//! wing with the king on 8八. The right-wing variants are the same shell
+//! mirrored, as built by a left-handed ranging rook.
+
+use crate::evaluation::castle_geometry::{
+    CastlePieceClass, CastlePieceDescriptor, CastlePieceRole, RelativeOffset,
+};
+use crate::evaluation::castles::{
+    mirror_descriptors, CastlePattern, CastleVariant, GOLD_FAMILY, PAWN_WALL_FAMILY, SILVER_FAMILY,
+};
+use crate::types::evaluation::TaperedScore;
+
+/// King on 8八: silver 7八, golds 6九 and 5八, pawns 9七/8七/7六.
+fn base_shell() -> Vec<CastlePieceDescriptor> {
+    vec![
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(SILVER_FAMILY),
+            RelativeOffset::new(0, 1),
+            true,
+            10,
+            CastlePieceRole::PrimaryDefender,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(GOLD_FAMILY),
+            RelativeOffset::new(1, 2),
+            true,
+            10,
+            CastlePieceRole::PrimaryDefender,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(GOLD_FAMILY),
+            RelativeOffset::new(0, 3),
+            false,
+            7,
+            CastlePieceRole::SecondaryDefender,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(PAWN_WALL_FAMILY),
+            RelativeOffset::new(-1, 0),
+            false,
+            6,
+            CastlePieceRole::PawnShield,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(PAWN_WALL_FAMILY),
+            RelativeOffset::new(-1, -1),
+            false,
+            5,
+            CastlePieceRole::PawnShield,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(PAWN_WALL_FAMILY),
+            RelativeOffset::new(-2, 1),
+            false,
+            6,
+            CastlePieceRole::PawnShield,
+        ),
+    ]
+}
+
+/// King on 8八 with the silver raised: silver 7七, golds 7八 and 6九.
+fn advanced_shell() -> Vec<CastlePieceDescriptor> {
+    vec![
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(SILVER_FAMILY),
+            RelativeOffset::new(-1, 1),
+            true,
+            10,
+            CastlePieceRole::PrimaryDefender,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(GOLD_FAMILY),
+            RelativeOffset::new(0, 1),
+            true,
+            10,
+            CastlePieceRole::PrimaryDefender,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(GOLD_FAMILY),
+            RelativeOffset::new(1, 2),
+            false,
+            7,
+            CastlePieceRole::SecondaryDefender,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(PAWN_WALL_FAMILY),
+            RelativeOffset::new(-1, 0),
+            false,
+            6,
+            CastlePieceRole::PawnShield,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(PAWN_WALL_FAMILY),
+            RelativeOffset::new(-1, -1),
+            false,
+            5,
+            CastlePieceRole::PawnShield,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(PAWN_WALL_FAMILY),
+            RelativeOffset::new(-2, 1),
+            false,
+            6,
+            CastlePieceRole::PawnShield,
+        ),
+    ]
+}
+
+pub fn get_left_mino_castle() -> CastlePattern {
+    let base = base_shell();
+    let advanced = advanced_shell();
+
+    let mut variants = Vec::new();
+    variants.push(CastleVariant::from_descriptors("left-base", &base));
+    variants.push(CastleVariant::from_descriptors("right-base", &mirror_descriptors(&base)));
+    variants.push(CastleVariant::from_descriptors("left-advanced", &advanced));
+    variants
+        .push(CastleVariant::from_descriptors("right-advanced", &mirror_descriptors(&advanced)));
+
+    CastlePattern {
+        name: "Left Mino",
+        variants,
+        score: TaperedScore::new_tapered(170, 60),
+        flexibility: 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_left_mino_castle_variants() {
+        let pattern = get_left_mino_castle();
+        assert_eq!(pattern.name, "Left Mino");
+        assert_eq!(pattern.variants.len(), 4);
+
+        for variant in &pattern.variants {
+            let required = variant.pieces.iter().filter(|piece| piece.required).count();
+            assert!(required >= 2);
+        }
+    }
+}
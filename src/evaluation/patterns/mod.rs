@@ -1,15 +1,31 @@
 //! Castle pattern definitions and recognition logic
 //!
 //! This module contains the specific castle patterns used in Shogi,
-//! including Mino, Anaguma, and Yagura formations.
+//! including Mino, Anaguma, and Yagura formations along with the
+//! Mino family (Silver Crown, Diamond/High Mino, Left Mino) and the
+//! static-rook Boat, Elmo, Nakahara, and Bonanza castles.
 
 pub mod anaguma;
+pub mod bonanza;
+pub mod boat;
 pub mod common;
+pub mod diamond_mino;
+pub mod elmo;
+pub mod left_mino;
 pub mod mino;
+pub mod nakahara;
+pub mod silver_crown;
 pub mod yagura;
 
 // Re-export the main pattern types
 pub use anaguma::*;
+pub use bonanza::*;
+pub use boat::*;
 pub use common::*;
+pub use diamond_mino::*;
+pub use elmo::*;
+pub use left_mino::*;
 pub use mino::*;
+pub use nakahara::*;
+pub use silver_crown::*;
 pub use yagura::*;
@@ -0,0 +1,100 @@
+//! Nakahara castle
+//!
+//! The Side Pawn Capture (Yokofudori) castle: king on 6九, gold on 7八 and the
+//! silver left at home on 7九, with the other gold on 5八 guarding the centre.
+
+use crate::evaluation::castle_geometry::{
+    CastlePieceClass, CastlePieceDescriptor, CastlePieceRole, RelativeOffset,
+};
+use crate::evaluation::castles::{
+    mirror_descriptors, CastlePattern, CastleVariant, GOLD_FAMILY, KNIGHT_FAMILY, PAWN_WALL_FAMILY,
+    SILVER_FAMILY,
+};
+use crate::types::evaluation::TaperedScore;
+
+/// King on 6九: gold 7八, silver 7九, gold 5八, pawns 7七/6七/5七.
+fn base_shell() -> Vec<CastlePieceDescriptor> {
+    vec![
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(GOLD_FAMILY),
+            RelativeOffset::new(-1, -1),
+            true,
+            10,
+            CastlePieceRole::PrimaryDefender,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(SILVER_FAMILY),
+            RelativeOffset::new(0, -1),
+            true,
+            9,
+            CastlePieceRole::PrimaryDefender,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(GOLD_FAMILY),
+            RelativeOffset::new(-1, 1),
+            false,
+            8,
+            CastlePieceRole::SecondaryDefender,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(PAWN_WALL_FAMILY),
+            RelativeOffset::new(-2, -1),
+            false,
+            6,
+            CastlePieceRole::PawnShield,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(PAWN_WALL_FAMILY),
+            RelativeOffset::new(-2, 0),
+            false,
+            6,
+            CastlePieceRole::PawnShield,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(PAWN_WALL_FAMILY),
+            RelativeOffset::new(-2, 1),
+            false,
+            5,
+            CastlePieceRole::PawnShield,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(KNIGHT_FAMILY),
+            RelativeOffset::new(0, -2),
+            false,
+            4,
+            CastlePieceRole::Buffer,
+        ),
+    ]
+}
+
+pub fn get_nakahara_castle() -> CastlePattern {
+    let base = base_shell();
+
+    let mut variants = Vec::new();
+    variants.push(CastleVariant::from_descriptors("left-base", &base));
+    variants.push(CastleVariant::from_descriptors("right-base", &mirror_descriptors(&base)));
+
+    CastlePattern {
+        name: "Nakahara",
+        variants,
+        score: TaperedScore::new_tapered(130, 50),
+        flexibility: 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nakahara_castle_variants() {
+        let pattern = get_nakahara_castle();
+        assert_eq!(pattern.name, "Nakahara");
+        assert_eq!(pattern.variants.len(), 2);
+
+        for variant in &pattern.variants {
+            let required = variant.pieces.iter().filter(|piece| piece.required).count();
+            assert!(required >= 2);
+        }
+    }
+}
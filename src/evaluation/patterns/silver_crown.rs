@@ -0,0 +1,101 @@
+//! Silver Crown (Ginkanmuri)
+//!
+//! The king sits on 2八 capped by a silver on 2七 with a gold tucked in on 3八.
+//! The second gold usually stands on 4七 and the edge pawns are pushed to the
+//! sixth rank, which keeps the castle thick in the endgame.
+
+use crate::evaluation::castle_geometry::{
+    CastlePieceClass, CastlePieceDescriptor, CastlePieceRole, RelativeOffset,
+};
+use crate::evaluation::castles::{
+    mirror_descriptors, CastlePattern, CastleVariant, GOLD_FAMILY, KNIGHT_FAMILY, PAWN_WALL_FAMILY,
+    SILVER_FAMILY,
+};
+use crate::types::evaluation::TaperedScore;
+
+/// King on 2八: silver 2七, golds 3八 and 4七, pawns 1六/2六/3六.
+fn base_shell() -> Vec<CastlePieceDescriptor> {
+    vec![
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(SILVER_FAMILY),
+            RelativeOffset::new(-1, 0),
+            true,
+            10,
+            CastlePieceRole::PrimaryDefender,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(GOLD_FAMILY),
+            RelativeOffset::new(0, -1),
+            true,
+            10,
+            CastlePieceRole::PrimaryDefender,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(GOLD_FAMILY),
+            RelativeOffset::new(-1, -2),
+            false,
+            7,
+            CastlePieceRole::SecondaryDefender,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(PAWN_WALL_FAMILY),
+            RelativeOffset::new(-2, 0),
+            false,
+            6,
+            CastlePieceRole::PawnShield,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(PAWN_WALL_FAMILY),
+            RelativeOffset::new(-2, -1),
+            false,
+            6,
+            CastlePieceRole::PawnShield,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(PAWN_WALL_FAMILY),
+            RelativeOffset::new(-2, 1),
+            false,
+            5,
+            CastlePieceRole::PawnShield,
+        ),
+        CastlePieceDescriptor::new(
+            CastlePieceClass::AnyOf(KNIGHT_FAMILY),
+            RelativeOffset::new(1, 0),
+            false,
+            4,
+            CastlePieceRole::SecondaryDefender,
+        ),
+    ]
+}
+
+pub fn get_silver_crown_castle() -> CastlePattern {
+    let base = base_shell();
+
+    let mut variants = Vec::new();
+    variants.push(CastleVariant::from_descriptors("right-base", &base));
+    variants.push(CastleVariant::from_descriptors("left-base", &mirror_descriptors(&base)));
+
+    CastlePattern {
+        name: "Silver Crown",
+        variants,
+        score: TaperedScore::new_tapered(200, 90),
+        flexibility: 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_silver_crown_castle_variants() {
+        let pattern = get_silver_crown_castle();
+        assert_eq!(pattern.name, "Silver Crown");
+        assert_eq!(pattern.variants.len(), 2);
+
+        for variant in &pattern.variants {
+            let required = variant.pieces.iter().filter(|piece| piece.required).count();
+            assert!(required >= 2);
+        }
+    }
+}
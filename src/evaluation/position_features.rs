@@ -27,6 +27,7 @@
 //! ```
 
 use crate::bitboards::BitboardBoard;
+use crate::evaluation::king_safety::KingDangerConfig;
 use crate::evaluation::storm_tracking::StormState;
 use crate::moves::MoveGenerator;
 use crate::types::board::CapturedPieces;
@@ -356,15 +357,23 @@ impl PositionFeatureEvaluator {
         mg_score += castle_bonus.mg;
         eg_score += castle_bonus.eg;
 
-        let enemy_hand_pressure =
-            self.evaluate_enemy_hand_pressure(board, king_pos, player, captured_pieces);
-        mg_score -= enemy_hand_pressure.mg;
-        eg_score -= enemy_hand_pressure.eg;
+        if self.config.king_danger.enabled {
+            // 5. Enemy attack on the king, including checks dropped from hand
+            let danger =
+                self.config.king_danger.evaluate(board, king_pos, player, captured_pieces);
+            mg_score -= danger.mg;
+            eg_score -= danger.eg;
+        } else {
+            let enemy_hand_pressure =
+                self.evaluate_enemy_hand_pressure(board, king_pos, player, captured_pieces);
+            mg_score -= enemy_hand_pressure.mg;
+            eg_score -= enemy_hand_pressure.eg;
 
-        // 5. Enemy attackers near king
-        let attacker_penalty = self.evaluate_enemy_attackers(board, king_pos, player);
-        mg_score -= attacker_penalty.mg;
-        eg_score -= attacker_penalty.eg;
+            // 5. Enemy attackers near king
+            let attacker_penalty = self.evaluate_enemy_attackers(board, king_pos, player);
+            mg_score -= attacker_penalty.mg;
+            eg_score -= attacker_penalty.eg;
+        }
         
         // 5b. Open file attacks near king (rook on open file next to king)
         let open_file_penalty = self.evaluate_open_file_attacks(board, king_pos, player);
@@ -2544,6 +2553,9 @@ pub struct PositionFeatureConfig {
     pub enable_center_control: bool,
     /// Enable development evaluation
     pub enable_development: bool,
+    /// King-danger model used by king safety
+    #[serde(default)]
+    pub king_danger: KingDangerConfig,
 }
 
 impl Default for PositionFeatureConfig {
@@ -2554,6 +2566,7 @@ impl Default for PositionFeatureConfig {
             enable_mobility: true,
             enable_center_control: true,
             enable_development: true,
+            king_danger: KingDangerConfig::default(),
         }
     }
 }
@@ -2747,6 +2760,7 @@ mod tests {
             enable_mobility: true,
             enable_center_control: false,
             enable_development: true,
+            king_danger: KingDangerConfig::default(),
         };
 
         let evaluator = PositionFeatureEvaluator::with_config(config);
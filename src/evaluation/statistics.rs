@@ -835,23 +835,27 @@ impl std::fmt::Display for StatisticsReport {
             writeln!(f, "  Evaluations: {}", stats.evaluations)?;
             writeln!(
                 f,
-                "  Checks (fork/pin/skewer/discovered/knight/back-rank): {}/{}/{}/{}/{}/{}",
+                "  Checks (fork/pin/skewer/discovered/knight/back-rank/pawn): \
+                 {}/{}/{}/{}/{}/{}/{}",
                 stats.fork_checks,
                 stats.pin_checks,
                 stats.skewer_checks,
                 stats.discovered_checks,
                 stats.knight_fork_checks,
-                stats.back_rank_checks
+                stats.back_rank_checks,
+                stats.pawn_tactic_checks
             )?;
             writeln!(
                 f,
-                "  Findings (fork/pin/skewer/discovered/knight/back-rank): {}/{}/{}/{}/{}/{}",
+                "  Findings (fork/pin/skewer/discovered/knight/back-rank/pawn): \
+                 {}/{}/{}/{}/{}/{}/{}",
                 stats.forks_found,
                 stats.pins_found,
                 stats.skewers_found,
                 stats.discovered_attacks_found,
                 stats.knight_forks_found,
-                stats.back_rank_threats_found
+                stats.back_rank_threats_found,
+                stats.pawn_tactics_found
             )?;
         }
         if let Some(telemetry) = &self.telemetry {
@@ -7,6 +7,8 @@
 //! - Discovered attacks
 //! - Knight forks (special case with unique movement)
 //! - Back rank threats
+//! - Pawn tactics: hanging pawns (垂れ歩), joining pawns (継ぎ歩), tapping
+//!   pawns (叩きの歩) and pawn sacrifices that open a file
 //!
 //! # Example
 //!
@@ -36,7 +38,7 @@ pub struct TacticalPatternRecognizer {
 
 #[derive(Clone, Copy)]
 struct LineStep {
-    _position: Position,
+    position: Position,
     occupant: Option<Piece>,
 }
 
@@ -116,7 +118,7 @@ impl<'a> TacticalDetectionContext<'a> {
         while row >= 0 && row < 9 && col >= 0 && col < 9 {
             let position = Position::new(row as u8, col as u8);
             let occupant = self.board.get_piece(position);
-            steps.push(LineStep { _position: position, occupant });
+            steps.push(LineStep { position, occupant });
 
             if occupant.is_some() {
                 break;
@@ -133,10 +135,7 @@ impl<'a> TacticalDetectionContext<'a> {
         let mut steps = Vec::new();
         for &(dr, dc) in offsets {
             if let Some(position) = Self::offset_position(origin, dr, dc) {
-                steps.push(LineStep {
-                    _position: position,
-                    occupant: self.board.get_piece(position),
-                });
+                steps.push(LineStep { position, occupant: self.board.get_piece(position) });
             }
         }
         steps
@@ -235,6 +234,12 @@ impl<'a> TacticalDetectionContext<'a> {
         None
     }
 
+    /// Square a pawn of `owner` on `pos` moves to
+    fn pawn_step(pos: Position, owner: Player) -> Option<Position> {
+        let dir = if owner == Player::Black { -1 } else { 1 };
+        Self::offset_position(pos, dir, 0)
+    }
+
     fn offset_position(origin: Position, dr: i8, dc: i8) -> Option<Position> {
         let new_row = origin.row as i8 + dr;
         let new_col = origin.col as i8 + dc;
@@ -281,6 +286,7 @@ pub struct TacticalPhaseWeights {
     pub skewers: MotifPhaseWeight,
     pub discovered: MotifPhaseWeight,
     pub back_rank: MotifPhaseWeight,
+    pub pawn_tactics: MotifPhaseWeight,
 }
 
 impl Default for TacticalPhaseWeights {
@@ -292,6 +298,7 @@ impl Default for TacticalPhaseWeights {
             skewers: MotifPhaseWeight { mg: 1.0, eg: 0.8 },
             discovered: MotifPhaseWeight { mg: 0.9, eg: 0.6 },
             back_rank: MotifPhaseWeight { mg: 1.2, eg: 0.9 },
+            pawn_tactics: MotifPhaseWeight { mg: 1.0, eg: 0.8 },
         }
     }
 }
@@ -372,6 +379,13 @@ impl TacticalPatternRecognizer {
             eg_score += back_rank.eg;
         }
 
+        // Detect pawn tactics (drops from hand and file-opening sacrifices)
+        if self.config.enable_pawn_tactics {
+            let pawn_tactics = self.detect_pawn_tactics(&context);
+            mg_score += pawn_tactics.mg;
+            eg_score += pawn_tactics.eg;
+        }
+
         TaperedScore::new_tapered(mg_score, eg_score)
     }
 
@@ -1334,6 +1348,205 @@ impl TacticalPatternRecognizer {
         threats
     }
 
+    // ===================================================================
+    // PAWN TACTICS
+    // ===================================================================
+
+    /// Detect pawn tactics: hanging pawns (垂れ歩), joining pawns (継ぎ歩) and
+    /// tapping pawns (叩きの歩) dropped from hand, and pawn sacrifices that
+    /// open a file for a rook or lance
+    ///
+    /// Only one pawn can be dropped per move, so each drop motif scores its
+    /// best square rather than every legal one. Drops respect nifu and the
+    /// last-rank restriction through `is_drop_legal`.
+    fn detect_pawn_tactics(&mut self, ctx: &TacticalDetectionContext) -> TaperedScore {
+        self.stats.pawn_tactic_checks += 1;
+
+        let pawns_in_hand = ctx.player_hand_count(PieceType::Pawn);
+        let opponent_king = self.find_king_position(ctx.board, ctx.opponent);
+        let mut hanging = 0;
+        let mut joining = 0;
+        let mut tapping = 0;
+
+        if pawns_in_hand > 0 {
+            for pos in ctx.empty_squares() {
+                if !self.is_drop_legal(ctx, PieceType::Pawn, pos) {
+                    continue;
+                }
+                let Some(ahead) = TacticalDetectionContext::pawn_step(pos, ctx.player) else {
+                    continue;
+                };
+
+                match ctx.board.get_piece(ahead) {
+                    None => {
+                        hanging =
+                            hanging.max(self.hanging_pawn_value(ctx, pos, ahead, opponent_king))
+                    }
+                    Some(target) if target.player == ctx.opponent => {
+                        if target.piece_type == PieceType::Pawn {
+                            joining =
+                                joining.max(self.joining_pawn_value(ctx, ahead, pawns_in_hand));
+                        } else {
+                            let value =
+                                self.tapping_pawn_value(ctx, pos, ahead, target, opponent_king);
+                            tapping = tapping.max(value);
+                        }
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+
+        let sacrifice = self.file_opening_sacrifice_value(ctx, opponent_king);
+        let total = hanging + joining + tapping + sacrifice;
+        if total != 0 {
+            self.stats.pawn_tactics_found.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        }
+
+        self.apply_phase_weights(total, &self.config.phase_weights.pawn_tactics)
+    }
+
+    /// Hanging pawn (垂れ歩): a pawn dropped short of the promotion zone that
+    /// threatens to promote with its next step
+    fn hanging_pawn_value(
+        &self,
+        ctx: &TacticalDetectionContext,
+        pos: Position,
+        ahead: Position,
+        opponent_king: Option<Position>,
+    ) -> i32 {
+        let promotes = if ctx.player == Player::Black { ahead.row <= 2 } else { ahead.row >= 6 };
+        if !promotes {
+            return 0;
+        }
+        // A pawn the opponent simply takes for free is no threat
+        if ctx.board.is_square_attacked_by(pos, ctx.opponent)
+            && !ctx.board.is_square_attacked_by(pos, ctx.player)
+        {
+            return 0;
+        }
+
+        let near_king = opponent_king.is_some_and(|king| chebyshev_distance(king, ahead) <= 2);
+        self.config.hanging_pawn_bonus_cp
+            + if near_king { self.config.hanging_pawn_king_bonus_cp } else { 0 }
+    }
+
+    /// Joining pawn (継ぎ歩): after a pawn exchange, a pawn dropped in front of
+    /// the enemy pawn that took, dragging it further forward. Each further
+    /// capture costs us a pawn from hand, so the bonus grows with the pawns
+    /// we hold beyond the one dropped (up to two).
+    fn joining_pawn_value(
+        &self,
+        ctx: &TacticalDetectionContext,
+        target_pos: Position,
+        pawns_in_hand: usize,
+    ) -> i32 {
+        // The enemy pawn must already have left its starting rank
+        let lured =
+            if ctx.opponent == Player::White { target_pos.row > 2 } else { target_pos.row < 6 };
+        let follow_ups = pawns_in_hand.min(3).saturating_sub(1) as i32;
+        if !lured || follow_ups == 0 {
+            return 0;
+        }
+
+        let value = self.config.joining_pawn_bonus_cp * follow_ups;
+        // An undefended pawn is simply lost
+        if ctx.board.is_square_attacked_by(target_pos, ctx.opponent) {
+            value
+        } else {
+            value * 2
+        }
+    }
+
+    /// Tapping pawn (叩きの歩): a pawn dropped in front of an enemy piece. A
+    /// piece that cannot take it must give way; one that can is lured
+    /// forward, which matters most for the defenders around the king.
+    fn tapping_pawn_value(
+        &self,
+        ctx: &TacticalDetectionContext,
+        pos: Position,
+        target_pos: Position,
+        target: Piece,
+        opponent_king: Option<Position>,
+    ) -> i32 {
+        // Tapping the king is a check, which the other motifs already cover
+        if target.piece_type == PieceType::King {
+            return 0;
+        }
+
+        let can_capture = ctx
+            .gather_attacks(target_pos, target.piece_type, ctx.opponent)
+            .iter()
+            .any(|step| step.position == pos);
+        if !can_capture {
+            if ctx.board.is_square_attacked_by(pos, ctx.opponent) {
+                return 0;
+            }
+            return (target.piece_type.base_value() as f32 * self.config.tapping_pawn_ratio).round()
+                as i32;
+        }
+
+        let defends_king =
+            opponent_king.is_some_and(|king| chebyshev_distance(king, target_pos) <= 2);
+        if defends_king {
+            self.config.tapping_pawn_lure_bonus_cp
+        } else {
+            0
+        }
+    }
+
+    /// Pawn sacrifice that opens a file: one of our pawns in contact with, or
+    /// one step short of, an enemy pawn, with our rook or lance behind it on
+    /// the same file
+    fn file_opening_sacrifice_value(
+        &self,
+        ctx: &TacticalDetectionContext,
+        opponent_king: Option<Position>,
+    ) -> i32 {
+        let backward = if ctx.player == Player::Black { (1, 0) } else { (-1, 0) };
+        let mut best = 0;
+
+        for &(pos, piece) in &ctx.player_pieces {
+            if piece.piece_type != PieceType::Pawn {
+                continue;
+            }
+            let Some(ahead) = TacticalDetectionContext::pawn_step(pos, ctx.player) else {
+                continue;
+            };
+            let enemy_pawn_at = |square: Position| {
+                ctx.board
+                    .get_piece(square)
+                    .is_some_and(|p| p.player == ctx.opponent && p.piece_type == PieceType::Pawn)
+            };
+            let lever = enemy_pawn_at(ahead)
+                || (ctx.board.get_piece(ahead).is_none()
+                    && TacticalDetectionContext::pawn_step(ahead, ctx.player)
+                        .is_some_and(enemy_pawn_at));
+            if !lever {
+                continue;
+            }
+
+            let behind = ctx.trace_line(pos, backward).last().and_then(|step| step.occupant);
+            let backed = behind.is_some_and(|p| {
+                p.player == ctx.player
+                    && matches!(
+                        p.piece_type,
+                        PieceType::Rook | PieceType::PromotedRook | PieceType::Lance
+                    )
+            });
+            if !backed {
+                continue;
+            }
+
+            let king_file = opponent_king.is_some_and(|king| king.col.abs_diff(pos.col) <= 1);
+            let value = self.config.file_opening_sacrifice_bonus_cp
+                + if king_file { self.config.file_opening_king_bonus_cp } else { 0 };
+            best = best.max(value);
+        }
+
+        best
+    }
+
     // ===================================================================
     // HELPER METHODS
     // ===================================================================
@@ -1364,6 +1577,11 @@ impl TacticalPatternRecognizer {
     }
 }
 
+/// Larger of the row and column distances between two squares
+fn chebyshev_distance(a: Position, b: Position) -> u8 {
+    a.row.abs_diff(b.row).max(a.col.abs_diff(b.col))
+}
+
 impl Default for TacticalPatternRecognizer {
     fn default() -> Self {
         Self::new()
@@ -1379,6 +1597,7 @@ pub struct TacticalConfig {
     pub enable_discovered_attacks: bool,
     pub enable_knight_forks: bool,
     pub enable_back_rank_threats: bool,
+    pub enable_pawn_tactics: bool,
 
     // Centipawn-scaled scoring parameters
     pub fork_threat_ratio: f32,
@@ -1389,6 +1608,22 @@ pub struct TacticalConfig {
     pub discovered_attack_bonus_cp: i32,
     pub back_rank_penalty_cp: i32,
 
+    // Pawn tactics
+    /// Hanging pawn (垂れ歩) that threatens to promote next move
+    pub hanging_pawn_bonus_cp: i32,
+    /// Extra when the hanging pawn promotes next to the enemy king
+    pub hanging_pawn_king_bonus_cp: i32,
+    /// Joining pawn (継ぎ歩), per pawn in hand beyond the one dropped (max two)
+    pub joining_pawn_bonus_cp: i32,
+    /// Share of a tapped (叩きの歩) piece's value when it cannot take the pawn
+    pub tapping_pawn_ratio: f32,
+    /// Tapping a king defender that can take the pawn, luring it forward
+    pub tapping_pawn_lure_bonus_cp: i32,
+    /// Pawn sacrifice opening a file for a rook or lance behind it
+    pub file_opening_sacrifice_bonus_cp: i32,
+    /// Extra when the opened file is on or next to the enemy king's file
+    pub file_opening_king_bonus_cp: i32,
+
     pub phase_weights: TacticalPhaseWeights,
 
     /// Enable SIMD-optimized pattern matching
@@ -1416,6 +1651,7 @@ impl Default for TacticalConfig {
             enable_discovered_attacks: true,
             enable_knight_forks: true,
             enable_back_rank_threats: true,
+            enable_pawn_tactics: true,
 
             fork_threat_ratio: 0.18,
             knight_fork_ratio: 0.22,
@@ -1425,6 +1661,14 @@ impl Default for TacticalConfig {
             discovered_attack_bonus_cp: 80,
             back_rank_penalty_cp: 140,
 
+            hanging_pawn_bonus_cp: 40,
+            hanging_pawn_king_bonus_cp: 40,
+            joining_pawn_bonus_cp: 25,
+            tapping_pawn_ratio: 0.30,
+            tapping_pawn_lure_bonus_cp: 30,
+            file_opening_sacrifice_bonus_cp: 35,
+            file_opening_king_bonus_cp: 35,
+
             phase_weights: TacticalPhaseWeights::default(),
             #[cfg(feature = "simd")]
             enable_simd_pattern_matching: true, // Default to enabled when SIMD feature is available
@@ -1446,6 +1690,13 @@ impl TacticalConfig {
         config.skewer_penalty_ratio = 0.42;
         config.discovered_attack_bonus_cp = 120;
         config.back_rank_penalty_cp = 220;
+        config.hanging_pawn_bonus_cp = 55;
+        config.hanging_pawn_king_bonus_cp = 60;
+        config.joining_pawn_bonus_cp = 35;
+        config.tapping_pawn_ratio = 0.40;
+        config.tapping_pawn_lure_bonus_cp = 45;
+        config.file_opening_sacrifice_bonus_cp = 50;
+        config.file_opening_king_bonus_cp = 50;
         config.phase_weights = TacticalPhaseWeights {
             forks: MotifPhaseWeight { mg: 1.3, eg: 0.9 },
             knight_forks: MotifPhaseWeight { mg: 1.4, eg: 1.0 },
@@ -1453,6 +1704,7 @@ impl TacticalConfig {
             skewers: MotifPhaseWeight { mg: 1.2, eg: 1.0 },
             discovered: MotifPhaseWeight { mg: 1.1, eg: 0.8 },
             back_rank: MotifPhaseWeight { mg: 1.5, eg: 1.2 },
+            pawn_tactics: MotifPhaseWeight { mg: 1.3, eg: 1.0 },
         };
         config
     }
@@ -1466,6 +1718,13 @@ impl TacticalConfig {
         config.skewer_penalty_ratio = 0.2;
         config.discovered_attack_bonus_cp = 40;
         config.back_rank_penalty_cp = 90;
+        config.hanging_pawn_bonus_cp = 25;
+        config.hanging_pawn_king_bonus_cp = 25;
+        config.joining_pawn_bonus_cp = 15;
+        config.tapping_pawn_ratio = 0.20;
+        config.tapping_pawn_lure_bonus_cp = 20;
+        config.file_opening_sacrifice_bonus_cp = 20;
+        config.file_opening_king_bonus_cp = 20;
         config.phase_weights = TacticalPhaseWeights {
             forks: MotifPhaseWeight { mg: 0.7, eg: 0.4 },
             knight_forks: MotifPhaseWeight { mg: 0.8, eg: 0.5 },
@@ -1473,6 +1732,7 @@ impl TacticalConfig {
             skewers: MotifPhaseWeight { mg: 0.6, eg: 0.5 },
             discovered: MotifPhaseWeight { mg: 0.5, eg: 0.3 },
             back_rank: MotifPhaseWeight { mg: 0.8, eg: 0.6 },
+            pawn_tactics: MotifPhaseWeight { mg: 0.7, eg: 0.5 },
         };
         config
     }
@@ -1500,6 +1760,7 @@ pub struct TacticalStats {
     pub discovered_checks: u64,
     pub knight_fork_checks: u64,
     pub back_rank_checks: u64,
+    pub pawn_tactic_checks: u64,
 
     pub forks_found: std::sync::atomic::AtomicU64,
     pub pins_found: std::sync::atomic::AtomicU64,
@@ -1507,6 +1768,7 @@ pub struct TacticalStats {
     pub discovered_attacks_found: std::sync::atomic::AtomicU64,
     pub knight_forks_found: std::sync::atomic::AtomicU64,
     pub back_rank_threats_found: std::sync::atomic::AtomicU64,
+    pub pawn_tactics_found: std::sync::atomic::AtomicU64,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -1518,12 +1780,14 @@ pub struct TacticalStatsSnapshot {
     pub discovered_checks: u64,
     pub knight_fork_checks: u64,
     pub back_rank_checks: u64,
+    pub pawn_tactic_checks: u64,
     pub forks_found: u64,
     pub pins_found: u64,
     pub skewers_found: u64,
     pub discovered_attacks_found: u64,
     pub knight_forks_found: u64,
     pub back_rank_threats_found: u64,
+    pub pawn_tactics_found: u64,
 }
 
 impl TacticalStats {
@@ -1536,12 +1800,14 @@ impl TacticalStats {
             discovered_checks: self.discovered_checks,
             knight_fork_checks: self.knight_fork_checks,
             back_rank_checks: self.back_rank_checks,
+            pawn_tactic_checks: self.pawn_tactic_checks,
             forks_found: self.forks_found.load(Ordering::Relaxed),
             pins_found: self.pins_found.load(Ordering::Relaxed),
             skewers_found: self.skewers_found.load(Ordering::Relaxed),
             discovered_attacks_found: self.discovered_attacks_found.load(Ordering::Relaxed),
             knight_forks_found: self.knight_forks_found.load(Ordering::Relaxed),
             back_rank_threats_found: self.back_rank_threats_found.load(Ordering::Relaxed),
+            pawn_tactics_found: self.pawn_tactics_found.load(Ordering::Relaxed),
         }
     }
 }
@@ -1564,6 +1830,18 @@ mod tests {
         assert_eq!(config.king_fork_bonus_cp, 120);
     }
 
+    #[test]
+    fn test_pawn_tactics_presets() {
+        assert!(TacticalConfig::default().enable_pawn_tactics);
+        let aggressive = TacticalConfig::aggressive();
+        let conservative = TacticalConfig::conservative();
+        assert!(aggressive.hanging_pawn_bonus_cp > conservative.hanging_pawn_bonus_cp);
+        assert!(aggressive.tapping_pawn_ratio > conservative.tapping_pawn_ratio);
+        assert!(
+            aggressive.phase_weights.pawn_tactics.mg > conservative.phase_weights.pawn_tactics.mg
+        );
+    }
+
     #[test]
     fn test_fork_detection() {
         let mut recognizer = TacticalPatternRecognizer::new();
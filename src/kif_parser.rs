@@ -3,6 +3,7 @@
 //! Parser for Japanese Shogi KIF (棋譜) format game files
 //! Supports parsing game metadata, moves, and positions
 
+use crate::types::Handicap;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 // Note: Move and Player types are available but not directly imported here
@@ -34,6 +35,20 @@ pub struct KifGame {
 }
 
 impl KifGame {
+    /// Handicap named in the `手合割` header; records without one are even
+    /// games. Returns `None` for a setup this parser does not know.
+    pub fn handicap(&self) -> Option<Handicap> {
+        match self.metadata.game_type.as_deref() {
+            Some(name) => Handicap::from_str(name),
+            None => Some(Handicap::Even),
+        }
+    }
+
+    /// SFEN of the position the first move is played from
+    pub fn start_sfen(&self) -> Option<&'static str> {
+        self.handicap().map(|handicap| handicap.sfen())
+    }
+
     /// Load a KIF game from a file
     pub fn from_file(path: &str) -> Result<Self, String> {
         let file = File::open(path).map_err(|e| format!("Failed to open file: {}", e))?;
@@ -79,12 +94,13 @@ impl KifGame {
                 metadata.time_control = Some(
                     trimmed.split_once("持ち時間：").map(|(_, v)| v).unwrap_or("").to_string(),
                 );
-            } else if trimmed.starts_with("先手：") {
+            } else if trimmed.starts_with("先手：") || trimmed.starts_with("下手：") {
+                // Handicap records name the players 下手 (Black) and 上手 (White)
                 metadata.player1_name =
-                    Some(trimmed.split_once("先手：").map(|(_, v)| v).unwrap_or("").to_string());
-            } else if trimmed.starts_with("後手：") {
+                    Some(trimmed.split_once('：').map(|(_, v)| v).unwrap_or("").to_string());
+            } else if trimmed.starts_with("後手：") || trimmed.starts_with("上手：") {
                 metadata.player2_name =
-                    Some(trimmed.split_once("後手：").map(|(_, v)| v).unwrap_or("").to_string());
+                    Some(trimmed.split_once('：').map(|(_, v)| v).unwrap_or("").to_string());
             } else if trimmed.starts_with("手合割：") {
                 metadata.game_type =
                     Some(trimmed.split_once("手合割：").map(|(_, v)| v).unwrap_or("").to_string());
@@ -266,4 +282,18 @@ mod tests {
         let result = KifGame::kif_to_usi("７六歩(77)");
         assert_eq!(result.as_deref(), Some("7g7f"));
     }
+
+    #[test]
+    fn test_handicap_header() {
+        let content = "手合割：二枚落ち\n上手：A\n下手：B\n\
+                       手数----指手---------消費時間--\n   1 ６二銀(71)\n";
+        let game = KifGame::from_string(content).unwrap();
+        assert_eq!(game.handicap(), Some(Handicap::TwoPiece));
+        assert_eq!(game.start_sfen(), Some(Handicap::TwoPiece.sfen()));
+        assert_eq!(game.metadata.player2_name.as_deref(), Some("A"));
+        assert_eq!(game.moves.len(), 1);
+
+        let even = KifGame::from_string("手数----指手--\n   1 ７六歩(77)\n").unwrap();
+        assert_eq!(even.handicap(), Some(Handicap::Even));
+    }
 }
@@ -2,11 +2,14 @@ use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
 use rand::SeedableRng;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::{
     atomic::{AtomicBool, Ordering},
-    Arc, Mutex,
+    Arc, Mutex, MutexGuard, PoisonError,
 };
 
+pub mod adjudication;
+pub mod bench;
 pub mod bitboards;
 pub mod config;
 pub mod debug_utils;
@@ -17,6 +20,7 @@ pub mod moves;
 pub mod opening_book;
 pub mod opening_book_converter;
 pub mod search;
+pub mod session;
 pub mod tablebase;
 pub mod time_utils;
 pub mod tuning;
@@ -45,16 +49,30 @@ pub mod patterns {
 
 pub mod usi;
 
-use evaluation::pst_loader::{PieceSquareTableConfig, PieceSquareTablePreset};
+use adjudication::AdjudicationConfig;
+use evaluation::explain::EvalExplanation;
+use evaluation::pst_loader::PieceSquareTableConfig;
 use moves::*;
 use opening_book::OpeningBook;
 use search::search_engine::SearchEngine;
-use search::ParallelSearchConfig;
+use search::time_management::TimePlan;
+use search::{ParallelSearchConfig, ReproducibleSearch, SearchReporter, SkillLevel};
 use tablebase::MicroTablebase;
 use types::*;
+use utils::time::{Clock, TimeSource};
 
 // Re-export BitboardBoard for external use
 pub use bitboards::BitboardBoard;
+pub use session::{EngineSession, SearchHandle, SearchLimits, SearchOutcome};
+
+/// Default file used by the `SaveHash` / `LoadHash` USI buttons
+pub const DEFAULT_HASH_FILE: &str = "yggdrasil.hash";
+
+/// Default of the `NetworkDelay` option
+pub const DEFAULT_NETWORK_DELAY_MS: u32 = 120;
+
+/// SFEN of the standard starting position
+pub const STARTPOS_SFEN: &str = "lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b - 1";
 
 #[derive(Serialize, Deserialize)]
 struct PieceJson {
@@ -80,38 +98,98 @@ pub struct ShogiEngine {
     board: BitboardBoard,
     captured_pieces: CapturedPieces,
     current_player: Player,
-    opening_book: OpeningBook,
+    /// Shared with the copies of the engine a session searches on
+    opening_book: Arc<Mutex<OpeningBook>>,
     opening_book_prefilled: bool,
-    tablebase: MicroTablebase,
+    /// Shared like the opening book
+    tablebase: Arc<Mutex<MicroTablebase>>,
     stop_flag: Arc<AtomicBool>,
     search_engine: Arc<Mutex<SearchEngine>>,
     debug_mode: bool,
-    pondering: bool,
     depth: u8,
     thread_count: usize,
+    /// Transposition table size the search engine was built with (`USI_Hash`)
+    hash_size_mb: usize,
     parallel_options: ParallelOptions,
     pst_config: PieceSquareTableConfig,
+    /// Hash file used by the `SaveHash` / `LoadHash` buttons
+    hash_file: String,
+    /// Receiver of search `info` output
+    search_reporter: Arc<dyn SearchReporter>,
+    /// Position hashes since the last `position` command, for repetition
+    /// detection
+    game_history: Vec<u64>,
+    /// Starting array of the current game
+    handicap: Handicap,
+    /// Strength limit from the `SkillLevel`, `LimitStrength` and `Elo`
+    /// options
+    skill: SkillLevel,
+    /// Resignation and move-limit settings (`ResignValue`, `ResignMoves`,
+    /// `MaxMovesToDraw`)
+    adjudication: AdjudicationConfig,
+    /// Consecutive searches that scored at or below the resign value
+    losing_streak: u32,
+    /// Plies played before the position the history starts from, taken from
+    /// the SFEN move number
+    start_ply: u32,
+    /// Time source for time limits and reported search times
+    clock: Arc<dyn Clock>,
+    /// Time kept in reserve for transmission lag (`NetworkDelay`)
+    network_delay_ms: u32,
+    /// Budget for the next search, set by `search_with_limits`
+    time_plan: Option<TimePlan>,
+    /// Node limit of the next search, set by `search_with_limits`
+    node_limit: Option<u64>,
+    /// Reproducible search mode (`Reproducible`, `ReproducibleNodes`,
+    /// `RandomSeed`)
+    reproducible: ReproducibleSearch,
+    /// USI options changed from their defaults, with where each value came
+    /// from
+    option_settings: HashMap<&'static str, usi::options::OptionSetting>,
+    /// File the thread count is persisted to
+    prefs_path: std::path::PathBuf,
 }
 
 impl ShogiEngine {
     pub fn new() -> Self {
+        Self::with_prefs_path(Self::default_prefs_path())
+    }
+
+    /// Engine that loads and saves its preferences at `prefs_path` instead
+    /// of the user's config directory
+    pub fn with_prefs_path(prefs_path: std::path::PathBuf) -> Self {
         let stop_flag = Arc::new(AtomicBool::new(false));
         let thread_count = num_cpus::get();
         let mut engine = Self {
             board: BitboardBoard::new(),
             captured_pieces: CapturedPieces::new(),
             current_player: Player::Black,
-            opening_book: OpeningBook::new(),
+            opening_book: Arc::new(Mutex::new(OpeningBook::new())),
             opening_book_prefilled: false,
-            tablebase: MicroTablebase::new(),
+            tablebase: Arc::new(Mutex::new(MicroTablebase::new())),
             stop_flag: stop_flag.clone(),
             search_engine: Arc::new(Mutex::new(SearchEngine::new(Some(stop_flag), 16))),
             debug_mode: false,
-            pondering: false,
             depth: 0, // Default to 0 (unlimited/adaptive), like YaneuraOu
             thread_count,
+            hash_size_mb: 16,
             parallel_options: ParallelOptions::default(),
             pst_config: PieceSquareTableConfig::default(),
+            hash_file: DEFAULT_HASH_FILE.to_string(),
+            search_reporter: search::search_reporter::default_reporter(),
+            game_history: Vec::new(),
+            handicap: Handicap::Even,
+            skill: SkillLevel::default(),
+            adjudication: AdjudicationConfig::rules_only(),
+            losing_streak: 0,
+            start_ply: 0,
+            clock: utils::time::system_clock(),
+            network_delay_ms: DEFAULT_NETWORK_DELAY_MS,
+            time_plan: None,
+            node_limit: None,
+            reproducible: ReproducibleSearch::default(),
+            option_settings: HashMap::new(),
+            prefs_path,
         };
         engine.parallel_options.enable_parallel = thread_count > 1;
         engine.parallel_options.hash_size_mb = 16;
@@ -135,21 +213,16 @@ impl ShogiEngine {
         engine
     }
 
-    fn prefs_path() -> std::path::PathBuf {
+    fn default_prefs_path() -> std::path::PathBuf {
         if let Ok(dir) = std::env::var("SHOGI_PREFS_DIR") {
-            let p = std::path::PathBuf::from(dir);
-            let _ = std::fs::create_dir_all(&p);
-            return p.join("engine_prefs.json");
+            return std::path::PathBuf::from(dir).join("engine_prefs.json");
         }
         let base = dirs::config_dir().unwrap_or_else(|| std::path::PathBuf::from("."));
-        let dir = base.join("shogi-vibe");
-        let _ = std::fs::create_dir_all(&dir);
-        dir.join("engine_prefs.json")
+        base.join("shogi-vibe").join("engine_prefs.json")
     }
 
     fn load_prefs(&mut self) {
-        let path = Self::prefs_path();
-        if let Ok(data) = std::fs::read(&path) {
+        if let Ok(data) = std::fs::read(&self.prefs_path) {
             if let Ok(json) = serde_json::from_slice::<serde_json::Value>(&data) {
                 if let Some(tc) = json.get("thread_count").and_then(|v| v.as_u64()) {
                     self.thread_count = (tc as usize).clamp(1, 32);
@@ -161,11 +234,14 @@ impl ShogiEngine {
     }
 
     fn save_prefs(&self) {
-        let path = Self::prefs_path();
+        if let Some(dir) = self.prefs_path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
         let obj = serde_json::json!({
             "thread_count": self.thread_count
         });
-        let _ = std::fs::write(path, serde_json::to_vec_pretty(&obj).unwrap_or_default());
+        let _ =
+            std::fs::write(&self.prefs_path, serde_json::to_vec_pretty(&obj).unwrap_or_default());
     }
 
     fn sync_parallel_options(&mut self) {
@@ -181,6 +257,14 @@ impl ShogiEngine {
         }
     }
 
+    fn opening_book(&self) -> MutexGuard<'_, OpeningBook> {
+        self.opening_book.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn tablebase(&self) -> MutexGuard<'_, MicroTablebase> {
+        self.tablebase.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
     /// Load default opening book from embedded data
     fn load_default_opening_book(&mut self) {
         // Try to load from embedded JSON data first
@@ -198,7 +282,7 @@ impl ShogiEngine {
     }
 
     fn maybe_prefill_opening_book(&mut self) {
-        if self.opening_book_prefilled || !self.opening_book.is_loaded() {
+        if self.opening_book_prefilled || !self.opening_book().is_loaded() {
             return;
         }
 
@@ -209,7 +293,7 @@ impl ShogiEngine {
 
             let depth = search_engine_guard.opening_book_prefill_depth().max(1);
             let inserted =
-                search_engine_guard.prefill_tt_from_opening_book(&mut self.opening_book, depth);
+                search_engine_guard.prefill_tt_from_opening_book(&mut self.opening_book(), depth);
 
             self.opening_book_prefilled = true;
 
@@ -222,7 +306,7 @@ impl ShogiEngine {
 
     /// Load opening book from binary data
     pub fn load_opening_book_from_binary(&mut self, data: &[u8]) -> Result<(), String> {
-        self.opening_book
+        self.opening_book()
             .load_from_binary(data)
             .map_err(|e| format!("Failed to load opening book: {:?}", e))?;
         self.opening_book_prefilled = false;
@@ -232,7 +316,7 @@ impl ShogiEngine {
 
     /// Load opening book from JSON data
     pub fn load_opening_book_from_json(&mut self, json_data: &str) -> Result<(), String> {
-        self.opening_book
+        self.opening_book()
             .load_from_json(json_data)
             .map_err(|e| format!("Failed to load opening book: {:?}", e))?;
         self.opening_book_prefilled = false;
@@ -242,30 +326,32 @@ impl ShogiEngine {
 
     /// Check if opening book is loaded
     pub fn is_opening_book_loaded(&self) -> bool {
-        self.opening_book.is_loaded()
+        self.opening_book().is_loaded()
     }
 
     /// Get opening book statistics
     pub fn get_opening_book_stats(&self) -> String {
-        let stats = self.opening_book.get_stats();
+        let opening_book = self.opening_book();
+        let stats = opening_book.get_stats();
         format!(
             "Positions: {}, Moves: {}, Version: {}, Loaded: {}",
             stats.position_count,
             stats.move_count,
             stats.version,
-            self.opening_book.is_loaded()
+            opening_book.is_loaded()
         )
     }
 
     /// Get detailed opening book information
     pub fn get_opening_book_info(&mut self) -> String {
-        if !self.opening_book.is_loaded() {
+        let mut opening_book = self.opening_book();
+        if !opening_book.is_loaded() {
             return "Opening book not loaded".to_string();
         }
 
         let fen = self.board.to_fen(self.current_player, &self.captured_pieces);
-        let available_moves = self.opening_book.get_moves(&fen);
-        let stats = self.opening_book.get_stats();
+        let available_moves = opening_book.get_moves(&fen);
+        let stats = opening_book.get_stats();
 
         let mut info = format!(
             "Opening Book Info:\n- Positions: {}\n- Total Moves: {}\n- Version: {}\n- Current \
@@ -296,12 +382,12 @@ impl ShogiEngine {
 
     /// Get opening book move for current position with detailed info
     pub fn get_opening_book_move_info(&mut self) -> Option<String> {
-        if !self.opening_book.is_loaded() {
+        if !self.opening_book().is_loaded() {
             return None;
         }
 
         let fen = self.board.to_fen(self.current_player, &self.captured_pieces);
-        if let Some(book_moves) = self.opening_book.get_moves(&fen) {
+        if let Some(book_moves) = self.opening_book().get_moves(&fen) {
             if let Some(best_book_move) = book_moves.iter().max_by(|a, b| a.weight.cmp(&b.weight)) {
                 Some(format!(
                     "Opening book move: {} (weight: {}, eval: {}, opening: {})",
@@ -320,22 +406,36 @@ impl ShogiEngine {
 
     /// Get a random opening book move for variety
     pub fn get_random_opening_book_move(&mut self) -> Option<Move> {
-        if !self.opening_book.is_loaded() {
+        if !self.opening_book().is_loaded() {
             return None;
         }
 
         let fen = self.board.to_fen(self.current_player, &self.captured_pieces);
-        self.opening_book.get_random_move(&fen)
+        match self.active_reproducible() {
+            Some(reproducible) => {
+                let position_hash = match self.search_engine.lock() {
+                    Ok(search_engine_guard) => search_engine_guard.position_hash(
+                        &self.board,
+                        self.current_player,
+                        &self.captured_pieces,
+                    ),
+                    Err(_) => 0,
+                };
+                self.opening_book()
+                    .get_random_move_with(&fen, &mut reproducible.rng(position_hash))
+            }
+            None => self.opening_book().get_random_move(&fen),
+        }
     }
 
     /// Get all available opening book moves for current position
     pub fn get_all_opening_book_moves(&mut self) -> Vec<String> {
-        if !self.opening_book.is_loaded() {
+        if !self.opening_book().is_loaded() {
             return vec!["Opening book not loaded".to_string()];
         }
 
         let fen = self.board.to_fen(self.current_player, &self.captured_pieces);
-        if let Some(moves) = self.opening_book.get_moves(&fen) {
+        if let Some(moves) = self.opening_book().get_moves(&fen) {
             moves
                 .iter()
                 .enumerate()
@@ -355,6 +455,11 @@ impl ShogiEngine {
         }
     }
 
+    /// Send search `info` output to `reporter` instead of stdout.
+    pub fn set_search_reporter(&mut self, reporter: Arc<dyn SearchReporter>) {
+        self.search_reporter = reporter;
+    }
+
     pub fn is_debug_mode(&self) -> bool {
         self.debug_mode
     }
@@ -362,6 +467,7 @@ impl ShogiEngine {
     // Methods for JSON-based position setting (used for external integrations)
     pub fn set_position(&mut self, board_json: &str) {
         self.board = BitboardBoard::empty(); // Clear the board
+        self.game_history.clear();
         if let Ok(pieces) = serde_json::from_str::<Vec<PieceJson>>(board_json) {
             for piece_json in pieces {
                 let player =
@@ -391,6 +497,54 @@ impl ShogiEngine {
         crate::utils::telemetry::debug_log(&format!("Set max depth to: {} (0 = unlimited)", depth));
     }
 
+    pub fn skill(&self) -> SkillLevel {
+        self.skill
+    }
+
+    /// Limit playing strength; `SkillLevel::default()` is full strength
+    pub fn set_skill(&mut self, skill: SkillLevel) {
+        self.skill = skill;
+    }
+
+    pub fn adjudication(&self) -> &AdjudicationConfig {
+        &self.adjudication
+    }
+
+    /// Set when the engine resigns and when the game is drawn by the move
+    /// limit; only the resign and ply-limit fields are used
+    pub fn set_adjudication(&mut self, adjudication: AdjudicationConfig) {
+        self.adjudication = adjudication;
+    }
+
+    /// Keep `delay_ms` of every move's time in reserve for transmission lag
+    pub fn set_network_delay(&mut self, delay_ms: u32) {
+        self.network_delay_ms = delay_ms;
+    }
+
+    pub fn reproducible(&self) -> ReproducibleSearch {
+        self.reproducible
+    }
+
+    /// Set the reproducible search mode. While it is enabled searches repeat
+    /// exactly: one thread, no time checks, fresh tables and seeded random
+    /// choices.
+    pub fn set_reproducible(&mut self, reproducible: ReproducibleSearch) {
+        self.reproducible = reproducible;
+    }
+
+    /// The reproducible mode settings when the mode is on
+    fn active_reproducible(&self) -> Option<ReproducibleSearch> {
+        self.reproducible.enabled.then_some(self.reproducible)
+    }
+
+    /// Measure search time on `clock`; a `VirtualClock` makes time limits
+    /// depend on nodes searched only. Helper threads of a parallel search
+    /// still use the wall clock, so use one thread with a virtual clock.
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        debug_utils::set_timing_clock(clock.clone());
+        self.clock = clock;
+    }
+
     pub fn to_string_for_debug(&self) -> String {
         let mut s = String::new();
         s.push_str("White (captured): ");
@@ -419,6 +573,24 @@ impl ShogiEngine {
     pub fn current_player(&self) -> Player {
         self.current_player
     }
+
+    pub fn board(&self) -> &BitboardBoard {
+        &self.board
+    }
+
+    pub fn captured_pieces(&self) -> &CapturedPieces {
+        &self.captured_pieces
+    }
+
+    /// Plies played in the game, counting from the SFEN move number
+    pub fn game_ply(&self) -> u32 {
+        self.start_ply + self.game_history.len().saturating_sub(1) as u32
+    }
+
+    /// Starting array of the current game
+    pub fn handicap(&self) -> Handicap {
+        self.handicap
+    }
 }
 
 impl ShogiEngine {
@@ -436,6 +608,93 @@ impl ShogiEngine {
         self.parallel_options.clone()
     }
 
+    /// Search under USI `go` limits. A clock gets a `TimePlan` from the
+    /// remaining time, increment, byoyomi, game ply and phase; other limits
+    /// use a fixed budget. A `nodes` limit caps the search on top of them.
+    pub fn search_with_limits(
+        &mut self,
+        limits: &session::SearchLimits,
+        stop_flag: Option<Arc<AtomicBool>>,
+    ) -> SearchOutcome {
+        let depth = limits.depth.unwrap_or(self.depth);
+        self.time_plan = self.plan_move(limits);
+        let time_limit_ms = match self.time_plan {
+            Some(ref plan) => plan.maximum_ms,
+            None => limits.time_budget_ms(self.current_player),
+        };
+        self.node_limit = limits.nodes;
+        let outcome = self.search_position(depth, time_limit_ms, stop_flag);
+        // Book and tablebase moves return before the plan is used
+        self.time_plan = None;
+        self.node_limit = None;
+        outcome
+    }
+
+    /// Time plan for a search of the current position under clock `limits`
+    fn plan_move(&self, limits: &session::SearchLimits) -> Option<TimePlan> {
+        let phase = match self.search_engine.lock() {
+            Ok(search_engine_guard) => {
+                search_engine_guard.game_phase(&self.board, &self.captured_pieces)
+            }
+            Err(_) => GAME_PHASE_MAX,
+        };
+        limits
+            .move_clock(self.current_player, self.game_ply(), phase)
+            .map(|clock| TimePlan::new(&clock, self.network_delay_ms))
+    }
+
+    /// Time to spend on the current position under `limits`: the planned
+    /// optimum for clock limits, otherwise the fixed budget
+    pub(crate) fn move_time_ms(&self, limits: &session::SearchLimits) -> u32 {
+        match self.plan_move(limits) {
+            Some(plan) => plan.optimum_ms,
+            None => limits.time_budget_ms(self.current_player),
+        }
+    }
+
+    /// Search the current position and collect the full result: score, bound,
+    /// PV and statistics besides the best move
+    pub fn search_position(
+        &mut self,
+        depth: u8,
+        time_limit_ms: u32,
+        stop_flag: Option<Arc<AtomicBool>>,
+    ) -> SearchOutcome {
+        let recorder = Arc::new(session::SearchRecorder::new(self.search_reporter.clone()));
+        let reporter = std::mem::replace(&mut self.search_reporter, recorder.clone());
+        let plies_to_draw = (self.adjudication.max_plies > 0)
+            .then(|| self.adjudication.max_plies.saturating_sub(self.game_ply()));
+        if let Ok(mut search_engine_guard) = self.search_engine.lock() {
+            search_engine_guard.set_game_history(&self.game_history);
+            search_engine_guard.set_plies_to_draw(plies_to_draw);
+        }
+
+        let start = TimeSource::start(&self.clock);
+        let best_move = self.get_best_move(depth, time_limit_ms, stop_flag);
+        let time_ms = u64::from(start.elapsed_ms());
+        self.search_reporter = reporter;
+
+        let pv = match (&best_move, self.search_engine.lock()) {
+            (Some(_), Ok(search_engine_guard)) => search_engine_guard.get_pv_for_reporting(
+                &self.board,
+                &self.captured_pieces,
+                self.current_player,
+                depth,
+            ),
+            _ => Vec::new(),
+        };
+        let mut outcome = recorder.outcome(best_move, pv, time_ms);
+        if outcome.best_move.is_some() && self.adjudication.resign_score > 0 {
+            self.losing_streak = if outcome.score <= -self.adjudication.resign_score {
+                self.losing_streak + 1
+            } else {
+                0
+            };
+            outcome.resign = self.adjudication.should_resign(outcome.score, self.losing_streak);
+        }
+        outcome
+    }
+
     pub fn get_best_move(
         &mut self,
         depth: u8,
@@ -468,7 +727,7 @@ impl ShogiEngine {
 
         // Check tablebase first
         if let Some(tablebase_result) =
-            self.tablebase.probe(&self.board, self.current_player, &self.captured_pieces)
+            self.tablebase().probe(&self.board, self.current_player, &self.captured_pieces)
         {
             crate::debug_utils::end_timing("tablebase_check", "GET_BEST_MOVE");
             if let Some(best_move) = tablebase_result.best_move {
@@ -492,8 +751,8 @@ impl ShogiEngine {
 
         // Check opening book second
         crate::debug_utils::start_timing("opening_book_check");
-        if self.opening_book.is_loaded() {
-            if let Some(book_move) = self.opening_book.get_best_move(&fen) {
+        if self.opening_book().is_loaded() {
+            if let Some(book_move) = self.opening_book().get_best_move(&fen) {
                 crate::utils::telemetry::debug_log(&format!(
                     "Found opening book move: {}",
                     book_move.to_usi_string()
@@ -532,15 +791,39 @@ impl ShogiEngine {
             "Creating searcher with depth: {} (requested: {}, 0 = unlimited), time_limit: {}ms",
             actual_depth, depth, time_limit_ms
         ));
+        // A reproducible search runs on one thread with a frozen clock, so
+        // only depth and nodes limit it
+        let reproducible = self.active_reproducible();
+        let mut parallel_options = self.parallel_options.clone();
+        let mut thread_count = self.thread_count;
+        let mut time_plan = self.time_plan.take();
+        let mut clock = self.clock.clone();
+        if let Some(ref reproducible) = reproducible {
+            parallel_options.enable_parallel = false;
+            thread_count = 1;
+            time_plan = None;
+            clock = reproducible.clock();
+        }
+        let search_start = TimeSource::start(&clock);
+        let node_limit = [
+            self.node_limit.or(reproducible.and_then(|r| r.node_limit)),
+            self.skill.node_limit(),
+        ]
+        .into_iter()
+        .flatten()
+        .min();
         let parallel_config =
-            ParallelSearchConfig::from_parallel_options(&self.parallel_options, self.thread_count);
+            ParallelSearchConfig::from_parallel_options(&parallel_options, thread_count);
         let mut searcher = search::search_engine::IterativeDeepening::new_with_threads(
             actual_depth,
             time_limit_ms,
             stop_flag,
-            self.thread_count,
+            thread_count,
             parallel_config,
-        );
+        )
+        .with_reporter(self.search_reporter.clone())
+        .with_node_limit(node_limit)
+        .with_time_plan(time_plan);
 
         crate::utils::telemetry::debug_log("Trying to get search engine lock");
 
@@ -551,6 +834,10 @@ impl ShogiEngine {
         crate::utils::telemetry::debug_log("About to lock search engine");
         let search_result = self.search_engine.lock().map(|mut search_engine_guard| {
             crate::utils::telemetry::debug_log("Got search engine lock, starting search");
+            if reproducible.is_some() {
+                search_engine_guard.reset_search_state();
+            }
+            search_engine_guard.set_clock(clock);
             searcher.search(
                 &mut search_engine_guard,
                 &self.board,
@@ -558,10 +845,12 @@ impl ShogiEngine {
                 self.current_player,
             )
         });
+        // Keep only the result; a poisoned lock still borrows the engine
+        let search_result = search_result.ok().flatten();
 
         crate::utils::telemetry::debug_log("Search completed, checking result");
 
-        if let Ok(Some((move_, _score))) = search_result {
+        if let Some((move_, _score)) = search_result {
             // CRITICAL: Verify move is legal before returning
             // This prevents illegal moves from being played
             let move_generator = MoveGenerator::new();
@@ -721,7 +1010,11 @@ impl ShogiEngine {
                 crate::utils::telemetry::debug_log("Falling back to first legal move due to illegal move from search");
                 return current_legal_moves.first().cloned();
             }
-            
+
+            if self.skill.is_limited() {
+                let time_left_ms = time_limit_ms.saturating_sub(search_start.elapsed_ms());
+                return Some(self.pick_skill_move(move_, time_left_ms));
+            }
             Some(move_)
         } else {
             // Fallback to random move if search fails
@@ -740,6 +1033,35 @@ impl ShogiEngine {
         }
     }
 
+    /// Replace the search's move with a pick among the best root moves, as
+    /// the skill level allows, scoring them within what is left of the
+    /// move's time
+    fn pick_skill_move(&mut self, best_move: Move, time_left_ms: u32) -> Move {
+        let (candidates, position_hash) = match self.search_engine.lock() {
+            Ok(mut search_engine_guard) => (
+                search_engine_guard.score_root_moves(
+                    &self.board,
+                    &self.captured_pieces,
+                    self.current_player,
+                    self.skill.candidate_depth(),
+                    time_left_ms,
+                ),
+                search_engine_guard.position_hash(
+                    &self.board,
+                    self.current_player,
+                    &self.captured_pieces,
+                ),
+            ),
+            Err(_) => return best_move,
+        };
+        match self.active_reproducible() {
+            Some(reproducible) => {
+                self.skill.pick_move(&best_move, &candidates, &mut reproducible.rng(position_hash))
+            }
+            None => self.skill.pick_move(&best_move, &candidates, &mut rand::thread_rng()),
+        }
+    }
+
     /// Apply a move to the engine's board
     pub fn apply_move(&mut self, move_: &Move) -> bool {
         use crate::moves::MoveGenerator;
@@ -778,7 +1100,12 @@ impl ShogiEngine {
             return false;
         }
 
-        // Apply the move
+        self.play_move(move_);
+        true
+    }
+
+    /// Make a legal move, update the hands and record the new position
+    fn play_move(&mut self, move_: &Move) {
         if let Some(captured_piece) = self.board.make_move(move_) {
             // A piece was captured - add it to captured pieces
             self.captured_pieces.add_piece(captured_piece.piece_type, self.current_player);
@@ -805,9 +1132,76 @@ impl ShogiEngine {
 
         // Switch turns
         self.current_player = self.current_player.opposite();
+        self.record_position();
 
         crate::utils::telemetry::debug_log(&format!("Applied move: {}", move_.to_usi_string()));
-        true
+    }
+
+    fn set_handicap(&mut self, handicap: Handicap) {
+        self.handicap = handicap;
+        if let Ok(mut search_engine_guard) = self.search_engine.lock() {
+            search_engine_guard.set_handicap(handicap);
+        }
+    }
+
+    fn record_position(&mut self) {
+        if let Ok(search_engine_guard) = self.search_engine.lock() {
+            self.game_history.push(search_engine_guard.position_hash(
+                &self.board,
+                self.current_player,
+                &self.captured_pieces,
+            ));
+        }
+    }
+
+    /// Set the position from `startpos`, `handicap <name>`, `sfen <sfen>` or
+    /// a bare SFEN, followed by the USI moves played since
+    pub fn set_usi_position(&mut self, position: &str, moves: &[&str]) -> Result<(), String> {
+        let sfen = match position.trim() {
+            "startpos" => STARTPOS_SFEN,
+            other => match other.strip_prefix("handicap ") {
+                Some(name) => Handicap::from_str(name)
+                    .ok_or_else(|| format!("Unknown handicap '{}'", name.trim()))?
+                    .sfen(),
+                None => other.strip_prefix("sfen ").unwrap_or(other).trim(),
+            },
+        };
+        let (board, player, captured_pieces) =
+            BitboardBoard::from_fen(sfen).map_err(|e| format!("Failed to parse FEN: {}", e))?;
+        self.board = board;
+        self.current_player = player;
+        self.captured_pieces = captured_pieces;
+        self.set_handicap(Handicap::from_sfen(sfen).unwrap_or_default());
+        self.start_ply = sfen
+            .split_whitespace()
+            .nth(3)
+            .and_then(|n| n.parse::<u32>().ok())
+            .map_or(0, |move_number| move_number.saturating_sub(1));
+        self.game_history.clear();
+        self.record_position();
+
+        for move_str in moves {
+            self.push_usi_move(move_str)?;
+        }
+        Ok(())
+    }
+
+    /// Play a USI move, rejecting it if it is not legal in the current
+    /// position
+    pub fn push_usi_move(&mut self, usi_move: &str) -> Result<(), String> {
+        use crate::moves::MoveGenerator;
+
+        let move_ = Move::from_usi_string(usi_move, self.current_player, &self.board)
+            .map_err(|e| format!("Failed to parse move '{}': {}", usi_move, e))?;
+        // Compare the USI text: a parsed move carries no capture details
+        let move_usi = move_.to_usi_string();
+        let legal_move = MoveGenerator::new()
+            .generate_legal_moves(&self.board, self.current_player, &self.captured_pieces)
+            .into_iter()
+            .find(|m| m.to_usi_string() == move_usi)
+            .ok_or_else(|| format!("Failed to parse move '{}': illegal move", usi_move))?;
+        self.play_move(&legal_move);
+        Ok(())
     }
 
     /// Check if the current position is a terminal state (checkmate, stalemate)
@@ -841,108 +1235,36 @@ impl ShogiEngine {
     }
 
     pub fn handle_position(&mut self, parts: &[&str]) -> Vec<String> {
-        let mut output = Vec::new();
-        let sfen_str: String;
-        let mut moves_start_index: Option<usize> = None;
+        crate::utils::telemetry::debug_log(&format!("handle_position parts: {:?}", parts));
 
-        crate::utils::telemetry::debug_log(&format!(
-            "handle_position called with {} parts",
-            parts.len()
-        ));
-        crate::utils::telemetry::debug_log(&format!("Parts: {:?}", parts));
-
-        if parts.is_empty() {
-            output.push("info string error Invalid position command".to_string());
-            return output;
-        }
-
-        if parts[0] == "startpos" {
-            sfen_str =
-                "lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b - 1".to_string();
-            crate::utils::telemetry::debug_log("Using startpos");
-            if parts.len() > 1 && parts[1] == "moves" {
-                moves_start_index = Some(2);
-            }
-        } else if parts[0] == "sfen" {
-            // sfen can be up to 4 parts, plus "moves"
-            let mut sfen_parts = Vec::new();
-            let mut current_index = 1;
-            while current_index < parts.len() && parts[current_index] != "moves" {
-                sfen_parts.push(parts[current_index]);
-                current_index += 1;
-            }
-            sfen_str = sfen_parts.join(" ");
-            crate::utils::telemetry::debug_log(&format!("Parsed SFEN: '{}'", sfen_str));
-            if current_index < parts.len() && parts[current_index] == "moves" {
-                moves_start_index = Some(current_index + 1);
+        let moves_index = parts.iter().position(|&p| p == "moves");
+        let (position_parts, moves) = match moves_index {
+            Some(index) => (&parts[..index], &parts[index + 1..]),
+            None => (parts, &parts[parts.len()..]),
+        };
+        let position = match position_parts.first() {
+            Some(&"startpos") => "startpos".to_string(),
+            Some(&"handicap") | Some(&"sfen") => position_parts.join(" "),
+            Some(_) => {
+                return vec![
+                    "info string error Invalid position command: expected 'startpos', \
+                     'handicap' or 'sfen'"
+                        .to_string(),
+                ];
             }
-        } else {
-            output.push(
-                "info string error Invalid position command: expected 'startpos' or 'sfen'"
-                    .to_string(),
-            );
-            return output;
-        }
+            None => return vec!["info string error Invalid position command".to_string()],
+        };
 
-        crate::utils::telemetry::debug_log(&format!("About to parse SFEN: '{}'", sfen_str));
-        match BitboardBoard::from_fen(&sfen_str) {
-            Ok((board, player, captured_pieces)) => {
-                crate::utils::telemetry::debug_log(&format!(
-                    "SFEN parsed successfully, player: {:?}",
-                    player
-                ));
-                self.board = board;
-                self.current_player = player;
-                self.captured_pieces = captured_pieces;
-
-                // CRITICAL DEBUG: Verify the state was actually set
-                let verify_fen = self.board.to_fen(self.current_player, &self.captured_pieces);
-                crate::utils::telemetry::debug_log("========================================");
-                crate::utils::telemetry::debug_log("[HANDLE_POSITION] STATE SET - VERIFICATION:");
-                crate::utils::telemetry::debug_log(&format!(
-                    "[HANDLE_POSITION]   self.current_player = {:?}",
-                    self.current_player
-                ));
+        match self.set_usi_position(&position, moves) {
+            Ok(()) => {
                 crate::utils::telemetry::debug_log(&format!(
-                    "[HANDLE_POSITION]   Verification FEN: {}",
-                    verify_fen
+                    "[HANDLE_POSITION] {}",
+                    self.board.to_fen(self.current_player, &self.captured_pieces)
                 ));
-                crate::utils::telemetry::debug_log(&format!(
-                    "[HANDLE_POSITION]   self.captured_pieces: black={:?}, white={:?}",
-                    self.captured_pieces.black, self.captured_pieces.white
-                ));
-                crate::utils::telemetry::debug_log("========================================");
-            }
-            Err(e) => {
-                crate::utils::telemetry::debug_log(&format!("SFEN parse FAILED: {}", e));
-                output.push(format!("info string error Failed to parse FEN: {}", e));
-                return output;
+                vec!["info string Board state updated.".to_string()]
             }
+            Err(e) => vec![format!("info string error {}", e)],
         }
-
-        if let Some(start_index) = moves_start_index {
-            for move_str in &parts[start_index..] {
-                match Move::from_usi_string(move_str, self.current_player, &self.board) {
-                    Ok(mv) => {
-                        if let Some(captured) = self.board.make_move(&mv) {
-                            self.captured_pieces
-                                .add_piece(captured.piece_type, self.current_player);
-                        }
-                        self.current_player = self.current_player.opposite();
-                    }
-                    Err(e) => {
-                        output.push(format!(
-                            "info string error Failed to parse move '{}': {}",
-                            move_str, e
-                        ));
-                        return output;
-                    }
-                }
-            }
-        }
-
-        output.push("info string Board state updated.".to_string());
-        output
     }
 
     pub fn handle_stop(&mut self) -> Vec<String> {
@@ -950,544 +1272,32 @@ impl ShogiEngine {
         Vec::new()
     }
 
-    pub fn handle_setoption(&mut self, parts: &[&str]) -> Vec<String> {
-        let mut output = Vec::new();
-        if parts.len() >= 4 && parts[0] == "name" && parts[2] == "value" {
-            match parts[1] {
-                "USI_Hash" => {
-                    if let Ok(size) = parts[3].parse::<usize>() {
-                        let size = size.clamp(1, 1024);
-                        if let Ok(mut search_engine_guard) = self.search_engine.lock() {
-                            *search_engine_guard =
-                                SearchEngine::new(Some(self.stop_flag.clone()), size);
-                            self.parallel_options.hash_size_mb = size.min(512);
-                            search_engine_guard.set_parallel_options(self.parallel_options.clone());
-                            output.push(format!("info string Set USI_Hash to {} MB", size));
-                        }
-                        self.opening_book_prefilled = false;
-                        self.maybe_prefill_opening_book();
-                    }
-                }
-                "PSTPreset" => {
-                    let value = parts[3..].join(" ");
-                    let trimmed = value.trim();
-                    let parsed = match trimmed {
-                        "Builtin" => Some(PieceSquareTablePreset::Builtin),
-                        "Default" => Some(PieceSquareTablePreset::Default),
-                        "Custom" => Some(PieceSquareTablePreset::Custom),
-                        _ => None,
-                    };
-
-                    if let Some(preset) = parsed {
-                        let previous_config = self.pst_config.clone();
-                        let preset_is_custom = matches!(preset, PieceSquareTablePreset::Custom);
-                        self.pst_config.preset = preset;
-                        if !preset_is_custom {
-                            self.pst_config.values_path = None;
-                        }
-
-                        match self.apply_pst_config() {
-                            Ok(()) => {
-                                output.push(format!("info string PST preset set to {}", trimmed))
-                            }
-                            Err(err) => {
-                                self.pst_config = previous_config;
-                                output.push(format!(
-                                    "info string error Failed to apply PST preset '{}': {}",
-                                    trimmed, err
-                                ));
-                            }
-                        }
-                    } else {
-                        output.push(format!(
-                            "info string error Unknown PSTPreset value '{}'",
-                            trimmed
-                        ));
-                    }
-                }
-                "PSTPath" => {
-                    let value = parts[3..].join(" ");
-                    let trimmed = value.trim();
-                    let previous_config = self.pst_config.clone();
-
-                    if trimmed.is_empty() {
-                        self.pst_config.values_path = None;
-                        if matches!(self.pst_config.preset, PieceSquareTablePreset::Custom) {
-                            self.pst_config.preset = PieceSquareTablePreset::Builtin;
-                        }
-                        match self.apply_pst_config() {
-                            Ok(()) => output.push(
-                                "info string Cleared PSTPath override; using built-in tables"
-                                    .to_string(),
-                            ),
-                            Err(err) => {
-                                self.pst_config = previous_config;
-                                output.push(format!(
-                                    "info string error Failed to clear PSTPath: {}",
-                                    err
-                                ));
-                            }
-                        }
-                    } else {
-                        self.pst_config.values_path = Some(trimmed.to_string());
-                        self.pst_config.preset = PieceSquareTablePreset::Custom;
-                        match self.apply_pst_config() {
-                            Ok(()) => output
-                                .push(format!("info string Loaded PST from path '{}'", trimmed)),
-                            Err(err) => {
-                                self.pst_config = previous_config;
-                                output.push(format!(
-                                    "info string error Failed to load PST from '{}': {}",
-                                    trimmed, err
-                                ));
-                            }
-                        }
-                    }
-                }
-                "PrefillOpeningBook" => {
-                    if let Ok(enabled) = parts[3].parse::<bool>() {
-                        if let Ok(mut search_engine_guard) = self.search_engine.lock() {
-                            let mut config = search_engine_guard.get_engine_config();
-                            config.prefill_opening_book = enabled;
-                            match search_engine_guard.update_engine_config(config) {
-                                Ok(()) => {
-                                    output.push(format!(
-                                        "info string {} opening book prefill",
-                                        if enabled { "Enabled" } else { "Disabled" }
-                                    ));
-                                }
-                                Err(e) => {
-                                    output.push(format!(
-                                        "info string error Failed to update config: {}",
-                                        e
-                                    ));
-                                }
-                            }
-                        }
+    /// Save the transposition table to `path`.
+    pub fn save_hash(&self, path: &str) -> Result<usize, String> {
+        let mut search_engine_guard =
+            self.search_engine.lock().map_err(|_| "search engine unavailable".to_string())?;
+        search_engine_guard.save_transposition_table(path).map_err(|e| e.to_string())
+    }
 
-                        if enabled {
-                            self.opening_book_prefilled = false;
-                            self.maybe_prefill_opening_book();
-                        }
-                    }
-                }
-                "OpeningBookPrefillDepth" => {
-                    if let Ok(depth) = parts[3].parse::<u8>() {
-                        if depth == 0 {
-                            output.push(
-                                "info string error OpeningBookPrefillDepth must be >= 1"
-                                    .to_string(),
-                            );
-                        } else if let Ok(mut search_engine_guard) = self.search_engine.lock() {
-                            let mut config = search_engine_guard.get_engine_config();
-                            config.opening_book_prefill_depth = depth;
-                            match search_engine_guard.update_engine_config(config) {
-                                Ok(()) => {
-                                    output.push(format!(
-                                        "info string Set opening book prefill depth to {}",
-                                        depth
-                                    ));
-                                    self.opening_book_prefilled = false;
-                                }
-                                Err(e) => output.push(format!(
-                                    "info string error Failed to update config: {}",
-                                    e
-                                )),
-                            }
-                        }
+    /// Load the transposition table from `path`, refusing files written with
+    /// a different Zobrist seed or entry layout.
+    pub fn load_hash(&self, path: &str) -> Result<usize, String> {
+        let mut search_engine_guard =
+            self.search_engine.lock().map_err(|_| "search engine unavailable".to_string())?;
+        search_engine_guard.load_transposition_table(path).map_err(|e| e.to_string())
+    }
 
-                        self.maybe_prefill_opening_book();
-                    }
-                }
-                "MaxDepth" | "depth" => {
-                    // Support both MaxDepth (new) and depth (legacy) for backward compatibility
-                    if let Ok(depth) = parts[3].parse::<u8>() {
-                        if depth <= 100 {
-                            self.set_max_depth(depth);
-                            let label = if parts[1] == "depth" { "depth" } else { "MaxDepth" };
-                            let message = if depth == 0 {
-                                format!("info string Set {} to 0 (unlimited/adaptive)", label)
-                            } else {
-                                format!("info string Set {} to {}", label, depth)
-                            };
-                            output.push(message);
-                        } else {
-                            output.push(
-                                "info string error MaxDepth must be between 0 and 100".to_string(),
-                            );
-                        }
-                    }
-                }
-                // Quiescence search options
-                "QuiescenceDepth" => {
-                    if let Ok(depth) = parts[3].parse::<u8>() {
-                        if let Ok(mut search_engine_guard) = self.search_engine.lock() {
-                            let mut config = search_engine_guard.get_engine_config();
-                            config.quiescence.max_depth = depth;
-                            let _ = search_engine_guard.update_engine_config(config);
-                            output
-                                .push(format!("info string Set quiescence max_depth to {}", depth));
-                        }
-                    }
-                }
-                "EnableQuiescence" => {
-                    if let Ok(_enabled) = parts[3].parse::<bool>() {
-                        // Note: QuiescenceConfig doesn't have an 'enabled' field
-                        // Quiescence search is always enabled in the engine
-                        if let Ok(_search_engine_guard) = self.search_engine.lock() {
-                            output.push(format!(
-                                "info string Quiescence search is always enabled in the engine"
-                            ));
-                        }
-                    }
-                }
-                // Null-move pruning options
-                "EnableNullMove" => {
-                    if let Ok(enabled) = parts[3].parse::<bool>() {
-                        if let Ok(mut search_engine_guard) = self.search_engine.lock() {
-                            let mut config = search_engine_guard.get_engine_config();
-                            config.null_move.enabled = enabled;
-                            let _ = search_engine_guard.update_engine_config(config);
-                            output.push(format!(
-                                "info string {} null-move pruning",
-                                if enabled { "Enabled" } else { "Disabled" }
-                            ));
-                        }
-                    }
-                }
-                "NullMoveMinDepth" => {
-                    if let Ok(depth) = parts[3].parse::<u8>() {
-                        if let Ok(mut search_engine_guard) = self.search_engine.lock() {
-                            let mut config = search_engine_guard.get_engine_config();
-                            config.null_move.min_depth = depth;
-                            let _ = search_engine_guard.update_engine_config(config);
-                            output
-                                .push(format!("info string Set null-move min_depth to {}", depth));
-                        }
-                    }
-                }
-                // Late move reduction options
-                "EnableLMR" => {
-                    if let Ok(enabled) = parts[3].parse::<bool>() {
-                        if let Ok(mut search_engine_guard) = self.search_engine.lock() {
-                            let mut config = search_engine_guard.get_engine_config();
-                            config.lmr.enabled = enabled;
-                            let _ = search_engine_guard.update_engine_config(config);
-                            output.push(format!(
-                                "info string {} late move reduction",
-                                if enabled { "Enabled" } else { "Disabled" }
-                            ));
-                        }
-                    }
-                }
-                // IID options
-                "EnableIID" => {
-                    if let Ok(enabled) = parts[3].parse::<bool>() {
-                        if let Ok(mut search_engine_guard) = self.search_engine.lock() {
-                            let mut config = search_engine_guard.get_engine_config();
-                            config.iid.enabled = enabled;
-                            let _ = search_engine_guard.update_engine_config(config);
-                            output.push(format!(
-                                "info string {} internal iterative deepening",
-                                if enabled { "Enabled" } else { "Disabled" }
-                            ));
-                        }
-                    }
-                }
-                // Aspiration windows options
-                "EnableAspirationWindows" => {
-                    if let Ok(enabled) = parts[3].parse::<bool>() {
-                        if let Ok(mut search_engine_guard) = self.search_engine.lock() {
-                            let mut config = search_engine_guard.get_engine_config();
-                            config.aspiration_windows.enabled = enabled;
-                            let _ = search_engine_guard.update_engine_config(config);
-                            output.push(format!(
-                                "info string {} aspiration windows",
-                                if enabled { "Enabled" } else { "Disabled" }
-                            ));
-                        }
-                    }
-                }
-                "AspirationWindowSize" => {
-                    if let Ok(size) = parts[3].parse::<u16>() {
-                        if size >= 10 && size <= 500 {
-                            if let Ok(mut search_engine_guard) = self.search_engine.lock() {
-                                let mut config = search_engine_guard.get_engine_config();
-                                config.aspiration_windows.base_window_size = size as i32;
-                                let _ = search_engine_guard.update_engine_config(config);
-                                output.push(format!(
-                                    "info string Set aspiration window size to {}",
-                                    size
-                                ));
-                            }
-                        } else {
-                            output.push(
-                                "info string error AspirationWindowSize must be between 10 and 500"
-                                    .to_string(),
-                            );
-                        }
-                    }
-                }
-                "EnablePositionTypeTracking" => {
-                    if let Ok(enabled) = parts[3].parse::<bool>() {
-                        if let Ok(mut search_engine_guard) = self.search_engine.lock() {
-                            let mut config = search_engine_guard.get_engine_config();
-                            config.aspiration_windows.enable_position_type_tracking = enabled;
-                            let _ = search_engine_guard.update_engine_config(config);
-                            output.push(format!(
-                                "info string {} position type tracking",
-                                if enabled { "Enabled" } else { "Disabled" }
-                            ));
-                        }
-                    }
-                }
-                // Time Management Options (Task 8.0, 4.0)
-                "TimeCheckFrequency" => {
-                    if let Ok(frequency) = parts[3].parse::<u32>() {
-                        if frequency >= 1 && frequency <= 100000 {
-                            if let Ok(mut search_engine_guard) = self.search_engine.lock() {
-                                let mut config = search_engine_guard.get_engine_config();
-                                config.time_management.time_check_frequency = frequency;
-                                let _ = search_engine_guard.update_engine_config(config);
-                                output.push(format!(
-                                    "info string Set time check frequency to {} nodes",
-                                    frequency
-                                ));
-                            }
-                        } else {
-                            output.push(
-                                "info string error TimeCheckFrequency must be between 1 and 100000"
-                                    .to_string(),
-                            );
-                        }
-                    }
-                }
-                "TimeSafetyMargin" => {
-                    if let Ok(margin) = parts[3].parse::<u32>() {
-                        if margin <= 10000 {
-                            if let Ok(mut search_engine_guard) = self.search_engine.lock() {
-                                let mut config = search_engine_guard.get_engine_config();
-                                config.time_management.absolute_safety_margin_ms = margin;
-                                let _ = search_engine_guard.update_engine_config(config);
-                                output.push(format!(
-                                    "info string Set time safety margin to {}ms",
-                                    margin
-                                ));
-                            }
-                        } else {
-                            output.push(
-                                "info string error TimeSafetyMargin must be between 0 and 10000"
-                                    .to_string(),
-                            );
-                        }
-                    }
-                }
-                "TimeAllocationStrategy" => {
-                    if let Ok(mut search_engine_guard) = self.search_engine.lock() {
-                        let mut config = search_engine_guard.get_engine_config();
-                        match parts[3] {
-                            "Equal" => {
-                                config.time_management.allocation_strategy =
-                                    crate::types::all::TimeAllocationStrategy::Equal;
-                                let _ = search_engine_guard.update_engine_config(config);
-                                output.push(
-                                    "info string Set time allocation strategy to Equal".to_string(),
-                                );
-                            }
-                            "Exponential" => {
-                                config.time_management.allocation_strategy =
-                                    crate::types::all::TimeAllocationStrategy::Exponential;
-                                let _ = search_engine_guard.update_engine_config(config);
-                                output.push(
-                                    "info string Set time allocation strategy to Exponential"
-                                        .to_string(),
-                                );
-                            }
-                            "Adaptive" => {
-                                config.time_management.allocation_strategy =
-                                    crate::types::all::TimeAllocationStrategy::Adaptive;
-                                let _ = search_engine_guard.update_engine_config(config);
-                                output.push(
-                                    "info string Set time allocation strategy to Adaptive"
-                                        .to_string(),
-                                );
-                            }
-                            _ => {
-                                output.push(
-                                    "info string error TimeAllocationStrategy must be Equal, \
-                                     Exponential, or Adaptive"
-                                        .to_string(),
-                                );
-                            }
-                        }
-                    }
-                }
-                "EnableTimeBudget" => {
-                    if let Ok(enabled) = parts[3].parse::<bool>() {
-                        if let Ok(mut search_engine_guard) = self.search_engine.lock() {
-                            let mut config = search_engine_guard.get_engine_config();
-                            config.time_management.enable_time_budget = enabled;
-                            let _ = search_engine_guard.update_engine_config(config);
-                            output.push(format!(
-                                "info string {} time budget allocation",
-                                if enabled { "Enabled" } else { "Disabled" }
-                            ));
-                        }
-                    }
-                }
-                "EnableCheckOptimization" => {
-                    if let Ok(enabled) = parts[3].parse::<bool>() {
-                        if let Ok(mut search_engine_guard) = self.search_engine.lock() {
-                            let mut config = search_engine_guard.get_engine_config();
-                            config.time_management.enable_check_optimization = enabled;
-                            let _ = search_engine_guard.update_engine_config(config);
-                            output.push(format!(
-                                "info string {} check position optimization",
-                                if enabled { "Enabled" } else { "Disabled" }
-                            ));
-                        }
-                    }
-                }
-                // Tablebase options
-                "EnableTablebase" => {
-                    if parts[3] == "true" {
-                        self.enable_tablebase();
-                        output.push("info string Enabled tablebase".to_string());
-                    } else if parts[3] == "false" {
-                        self.disable_tablebase();
-                        output.push("info string Disabled tablebase".to_string());
-                    }
-                }
-                "USI_Threads" => {
-                    if let Ok(threads) = parts[3].parse::<usize>() {
-                        self.thread_count = threads.clamp(1, 32);
-                        if self.thread_count <= 1 {
-                            self.parallel_options.enable_parallel = false;
-                        }
-                        self.sync_parallel_options();
-                        self.save_prefs();
-                        output
-                            .push(format!("info string Set USI_Threads to {}", self.thread_count));
-                    } else {
-                        output.push("info string error Invalid thread count value".to_string());
-                    }
-                }
-                "ParallelEnable" => {
-                    if let Ok(enabled) = parts[3].parse::<bool>() {
-                        self.parallel_options.enable_parallel = enabled;
-                        self.sync_parallel_options();
-                        output.push(format!(
-                            "info string {} parallel search",
-                            if enabled { "Enabled" } else { "Disabled" }
-                        ));
-                    }
-                }
-                "ParallelHash" => {
-                    if let Ok(size) = parts[3].parse::<usize>() {
-                        self.parallel_options.hash_size_mb = size.clamp(1, 512);
-                        self.sync_parallel_options();
-                        output.push(format!(
-                            "info string Set ParallelHash to {} MB",
-                            self.parallel_options.hash_size_mb
-                        ));
-                    }
-                }
-                "ParallelMinDepth" => {
-                    if let Ok(depth) = parts[3].parse::<u8>() {
-                        self.parallel_options.min_depth_parallel = depth;
-                        self.sync_parallel_options();
-                        output.push(format!("info string Set ParallelMinDepth to {}", depth));
-                    }
-                }
-                "ParallelMetrics" => {
-                    if let Ok(enabled) = parts[3].parse::<bool>() {
-                        self.parallel_options.enable_metrics = enabled;
-                        self.sync_parallel_options();
-                        output.push(format!(
-                            "info string {} parallel work metrics",
-                            if enabled { "Enabled" } else { "Disabled" }
-                        ));
-                    }
-                }
-                "YBWCEnable" => {
-                    if let Ok(enabled) = parts[3].parse::<bool>() {
-                        self.parallel_options.ybwc_enabled = enabled;
-                        self.sync_parallel_options();
-                        output.push(format!(
-                            "info string {} YBWC",
-                            if enabled { "Enabled" } else { "Disabled" }
-                        ));
-                    }
-                }
-                "YBWCMinDepth" => {
-                    if let Ok(depth) = parts[3].parse::<u8>() {
-                        self.parallel_options.ybwc_min_depth = depth;
-                        self.sync_parallel_options();
-                        output.push(format!("info string Set YBWCMinDepth to {}", depth));
-                    }
-                }
-                "YBWCMinBranch" => {
-                    if let Ok(branch) = parts[3].parse::<usize>() {
-                        self.parallel_options.ybwc_min_branch = branch.max(1);
-                        self.sync_parallel_options();
-                        output.push(format!(
-                            "info string Set YBWCMinBranch to {}",
-                            self.parallel_options.ybwc_min_branch
-                        ));
-                    }
-                }
-                "YBWCMaxSiblings" => {
-                    if let Ok(max) = parts[3].parse::<usize>() {
-                        self.parallel_options.ybwc_max_siblings = max.max(1);
-                        self.sync_parallel_options();
-                        output.push(format!(
-                            "info string Set YBWCMaxSiblings to {}",
-                            self.parallel_options.ybwc_max_siblings
-                        ));
-                    }
-                }
-                "YBWCScalingShallow" => {
-                    if let Ok(divisor) = parts[3].parse::<usize>() {
-                        self.parallel_options.ybwc_shallow_divisor = divisor.max(1);
-                        self.sync_parallel_options();
-                        output.push(format!(
-                            "info string Set YBWCScalingShallow to {}",
-                            self.parallel_options.ybwc_shallow_divisor
-                        ));
-                    }
-                }
-                "YBWCScalingMid" => {
-                    if let Ok(divisor) = parts[3].parse::<usize>() {
-                        self.parallel_options.ybwc_mid_divisor = divisor.max(1);
-                        self.sync_parallel_options();
-                        output.push(format!(
-                            "info string Set YBWCScalingMid to {}",
-                            self.parallel_options.ybwc_mid_divisor
-                        ));
-                    }
-                }
-                "YBWCScalingDeep" => {
-                    if let Ok(divisor) = parts[3].parse::<usize>() {
-                        self.parallel_options.ybwc_deep_divisor = divisor.max(1);
-                        self.sync_parallel_options();
-                        output.push(format!(
-                            "info string Set YBWCScalingDeep to {}",
-                            self.parallel_options.ybwc_deep_divisor
-                        ));
-                    }
-                }
-                _ => {
-                    output.push(format!("info string Unknown option: {}", parts[1]));
-                }
-            }
-        }
-        output
+    /// Handle `setoption`; names, validation and messages come from the
+    /// option registry in [`usi::options`].
+    pub fn handle_setoption(&mut self, parts: &[&str]) -> Vec<String> {
+        usi::options::apply_setoption(self, parts)
     }
 
     pub fn handle_usinewgame(&mut self) -> Vec<String> {
         if let Ok(mut search_engine_guard) = self.search_engine.lock() {
             search_engine_guard.clear();
         }
+        self.losing_streak = 0;
         Vec::new()
     }
 
@@ -1526,13 +1336,6 @@ impl ShogiEngine {
         output
     }
 
-    pub fn handle_ponderhit(&mut self) -> Vec<String> {
-        self.pondering = false;
-        // The engine should switch from pondering to normal search.
-        // For now, we just print an info string.
-        vec!["info string ponderhit received".to_string()]
-    }
-
     pub fn handle_gameover(&self, parts: &[&str]) -> Vec<String> {
         if let Some(result) = parts.get(0) {
             vec![format!("info string game over: {}", result)]
@@ -1541,21 +1344,88 @@ impl ShogiEngine {
         }
     }
 
+    /// Explain the evaluation of the current position: `eval` prints a
+    /// table of its terms, `eval json` the same breakdown as JSON
+    pub fn handle_eval(&mut self, parts: &[&str]) -> Vec<String> {
+        let explanation = match self.explain_evaluation() {
+            Ok(explanation) => explanation,
+            Err(err) => return vec![format!("info string error {}", err)],
+        };
+        match parts.first() {
+            None => explanation.to_table().lines().map(str::to_string).collect(),
+            Some(&"json") => match explanation.to_json() {
+                Ok(json) => json.lines().map(str::to_string).collect(),
+                Err(err) => vec![format!("info string error {}", err)],
+            },
+            Some(other) => vec![format!("info string unknown eval format {} (use: json)", other)],
+        }
+    }
+
+    /// Term-by-term breakdown of the current position's evaluation, as the
+    /// search's evaluator sees it
+    pub fn explain_evaluation(&self) -> Result<EvalExplanation, String> {
+        let mut guard =
+            self.search_engine.lock().map_err(|_| "Failed to acquire search engine lock")?;
+        Ok(guard.get_evaluator_mut().explain(&self.board, self.current_player, &self.captured_pieces))
+    }
+
+    /// Load the NNUE network in `path`; an empty path unloads it
+    pub fn load_eval_file(&mut self, path: &str) -> Result<(), String> {
+        let mut guard =
+            self.search_engine.lock().map_err(|_| "Failed to acquire search engine lock")?;
+        let evaluator = guard.get_evaluator_mut();
+        if path.is_empty() {
+            evaluator.set_nnue_network(None);
+            Ok(())
+        } else {
+            evaluator.load_nnue(path).map_err(|err| err.to_string())
+        }
+    }
+
+    /// Score king safety with a king-danger file written by the tuner, or
+    /// with the built-in model when `path` is empty
+    pub fn load_king_danger_file(&mut self, path: &str) -> Result<(), String> {
+        let mut guard =
+            self.search_engine.lock().map_err(|_| "Failed to acquire search engine lock")?;
+        let evaluator = guard.get_evaluator_mut();
+        if path.is_empty() {
+            evaluator.set_king_danger_config(Default::default());
+            Ok(())
+        } else {
+            evaluator.load_king_danger(path)
+        }
+    }
+
+    /// Evaluate with the loaded NNUE network, or with the classical
+    /// evaluator when `enabled` is false
+    pub fn set_use_nnue(&mut self, enabled: bool) -> Result<(), String> {
+        let mut guard =
+            self.search_engine.lock().map_err(|_| "Failed to acquire search engine lock")?;
+        guard.get_evaluator_mut().set_use_nnue(enabled);
+        Ok(())
+    }
+
+    /// Check if searches evaluate with an NNUE network
+    pub fn is_using_nnue(&self) -> bool {
+        self.search_engine.lock().map(|guard| guard.get_evaluator().is_using_nnue()).unwrap_or(false)
+    }
+
     // Tablebase methods
     pub fn enable_tablebase(&mut self) {
-        self.tablebase.enable();
+        self.tablebase().enable();
     }
 
     pub fn disable_tablebase(&mut self) {
-        self.tablebase.disable();
+        self.tablebase().disable();
     }
 
     pub fn is_tablebase_enabled(&self) -> bool {
-        self.tablebase.is_enabled()
+        self.tablebase().is_enabled()
     }
 
     pub fn get_tablebase_stats(&self) -> String {
-        let stats = self.tablebase.get_stats();
+        let tablebase = self.tablebase();
+        let stats = tablebase.get_stats();
         format!(
             "Tablebase Stats: Probes={}, Cache Hits={}, Solver Hits={}, Misses={}, Cache Hit \
              Rate={:.2}%, Solver Hit Rate={:.2}%, Overall Hit Rate={:.2}%, Avg Probe Time={:.2}ms",
@@ -1571,7 +1441,7 @@ impl ShogiEngine {
     }
 
     pub fn reset_tablebase_stats(&mut self) {
-        self.tablebase.reset_stats();
+        self.tablebase().reset_stats();
     }
 }
 
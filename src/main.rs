@@ -1,4 +1,4 @@
-use shogi_engine::usi::run_usi_loop;
+use shogi_engine::usi::{handle_bench, run_usi_loop_with_config};
 use std::{
     any::Any,
     backtrace::Backtrace,
@@ -58,5 +58,31 @@ fn install_signal_handlers() {
 fn main() {
     install_panic_hook();
     install_signal_handlers();
-    run_with_panic_logging(|| run_usi_loop());
+
+    // `usi-engine bench [depth] [threads] [hash]` runs the benchmark and exits
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("bench") {
+        run_with_panic_logging(move || {
+            let bench_args: Vec<&str> = args[1..].iter().map(String::as_str).collect();
+            for line in handle_bench(&bench_args) {
+                println!("{}", line);
+            }
+        });
+        return;
+    }
+
+    // `usi-engine --config <path|preset>` loads an engine profile or an
+    // `EngineConfig` file or preset before the USI loop
+    let config_path = match args.iter().position(|arg| arg == "--config") {
+        Some(i) => match args.get(i + 1) {
+            Some(path) => Some(path.clone()),
+            None => {
+                eprintln!("--config needs a path or a preset name");
+                process::exit(2);
+            }
+        },
+        None => args.iter().find_map(|arg| arg.strip_prefix("--config=").map(str::to_string)),
+    };
+
+    run_with_panic_logging(move || run_usi_loop_with_config(config_path.as_deref()));
 }
@@ -142,7 +142,7 @@ impl MoveGenerator {
         let legal_moves: Vec<Move> = pseudo_legal_moves
             .into_iter()
             .filter(|m| {
-                let mut temp_board = board.clone();
+                let mut temp_board = board.clone_without_nnue();
                 let mut temp_captured = captured_pieces.clone();
 
                 if let Some(captured) = temp_board.make_move(m) {
@@ -215,7 +215,7 @@ impl MoveGenerator {
         pseudo_legal_moves
             .into_iter()
             .filter(|m| {
-                let mut temp_board = board.clone();
+                let mut temp_board = board.clone_without_nnue();
                 let mut temp_captured = captured_pieces.clone();
                 if let Some(captured) = temp_board.make_move(m) {
                     temp_captured.add_piece(captured.piece_type, m.player);
@@ -747,15 +747,25 @@ impl MoveGenerator {
         player: Player,
         captured_pieces: &CapturedPieces,
     ) -> Vec<Move> {
-        let mut check_moves = Vec::new();
-        let opponent = player.opposite();
-
         // Generate all pseudo-legal moves
         let all_moves = self.generate_pseudo_legal_moves(board, player, captured_pieces);
+        self.filter_checks(board, player, captured_pieces, all_moves)
+    }
+
+    /// Keep only the moves that give check to the opponent, marking them
+    fn filter_checks(
+        &self,
+        board: &BitboardBoard,
+        player: Player,
+        captured_pieces: &CapturedPieces,
+        candidates: Vec<Move>,
+    ) -> Vec<Move> {
+        let mut check_moves = Vec::new();
+        let opponent = player.opposite();
 
-        for mut move_ in all_moves {
+        for mut move_ in candidates {
             // Make the move on a temporary board
-            let mut temp_board = board.clone();
+            let mut temp_board = board.clone_without_nnue();
             let mut temp_captured = captured_pieces.clone();
 
             if let Some(captured) = temp_board.make_move(&move_) {
@@ -830,7 +840,7 @@ impl MoveGenerator {
             if let Some(piece) = board.get_piece(from) {
                 // Check if this piece can attack opponent pieces from the new position
                 let opponent = player.opposite();
-                let mut temp_board = board.clone();
+                let mut temp_board = board.clone_without_nnue();
                 temp_board.remove_piece(from);
                 temp_board.place_piece(piece, move_.to);
 
@@ -860,6 +870,30 @@ impl MoveGenerator {
         board: &BitboardBoard,
         player: Player,
         captured_pieces: &CapturedPieces,
+    ) -> Vec<Move> {
+        self.generate_quiescence_moves_internal(board, player, captured_pieces, true)
+    }
+
+    /// Generate quiescence moves for pieces already on the board
+    ///
+    /// Same as [`Self::generate_quiescence_moves`] but without checking drops,
+    /// which drop-aware quiescence selects separately through
+    /// [`Self::generate_quiescence_drops`] under its own budget.
+    pub fn generate_quiescence_board_moves(
+        &self,
+        board: &BitboardBoard,
+        player: Player,
+        captured_pieces: &CapturedPieces,
+    ) -> Vec<Move> {
+        self.generate_quiescence_moves_internal(board, player, captured_pieces, false)
+    }
+
+    fn generate_quiescence_moves_internal(
+        &self,
+        board: &BitboardBoard,
+        player: Player,
+        captured_pieces: &CapturedPieces,
+        include_drop_checks: bool,
     ) -> Vec<Move> {
         // Pre-allocate with estimated capacity to reduce allocations
         let mut moves = Vec::with_capacity(32);
@@ -869,7 +903,12 @@ impl MoveGenerator {
         moves.extend(captures);
 
         // 2. Generate checks - high priority for tactical positions
-        let checks = self.generate_checks(board, player, captured_pieces);
+        let checks = if include_drop_checks {
+            self.generate_checks(board, player, captured_pieces)
+        } else {
+            let piece_moves = self.generate_all_piece_moves(board, player);
+            self.filter_checks(board, player, captured_pieces, piece_moves)
+        };
         moves.extend(checks);
 
         // 3. Generate promotions - important for endgame tactics
@@ -888,6 +927,79 @@ impl MoveGenerator {
         moves
     }
 
+    /// Generate drops worth searching in quiescence
+    ///
+    /// Returns drops that give check (when `include_checks`) and drops that
+    /// attack an undefended rook, bishop, dragon or horse (when
+    /// `include_threats`). Checking drops are marked with `gives_check`. No SEE
+    /// filtering is done here; the caller decides which drops fit its budget.
+    pub fn generate_quiescence_drops(
+        &self,
+        board: &BitboardBoard,
+        player: Player,
+        captured_pieces: &CapturedPieces,
+        include_checks: bool,
+        include_threats: bool,
+    ) -> Vec<Move> {
+        let opponent = player.opposite();
+        let king_pos = if include_checks { board.find_king_position(opponent) } else { None };
+
+        // Majors the opponent does not defend
+        let targets: Vec<Position> = if include_threats {
+            board
+                .iter_pieces()
+                .filter(|(pos, piece)| {
+                    piece.player == opponent
+                        && matches!(
+                            piece.piece_type,
+                            PieceType::Rook
+                                | PieceType::Bishop
+                                | PieceType::PromotedRook
+                                | PieceType::PromotedBishop
+                        )
+                        && !board.is_square_attacked_by(*pos, opponent)
+                })
+                .map(|(pos, _)| pos)
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        if king_pos.is_none() && targets.is_empty() {
+            return Vec::new();
+        }
+
+        let mut drops = Vec::new();
+        for mut drop in self.generate_drop_moves(board, player, captured_pieces) {
+            let may_check =
+                king_pos.is_some_and(|king| drop_may_reach(drop.piece_type, drop.to, king));
+            let may_threaten =
+                targets.iter().any(|&target| drop_may_reach(drop.piece_type, drop.to, target));
+            if !may_check && !may_threaten {
+                continue;
+            }
+
+            // Generate the dropped piece's moves from its square to see what it
+            // really attacks; the geometric test ignores blockers and direction
+            let piece = Piece::new(drop.piece_type, player);
+            let attacked: Vec<Position> = self
+                .generate_moves_for_single_piece(board, &piece, drop.to)
+                .into_iter()
+                .map(|m| m.to)
+                .collect();
+
+            let gives_check = may_check && king_pos.is_some_and(|king| attacked.contains(&king));
+            let threatens =
+                !gives_check && may_threaten && targets.iter().any(|t| attacked.contains(t));
+
+            if gives_check || threatens {
+                drop.gives_check = gives_check;
+                drops.push(drop);
+            }
+        }
+        drops
+    }
+
     /// Optimized deduplication and sorting for quiescence moves
     fn deduplicate_and_sort_quiescence_moves(&self, moves: &mut Vec<Move>) {
         if moves.is_empty() {
@@ -1291,6 +1403,20 @@ fn is_legal_drop_location(
     }
 }
 
+/// Cheap geometric pre-filter: could a piece dropped on `from` attack
+/// `target` on an empty board, in either direction of play?
+fn drop_may_reach(piece_type: PieceType, from: Position, target: Position) -> bool {
+    let dr = (from.row as i8 - target.row as i8).abs();
+    let dc = (from.col as i8 - target.col as i8).abs();
+    match piece_type {
+        PieceType::Rook => dr == 0 || dc == 0,
+        PieceType::Bishop => dr == dc,
+        PieceType::Lance => dc == 0,
+        PieceType::Knight => dr == 2 && dc == 1,
+        _ => dr <= 1 && dc <= 1,
+    }
+}
+
 /// Check if dropping a pawn at the given position gives immediate checkmate
 /// (Uchifuzume) This is illegal in Shogi - you cannot drop a pawn to deliver
 /// checkmate
@@ -1320,7 +1446,7 @@ fn is_pawn_drop_mate(board: &BitboardBoard, drop_pos: Position, player: Player)
     // Now check if it's actually checkmate (king has no escape)
     // This requires simulating the pawn drop and checking if the king has any legal
     // moves
-    let mut temp_board = board.clone();
+    let mut temp_board = board.clone_without_nnue();
     temp_board.place_piece(Piece::new(PieceType::Pawn, player), drop_pos);
 
     // Check if opponent king has any legal moves or if check can be blocked
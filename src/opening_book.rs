@@ -1,3 +1,5 @@
+use crate::bitboards::BitboardBoard;
+use crate::evaluation::opening_strategy::{classify_opening, OpeningClassification, OpeningFamily};
 use crate::types::core::{Move, PieceType, Player, Position};
 use lru::LruCache;
 use serde::{Deserialize, Serialize};
@@ -485,6 +487,14 @@ impl PositionEntry {
         self.moves.push(book_move);
     }
 
+    /// Classify the opening strategy of this position from its FEN
+    ///
+    /// Returns `None` when the FEN cannot be parsed.
+    pub fn classify_strategy(&self) -> Option<OpeningClassification> {
+        let (board, _, captured) = BitboardBoard::from_fen(&self.fen).ok()?;
+        Some(classify_opening(&board, &captured))
+    }
+
     /// Get the best move by weight and evaluation
     pub fn get_best_move(&self) -> Option<&BookMove> {
         self.moves.iter().max_by(|a, b| {
@@ -555,14 +565,16 @@ impl PositionEntry {
 
     /// Get a random move weighted by move weights
     pub fn get_random_move(&self) -> Option<&BookMove> {
-        use rand::Rng;
+        self.get_random_move_with(&mut rand::thread_rng())
+    }
 
+    /// Get a move weighted by move weights, drawn from `rng`
+    pub fn get_random_move_with<R: rand::Rng>(&self, rng: &mut R) -> Option<&BookMove> {
         let total_weight: u32 = self.moves.iter().map(|m| m.weight).sum();
         if total_weight == 0 || self.moves.is_empty() {
             return None;
         }
 
-        let mut rng = rand::thread_rng();
         let mut random_value = rng.gen_range(0..total_weight);
 
         for book_move in &self.moves {
@@ -823,12 +835,17 @@ impl OpeningBook {
 
     /// Get a random move for a position with weighted random selection
     pub fn get_random_move(&mut self, fen: &str) -> Option<Move> {
+        self.get_random_move_with(fen, &mut rand::thread_rng())
+    }
+
+    /// Get a weighted random move for a position, drawn from `rng`
+    pub fn get_random_move_with<R: rand::Rng>(&mut self, fen: &str, rng: &mut R) -> Option<Move> {
         let hash = self.hash_fen(fen);
         let player = Self::determine_player_from_fen(fen);
 
         // First check cache
         if let Some(entry) = self.position_cache.get(&hash) {
-            if let Some(book_move) = entry.get_random_move() {
+            if let Some(book_move) = entry.get_random_move_with(rng) {
                 return Some(book_move.to_engine_move(player));
             }
         }
@@ -837,7 +854,7 @@ impl OpeningBook {
         if let Some(entry) = self.positions.get(&hash) {
             // Add to cache for future access
             self.position_cache.put(hash, entry.clone());
-            if let Some(book_move) = entry.get_random_move() {
+            if let Some(book_move) = entry.get_random_move_with(rng) {
                 return Some(book_move.to_engine_move(player));
             }
         }
@@ -848,7 +865,7 @@ impl OpeningBook {
                 if let Some(entry) = self.positions.get(&hash) {
                     // Add to cache for future access
                     self.position_cache.put(hash, entry.clone());
-                    if let Some(book_move) = entry.get_random_move() {
+                    if let Some(book_move) = entry.get_random_move_with(rng) {
                         return Some(book_move.to_engine_move(player));
                     }
                 }
@@ -1008,6 +1025,26 @@ impl OpeningBook {
         &self.metadata
     }
 
+    /// Count book positions by classified opening family
+    ///
+    /// Covers both eagerly and lazily loaded positions; entries whose FEN
+    /// cannot be parsed are skipped.
+    pub fn strategy_distribution(&self) -> HashMap<OpeningFamily, usize> {
+        let mut distribution = HashMap::new();
+        let fens = self
+            .positions
+            .values()
+            .map(|entry| entry.fen.as_str())
+            .chain(self.lazy_positions.values().map(|entry| entry.fen.as_str()));
+        for fen in fens {
+            if let Ok((board, _, captured)) = BitboardBoard::from_fen(fen) {
+                let family = classify_opening(&board, &captured).family;
+                *distribution.entry(family).or_insert(0) += 1;
+            }
+        }
+        distribution
+    }
+
     /// Get cache statistics
     pub fn get_cache_stats(&self) -> (usize, usize) {
         (self.position_cache.len(), self.position_cache.cap().get())
@@ -1416,10 +1453,15 @@ impl OpeningBook {
             return None;
         }
 
+        // Untagged moves fall back to the strategy recognised on the board.
         let template = book_move
             .opening_name
             .as_deref()
             .and_then(templates::find_template_for_opening)
+            .or_else(|| {
+                let (board, _, captured) = BitboardBoard::from_fen(fen).ok()?;
+                Some(classify_opening(&board, &captured).family.template())
+            })
             .unwrap_or_else(templates::default_template);
 
         let is_rook_swing = Self::is_rook_swing(book_move);
@@ -1555,14 +1597,16 @@ impl OpeningBook {
         hash
     }
 
-    /// Determine player from FEN string
     /// Determine player to move from FEN string
+    ///
+    /// Handicap positions have White (上手) to move, so the side-to-move
+    /// field must be read rather than assuming Black.
     pub fn determine_player_from_fen(fen: &str) -> Player {
-        // FEN format: "board position active_player captured_pieces move_number"
-        // The active player is the 4th field (index 3)
+        // SFEN format: "board active_player captured_pieces move_number"
+        // The active player is the 2nd field (index 1)
         let parts: Vec<&str> = fen.split_whitespace().collect();
-        if parts.len() >= 4 {
-            match parts[3] {
+        if parts.len() >= 2 {
+            match parts[1] {
                 "b" | "B" => Player::Black,
                 "w" | "W" => Player::White,
                 _ => Player::Black, // Default to Black if unclear
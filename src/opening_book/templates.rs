@@ -42,7 +42,16 @@ const STATIC_ROOK_TEMPLATE: OpeningTemplate = OpeningTemplate {
     canonical_name: "Static Rook",
     description: "Classical static-rook shells (Yagura, Anaguma, Ibisha). Requires 7g7f and 2g2f \
          pawn pushes before king/rook maneuvers.",
-    aliases: &["Yagura", "Anaguma", "Ibisha", "Central Pawn", "Side Pawn"],
+    aliases: &[
+        "Yagura",
+        "Anaguma",
+        "Ibisha",
+        "Central Pawn",
+        "Side Pawn",
+        "Aigakari",
+        "Kakugawari",
+        "Yokofudori",
+    ],
     king_move_min_ply: 6,  // after both pawn pushes + one consolidation ply
     rook_swing_min_ply: 5, // after 7g7f + 2g2f have time to land
     priority_moves: &["7g7f", "2g2f"],
@@ -53,7 +62,18 @@ const RANGING_ROOK_TEMPLATE: OpeningTemplate = OpeningTemplate {
     description:
         "Rook-ranging systems (Fourth File Rook, Quick Attack, Bishop Exchange, Ai Funibisha). \
          Early rook swings are expected once the 7g pawn advances.",
-    aliases: &["Ranging Rook", "Quick Attack", "Bishop Exchange", "Ai Funibisha"],
+    aliases: &[
+        "Ranging Rook",
+        "Quick Attack",
+        "Bishop Exchange",
+        "Ai Funibisha",
+        "Nakabisha",
+        "Gokigen Nakabisha",
+        "Shikenbisha",
+        "Sankenbisha",
+        "Mukaibisha",
+        "Ai-furibisha",
+    ],
     king_move_min_ply: 6,
     rook_swing_min_ply: 2, // rook can range immediately as part of the plan
     priority_moves: &["7g7f"],
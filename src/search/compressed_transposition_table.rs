@@ -275,6 +275,21 @@ impl CompressedTranspositionTable {
         self.stats = CompressedTranspositionStats::default();
     }
 
+    /// Decode every retained entry, oldest first within each segment.
+    pub fn entries(&self) -> Vec<TranspositionEntry> {
+        self.segments
+            .iter()
+            .flat_map(|segment| segment.records.iter())
+            .map(|record| {
+                let mut entry = decode_entry(&record.payload, record.hash_key, record.age);
+                entry.source = record.source;
+                entry.flag = record.flag;
+                entry.best_move = record.best_move.clone();
+                entry
+            })
+            .collect()
+    }
+
     fn segment_index(&self, hash_key: u64) -> usize {
         (hash_key as usize) & self.segment_mask
     }
@@ -7,9 +7,12 @@
 use crate::search::compressed_transposition_table::{
     CompressedTranspositionStats, CompressedTranspositionTable, CompressedTranspositionTableConfig,
 };
+use crate::search::error_handling::TranspositionResult;
 use crate::search::thread_safe_table::ThreadSafeTranspositionTable;
 use crate::search::transposition_config::TranspositionConfig;
+use crate::search::tt_persistence::{read_tt_file, write_tt_file, TtFileKind, TtFileRecord};
 use crate::types::TranspositionEntry;
+use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
 use std::sync::{Arc, Mutex};
 use std::thread;
@@ -257,6 +260,45 @@ impl HierarchicalTranspositionTable {
         self.l2.lock().unwrap().stats().clone()
     }
 
+    /// Save both tiers to a hash file.
+    ///
+    /// L1 entries are written with tier 1 and L2 entries with tier 2 so that
+    /// loading restores each entry to the tier it came from. Returns the
+    /// number of entries written.
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> TranspositionResult<usize> {
+        let mut records = self.l1.persisted_records(1);
+        records.extend(
+            self.l2
+                .lock()
+                .unwrap()
+                .entries()
+                .into_iter()
+                .map(|entry| TtFileRecord { entry, tier: 2 }),
+        );
+        write_tt_file(path, TtFileKind::Hierarchical, &records)?;
+        Ok(records.len())
+    }
+
+    /// Load a hash file into the table.
+    ///
+    /// Tier 2 records go to L2; all other records (including files written
+    /// by the single-level table) go to L1. Files whose Zobrist seed or
+    /// entry layout differ from the running engine are refused. Returns the
+    /// number of entries read.
+    pub fn load_from_file<P: AsRef<Path>>(&mut self, path: P) -> TranspositionResult<usize> {
+        let (_, records) = read_tt_file(path)?;
+        let count = records.len();
+        let mut l2 = self.l2.lock().unwrap();
+        for record in records {
+            if record.tier == 2 {
+                l2.store(&record.entry);
+            } else {
+                self.l1.store(record.entry);
+            }
+        }
+        Ok(count)
+    }
+
     /// L2 reference for testing/diagnostics.
     #[cfg(test)]
     pub(crate) fn l2_mut(&self) -> std::sync::MutexGuard<'_, CompressedTranspositionTable> {
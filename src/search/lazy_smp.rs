@@ -0,0 +1,351 @@
+//! Lazy SMP helpers for the parallel search engine.
+//!
+//! Lazy SMP is the alternative to YBWC root splitting: every helper thread
+//! runs its own iterative deepening over the shared transposition table while
+//! the main thread runs the regular `IterativeDeepening` loop. Helpers start
+//! at staggered depths and skip some iterations so that they diverge from the
+//! main thread and fill the TT with entries the main thread can reuse. Each
+//! helper owns a private `SearchEngine`, so history, killer and counter-move
+//! tables are per thread.
+//!
+//! When the main thread finishes, it stops the helpers and picks the final
+//! move by depth/score voting over all threads that completed an iteration.
+
+use crate::bitboards::BitboardBoard;
+use crate::search::search_engine::{SearchEngine, MAX_SCORE, MIN_SCORE};
+use crate::search::search_reporter::{ScoreBound, SearchInfo};
+use crate::search::ThreadSafeTranspositionTable;
+use crate::types::board::CapturedPieces;
+use crate::types::core::{Move, Player};
+use std::collections::HashMap;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    mpsc, Arc, RwLock,
+};
+use std::time::Instant;
+
+/// Skip sizes per helper slot (helper `i` uses slot `(i - 1) % 20`).
+const SKIP_SIZE: [u8; 20] = [1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 3, 3, 4, 4, 4, 4, 4, 4, 4, 4];
+
+/// Skip phases per helper slot; together with `SKIP_SIZE` this spreads the
+/// helpers across different iteration depths.
+const SKIP_PHASE: [u8; 20] = [0, 1, 0, 1, 2, 3, 0, 1, 2, 3, 4, 5, 0, 1, 2, 3, 4, 5, 6, 7];
+
+/// Scores at or beyond this magnitude are treated as proven wins/losses when
+/// voting.
+const PROVEN_SCORE_THRESHOLD: i32 = 50_000;
+
+/// Bonus added to every vote so that the lowest-scoring thread still counts.
+const VOTE_SCORE_OFFSET: i64 = 14;
+
+/// Size of a helper's private transposition table. Helpers search into the
+/// shared table, so the private one only has to exist; sizing it like the
+/// shared table would allocate and free the full hash on every `go`.
+const HELPER_PRIVATE_HASH_MB: usize = 1;
+
+/// First iteration depth for a helper thread. Odd helpers start one ply
+/// deeper than the main thread.
+pub fn helper_start_depth(thread_id: usize) -> u8 {
+    1 + (thread_id % 2) as u8
+}
+
+/// Whether helper `thread_id` should skip iteration `depth`.
+///
+/// Thread 0 is the main thread and never skips.
+pub fn helper_should_skip_depth(thread_id: usize, depth: u8) -> bool {
+    if thread_id == 0 {
+        return false;
+    }
+    let slot = (thread_id - 1) % SKIP_SIZE.len();
+    let size = SKIP_SIZE[slot] as u32;
+    let phase = SKIP_PHASE[slot] as u32;
+    ((depth as u32 + phase) / size) % 2 != 0
+}
+
+/// Last completed iteration of a single Lazy SMP thread.
+#[derive(Debug, Clone)]
+pub struct LazySmpThreadResult {
+    /// Thread index (0 = main thread).
+    pub thread_id: usize,
+    /// Best move found at `depth`.
+    pub best_move: Move,
+    /// Score of `best_move` from the side to move's perspective.
+    pub score: i32,
+    /// Deepest fully completed iteration.
+    pub depth: u8,
+    /// Nodes searched by this thread.
+    pub nodes: u64,
+    /// Entries this thread wrote to the shared transposition table.
+    pub tt_writes: u64,
+}
+
+impl LazySmpThreadResult {
+    /// Report of this result as the final iteration, used when the vote picks
+    /// a move other than the main thread's so that the reported score and
+    /// depth belong to the move played. Its nodes are already counted in the
+    /// main thread's reports, so none are added here.
+    pub fn search_info(&self, pv: String, time_ms: u64) -> SearchInfo {
+        SearchInfo {
+            depth: self.depth,
+            seldepth: self.depth,
+            multipv: Some(1),
+            score: self.score,
+            bound: ScoreBound::Exact,
+            time_ms,
+            nodes: 0,
+            nps: 0,
+            pv,
+        }
+    }
+}
+
+/// Select the final move by depth/score voting.
+///
+/// Every thread votes for its best move with weight
+/// `(score - min_score + 14) * depth`. The main thread (or the first entry)
+/// is the default; another thread replaces it when its move collects more
+/// votes, when it proves a faster win, or when votes tie and it searched
+/// deeper.
+pub fn select_voted_result(results: &[LazySmpThreadResult]) -> Option<&LazySmpThreadResult> {
+    let first = results.first()?;
+    let min_score = results.iter().map(|r| r.score).min().unwrap_or(0) as i64;
+
+    let mut votes: HashMap<String, i64> = HashMap::new();
+    for result in results {
+        let weight = (result.score as i64 - min_score + VOTE_SCORE_OFFSET) * result.depth as i64;
+        *votes.entry(result.best_move.to_usi_string()).or_insert(0) += weight;
+    }
+    let votes_for = |r: &LazySmpThreadResult| -> i64 {
+        votes.get(&r.best_move.to_usi_string()).copied().unwrap_or(0)
+    };
+
+    let mut best = results.iter().find(|r| r.thread_id == 0).unwrap_or(first);
+    for candidate in results {
+        if std::ptr::eq(candidate, best) {
+            continue;
+        }
+        let best_proven = best.score.abs() >= PROVEN_SCORE_THRESHOLD;
+        let candidate_proven = candidate.score.abs() >= PROVEN_SCORE_THRESHOLD;
+        let replace = if best_proven || candidate_proven {
+            // Prefer the most favourable proven score (shortest win or longest loss)
+            candidate.score > best.score
+        } else {
+            let (cv, bv) = (votes_for(candidate), votes_for(best));
+            cv > bv || (cv == bv && candidate.depth > best.depth)
+        };
+        if replace {
+            best = candidate;
+        }
+    }
+    Some(best)
+}
+
+/// Handle to the helper threads of a running Lazy SMP search.
+///
+/// Dropping the session stops the helpers without waiting for them.
+pub struct LazySmpSession {
+    stop: Arc<AtomicBool>,
+    results: mpsc::Receiver<Option<LazySmpThreadResult>>,
+    helper_count: usize,
+}
+
+impl LazySmpSession {
+    pub(crate) fn new(
+        stop: Arc<AtomicBool>,
+        results: mpsc::Receiver<Option<LazySmpThreadResult>>,
+        helper_count: usize,
+    ) -> Self {
+        Self { stop, results, helper_count }
+    }
+
+    /// Number of helper threads started for this search.
+    pub fn helper_count(&self) -> usize {
+        self.helper_count
+    }
+
+    /// Signal all helpers to stop at their next node check.
+    pub fn stop(&self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+
+    /// Stop the helpers, wait for them and vote on the final move.
+    ///
+    /// `main_result` is the main thread's best move, score and completed
+    /// depth. Returns the voted result, `None` only when no thread completed
+    /// an iteration, together with every thread's last result.
+    pub fn finish(
+        self,
+        main_result: Option<(Move, i32, u8)>,
+    ) -> (Option<LazySmpThreadResult>, Vec<LazySmpThreadResult>) {
+        self.stop();
+        let mut all = Vec::with_capacity(self.helper_count + 1);
+        if let Some((best_move, score, depth)) = main_result {
+            all.push(LazySmpThreadResult {
+                thread_id: 0,
+                best_move,
+                score,
+                depth,
+                nodes: 0,
+                tt_writes: 0,
+            });
+        }
+        // Each helper sends exactly once; a panicked helper drops its sender.
+        while let Ok(result) = self.results.recv() {
+            if let Some(result) = result {
+                all.push(result);
+            }
+        }
+        crate::utils::telemetry::debug_log(&format!(
+            "LAZY_SMP: {} helpers finished, {} thread results, depths {:?}",
+            self.helper_count,
+            all.len(),
+            all.iter().map(|r| r.depth).collect::<Vec<_>>()
+        ));
+        let voted = select_voted_result(&all).cloned();
+        (voted, all)
+    }
+}
+
+impl Drop for LazySmpSession {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Per-helper search parameters.
+pub(crate) struct LazySmpHelperTask {
+    pub thread_id: usize,
+    pub board: BitboardBoard,
+    pub captured_pieces: CapturedPieces,
+    pub player: Player,
+    pub max_depth: u8,
+    pub time_limit_ms: u32,
+    pub stop: Arc<AtomicBool>,
+    /// Engine-level stop flag, checked between iterations
+    pub engine_stop: Option<Arc<AtomicBool>>,
+    pub shared_tt: Arc<RwLock<ThreadSafeTranspositionTable>>,
+}
+
+/// Iterative deepening loop of a single helper thread.
+pub(crate) fn run_helper(task: LazySmpHelperTask) -> Option<LazySmpThreadResult> {
+    let LazySmpHelperTask {
+        thread_id,
+        board,
+        captured_pieces,
+        player,
+        max_depth,
+        time_limit_ms,
+        stop,
+        engine_stop,
+        shared_tt,
+    } = task;
+    let stopped = || {
+        stop.load(Ordering::Relaxed)
+            || engine_stop.as_ref().is_some_and(|flag| flag.load(Ordering::Relaxed))
+    };
+
+    let mut engine = SearchEngine::new(Some(stop.clone()), HELPER_PRIVATE_HASH_MB);
+    engine.set_shared_transposition_table(shared_tt);
+
+    let start = Instant::now();
+    let mut last: Option<LazySmpThreadResult> = None;
+    let mut depth = helper_start_depth(thread_id);
+    while depth <= max_depth && !stopped() {
+        if helper_should_skip_depth(thread_id, depth) {
+            depth += 1;
+            continue;
+        }
+        let elapsed = start.elapsed().as_millis() as u32;
+        if elapsed >= time_limit_ms {
+            break;
+        }
+        let mut work_board = board.clone();
+        let result = engine.search_at_depth(
+            &mut work_board,
+            &captured_pieces,
+            player,
+            depth,
+            time_limit_ms - elapsed,
+            MIN_SCORE,
+            MAX_SCORE,
+        );
+        engine.flush_tt_buffer();
+        // An iteration interrupted by the stop flag is incomplete; discard it
+        if stopped() {
+            break;
+        }
+        if let Some((best_move, score)) = result {
+            last = Some(LazySmpThreadResult {
+                thread_id,
+                best_move,
+                score,
+                depth,
+                nodes: engine.get_nodes_searched(),
+                tt_writes: engine.shared_tt_store_writes(),
+            });
+            if score.abs() >= PROVEN_SCORE_THRESHOLD {
+                break;
+            }
+        }
+        depth += 1;
+    }
+    last
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::core::{PieceType, Position};
+
+    fn result(thread_id: usize, to_col: u8, score: i32, depth: u8) -> LazySmpThreadResult {
+        LazySmpThreadResult {
+            thread_id,
+            best_move: Move::new_move(
+                Position::new(6, to_col),
+                Position::new(5, to_col),
+                PieceType::Pawn,
+                Player::Black,
+                false,
+            ),
+            score,
+            depth,
+            nodes: 0,
+            tt_writes: 0,
+        }
+    }
+
+    #[test]
+    fn test_main_thread_never_skips() {
+        assert!((1..64).all(|d| !helper_should_skip_depth(0, d)));
+    }
+
+    #[test]
+    fn test_helpers_are_staggered() {
+        // Helpers 1 and 2 share skip size 1 but opposite phases
+        let h1: Vec<bool> = (1..9).map(|d| helper_should_skip_depth(1, d)).collect();
+        let h2: Vec<bool> = (1..9).map(|d| helper_should_skip_depth(2, d)).collect();
+        assert!(h1.iter().zip(&h2).all(|(a, b)| a != b));
+        assert_eq!(helper_start_depth(1), 2);
+        assert_eq!(helper_start_depth(2), 1);
+    }
+
+    #[test]
+    fn test_voting_prefers_majority_move() {
+        let results = vec![result(0, 0, 40, 8), result(1, 1, 50, 8), result(2, 1, 45, 8)];
+        let best = select_voted_result(&results).unwrap();
+        assert_eq!(best.best_move, results[1].best_move);
+    }
+
+    #[test]
+    fn test_voting_keeps_main_thread_when_alone() {
+        let results = vec![result(0, 3, -20, 6)];
+        assert_eq!(select_voted_result(&results).unwrap().thread_id, 0);
+        assert!(select_voted_result(&[]).is_none());
+    }
+
+    #[test]
+    fn test_voting_prefers_proven_win() {
+        let results = vec![result(0, 0, 120, 10), result(1, 1, 120, 10), result(2, 2, 60_000, 5)];
+        assert_eq!(select_voted_result(&results).unwrap().thread_id, 2);
+    }
+}
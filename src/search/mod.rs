@@ -1,21 +1,32 @@
 pub mod board_trait;
 pub mod iterative_deepening;
+pub mod lazy_smp;
 pub mod null_move;
 pub mod parallel_search;
 pub mod pvs;
 pub mod quiescence;
 pub mod reductions;
+pub mod reproducible;
 pub mod search_engine;
+pub mod search_reporter;
 pub mod shogi_hash;
 pub mod shogi_position_tests;
+pub mod skill;
 pub mod statistics;
 pub mod time_management;
 pub mod transposition_table;
+pub mod tree_recorder;
+pub mod tt_persistence;
 pub mod zobrist;
 pub use parallel_search::{
     ParallelSearchConfig, ParallelSearchEngine, ThreadLocalSearchContext, WorkDistributionStats,
     WorkStealingQueue, WorkUnit,
 };
+pub use search_reporter::{
+    ChannelReporter, NullReporter, ScoreBound, SearchEvent, SearchInfo, SearchReporter, UsiReporter,
+};
+pub use reproducible::ReproducibleSearch;
+pub use skill::SkillLevel;
 pub mod advanced_statistics;
 pub mod cache_management;
 pub mod comprehensive_tests;
@@ -4,7 +4,7 @@
 //! management methods for the move ordering system.
 
 use crate::types::core::Move;
-use std::collections::HashMap;
+use crate::utils::common::DeterministicHashMap;
 
 /// Cache eviction policy for move ordering cache
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
@@ -84,7 +84,7 @@ impl Default for CacheConfig {
 pub struct MoveOrderingCacheManager {
     /// Move ordering result cache
     /// Maps (position_hash, depth) -> cache entry with metadata
-    cache: HashMap<(u64, u8), MoveOrderingCacheEntry>,
+    cache: DeterministicHashMap<(u64, u8), MoveOrderingCacheEntry>,
     /// LRU access counter (incremented on each cache access for LRU tracking)
     lru_access_counter: u64,
 }
@@ -92,7 +92,7 @@ pub struct MoveOrderingCacheManager {
 impl MoveOrderingCacheManager {
     /// Create a new cache manager
     pub fn new() -> Self {
-        Self { cache: HashMap::new(), lru_access_counter: 0 }
+        Self { cache: DeterministicHashMap::default(), lru_access_counter: 0 }
     }
 
     /// Get a cached entry for a key
@@ -307,7 +307,7 @@ impl Default for MoveOrderingCacheManager {
 #[derive(Debug, Clone)]
 pub struct MoveScoreCache {
     /// Main cache: maps move hash -> score
-    cache: HashMap<u64, i32>,
+    cache: DeterministicHashMap<u64, i32>,
     /// Fast cache (L1 cache simulation): small Vec for hot scores
     fast_cache: Vec<(u64, i32)>,
     /// Maximum size for main cache
@@ -320,7 +320,7 @@ impl MoveScoreCache {
     /// Create a new move score cache
     pub fn new(max_size: usize, fast_cache_max_size: usize) -> Self {
         Self {
-            cache: HashMap::new(),
+            cache: DeterministicHashMap::default(),
             fast_cache: Vec::with_capacity(fast_cache_max_size),
             max_size,
             fast_cache_max_size,
@@ -38,7 +38,7 @@ use crate::types::TranspositionFlag;
 use crate::utils::time::TimeSource;
 use std::collections::HashMap;
 use std::fmt;
-use std::ptr;
+use std::sync::{Arc, PoisonError, RwLock};
 
 // Task 1.22: Modularized move ordering - submodules are in the same directory
 mod cache;
@@ -1490,7 +1490,7 @@ pub struct MoveOrdering {
     /// cache module)
     move_score_cache: MoveScoreCache,
     /// Transposition table reference for PV move retrieval
-    transposition_table: *const crate::search::ThreadSafeTranspositionTable,
+    transposition_table: Option<Arc<RwLock<crate::search::ThreadSafeTranspositionTable>>>,
     /// Hash calculator for position hashing
     hash_calculator: crate::search::ShogiHashHandler,
     /// PV ordering manager (Task 6.0: extracted to module)
@@ -2141,7 +2141,7 @@ impl MoveOrdering {
                 config.cache_config.max_cache_size,
                 64, // Fast cache size
             ),
-            transposition_table: ptr::null(),
+            transposition_table: None,
             hash_calculator: crate::search::ShogiHashHandler::new(
                 config.cache_config.max_cache_size,
             ),
@@ -3587,8 +3587,11 @@ impl MoveOrdering {
     // ==================== PV Move Ordering Methods ====================
 
     /// Set the transposition table reference for PV move retrieval
-    pub fn set_transposition_table(&mut self, tt: &crate::search::ThreadSafeTranspositionTable) {
-        self.transposition_table = tt as *const crate::search::ThreadSafeTranspositionTable;
+    pub fn set_transposition_table(
+        &mut self,
+        tt: Arc<RwLock<crate::search::ThreadSafeTranspositionTable>>,
+    ) {
+        self.transposition_table = Some(tt);
     }
 
     /// Score a move that matches the PV move from transposition table
@@ -3609,9 +3612,7 @@ impl MoveOrdering {
         player: Player,
         depth: u8,
     ) -> Option<Move> {
-        if self.transposition_table.is_null() {
-            return None;
-        }
+        let transposition_table = self.transposition_table.as_ref()?;
 
         // Calculate position hash
         let position_hash = self.hash_calculator.get_position_hash(board, player, captured_pieces);
@@ -3629,8 +3630,10 @@ impl MoveOrdering {
         // Query transposition table
         self.stats.tt_lookups += 1;
 
-        // Safe access to transposition table
-        let tt_entry = unsafe { (*self.transposition_table).probe(position_hash, depth) };
+        let tt_entry = transposition_table
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .probe(position_hash, depth);
 
         let pv_move = if let Some(entry) = tt_entry {
             self.stats.tt_hits += 1;
@@ -3675,9 +3678,9 @@ impl MoveOrdering {
         best_move: Move,
         score: i32,
     ) {
-        if self.transposition_table.is_null() {
+        let Some(transposition_table) = self.transposition_table.as_ref() else {
             return;
-        }
+        };
 
         // Calculate position hash
         let position_hash = self.hash_calculator.get_position_hash(board, player, captured_pieces);
@@ -3694,13 +3697,7 @@ impl MoveOrdering {
         };
 
         // Store in transposition table
-        unsafe {
-            if let Some(tt_ref) = self.transposition_table.as_ref() {
-                let tt_mut = tt_ref as *const crate::search::ThreadSafeTranspositionTable
-                    as *mut crate::search::ThreadSafeTranspositionTable;
-                (*tt_mut).store(entry);
-            }
-        }
+        transposition_table.read().unwrap_or_else(PoisonError::into_inner).store(entry);
 
         // Update cache (Task 6.0: use PVOrdering module)
         if !self.pv_ordering.is_cache_full(self.config.cache_config.max_cache_size) {
@@ -6327,7 +6324,7 @@ impl MoveOrdering {
                 // Only penalize if moving from starting position to exposed central square
                 if from_starting && exposed_central {
                     // Make move on temp board to check resulting position
-                    let mut temp_board = board.clone();
+                    let mut temp_board = board.clone_without_nnue();
                     if temp_board.make_move(move_).is_some() {
                         // Check if key defensive squares are guarded
                         let critical_squares = if move_.player == crate::types::core::Player::Black {
@@ -6362,7 +6359,7 @@ impl MoveOrdering {
             
             if file == 8 || file == 2 {
                 // This is a pawn capture on flank file
-                let mut temp_board = board.clone();
+                let mut temp_board = board.clone_without_nnue();
                 if temp_board.make_move(move_).is_some() {
                     let opponent = move_.player.opposite();
                     
@@ -6585,7 +6582,7 @@ impl MoveOrdering {
             // Check for specific vulnerabilities on files 7 and 2 (bishop promotion threats)
             if file == 7 || file == 2 {
                 // Make the move on a temporary board to check resulting position
-                let mut temp_board = board.clone();
+                let mut temp_board = board.clone_without_nnue();
                 if temp_board.make_move(move_).is_none() {
                     return penalty; // Move is invalid, return accumulated penalty
                 }
@@ -6782,7 +6779,7 @@ impl MoveOrdering {
         use crate::types::core::{PieceType, Position};
         
         // Make move on temp board to check resulting position
-        let mut temp_board = board.clone();
+        let mut temp_board = board.clone_without_nnue();
         if temp_board.make_move(move_).is_none() {
             return None;
         }
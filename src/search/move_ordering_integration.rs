@@ -21,13 +21,14 @@
 //! use shogi_engine::search::{TranspositionMoveOrderer, ThreadSafeTranspositionTable};
 //! use shogi_engine::bitboards::BitboardBoard;
 //! use shogi_engine::types::{Move, Player, CapturedPieces};
+//! use std::sync::{Arc, RwLock};
 //!
 //! // Create move orderer
 //! let mut orderer = TranspositionMoveOrderer::new();
 //!
 //! // Set transposition table reference
-//! let tt = ThreadSafeTranspositionTable::new(Default::default());
-//! orderer.set_transposition_table(&tt);
+//! let tt = Arc::new(RwLock::new(ThreadSafeTranspositionTable::new(Default::default())));
+//! orderer.set_transposition_table(tt);
 //!
 //! // Order moves for a position
 //! let board = BitboardBoard::new();
@@ -71,11 +72,12 @@ use crate::types::board::CapturedPieces;
 use crate::types::core::{Move, Player};
 use crate::types::search::TranspositionFlag;
 use std::collections::HashMap;
+use std::sync::{Arc, PoisonError, RwLock};
 
 /// Enhanced move ordering system with transposition table integration
 pub struct TranspositionMoveOrderer {
     /// Transposition table for accessing stored best moves
-    transposition_table: *const ThreadSafeTranspositionTable,
+    transposition_table: Option<Arc<RwLock<ThreadSafeTranspositionTable>>>,
     /// Hash calculator for position hashing
     pub hash_calculator: ShogiHashHandler,
     /// Move ordering statistics
@@ -124,7 +126,7 @@ impl TranspositionMoveOrderer {
     /// Create a new move orderer
     pub fn new() -> Self {
         Self {
-            transposition_table: std::ptr::null(),
+            transposition_table: None,
             hash_calculator: ShogiHashHandler::new(1000),
             stats: MoveOrderingStats::default(),
             history_table: [[0; 81]; 81],
@@ -134,8 +136,8 @@ impl TranspositionMoveOrderer {
     }
 
     /// Set the transposition table reference
-    pub fn set_transposition_table(&mut self, tt: &ThreadSafeTranspositionTable) {
-        self.transposition_table = tt as *const ThreadSafeTranspositionTable;
+    pub fn set_transposition_table(&mut self, tt: Arc<RwLock<ThreadSafeTranspositionTable>>) {
+        self.transposition_table = Some(tt);
     }
 
     /// Order moves with transposition table integration
@@ -207,16 +209,12 @@ impl TranspositionMoveOrderer {
         let position_hash = self.hash_calculator.get_position_hash(board, player, captured_pieces);
 
         // Probe transposition table for best move and other hints
-        let (best_move, tt_depth, tt_score, tt_flag) = if !self.transposition_table.is_null() {
-            unsafe {
-                let tt = &*self.transposition_table;
-                if let Some(entry) = tt.probe(position_hash, depth) {
-                    self.stats.tt_hint_moves += 1;
-                    (entry.best_move, entry.depth, Some(entry.score), Some(entry.flag))
-                } else {
-                    (None, 0, None, None)
-                }
-            }
+        let entry = self.transposition_table.as_ref().and_then(|tt| {
+            tt.read().unwrap_or_else(PoisonError::into_inner).probe(position_hash, depth)
+        });
+        let (best_move, tt_depth, tt_score, tt_flag) = if let Some(entry) = entry {
+            self.stats.tt_hint_moves += 1;
+            (entry.best_move, entry.depth, Some(entry.score), Some(entry.flag))
         } else {
             (None, 0, None, None)
         };
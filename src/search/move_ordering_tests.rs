@@ -9,6 +9,7 @@ use crate::types::board::CapturedPieces;
 use crate::types::core::{Move, Piece, PieceType, Player, Position};
 use crate::types::search::TranspositionFlag;
 use crate::types::transposition::TranspositionEntry;
+use std::sync::{Arc, RwLock};
 use std::time::Instant;
 
 /// Test suite for move ordering performance
@@ -133,8 +134,10 @@ impl MoveOrderingTestSuite {
         println!("Testing transposition table integration...");
 
         let mut orderer = TranspositionMoveOrderer::new();
-        let tt = ThreadSafeTranspositionTable::new(TranspositionConfig::default());
-        orderer.set_transposition_table(&tt);
+        let tt = Arc::new(RwLock::new(ThreadSafeTranspositionTable::new(
+            TranspositionConfig::default(),
+        )));
+        orderer.set_transposition_table(tt.clone());
 
         let board = BitboardBoard::new();
         let captured = CapturedPieces::new();
@@ -164,7 +167,7 @@ impl MoveOrderingTestSuite {
             source: crate::types::EntrySource::MainSearch,
         };
 
-        tt.store(entry);
+        tt.read().unwrap().store(entry);
 
         // Create moves including the best move
         let moves = vec![
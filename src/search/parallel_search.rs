@@ -1,5 +1,6 @@
 //! Parallel search implementation using Young Brothers Wait Concept (YBWC)
-//! algorithm with work-stealing.
+//! algorithm with work-stealing, or Lazy SMP (see `lazy_smp`) selected via
+//! `ParallelSearchConfig::search_mode`.
 //!
 //! This module provides multi-threaded search capabilities to utilize multiple
 //! CPU cores, achieving near-linear speedup with the number of available cores.
@@ -22,12 +23,17 @@
 use crate::bitboards::BitboardBoard;
 use crate::evaluation::PositionEvaluator;
 use crate::moves::MoveGenerator;
+pub use crate::search::lazy_smp::LazySmpSession;
+use crate::search::lazy_smp::{run_helper as run_lazy_smp_helper, LazySmpHelperTask};
 use crate::search::search_engine::SearchEngine;
 use crate::search::search_engine::GLOBAL_NODES_SEARCHED;
+use crate::search::search_reporter::{
+    default_reporter, ScoreBound, SearchEvent, SearchInfo, SearchReporter,
+};
 use crate::search::ThreadSafeTranspositionTable;
 use crate::types::board::CapturedPieces;
 use crate::types::core::{Move, Player};
-use crate::types::search::ParallelOptions;
+use crate::types::search::{ParallelOptions, ParallelSearchMode};
 use crate::utils::time::TimeSource;
 use crossbeam_deque::{Injector, Steal};
 use num_cpus;
@@ -388,6 +394,9 @@ pub struct ParallelSearchConfig {
 
     /// Mode controlling work distribution metrics collection.
     pub work_metrics_mode: WorkMetricsMode,
+
+    /// Parallel algorithm (root splitting/YBWC or Lazy SMP).
+    pub search_mode: ParallelSearchMode,
 }
 
 impl Default for ParallelSearchConfig {
@@ -406,6 +415,7 @@ impl Default for ParallelSearchConfig {
             ybwc_mid_divisor: 4,
             ybwc_deep_divisor: 2,
             work_metrics_mode: WorkMetricsMode::Disabled,
+            search_mode: ParallelSearchMode::Ybwc,
         }
     }
 }
@@ -435,6 +445,7 @@ impl ParallelSearchConfig {
             ybwc_mid_divisor: 4,
             ybwc_deep_divisor: 2,
             work_metrics_mode: WorkMetricsMode::Disabled,
+            search_mode: ParallelSearchMode::Ybwc,
         }
     }
 
@@ -470,8 +481,14 @@ impl ParallelSearchConfig {
         config.ybwc_deep_divisor = options.ybwc_deep_divisor.max(1);
         config.work_metrics_mode =
             if options.enable_metrics { WorkMetricsMode::Basic } else { WorkMetricsMode::Disabled };
+        config.search_mode = options.search_mode;
         config
     }
+
+    /// Whether helpers should run Lazy SMP instead of root splitting.
+    pub fn is_lazy_smp(&self) -> bool {
+        self.enable_parallel && self.search_mode == ParallelSearchMode::LazySmp
+    }
 }
 
 /// Thread-local search context for parallel search workers.
@@ -786,6 +803,9 @@ pub struct ParallelSearchEngine {
 
     /// Work distribution statistics.
     work_stats: Arc<WorkDistributionRecorder>,
+
+    /// Receiver of `info` lines streamed during root-split searches.
+    reporter: Arc<dyn SearchReporter>,
 }
 
 impl ParallelSearchEngine {
@@ -824,9 +844,8 @@ impl ParallelSearchEngine {
             .build()
             .map_err(|e| format!("Failed to create thread pool: {}", e))?;
 
-        // For now, we'll create a placeholder transposition table.
-        // This will be replaced with the actual shared TT from SearchEngine in later
-        // checkpoints.
+        // A standalone engine gets a table of its own; the search driver uses
+        // `new_with_shared_tt` with the search engine's table instead
         let tt_config = crate::search::TranspositionConfig::performance_optimized();
         let transposition_table =
             Arc::new(RwLock::new(ThreadSafeTranspositionTable::new(tt_config)));
@@ -843,6 +862,7 @@ impl ParallelSearchEngine {
             stop_flag: None,
             work_queues,
             work_stats: Arc::new(WorkDistributionRecorder::new(num_threads, metrics_mode)),
+            reporter: default_reporter(),
         })
     }
 
@@ -907,6 +927,7 @@ impl ParallelSearchEngine {
             stop_flag,
             work_queues,
             work_stats: Arc::new(WorkDistributionRecorder::new(num_threads, metrics_mode)),
+            reporter: default_reporter(),
         })
     }
 
@@ -1000,9 +1021,15 @@ impl ParallelSearchEngine {
             stop_flag,
             work_queues,
             work_stats: Arc::new(WorkDistributionRecorder::new(num_threads, metrics_mode)),
+            reporter: default_reporter(),
         })
     }
 
+    /// Send search output to `reporter` instead of the default USI output.
+    pub fn set_reporter(&mut self, reporter: Arc<dyn SearchReporter>) {
+        self.reporter = reporter;
+    }
+
     /// Perform parallel search on root-level moves.
     ///
     /// This method parallelizes the search across all root moves,
@@ -1084,6 +1111,7 @@ impl ParallelSearchEngine {
         let best_for_consumer = best_shared.clone();
 
         // Start consumer thread to stream info lines as results arrive
+        let consumer_reporter = self.reporter.clone();
         let consumer = thread::spawn(move || {
             let mut best_pv = String::new();
             while let Ok((mv, score, pv)) = rx.recv() {
@@ -1104,22 +1132,19 @@ impl ParallelSearchEngine {
                 let seldepth_raw =
                     crate::search::search_engine::GLOBAL_SELDEPTH.load(Ordering::Relaxed) as u8;
                 let seldepth = if seldepth_raw == 0 { depth } else { seldepth_raw.max(depth) };
-                // Emit real USI info line with score and PV (skip during silent benches)
-                if std::env::var("SHOGI_SILENT_BENCH").is_err() {
-                    if !best_pv.is_empty() {
-                        println!(
-                            "info depth {} seldepth {} multipv 1 score cp {} time {} nodes {} nps \
-                             {} pv {}",
-                            depth,
-                            seldepth,
-                            if let Ok(g) = best_for_consumer.lock() { g.1 } else { score },
-                            elapsed,
-                            nodes,
-                            nps,
-                            best_pv
-                        );
-                    }
-                    let _ = std::io::Write::flush(&mut std::io::stdout());
+                // Emit real USI info line with score and PV
+                if !best_pv.is_empty() {
+                    consumer_reporter.report(SearchEvent::PvUpdate(SearchInfo {
+                        depth,
+                        seldepth,
+                        multipv: Some(1),
+                        score: if let Ok(g) = best_for_consumer.lock() { g.1 } else { score },
+                        bound: ScoreBound::Exact,
+                        time_ms: elapsed,
+                        nodes,
+                        nps,
+                        pv: best_pv.clone(),
+                    }));
                 }
             }
         });
@@ -1308,7 +1333,7 @@ impl ParallelSearchEngine {
             }
 
             // Emit final info line with the complete PV if we have at least 2 moves
-            if full_pv.len() >= 2 && std::env::var("SHOGI_SILENT_BENCH").is_err() {
+            if full_pv.len() >= 2 {
                 let elapsed = bench_start.elapsed().as_millis() as u64;
                 let nodes = GLOBAL_NODES_SEARCHED.load(Ordering::Relaxed);
                 let nps =
@@ -1318,12 +1343,17 @@ impl ParallelSearchEngine {
                     full_pv.iter().map(|m| m.to_usi_string()).collect::<Vec<String>>().join(" ");
 
                 if !pv_string.is_empty() {
-                    println!(
-                        "info depth {} seldepth {} multipv 1 score cp {} time {} nodes {} nps {} \
-                         pv {}",
-                        depth, seldepth_final, *best_score, elapsed, nodes, nps, pv_string
-                    );
-                    let _ = std::io::Write::flush(&mut std::io::stdout());
+                    self.reporter.report(SearchEvent::PvUpdate(SearchInfo {
+                        depth,
+                        seldepth: seldepth_final,
+                        multipv: Some(1),
+                        score: *best_score,
+                        bound: ScoreBound::Exact,
+                        time_ms: elapsed,
+                        nodes,
+                        nps,
+                        pv: pv_string,
+                    }));
                 }
             }
         }
@@ -1613,4 +1643,58 @@ impl ParallelSearchEngine {
     pub fn get_work_stats(&self) -> Option<WorkDistributionStats> {
         self.work_stats.snapshot()
     }
+
+    /// Whether this engine was configured for Lazy SMP.
+    pub fn is_lazy_smp(&self) -> bool {
+        self.config.is_lazy_smp()
+    }
+
+    /// Start Lazy SMP helper threads for a root position.
+    ///
+    /// Spawns `num_threads - 1` helpers on the engine's thread pool. Each
+    /// helper runs its own iterative deepening up to `max_depth` over the
+    /// shared transposition table, with staggered start depths and skipped
+    /// iterations. The caller runs the main thread's search and then calls
+    /// [`LazySmpSession::finish`] to stop the helpers and vote on the move.
+    ///
+    /// Helpers search into the engine's shared transposition table. They stop
+    /// when the session is finished or dropped, or when `time_limit_ms`
+    /// elapses; the engine-level stop flag is checked between iterations and
+    /// reaches a running iteration through the main thread, which finishes the
+    /// session as soon as it sees the flag.
+    pub fn start_lazy_smp(
+        &self,
+        board: &BitboardBoard,
+        captured_pieces: &CapturedPieces,
+        player: Player,
+        max_depth: u8,
+        time_limit_ms: u32,
+    ) -> LazySmpSession {
+        let helper_stop = Arc::new(AtomicBool::new(false));
+        let helper_count = self.config.num_threads.saturating_sub(1);
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        for thread_id in 1..=helper_count {
+            let task = LazySmpHelperTask {
+                thread_id,
+                board: board.clone(),
+                captured_pieces: captured_pieces.clone(),
+                player,
+                max_depth,
+                time_limit_ms,
+                stop: helper_stop.clone(),
+                engine_stop: self.stop_flag.clone(),
+                shared_tt: self.transposition_table.clone(),
+            };
+            let tx = tx.clone();
+            let work_stats = self.work_stats.clone();
+            self.thread_pool.spawn(move || {
+                let result = run_lazy_smp_helper(task);
+                work_stats.record_work(thread_id);
+                let _ = tx.send(result);
+            });
+        }
+
+        LazySmpSession::new(helper_stop, rx, helper_count)
+    }
 }
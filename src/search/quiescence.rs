@@ -8,8 +8,16 @@
 //! Extracted from `search_engine.rs` as part of Task 1.0: File Modularization
 //! and Structure Improvements.
 
-use crate::types::core::Move;
-use crate::types::search::{QuiescenceConfig, QuiescenceStats};
+use crate::bitboards::BitboardBoard;
+use crate::moves::MoveGenerator;
+use crate::types::board::CapturedPieces;
+use crate::types::core::{Move, Piece};
+use crate::types::search::{
+    DropQuiescenceConfig, DropQuiescenceStats, QuiescenceConfig, QuiescenceStats,
+};
+
+/// Upper bound on exchange length considered by [`QuiescenceHelper::drop_see`]
+const MAX_DROP_EXCHANGE_LENGTH: usize = 16;
 
 /// Quiescence search helper functions for pruning and extensions
 pub struct QuiescenceHelper {
@@ -307,6 +315,151 @@ impl QuiescenceHelper {
         false
     }
 
+    /// Static exchange evaluation of a drop
+    ///
+    /// Plays out the capture sequence on the drop square, each side always
+    /// recapturing with its least valuable legal capture, and returns the net
+    /// material result for the dropping side (0 when the drop is safe, negative
+    /// when the dropped piece is lost).
+    pub fn drop_see(
+        &self,
+        move_generator: &MoveGenerator,
+        board: &BitboardBoard,
+        captured_pieces: &CapturedPieces,
+        drop: &Move,
+    ) -> i32 {
+        let mut temp_board = board.clone_without_nnue();
+        temp_board.place_piece(Piece::new(drop.piece_type, drop.player), drop.to);
+
+        let mut gains = vec![0];
+        let mut piece_on_square = drop.piece_type.base_value();
+        let mut side = drop.player.opposite();
+
+        while gains.len() < MAX_DROP_EXCHANGE_LENGTH {
+            let recapture = move_generator
+                .generate_legal_captures(&temp_board, side, captured_pieces)
+                .into_iter()
+                .filter(|m| m.to == drop.to)
+                .min_by_key(|m| m.piece_type.base_value());
+            let Some(recapture) = recapture else {
+                break;
+            };
+
+            let previous = gains[gains.len() - 1];
+            gains.push(piece_on_square - previous);
+            piece_on_square = recapture.piece_type.base_value();
+            temp_board.make_move(&recapture);
+            side = side.opposite();
+        }
+
+        // Either side may stop the exchange when continuing loses material
+        while gains.len() > 1 {
+            let last = gains.pop().unwrap_or(0);
+            let previous = gains.len() - 1;
+            gains[previous] = -(-gains[previous]).max(last);
+        }
+        gains[0]
+    }
+
+    /// Choose which candidate drops quiescence searches at this node
+    ///
+    /// Drops losing material by SEE are rejected, the rest are ordered with
+    /// checks first and then by SEE, and the list is cut to the per-node
+    /// budget.
+    pub fn select_quiescence_drops(
+        &self,
+        move_generator: &MoveGenerator,
+        board: &BitboardBoard,
+        captured_pieces: &CapturedPieces,
+        candidates: Vec<Move>,
+        config: &DropQuiescenceConfig,
+        stats: &mut DropQuiescenceStats,
+    ) -> Vec<Move> {
+        let mut scored = Vec::with_capacity(candidates.len());
+        for drop in candidates {
+            if drop.gives_check {
+                stats.checking_drops_found += 1;
+            } else {
+                stats.threat_drops_found += 1;
+            }
+
+            let see = self.drop_see(move_generator, board, captured_pieces, &drop);
+            if see < config.see_threshold {
+                stats.see_rejected += 1;
+                continue;
+            }
+            scored.push((see, drop));
+        }
+
+        scored.sort_by(|(see_a, a), (see_b, b)| {
+            b.gives_check
+                .cmp(&a.gives_check)
+                .then(see_b.cmp(see_a))
+                .then(a.piece_type.base_value().cmp(&b.piece_type.base_value()))
+        });
+
+        if scored.len() > config.max_drops_per_node {
+            stats.budget_rejected += (scored.len() - config.max_drops_per_node) as u64;
+            scored.truncate(config.max_drops_per_node);
+        }
+
+        scored.into_iter().map(|(_, drop)| drop).collect()
+    }
+
+    /// Choose which check evasions quiescence searches
+    ///
+    /// Board moves (king moves, captures of the checker, interposing piece
+    /// moves) are all kept. Interposing drops are reduced to the best drop per
+    /// square by SEE, filtered by the SEE threshold and cut to the
+    /// interposition budget. If only losing interpositions exist, the best one
+    /// is still kept so a defendable check is never scored as mate.
+    pub fn select_evasions(
+        &self,
+        move_generator: &MoveGenerator,
+        board: &BitboardBoard,
+        captured_pieces: &CapturedPieces,
+        legal_moves: Vec<Move>,
+        config: &DropQuiescenceConfig,
+        stats: &mut DropQuiescenceStats,
+    ) -> Vec<Move> {
+        let (drops, mut evasions): (Vec<Move>, Vec<Move>) =
+            legal_moves.into_iter().partition(|m| m.from.is_none());
+
+        // Best interposition per square: highest SEE, then cheapest piece
+        let mut best_per_square: Vec<(i32, Move)> = Vec::new();
+        for drop in drops {
+            let see = self.drop_see(move_generator, board, captured_pieces, &drop);
+            match best_per_square.iter_mut().find(|(_, m)| m.to == drop.to) {
+                Some(entry) => {
+                    let cheaper = drop.piece_type.base_value() < entry.1.piece_type.base_value();
+                    if see > entry.0 || (see == entry.0 && cheaper) {
+                        *entry = (see, drop);
+                    }
+                }
+                None => best_per_square.push((see, drop)),
+            }
+        }
+        best_per_square.sort_by(|(see_a, _), (see_b, _)| see_b.cmp(see_a));
+
+        let fallback = best_per_square.first().map(|(_, drop)| drop.clone());
+        let mut selected = Vec::new();
+        for (see, drop) in best_per_square {
+            if see < config.see_threshold {
+                stats.see_rejected += 1;
+            } else if selected.len() >= config.max_interposition_drops {
+                stats.budget_rejected += 1;
+            } else {
+                selected.push(drop);
+            }
+        }
+
+        if evasions.is_empty() && selected.is_empty() {
+            selected.extend(fallback);
+        }
+        evasions.extend(selected);
+        evasions
+    }
+
     /// Get quiescence statistics
     pub fn get_stats(&self) -> &QuiescenceStats {
         &self.stats
@@ -336,7 +489,7 @@ impl QuiescenceHelper {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::core::Position;
+    use crate::types::core::{Player, Position};
     use crate::types::PieceType;
 
     fn create_test_move(is_capture: bool, gives_check: bool) -> Move {
@@ -353,6 +506,113 @@ mod tests {
         )
     }
 
+    fn drop_helper() -> (QuiescenceHelper, MoveGenerator) {
+        (QuiescenceHelper::new(QuiescenceConfig::default()), MoveGenerator::new())
+    }
+
+    #[test]
+    fn test_drop_see_hanging_and_defended() {
+        let (helper, generator) = drop_helper();
+
+        // White pawn on 5e attacks 5f; a rook dropped there is simply lost
+        let (board, _, captured) =
+            BitboardBoard::from_fen("4k4/9/9/9/4p4/9/9/9/4K4 b R 1").unwrap();
+        let drop = Move::new_drop(PieceType::Rook, Position::new(5, 4), Player::Black);
+        assert_eq!(helper.drop_see(&generator, &board, &captured, &drop), -1000);
+
+        // With a gold behind it the exchange is rook for pawn
+        let (board, _, captured) =
+            BitboardBoard::from_fen("4k4/9/9/9/4p4/9/4G4/9/4K4 b R 1").unwrap();
+        assert_eq!(helper.drop_see(&generator, &board, &captured, &drop), -900);
+
+        // An unattacked square is safe
+        let safe = Move::new_drop(PieceType::Rook, Position::new(3, 0), Player::Black);
+        assert_eq!(helper.drop_see(&generator, &board, &captured, &safe), 0);
+    }
+
+    #[test]
+    fn test_checking_drops_are_generated_and_marked() {
+        let generator = MoveGenerator::new();
+        let (board, _, captured) = BitboardBoard::from_fen("4k4/9/9/9/9/9/9/9/4K4 b G 1").unwrap();
+
+        let drops =
+            generator.generate_quiescence_drops(&board, Player::Black, &captured, true, false);
+        assert!(!drops.is_empty());
+        assert!(drops.iter().all(|m| m.gives_check && m.from.is_none()));
+        assert!(drops.iter().any(|m| m.to == Position::new(1, 4)));
+    }
+
+    #[test]
+    fn test_threat_drops_filtered_by_see() {
+        let (helper, generator) = drop_helper();
+        let config = DropQuiescenceConfig::default();
+
+        // A pawn dropped in front of an undefended rook is simply taken
+        let (board, _, captured) = BitboardBoard::from_fen("8k/9/2r6/9/9/9/9/9/K8 b P 1").unwrap();
+        let candidates =
+            generator.generate_quiescence_drops(&board, Player::Black, &captured, false, true);
+        assert!(candidates.iter().any(|m| m.to == Position::new(3, 2)));
+        let mut stats = DropQuiescenceStats::default();
+        let selected = helper.select_quiescence_drops(
+            &generator, &board, &captured, candidates, &config, &mut stats,
+        );
+        assert!(selected.is_empty());
+        assert!(stats.see_rejected > 0);
+
+        // Backed by a silver, the same pawn drop wins the rook
+        let (board, _, captured) =
+            BitboardBoard::from_fen("8k/9/2r6/9/2S6/9/9/9/K8 b P 1").unwrap();
+        let candidates =
+            generator.generate_quiescence_drops(&board, Player::Black, &captured, false, true);
+        let mut stats = DropQuiescenceStats::default();
+        let selected = helper.select_quiescence_drops(
+            &generator, &board, &captured, candidates, &config, &mut stats,
+        );
+        assert!(selected.iter().any(|m| m.to == Position::new(3, 2)));
+        assert!(stats.threat_drops_found > 0);
+    }
+
+    #[test]
+    fn test_drop_budget_limits_selection() {
+        let (helper, generator) = drop_helper();
+        let config = DropQuiescenceConfig { max_drops_per_node: 1, ..Default::default() };
+        let (board, _, captured) = BitboardBoard::from_fen("4k4/9/9/9/9/9/9/9/4K4 b G 1").unwrap();
+        let candidates =
+            generator.generate_quiescence_drops(&board, Player::Black, &captured, true, false);
+        let candidate_count = candidates.len();
+
+        let mut stats = DropQuiescenceStats::default();
+        let selected = helper.select_quiescence_drops(
+            &generator, &board, &captured, candidates, &config, &mut stats,
+        );
+        assert!(selected.len() <= 1);
+        assert_eq!(
+            selected.len() as u64 + stats.see_rejected + stats.budget_rejected,
+            candidate_count as u64
+        );
+    }
+
+    #[test]
+    fn test_evasions_keep_one_interposition_per_square() {
+        let (helper, generator) = drop_helper();
+        let config = DropQuiescenceConfig::default();
+
+        // Black king on 9i checked along the 9th file by a rook; gold and
+        // silver in hand can interpose on any of the squares between
+        let (board, _, captured) = BitboardBoard::from_fen("r8/9/9/9/9/9/9/9/K7k b GS 1").unwrap();
+        let legal = generator.generate_legal_moves(&board, Player::Black, &captured);
+        let mut stats = DropQuiescenceStats::default();
+        let evasions =
+            helper.select_evasions(&generator, &board, &captured, legal, &config, &mut stats);
+
+        let drops: Vec<&Move> = evasions.iter().filter(|m| m.from.is_none()).collect();
+        assert!(drops.len() <= config.max_interposition_drops);
+        for (i, a) in drops.iter().enumerate() {
+            assert!(drops[i + 1..].iter().all(|b| b.to != a.to));
+        }
+        assert!(evasions.iter().any(|m| m.from.is_some()), "king moves are always kept");
+    }
+
     #[test]
     fn test_delta_pruning() {
         let config = QuiescenceConfig::default();
@@ -0,0 +1,142 @@
+//! Reproducible search for regression testing and data generation.
+//!
+//! A normal search does not repeat itself: it stops on the wall clock, helper
+//! threads race over the shared transposition table, and tables filled by
+//! earlier searches steer move ordering. With [`ReproducibleSearch`] set the
+//! engine instead
+//!
+//! - searches with one thread
+//! - measures time on a clock that never advances, so only the depth and
+//!   node limits end a search
+//! - clears the hash tables, ordering heuristics and caches before every
+//!   search
+//! - draws book and skill-level randomness from a generator seeded with
+//!   [`ReproducibleSearch::seed`] and the position
+//!
+//! so the same position, options and limits give the same best move, score
+//! and node count on every run. Only `stop` can still end a search early.
+
+use crate::utils::time::{Clock, VirtualClock};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::sync::Arc;
+
+/// Settings of the `Reproducible`, `ReproducibleNodes` and `RandomSeed`
+/// options
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ReproducibleSearch {
+    /// Reproducible mode is on
+    pub enabled: bool,
+    /// Nodes per search when the `go` command gives no `nodes` limit; `None`
+    /// searches to the depth limit
+    pub node_limit: Option<u64>,
+    /// Seed of the random choices
+    pub seed: u64,
+}
+
+impl ReproducibleSearch {
+    /// Reproducible mode searching `node_limit` nodes per move
+    pub fn new(node_limit: Option<u64>) -> Self {
+        Self { enabled: true, node_limit, seed: 0 }
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Generator for the random choices in the position with
+    /// `position_hash`, so repeating a position repeats the choice
+    pub fn rng(&self, position_hash: u64) -> StdRng {
+        StdRng::seed_from_u64(self.seed ^ position_hash)
+    }
+
+    /// The clock searches run on: it stays at zero, so no time limit is
+    /// ever reached
+    pub fn clock(&self) -> Arc<dyn Clock> {
+        Arc::new(VirtualClock::new(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::opening_book::OpeningBook;
+    use crate::search::NullReporter;
+    use crate::{SearchLimits, SearchOutcome, ShogiEngine};
+    use rand::Rng;
+    use std::sync::Mutex;
+
+    fn reproducible_engine(reproducible: ReproducibleSearch) -> ShogiEngine {
+        let mut engine = ShogiEngine::new();
+        // Search the opening positions instead of playing book moves
+        engine.opening_book = Arc::new(Mutex::new(OpeningBook::new()));
+        engine.set_search_reporter(Arc::new(NullReporter));
+        engine.set_reproducible(reproducible);
+        engine
+    }
+
+    fn search(engine: &mut ShogiEngine, fen: &str, limits: &SearchLimits) -> SearchOutcome {
+        let mut parts = vec!["sfen"];
+        parts.extend(fen.split_whitespace());
+        engine.handle_position(&parts);
+        engine.search_with_limits(limits, None)
+    }
+
+    fn signature(outcome: &SearchOutcome) -> (Option<String>, i32, u64) {
+        (outcome.best_move.as_ref().map(|mv| mv.to_usi_string()), outcome.score, outcome.nodes)
+    }
+
+    #[test]
+    fn test_repeated_searches_are_identical() {
+        let reproducible = ReproducibleSearch::new(Some(300)).with_seed(1);
+        let mut engine = reproducible_engine(reproducible);
+        let mut fresh_engine = reproducible_engine(reproducible);
+        let node_limited = SearchLimits::default();
+        let depth_limited = SearchLimits::depth(2);
+        let mut searched_nodes = 0;
+
+        for position in crate::bench::standard_positions() {
+            for limits in [&node_limited, &depth_limited] {
+                let first = signature(&search(&mut engine, &position.fen, limits));
+                let repeated = signature(&search(&mut engine, &position.fen, limits));
+                let fresh = signature(&search(&mut fresh_engine, &position.fen, limits));
+                assert!(first.0.is_some(), "{}: no move", position.name);
+                assert_eq!(first, repeated, "{}: repeated search differs", position.name);
+                assert_eq!(first, fresh, "{}: fresh engine differs", position.name);
+                searched_nodes += first.2;
+            }
+        }
+        assert!(searched_nodes > 0);
+    }
+
+    #[test]
+    fn test_go_nodes_limits_the_search() {
+        let mut engine = reproducible_engine(ReproducibleSearch::new(None));
+        let fen = crate::STARTPOS_SFEN;
+        let small = search(&mut engine, fen, &SearchLimits::nodes(100));
+        let large = search(&mut engine, fen, &SearchLimits::nodes(600));
+        assert!(small.nodes > 0);
+        assert!(small.nodes < large.nodes, "{} vs {} nodes", small.nodes, large.nodes);
+        assert_eq!(
+            signature(&small),
+            signature(&search(&mut engine, fen, &SearchLimits::nodes(100)))
+        );
+    }
+
+    #[test]
+    fn test_rng_depends_on_seed_and_position() {
+        let config = ReproducibleSearch::new(Some(1000)).with_seed(7);
+        let draw = |config: &ReproducibleSearch, hash| config.rng(hash).gen::<u64>();
+        assert_eq!(draw(&config, 42), draw(&config, 42));
+        assert_ne!(draw(&config, 42), draw(&config, 43));
+        assert_ne!(draw(&config, 42), draw(&config.with_seed(8), 42));
+    }
+
+    #[test]
+    fn test_clock_is_frozen() {
+        let clock = ReproducibleSearch::default().clock();
+        clock.on_node();
+        assert_eq!(clock.now_us(), 0);
+    }
+}
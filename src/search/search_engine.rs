@@ -4,26 +4,35 @@ use crate::evaluation::*;
 use crate::moves::*;
 use crate::opening_book::OpeningBook;
 use crate::search::iterative_deepening::IterativeDeepeningHelper;
+use crate::search::lazy_smp::LazySmpThreadResult;
 use crate::search::move_ordering::MoveOrdering;
 use crate::search::null_move::NullMoveHelper;
 use crate::search::quiescence::QuiescenceHelper;
 use crate::search::reductions::ReductionsHelper;
+use crate::search::search_reporter::{
+    default_reporter, NullReporter, ScoreBound, SearchEvent, SearchInfo, SearchReporter,
+};
 use crate::search::statistics::SearchStatistics;
 use crate::search::tapered_search_integration::TaperedSearchEnhancer;
-use crate::search::time_management::TimeManager;
+use crate::search::time_management::{TimeManager, TimePlan};
+use crate::search::tree_recorder::{
+    NodeOutcome, SearchTree, SearchTreeRecorder, TreeRecorderConfig, TtProbe,
+};
 use crate::search::{BoardTrait, ParallelSearchConfig, ParallelSearchEngine};
 use crate::tablebase::MicroTablebase;
 use crate::types::board::CapturedPieces;
 use crate::types::board::GamePhase;
 use crate::types::core::{Move, Piece, PieceType, Player, Position};
+use crate::types::handicap::Handicap;
 use crate::types::search::{
     AspirationWindowConfig, AspirationWindowPlayingStyle, AspirationWindowStats, CoreSearchMetrics,
-    EngineConfig, EnginePreset, IIDBoardState, IIDConfig, IIDOverheadStats, IIDStats, LMRConfig,
-    LMRStats, NullMoveConfig, NullMoveStats, ParallelOptions, PositionComplexity, QuiescenceConfig,
-    QuiescenceEntry, QuiescenceStats, TTReplacementPolicy, TimeBudgetStats, TimeManagementConfig,
-    TranspositionFlag,
+    DropQuiescenceConfig, DropQuiescenceStats, EngineConfig, EnginePreset, IIDBoardState,
+    IIDConfig, IIDOverheadStats, IIDStats, LMRConfig, LMRStats, NullMoveConfig, NullMoveStats,
+    ParallelOptions, PositionComplexity, QuiescenceConfig, QuiescenceEntry, QuiescenceStats,
+    TTReplacementPolicy, TimeBudgetStats, TimeManagementConfig, TranspositionFlag,
 };
-use crate::utils::time::TimeSource;
+use crate::utils::common::DeterministicHashMap;
+use crate::utils::time::{system_clock, Clock, TimeSource};
 // Types still in all.rs (temporary backward compatibility)
 use crate::types::all::{
     AspirationWindowPerformanceMetrics, ConfidenceLevel, GameResult, IIDPVResult,
@@ -40,7 +49,7 @@ use rayon::prelude::*;
 use std::collections::HashMap;
 use std::sync::{
     atomic::{AtomicBool, AtomicU64, Ordering},
-    Arc, RwLock,
+    Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard,
 };
 
 // Score constants to replace magic numbers (Task 5.5)
@@ -48,6 +57,8 @@ use std::sync::{
 pub const MIN_SCORE: i32 = i32::MIN + 1;
 /// Maximum score value (one below i32::MAX to avoid sentinel value issues)
 pub const MAX_SCORE: i32 = i32::MAX - 1;
+/// Root `currmove` reports start once an iteration has run this long
+const CURRMOVE_REPORT_DELAY_MS: u32 = 3000;
 
 thread_local! {
     static YBWC_ENGINE_TLS: std::cell::RefCell<Option<SearchEngine>> = std::cell::RefCell::new(None);
@@ -191,6 +202,23 @@ mod search_tests {
         assert_eq!(default_config.reduction_factor, 2);
         assert!(default_config.enabled);
     }
+
+    #[test]
+    fn test_score_root_moves_budget() {
+        let stop_flag = Arc::new(AtomicBool::new(false));
+        let mut engine = SearchEngine::new(Some(stop_flag.clone()), 16);
+        let board = BitboardBoard::new();
+        let captured = CapturedPieces::new();
+
+        let scored = engine.score_root_moves(&board, &captured, Player::Black, 1, 60_000);
+        assert_eq!(scored.len(), 30);
+        assert!(scored.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+
+        // No time left, or a stopped search, scores nothing
+        assert!(engine.score_root_moves(&board, &captured, Player::Black, 1, 0).is_empty());
+        stop_flag.store(true, Ordering::Relaxed);
+        assert!(engine.score_root_moves(&board, &captured, Player::Black, 1, 60_000).is_empty());
+    }
 }
 
 #[cfg(test)]
@@ -255,13 +283,24 @@ pub struct SearchEngine {
     evaluator: PositionEvaluator,
     move_generator: MoveGenerator,
     tablebase: MicroTablebase,
-    transposition_table: crate::search::ThreadSafeTranspositionTable,
+    /// Main transposition table; parallel searches share this handle with
+    /// their worker and helper threads
+    transposition_table: Arc<RwLock<crate::search::ThreadSafeTranspositionTable>>,
     /// Optional shared transposition table for parallel search contexts
     shared_transposition_table: Option<Arc<RwLock<crate::search::ThreadSafeTranspositionTable>>>,
     hash_calculator: crate::search::ShogiHashHandler,
+    /// Starting array of the game being searched
+    handicap: Handicap,
+    /// Plies left before the game is drawn by the move limit
+    plies_to_draw: Option<u32>,
+    /// Time source for time limits; a virtual clock makes timing
+    /// reproducible
+    clock: Arc<dyn Clock>,
+    /// Share of the last root search's nodes spent on its best move
+    best_move_effort: f64,
     move_orderer: crate::search::TranspositionMoveOrderer,
     advanced_move_orderer: MoveOrdering,
-    quiescence_tt: HashMap<String, QuiescenceEntry>,
+    quiescence_tt: DeterministicHashMap<String, QuiescenceEntry>,
     quiescence_tt_age: u64, // Age counter for LRU tracking
     history_table: [[i32; 9]; 9],
     killer_moves: [Option<Move>; 2],
@@ -291,6 +330,9 @@ pub struct SearchEngine {
     time_management_config: TimeManagementConfig,
     /// Legacy stats fields - access through helper modules
     quiescence_stats: QuiescenceStats,
+    /// Drop handling in quiescence search, with its own statistics
+    drop_quiescence_config: DropQuiescenceConfig,
+    drop_quiescence_stats: DropQuiescenceStats,
     null_move_stats: NullMoveStats,
     lmr_stats: LMRStats,
     aspiration_stats: AspirationWindowStats,
@@ -358,6 +400,12 @@ pub struct SearchEngine {
     time_budget_stats: TimeBudgetStats,
     /// Time check node counter (Task 8.4)
     time_check_node_counter: u32,
+    /// Engine node count at which the search stops, set by `set_node_limit`
+    node_deadline: Option<u64>,
+    /// Optional recorder for the explored search tree
+    tree_recorder: Option<SearchTreeRecorder>,
+    /// Receiver of root `currmove` reports; set by `IterativeDeepening`
+    search_reporter: Arc<dyn SearchReporter>,
     // nodes_searched (cached for quick access) - removed
     // nodes_searched removed as it was unused
 }
@@ -951,19 +999,187 @@ fn convert_quiescence_config_back(
 }
 
 fn convert_null_move_config_back(
-    _config: &crate::types::search::NullMoveConfig,
+    config: &crate::types::search::NullMoveConfig,
 ) -> crate::types::all::NullMoveConfig {
-    // This is a simplified conversion - full conversion would require all fields
-    crate::types::all::NullMoveConfig::default() // Use default for now, full
-                                                 // conversion would be complex
+    crate::types::all::NullMoveConfig {
+        enabled: config.enabled,
+        min_depth: config.min_depth,
+        reduction_factor: config.reduction_factor,
+        max_pieces_threshold: config.max_pieces_threshold,
+        enable_dynamic_reduction: config.enable_dynamic_reduction,
+        enable_endgame_detection: config.enable_endgame_detection,
+        verification_margin: config.verification_margin,
+        dynamic_reduction_formula: match config.dynamic_reduction_formula {
+            crate::types::search::DynamicReductionFormula::Static => {
+                crate::types::all::DynamicReductionFormula::Static
+            }
+            crate::types::search::DynamicReductionFormula::Linear => {
+                crate::types::all::DynamicReductionFormula::Linear
+            }
+            crate::types::search::DynamicReductionFormula::Smooth => {
+                crate::types::all::DynamicReductionFormula::Smooth
+            }
+        },
+        enable_mate_threat_detection: config.enable_mate_threat_detection,
+        mate_threat_margin: config.mate_threat_margin,
+        enable_endgame_type_detection: config.enable_endgame_type_detection,
+        material_endgame_threshold: config.material_endgame_threshold,
+        king_activity_threshold: config.king_activity_threshold,
+        zugzwang_threshold: config.zugzwang_threshold,
+        preset: config.preset.clone().map(|p| match p {
+            crate::types::search::NullMovePreset::Aggressive => {
+                crate::types::all::NullMovePreset::Aggressive
+            }
+            crate::types::search::NullMovePreset::Conservative => {
+                crate::types::all::NullMovePreset::Conservative
+            }
+            crate::types::search::NullMovePreset::Balanced => {
+                crate::types::all::NullMovePreset::Balanced
+            }
+        }),
+        reduction_strategy: match config.reduction_strategy {
+            crate::types::search::NullMoveReductionStrategy::Static => {
+                crate::types::all::NullMoveReductionStrategy::Static
+            }
+            crate::types::search::NullMoveReductionStrategy::Dynamic => {
+                crate::types::all::NullMoveReductionStrategy::Dynamic
+            }
+            crate::types::search::NullMoveReductionStrategy::DepthBased => {
+                crate::types::all::NullMoveReductionStrategy::DepthBased
+            }
+            crate::types::search::NullMoveReductionStrategy::MaterialBased => {
+                crate::types::all::NullMoveReductionStrategy::MaterialBased
+            }
+            crate::types::search::NullMoveReductionStrategy::PositionTypeBased => {
+                crate::types::all::NullMoveReductionStrategy::PositionTypeBased
+            }
+        },
+        depth_scaling_factor: config.depth_scaling_factor,
+        min_depth_for_scaling: config.min_depth_for_scaling,
+        material_adjustment_factor: config.material_adjustment_factor,
+        piece_count_threshold: config.piece_count_threshold,
+        threshold_step: config.threshold_step,
+        opening_reduction_factor: config.opening_reduction_factor,
+        middlegame_reduction_factor: config.middlegame_reduction_factor,
+        endgame_reduction_factor: config.endgame_reduction_factor,
+        enable_per_depth_reduction: config.enable_per_depth_reduction,
+        reduction_factor_by_depth: config.reduction_factor_by_depth.clone(),
+        enable_per_position_type_threshold: config.enable_per_position_type_threshold,
+        opening_pieces_threshold: config.opening_pieces_threshold,
+        middlegame_pieces_threshold: config.middlegame_pieces_threshold,
+        endgame_pieces_threshold: config.endgame_pieces_threshold,
+    }
 }
 
 fn convert_lmr_config_back(
-    _config: &crate::types::search::LMRConfig,
+    config: &crate::types::search::LMRConfig,
 ) -> crate::types::all::LMRConfig {
-    // This is a simplified conversion - full conversion would require all fields
-    crate::types::all::LMRConfig::default() // Use default for now, full
-                                            // conversion would be complex
+    crate::types::all::LMRConfig {
+        enabled: config.enabled,
+        min_depth: config.min_depth,
+        min_move_index: config.min_move_index,
+        base_reduction: config.base_reduction,
+        max_reduction: config.max_reduction,
+        enable_dynamic_reduction: config.enable_dynamic_reduction,
+        enable_adaptive_reduction: config.enable_adaptive_reduction,
+        enable_extended_exemptions: config.enable_extended_exemptions,
+        re_search_margin: config.re_search_margin,
+        enable_position_type_margin: config.enable_position_type_margin,
+        tactical_re_search_margin: config.tactical_re_search_margin,
+        quiet_re_search_margin: config.quiet_re_search_margin,
+        classification_config: convert_position_classification_config_back(
+            &config.classification_config,
+        ),
+        escape_move_config: convert_escape_move_config_back(&config.escape_move_config),
+        adaptive_tuning_config: convert_adaptive_tuning_config_back(&config.adaptive_tuning_config),
+        advanced_reduction_config: convert_advanced_reduction_config_back(
+            &config.advanced_reduction_config,
+        ),
+        conditional_exemption_config: convert_conditional_exemption_config_back(
+            &config.conditional_exemption_config,
+        ),
+    }
+}
+
+fn convert_position_classification_config_back(
+    config: &crate::types::search::PositionClassificationConfig,
+) -> crate::types::all::PositionClassificationConfig {
+    crate::types::all::PositionClassificationConfig {
+        tactical_threshold: config.tactical_threshold,
+        quiet_threshold: config.quiet_threshold,
+        material_imbalance_threshold: config.material_imbalance_threshold,
+        min_moves_threshold: config.min_moves_threshold,
+    }
+}
+
+fn convert_escape_move_config_back(
+    config: &crate::types::search::EscapeMoveConfig,
+) -> crate::types::all::EscapeMoveConfig {
+    crate::types::all::EscapeMoveConfig {
+        enable_escape_move_exemption: config.enable_escape_move_exemption,
+        use_threat_based_detection: config.use_threat_based_detection,
+        fallback_to_heuristic: config.fallback_to_heuristic,
+    }
+}
+
+fn convert_adaptive_tuning_config_back(
+    config: &crate::types::search::AdaptiveTuningConfig,
+) -> crate::types::all::AdaptiveTuningConfig {
+    crate::types::all::AdaptiveTuningConfig {
+        enabled: config.enabled,
+        aggressiveness: match config.aggressiveness {
+            crate::types::search::TuningAggressiveness::Conservative => {
+                crate::types::all::TuningAggressiveness::Conservative
+            }
+            crate::types::search::TuningAggressiveness::Moderate => {
+                crate::types::all::TuningAggressiveness::Moderate
+            }
+            crate::types::search::TuningAggressiveness::Aggressive => {
+                crate::types::all::TuningAggressiveness::Aggressive
+            }
+        },
+        min_data_threshold: config.min_data_threshold,
+    }
+}
+
+fn convert_advanced_reduction_config_back(
+    config: &crate::types::search::AdvancedReductionConfig,
+) -> crate::types::all::AdvancedReductionConfig {
+    crate::types::all::AdvancedReductionConfig {
+        enabled: config.enabled,
+        strategy: match config.strategy {
+            crate::types::search::AdvancedReductionStrategy::Basic => {
+                crate::types::all::AdvancedReductionStrategy::Basic
+            }
+            crate::types::search::AdvancedReductionStrategy::DepthBased => {
+                crate::types::all::AdvancedReductionStrategy::DepthBased
+            }
+            crate::types::search::AdvancedReductionStrategy::MaterialBased => {
+                crate::types::all::AdvancedReductionStrategy::MaterialBased
+            }
+            crate::types::search::AdvancedReductionStrategy::HistoryBased => {
+                crate::types::all::AdvancedReductionStrategy::HistoryBased
+            }
+            crate::types::search::AdvancedReductionStrategy::Combined => {
+                crate::types::all::AdvancedReductionStrategy::Combined
+            }
+        },
+        // The search-side config does not carry the remaining fields
+        ..crate::types::all::AdvancedReductionConfig::default()
+    }
+}
+
+fn convert_conditional_exemption_config_back(
+    config: &crate::types::search::ConditionalExemptionConfig,
+) -> crate::types::all::ConditionalExemptionConfig {
+    crate::types::all::ConditionalExemptionConfig {
+        enable_conditional_capture_exemption: config.enable_conditional_capture_exemption,
+        min_capture_value_threshold: config.min_capture_value_threshold,
+        min_depth_for_conditional_capture: config.min_depth_for_conditional_capture,
+        enable_conditional_promotion_exemption: config.enable_conditional_promotion_exemption,
+        exempt_tactical_promotions_only: config.exempt_tactical_promotions_only,
+        min_depth_for_conditional_promotion: config.min_depth_for_conditional_promotion,
+    }
 }
 
 fn convert_aspiration_config_back(
@@ -985,11 +1201,67 @@ fn convert_aspiration_config_back(
 }
 
 fn convert_iid_config_back(
-    _config: &crate::types::search::IIDConfig,
+    config: &crate::types::search::IIDConfig,
 ) -> crate::types::all::IIDConfig {
-    // This is a simplified conversion - full conversion would require all fields
-    crate::types::all::IIDConfig::default() // Use default for now, full
-                                            // conversion would be complex
+    crate::types::all::IIDConfig {
+        enabled: config.enabled,
+        min_depth: config.min_depth,
+        iid_depth_ply: config.iid_depth_ply,
+        max_legal_moves: config.max_legal_moves,
+        time_overhead_threshold: config.time_overhead_threshold,
+        depth_strategy: match config.depth_strategy {
+            crate::types::search::IIDDepthStrategy::Fixed => {
+                crate::types::all::IIDDepthStrategy::Fixed
+            }
+            crate::types::search::IIDDepthStrategy::Relative => {
+                crate::types::all::IIDDepthStrategy::Relative
+            }
+            crate::types::search::IIDDepthStrategy::Dynamic => {
+                crate::types::all::IIDDepthStrategy::Dynamic
+            }
+            crate::types::search::IIDDepthStrategy::Adaptive => {
+                crate::types::all::IIDDepthStrategy::Adaptive
+            }
+        },
+        enable_time_pressure_detection: config.enable_time_pressure_detection,
+        enable_adaptive_tuning: config.enable_adaptive_tuning,
+        dynamic_base_depth: config.dynamic_base_depth,
+        dynamic_max_depth: config.dynamic_max_depth,
+        adaptive_min_depth: config.adaptive_min_depth,
+        max_estimated_iid_time_ms: config.max_estimated_iid_time_ms,
+        max_estimated_iid_time_percentage: config.max_estimated_iid_time_percentage,
+        enable_complexity_based_adjustments: config.enable_complexity_based_adjustments,
+        complexity_threshold_low: config.complexity_threshold_low,
+        complexity_threshold_medium: config.complexity_threshold_medium,
+        complexity_depth_adjustment_low: config.complexity_depth_adjustment_low,
+        complexity_depth_adjustment_medium: config.complexity_depth_adjustment_medium,
+        complexity_depth_adjustment_high: config.complexity_depth_adjustment_high,
+        enable_adaptive_move_count_threshold: config.enable_adaptive_move_count_threshold,
+        tactical_move_count_multiplier: config.tactical_move_count_multiplier,
+        quiet_move_count_multiplier: config.quiet_move_count_multiplier,
+        time_pressure_base_threshold: config.time_pressure_base_threshold,
+        time_pressure_complexity_multiplier: config.time_pressure_complexity_multiplier,
+        time_pressure_depth_multiplier: config.time_pressure_depth_multiplier,
+        tt_move_min_depth_for_skip: config.tt_move_min_depth_for_skip,
+        tt_move_max_age_for_skip: config.tt_move_max_age_for_skip,
+        preset: config.preset.clone().map(|p| match p {
+            crate::types::search::IIDPreset::Aggressive => crate::types::all::IIDPreset::Aggressive,
+            crate::types::search::IIDPreset::Conservative => {
+                crate::types::all::IIDPreset::Conservative
+            }
+            crate::types::search::IIDPreset::Balanced => crate::types::all::IIDPreset::Balanced,
+        }),
+        enable_game_phase_based_adjustment: config.enable_game_phase_based_adjustment,
+        enable_material_based_adjustment: config.enable_material_based_adjustment,
+        enable_time_based_adjustment: config.enable_time_based_adjustment,
+        game_phase_opening_multiplier: config.game_phase_opening_multiplier,
+        game_phase_middlegame_multiplier: config.game_phase_middlegame_multiplier,
+        game_phase_endgame_multiplier: config.game_phase_endgame_multiplier,
+        material_depth_multiplier: config.material_depth_multiplier,
+        material_threshold_for_adjustment: config.material_threshold_for_adjustment,
+        time_depth_multiplier: config.time_depth_multiplier,
+        time_threshold_for_adjustment: config.time_threshold_for_adjustment,
+    }
 }
 
 fn convert_iid_stats_to_all(stats: &crate::types::search::IIDStats) -> crate::types::all::IIDStats {
@@ -1236,8 +1508,9 @@ impl SearchEngine {
             }
         }
         // Fallback: write to local TT without holding shared lock
+        let table = self.transposition_table.read().unwrap_or_else(PoisonError::into_inner);
         for e in self.tt_write_buffer.drain(..) {
-            self.transposition_table.store(e);
+            table.store(e);
         }
     }
 
@@ -1254,9 +1527,8 @@ impl SearchEngine {
         if entry.source != crate::types::EntrySource::MainSearch {
             // This is an auxiliary search entry (NMP, IID, etc.)
             // Check if there's an existing entry that should be preserved
-            if let Some(existing) =
-                self.transposition_table.probe_with_prefetch(entry.hash_key, 0, None)
-            {
+            let existing = self.tt().probe_with_prefetch(entry.hash_key, 0, None);
+            if let Some(existing) = existing {
                 // Only prevent overwrite if existing entry is from MainSearch AND deeper
                 if existing.source == crate::types::EntrySource::MainSearch
                     && existing.depth > entry.depth
@@ -1276,9 +1548,8 @@ impl SearchEngine {
             }
         } else {
             // MainSearch entry - track if it's preserving a main entry
-            if let Some(existing) =
-                self.transposition_table.probe_with_prefetch(entry.hash_key, 0, None)
-            {
+            let existing = self.tt().probe_with_prefetch(entry.hash_key, 0, None);
+            if let Some(existing) = existing {
                 if existing.source == crate::types::EntrySource::MainSearch {
                     self.core_search_metrics.tt_main_entries_preserved += 1;
                 }
@@ -1319,7 +1590,7 @@ impl SearchEngine {
                 self.flush_tt_buffer();
             }
         } else {
-            self.transposition_table.store(entry);
+            self.tt().store(entry);
         }
 
         // Record TT store profiling (Task 3.0)
@@ -1348,12 +1619,21 @@ impl SearchEngine {
             evaluator: PositionEvaluator::new(),
             move_generator: MoveGenerator::new(),
             tablebase: MicroTablebase::new(),
-            transposition_table: crate::search::ThreadSafeTranspositionTable::new(config),
+            transposition_table: Arc::new(RwLock::new(
+                crate::search::ThreadSafeTranspositionTable::new(config),
+            )),
             shared_transposition_table: None,
             hash_calculator: crate::search::ShogiHashHandler::new(1000),
+            handicap: Handicap::Even,
+            plies_to_draw: None,
+            clock: system_clock(),
+            best_move_effort: 0.0,
             move_orderer: crate::search::TranspositionMoveOrderer::new(),
             advanced_move_orderer: MoveOrdering::new(),
-            quiescence_tt: HashMap::with_capacity(quiescence_capacity),
+            quiescence_tt: DeterministicHashMap::with_capacity_and_hasher(
+                quiescence_capacity,
+                Default::default(),
+            ),
             quiescence_tt_age: 0,
             history_table: [[0; 9]; 9],
             killer_moves: [None, None],
@@ -1380,6 +1660,8 @@ impl SearchEngine {
             time_management_config: TimeManagementConfig::default(),
             // Legacy stats fields (access through helper modules)
             quiescence_stats: QuiescenceStats::default(),
+            drop_quiescence_config: DropQuiescenceConfig::default(),
+            drop_quiescence_stats: DropQuiescenceStats::default(),
             null_move_stats: NullMoveStats::default(),
             lmr_stats: LMRStats::default(),
             aspiration_stats: AspirationWindowStats::default(),
@@ -1440,6 +1722,9 @@ impl SearchEngine {
             tt_buffer_entries_written: 0,
             time_budget_stats: TimeBudgetStats::default(),
             time_check_node_counter: 0,
+            node_deadline: None,
+            tree_recorder: None,
+            search_reporter: Arc::new(NullReporter),
             // nodes_searched removed
         };
         engine.parallel_options.hash_size_mb = hash_size_mb;
@@ -1454,7 +1739,28 @@ impl SearchEngine {
 
     /// Initialize the move orderer with the transposition table
     fn initialize_move_orderer(&mut self) {
-        self.move_orderer.set_transposition_table(&self.transposition_table);
+        self.move_orderer.set_transposition_table(self.transposition_table.clone());
+    }
+
+    /// Read access to the main transposition table
+    #[inline]
+    fn tt(&self) -> RwLockReadGuard<'_, crate::search::ThreadSafeTranspositionTable> {
+        self.transposition_table.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Exclusive access to the main transposition table, for clearing and
+    /// bulk loads
+    fn tt_mut(&mut self) -> RwLockWriteGuard<'_, crate::search::ThreadSafeTranspositionTable> {
+        self.transposition_table.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Handle to the main transposition table, shared with parallel workers
+    /// and Lazy SMP helpers so that they search into the same table as this
+    /// engine and keep it across moves.
+    pub fn transposition_table_handle(
+        &self,
+    ) -> Arc<RwLock<crate::search::ThreadSafeTranspositionTable>> {
+        self.transposition_table.clone()
     }
 
     /// Initialize advanced move ordering system
@@ -1491,10 +1797,10 @@ impl SearchEngine {
                 r
             } else {
                 TT_TRY_READ_FAILS.fetch_add(1, Ordering::Relaxed);
-                self.transposition_table.probe_with_prefetch(position_hash, depth, None)
+                self.tt().probe_with_prefetch(position_hash, depth, None)
             }
         } else {
-            self.transposition_table.probe_with_prefetch(position_hash, depth, None)
+            self.tt().probe_with_prefetch(position_hash, depth, None)
         };
         if let Some(tt_entry) = tt_entry_opt {
             let _ = self.advanced_move_orderer.integrate_with_transposition_table(
@@ -1512,6 +1818,31 @@ impl SearchEngine {
         self.search_statistics.get_nodes_searched()
     }
 
+    /// Entries this engine has written to a shared transposition table
+    pub fn shared_tt_store_writes(&self) -> u64 {
+        self.shared_tt_store_writes
+    }
+
+    /// Nodes searched by this engine since it was created, unaffected by
+    /// searches running in other engines of the process
+    pub fn total_nodes_searched(&self) -> u64 {
+        self.search_statistics.get_total_nodes_searched()
+    }
+
+    /// Stop the search once `limit` more nodes have been searched; `None`
+    /// removes the limit
+    pub fn set_node_limit(&mut self, limit: Option<u64>) {
+        self.node_deadline = limit.map(|nodes| self.total_nodes_searched() + nodes);
+    }
+
+    fn stop_requested(&self) -> bool {
+        self.stop_flag.as_ref().is_some_and(|flag| flag.load(Ordering::Relaxed))
+    }
+
+    fn node_limit_reached(&self) -> bool {
+        self.node_deadline.is_some_and(|deadline| self.total_nodes_searched() >= deadline)
+    }
+
     /// Set a shared transposition table for reporting and ordering in parallel
     /// contexts.
     pub fn set_shared_transposition_table(
@@ -1521,6 +1852,67 @@ impl SearchEngine {
         self.shared_transposition_table = Some(shared);
     }
 
+    /// Detach the shared transposition table and return to the local one.
+    pub fn clear_shared_transposition_table(&mut self) {
+        self.flush_tt_buffer();
+        self.shared_transposition_table = None;
+    }
+
+    /// Save the main transposition table to a hash file.
+    ///
+    /// This is the table parallel workers and Lazy SMP helpers search into,
+    /// so the file holds what every thread stored. Buffered writes are
+    /// flushed first. Returns the number of entries
+    /// written.
+    pub fn save_transposition_table<P: AsRef<std::path::Path>>(
+        &mut self,
+        path: P,
+    ) -> crate::search::TranspositionResult<usize> {
+        self.flush_tt_buffer();
+        self.tt().save_to_file(path)
+    }
+
+    /// Load a hash file into the main transposition table.
+    pub fn load_transposition_table<P: AsRef<std::path::Path>>(
+        &mut self,
+        path: P,
+    ) -> crate::search::TranspositionResult<usize> {
+        self.tt().load_from_file(path)
+    }
+
+    /// Start recording the explored search tree within the given budget.
+    ///
+    /// Any tree recorded so far is discarded.
+    pub fn enable_tree_recording(&mut self, config: TreeRecorderConfig) {
+        self.tree_recorder = Some(SearchTreeRecorder::new(config));
+    }
+
+    /// Stop recording and return the recorded tree, if recording was enabled.
+    pub fn take_recorded_tree(&mut self) -> Option<SearchTree> {
+        self.tree_recorder.take().map(SearchTreeRecorder::into_tree)
+    }
+
+    /// Set the reporter receiving root `currmove` updates.
+    pub fn set_search_reporter(&mut self, reporter: Arc<dyn SearchReporter>) {
+        self.search_reporter = reporter;
+    }
+
+    /// Transposition table occupancy in permille (sampled).
+    pub fn hashfull(&self) -> u32 {
+        match self.shared_transposition_table {
+            Some(ref shared) => shared.read().map(|tt| tt.hashfull()).unwrap_or(0),
+            None => self.tt().hashfull(),
+        }
+    }
+
+    /// Apply `f` to the tree recorder when recording is enabled.
+    #[inline]
+    fn record_tree(&mut self, f: impl FnOnce(&mut SearchTreeRecorder)) {
+        if let Some(recorder) = self.tree_recorder.as_mut() {
+            f(recorder);
+        }
+    }
+
     /// Calculate tactical complexity for position-specific strategies
     fn calculate_tactical_complexity(
         &self,
@@ -1533,7 +1925,7 @@ impl SearchEngine {
         let check_count = legal_moves
             .iter()
             .filter(|m| {
-                let mut test_board = board.clone();
+                let mut test_board = board.clone_without_nnue();
                 let mut test_captured = captured_pieces.clone();
                 if let Some(captured) = test_board.make_move(m) {
                     test_captured.add_piece(captured.piece_type, player);
@@ -1743,7 +2135,7 @@ impl SearchEngine {
         // needed
 
         // Estimate TT memory (approximate based on table size)
-        let tt_memory_bytes = (self.transposition_table.size() * 100) as u64; // Approximate entry size
+        let tt_memory_bytes = (self.tt().size() * 100) as u64; // Approximate entry size
 
         // Estimate cache memory from move ordering
         let cache_memory_bytes = ordering_stats.memory_usage_bytes as u64;
@@ -1778,15 +2170,11 @@ impl SearchEngine {
         let position_hash = self.hash_calculator.get_position_hash(board, player, captured_pieces);
 
         // Probe transposition table for best move
-        if let Some(entry) =
-            self.transposition_table.probe_with_prefetch(position_hash, depth, None)
-        {
+        if let Some(entry) = self.tt().probe_with_prefetch(position_hash, depth, None) {
             entry.best_move.clone()
         } else {
             // Try with maximum depth if not found at current depth
-            if let Some(entry) =
-                self.transposition_table.probe_with_prefetch(position_hash, 255, None)
-            {
+            if let Some(entry) = self.tt().probe_with_prefetch(position_hash, 255, None) {
                 entry.best_move.clone()
             } else {
                 None
@@ -1816,12 +2204,21 @@ impl SearchEngine {
             evaluator: PositionEvaluator::new(),
             move_generator: MoveGenerator::new(),
             tablebase: MicroTablebase::new(),
-            transposition_table: crate::search::ThreadSafeTranspositionTable::new(tt_config),
+            transposition_table: Arc::new(RwLock::new(
+                crate::search::ThreadSafeTranspositionTable::new(tt_config),
+            )),
             shared_transposition_table: None,
             hash_calculator: crate::search::ShogiHashHandler::new(1000),
+            handicap: Handicap::Even,
+            plies_to_draw: None,
+            clock: system_clock(),
+            best_move_effort: 0.0,
             move_orderer: crate::search::TranspositionMoveOrderer::new(),
             advanced_move_orderer: MoveOrdering::new(),
-            quiescence_tt: HashMap::with_capacity(quiescence_capacity),
+            quiescence_tt: DeterministicHashMap::with_capacity_and_hasher(
+                quiescence_capacity,
+                Default::default(),
+            ),
             quiescence_tt_age: 0,
             history_table: [[0; 9]; 9],
             killer_moves: [None, None],
@@ -1850,6 +2247,8 @@ impl SearchEngine {
             time_management_config: convert_time_management_config(&config.time_management),
             // Legacy stats fields (access through helper modules)
             quiescence_stats: QuiescenceStats::default(),
+            drop_quiescence_config: DropQuiescenceConfig::default(),
+            drop_quiescence_stats: DropQuiescenceStats::default(),
             null_move_stats: NullMoveStats::default(),
             lmr_stats: LMRStats::default(),
             aspiration_stats: AspirationWindowStats::default(),
@@ -1912,6 +2311,9 @@ impl SearchEngine {
             tt_buffer_entries_written: 0,
             time_budget_stats: TimeBudgetStats::default(),
             time_check_node_counter: 0,
+            node_deadline: None,
+            tree_recorder: None,
+            search_reporter: Arc::new(NullReporter),
             // nodes_searched removed
         };
         if engine.debug_logging {
@@ -1961,6 +2363,7 @@ impl SearchEngine {
 
         // Reset statistics when configuration changes
         self.quiescence_stats.reset();
+        self.drop_quiescence_stats.reset();
         self.null_move_stats.reset();
         self.lmr_stats.reset();
         self.aspiration_stats.reset();
@@ -2010,7 +2413,7 @@ impl SearchEngine {
             lmr: convert_lmr_config_back(&self.lmr_config),
             aspiration_windows: convert_aspiration_config_back(&self.aspiration_config),
             iid: convert_iid_config_back(&self.iid_config),
-            tt_size_mb: self.transposition_table.size() * 100 / (1024 * 1024), // Approximate
+            tt_size_mb: self.tt().size() * 100 / (1024 * 1024), // Approximate
             debug_logging: self.debug_logging,
             max_depth: 20, // This would need to be tracked separately
             time_management: convert_time_management_config_back(&self.time_management_config),
@@ -2028,7 +2431,7 @@ impl SearchEngine {
     /// Prefill the transposition table with entries derived from an opening
     /// book. Returns the number of entries inserted.
     pub fn prefill_tt_from_opening_book(&mut self, book: &mut OpeningBook, depth: u8) -> usize {
-        self.transposition_table.prefill_from_book(book, depth)
+        self.tt_mut().prefill_from_book(book, depth)
     }
 
     /// Check if opening book prefill is enabled in the current configuration.
@@ -2075,21 +2478,29 @@ impl SearchEngine {
         
         let start_row = if player == Player::Black { 8 } else { 0 };
         let mut undeveloped_major_pieces = 0;
-        
-        // Count undeveloped rooks and bishops
+
+        // A two-piece or larger handicap starts White without major pieces;
+        // its golds stand in as the development indicator
+        let removed = self.handicap.removed_pieces();
+        let indicators: &[PieceType] = if player == Player::White
+            && removed.contains(&PieceType::Rook)
+            && removed.contains(&PieceType::Bishop)
+        {
+            &[PieceType::Gold]
+        } else {
+            &[PieceType::Rook, PieceType::Bishop]
+        };
+
+        // Count undeveloped indicator pieces
         for row in 0..9 {
             for col in 0..9 {
                 let pos = crate::types::core::Position::new(row, col);
                 if let Some(piece) = board.get_piece(pos) {
-                    if piece.player == player {
-                        match piece.piece_type {
-                            PieceType::Rook | PieceType::Bishop => {
-                                if pos.row == start_row {
-                                    undeveloped_major_pieces += 1;
-                                }
-                            }
-                            _ => {}
-                        }
+                    if piece.player == player
+                        && indicators.contains(&piece.piece_type)
+                        && pos.row == start_row
+                    {
+                        undeveloped_major_pieces += 1;
                     }
                 }
             }
@@ -2317,9 +2728,7 @@ impl SearchEngine {
             {
                 let position_hash =
                     self.hash_calculator.get_position_hash(board, current_player, captured);
-                if let Some(tt_entry) =
-                    self.transposition_table.probe_with_prefetch(position_hash, 0, None)
-                {
+                if let Some(tt_entry) = self.tt().probe_with_prefetch(position_hash, 0, None) {
                     // Task 9.6: Check if TT entry depth is sufficient and age is acceptable
                     let tt_depth_ok = tt_entry.depth >= self.iid_config.tt_move_min_depth_for_skip;
                     let tt_age_ok = tt_entry.age <= self.iid_config.tt_move_max_age_for_skip;
@@ -2347,9 +2756,7 @@ impl SearchEngine {
                 {
                     let position_hash =
                         self.hash_calculator.get_position_hash(board, current_player, captured);
-                    if let Some(entry) =
-                        self.transposition_table.probe_with_prefetch(position_hash, 0, None)
-                    {
+                    if let Some(entry) = self.tt().probe_with_prefetch(position_hash, 0, None) {
                         trace_log!(
                             "IID_TT_MOVE",
                             &format!(
@@ -2740,7 +3147,7 @@ impl SearchEngine {
         time_limit_ms: u32,
         _hash_history: &mut Vec<u64>,
     ) -> (i32, Option<Move>) {
-        let iid_start_time = TimeSource::now();
+        let iid_start_time = self.start_timer();
         let initial_nodes = self.search_statistics.get_nodes_searched();
 
         // Create local hash_history for IID search (Task 5.2)
@@ -2841,8 +3248,7 @@ impl SearchEngine {
         // The TT might have a better move if the position was searched before
         let position_hash = self.hash_calculator.get_position_hash(board, player, captured_pieces);
         let mut best_move_from_tt: Option<Move> = None;
-        if let Some(entry) = self.transposition_table.probe_with_prefetch(position_hash, 255, None)
-        {
+        if let Some(entry) = self.tt().probe_with_prefetch(position_hash, 255, None) {
             if let Some(ref tt_move) = entry.best_move {
                 // Task 2.8: Verify IID move is in legal moves list before using
                 if legal_moves.iter().any(|m| self.moves_equal(m, tt_move)) {
@@ -2893,8 +3299,7 @@ impl SearchEngine {
         captured_pieces: &CapturedPieces,
     ) -> Option<Move> {
         let position_hash = self.hash_calculator.get_position_hash(board, player, captured_pieces);
-        if let Some(entry) = self.transposition_table.probe_with_prefetch(position_hash, 255, None)
-        {
+        if let Some(entry) = self.tt().probe_with_prefetch(position_hash, 255, None) {
             entry.best_move.clone()
         } else {
             None
@@ -4995,14 +5400,14 @@ impl SearchEngine {
             IIDPerformanceBenchmark { iterations, depth, time_limit_ms, ..Default::default() };
 
         for iteration in 0..iterations {
-            let _start_time = TimeSource::now();
+            let _start_time = self.start_timer();
             let mut hash_history = Vec::new();
 
             // Benchmark with IID enabled
             let iid_config = self.iid_config.clone();
             self.iid_config.enabled = true;
 
-            let iid_start = TimeSource::now();
+            let iid_start = self.start_timer();
             let iid_result = self.negamax_with_context(
                 board,
                 captured_pieces,
@@ -5025,7 +5430,7 @@ impl SearchEngine {
 
             // Benchmark with IID disabled
             self.iid_config.enabled = false;
-            let non_iid_start = TimeSource::now();
+            let non_iid_start = self.start_timer();
             let non_iid_result = self.negamax_with_context(
                 board,
                 captured_pieces,
@@ -5289,7 +5694,7 @@ impl SearchEngine {
         let max_moves = 200; // Prevent infinite games
 
         while move_count < max_moves {
-            let _start_time = TimeSource::now();
+            let _start_time = self.start_timer();
             let mut hash_history = Vec::new();
 
             // Find best move
@@ -5350,7 +5755,7 @@ impl SearchEngine {
         time_limit_ms: u32,
         _hash_history: &mut Vec<u64>,
     ) -> Option<Move> {
-        let start_time = TimeSource::now();
+        let start_time = self.start_timer();
 
         // Create local hash_history for search (Task 5.2)
         let initial_hash = self.hash_calculator.get_position_hash(board, player, captured_pieces);
@@ -5700,7 +6105,7 @@ impl SearchEngine {
 
         self.search_statistics.reset_nodes();
         self.current_depth = depth;
-        let start_time = TimeSource::now();
+        let start_time = self.start_timer();
         let mut alpha = alpha;
 
         let mut best_move: Option<Move> = None;
@@ -5788,11 +6193,24 @@ impl SearchEngine {
         let mut hash_history: Vec<u64> =
             vec![self.hash_calculator.get_position_hash(board, player, captured_pieces)];
 
+        if self.tree_recorder.is_some() {
+            let root_sfen = board.to_fen(player, captured_pieces);
+            self.record_tree(|r| r.begin_pass(&root_sfen, depth));
+        }
+
+        let mut root_move_nodes: Vec<u64> = Vec::with_capacity(sorted_moves.len());
         for (move_index, move_) in sorted_moves.iter().enumerate() {
             if self.should_stop(&start_time, time_limit_ms) {
                 trace_log!("SEARCH_AT_DEPTH", "Time limit reached, stopping move evaluation",);
                 break;
             }
+            if start_time.elapsed_ms() >= CURRMOVE_REPORT_DELAY_MS {
+                self.search_reporter.report(SearchEvent::CurrentMove {
+                    depth,
+                    mv: move_.to_usi_string(),
+                    number: move_index + 1,
+                });
+            }
 
             trace_log!(
                 "SEARCH_AT_DEPTH",
@@ -5833,6 +6251,8 @@ impl SearchEngine {
                 }
             }
 
+            let nodes_before = self.search_statistics.get_nodes_searched();
+            self.record_tree(|r| r.begin_child(move_, depth, alpha, beta, move_index + 1));
             let score = -self.negamax(
                 &mut *board,
                 &new_captured,
@@ -5845,7 +6265,9 @@ impl SearchEngine {
                 &mut hash_history,
                 true,
             );
+            self.record_tree(|r| r.end_child(score));
             crate::debug_utils::end_timing(&format!("move_eval_{}", move_index), "SEARCH_AT_DEPTH");
+            root_move_nodes.push(self.search_statistics.get_nodes_searched() - nodes_before);
 
             // Restore board state by unmaking the move
             board.unmake_move(&move_info);
@@ -6028,6 +6450,15 @@ impl SearchEngine {
             );
         }
 
+        // Share of the root nodes spent on the best move, for time management
+        let total_root_nodes: u64 = root_move_nodes.iter().sum();
+        self.best_move_effort = best_move
+            .as_ref()
+            .and_then(|best| sorted_moves.iter().position(|mv| mv == best))
+            .and_then(|index| root_move_nodes.get(index))
+            .filter(|_| total_root_nodes > 0)
+            .map_or(0.0, |&nodes| nodes as f64 / total_root_nodes as f64);
+
         // Validate move tracking consistency
         self.validate_move_tracking(&best_move, best_score, sorted_moves.len());
 
@@ -6211,6 +6642,7 @@ impl SearchEngine {
         let time_pressure = self.calculate_time_pressure_level(start_time, time_limit_ms);
 
         if self.should_stop(&start_time, time_limit_ms) {
+            self.record_tree(|r| r.update_current(|n| n.outcome = NodeOutcome::Stopped));
             // Try to return a meaningful score instead of 0
             if let Some(best_score) = best_score_tracked {
                 crate::debug_utils::trace_log(
@@ -6229,6 +6661,7 @@ impl SearchEngine {
         }
         // Track nodes and seldepth through SearchStatistics (Task 1.8)
         self.search_statistics.increment_nodes();
+        self.clock.on_node();
         // Track total nodes for metrics (Task 5.7)
         self.core_search_metrics.total_nodes += 1;
         // Update seldepth (selective depth) - track maximum depth reached
@@ -6249,6 +6682,7 @@ impl SearchEngine {
 
         // Task 7.0.4.2: Evaluate position once at entry and cache for reuse
         let cached_static_eval = self.evaluate_position(board, player, captured_pieces);
+        self.record_tree(|r| r.update_current(|n| n.static_eval = Some(cached_static_eval)));
 
         // Hash-based repetition detection (Task 5.1-5.3)
         // Use hash_calculator's built-in repetition detection instead of FEN strings
@@ -6257,12 +6691,16 @@ impl SearchEngine {
         // in hash_history
         let repetition_state = self.hash_calculator.get_repetition_state_for_hash(position_hash);
         if repetition_state.is_draw() {
+            self.record_tree(|r| r.update_current(|n| n.outcome = NodeOutcome::Repetition));
             crate::debug_utils::trace_log(
                 "NEGAMAX",
                 "Repetition detected (hash-based), returning 0 (draw)",
             );
             return 0; // Repetition is a draw
         }
+        if !is_root && self.plies_to_draw.is_some_and(|plies| u32::from(depth_from_root) >= plies) {
+            return 0; // The game is drawn by the move limit
+        }
 
         // Add current position hash to search history (Task 5.2)
         // Also add to hash_calculator's global history for game-wide repetition
@@ -6277,7 +6715,7 @@ impl SearchEngine {
         let tt_probe_start =
             if self.auto_profiling_enabled { Some(std::time::Instant::now()) } else { None };
 
-        let tt_entry = self.transposition_table.probe_with_prefetch(position_hash, depth, None);
+        let tt_entry = self.tt().probe_with_prefetch(position_hash, depth, None);
 
         // Record TT probe profiling (Task 3.0)
         if let Some(start) = tt_probe_start {
@@ -6288,6 +6726,12 @@ impl SearchEngine {
         if let Some(entry) = tt_entry {
             // Track TT hit (Task 5.7)
             self.core_search_metrics.total_tt_hits += 1;
+            let tt_probe = match entry.flag {
+                TranspositionFlag::Exact => TtProbe::Exact,
+                TranspositionFlag::LowerBound => TtProbe::LowerBound,
+                TranspositionFlag::UpperBound => TtProbe::UpperBound,
+            };
+            self.record_tree(|r| r.update_current(|n| n.tt_probe = tt_probe));
 
             // Track TT hit type (Task 5.7)
             match entry.flag {
@@ -6300,6 +6744,7 @@ impl SearchEngine {
                             entry.depth, entry.score
                         ),
                     );
+                    self.record_tree(|r| r.update_current(|n| n.outcome = NodeOutcome::TtCutoff));
                     return entry.score;
                 }
                 TranspositionFlag::LowerBound => {
@@ -6313,6 +6758,9 @@ impl SearchEngine {
                     );
                     if entry.score >= beta {
                         trace_log!("NEGAMAX", "TT lower bound cutoff");
+                        self.record_tree(|r| {
+                            r.update_current(|n| n.outcome = NodeOutcome::TtCutoff)
+                        });
                         return entry.score;
                     }
                 }
@@ -6327,6 +6775,9 @@ impl SearchEngine {
                     );
                     if entry.score <= alpha {
                         trace_log!("NEGAMAX", "TT upper bound cutoff");
+                        self.record_tree(|r| {
+                            r.update_current(|n| n.outcome = NodeOutcome::TtCutoff)
+                        });
                         return entry.score;
                     }
                 }
@@ -6381,6 +6832,7 @@ impl SearchEngine {
             // made within the recursive call are unmade before returning.
             // Unit tests verify this behavior (see test_null_move_board_state_isolation).
 
+            self.record_tree(|r| r.suspend());
             let null_move_score = self.perform_null_move_search(
                 board,
                 captured_pieces,
@@ -6391,6 +6843,7 @@ impl SearchEngine {
                 time_limit_ms,
                 &mut local_null_hash_history,
             );
+            self.record_tree(|r| r.resume());
 
             crate::debug_utils::end_timing("null_move_search", "NULL_MOVE");
 
@@ -6406,6 +6859,7 @@ impl SearchEngine {
                     Some(null_move_score),
                 );
                 self.null_move_stats.cutoffs += 1;
+                self.record_tree(|r| r.update_current(|n| n.outcome = NodeOutcome::NullMoveCutoff));
                 return beta;
             } else if self.is_mate_threat_score(null_move_score, beta) {
                 // Null move failed but score suggests mate threat - perform mate threat
@@ -6422,6 +6876,7 @@ impl SearchEngine {
                 crate::debug_utils::start_timing("mate_threat_verification");
 
                 // Use same hash history for mate threat verification
+                self.record_tree(|r| r.suspend());
                 let mate_threat_score = self.perform_mate_threat_verification(
                     board,
                     captured_pieces,
@@ -6432,6 +6887,7 @@ impl SearchEngine {
                     time_limit_ms,
                     &mut local_null_hash_history,
                 );
+                self.record_tree(|r| r.resume());
 
                 crate::debug_utils::end_timing("mate_threat_verification", "MATE_THREAT");
 
@@ -6447,6 +6903,9 @@ impl SearchEngine {
                         Some(mate_threat_score),
                     );
                     self.null_move_stats.cutoffs += 1;
+                    self.record_tree(|r| {
+                        r.update_current(|n| n.outcome = NodeOutcome::NullMoveCutoff)
+                    });
                     return beta;
                 } else {
                     // Mate threat verification failed - continue with verification search or full
@@ -6478,6 +6937,7 @@ impl SearchEngine {
                 crate::debug_utils::start_timing("verification_search");
 
                 // Use same hash history for verification search
+                self.record_tree(|r| r.suspend());
                 let verification_score = self.perform_verification_search(
                     board,
                     captured_pieces,
@@ -6488,6 +6948,7 @@ impl SearchEngine {
                     time_limit_ms,
                     &mut local_null_hash_history,
                 );
+                self.record_tree(|r| r.resume());
 
                 crate::debug_utils::end_timing("verification_search", "VERIFICATION");
 
@@ -6504,6 +6965,9 @@ impl SearchEngine {
                     );
                     self.null_move_stats.verification_cutoffs += 1;
                     self.null_move_stats.cutoffs += 1;
+                    self.record_tree(|r| {
+                        r.update_current(|n| n.outcome = NodeOutcome::NullMoveCutoff)
+                    });
                     return beta;
                 } else {
                     // Both null move and verification failed - continue with full search
@@ -6531,6 +6995,7 @@ impl SearchEngine {
             // crate::debug_utils::trace_log("QUIESCENCE", &format!("Starting quiescence
             // search (alpha: {}, beta: {})", alpha, beta));
             crate::debug_utils::start_timing("quiescence_search");
+            let extensions_before = self.quiescence_stats.extensions;
             let result = self.quiescence_search(
                 board,
                 captured_pieces,
@@ -6542,6 +7007,13 @@ impl SearchEngine {
                 5,
             );
             crate::debug_utils::end_timing("quiescence_search", "QUIESCENCE");
+            let extensions = self.quiescence_stats.extensions.saturating_sub(extensions_before);
+            self.record_tree(|r| {
+                r.update_current(|n| {
+                    n.outcome = NodeOutcome::Quiescence;
+                    n.extension = extensions.min(u8::MAX as u64) as u8;
+                })
+            });
             // crate::debug_utils::trace_log("QUIESCENCE", &format!("Quiescence search
             // completed: score={}", result));
             return result;
@@ -6557,6 +7029,7 @@ impl SearchEngine {
         if legal_moves.is_empty() {
             let is_check = board.is_king_in_check(player, captured_pieces);
             let score = if is_check { -100000 } else { 0 };
+            self.record_tree(|r| r.update_current(|n| n.outcome = NodeOutcome::NoLegalMoves));
             crate::debug_utils::trace_log(
                 "NEGAMAX",
                 &format!("No legal moves: check={}, score={}", is_check, score),
@@ -6571,10 +7044,7 @@ impl SearchEngine {
 
         // === INTERNAL ITERATIVE DEEPENING (IID) ===
         let mut iid_move = None;
-        let tt_move = self
-            .transposition_table
-            .probe(position_hash, 255)
-            .and_then(|entry| entry.best_move.clone());
+        let tt_move = self.tt().probe(position_hash, 255).and_then(|entry| entry.best_move.clone());
 
         // Task 7.0.2.6, 7.0.2.9: Skip IID at Medium/High time pressure, allow at
         // Low/None
@@ -6630,12 +7100,13 @@ impl SearchEngine {
             // Task 5.8: Estimate IID time before performing IID for accuracy tracking
             let estimated_iid_time_ms = self.estimate_iid_time(board, captured_pieces, iid_depth);
 
-            let iid_start_time = TimeSource::now();
+            let iid_start_time = self.start_timer();
             // Create local hash_history for IID call (Task 5.2)
             let initial_hash =
                 self.hash_calculator.get_position_hash(board, player, captured_pieces);
             let mut local_hash_history = vec![initial_hash];
             // Task 2.0: Receive (score, best_move) tuple from perform_iid_search
+            self.record_tree(|r| r.suspend());
             let (iid_score_result, iid_move_result) = self.perform_iid_search(
                 &mut board.clone(),
                 captured_pieces,
@@ -6647,6 +7118,7 @@ impl SearchEngine {
                 time_limit_ms,
                 &mut local_hash_history,
             );
+            self.record_tree(|r| r.resume());
             iid_move = iid_move_result;
 
             let actual_iid_time = iid_start_time.elapsed_ms();
@@ -6887,6 +7359,10 @@ impl SearchEngine {
                         "NEGAMAX",
                         &format!("Move {} pruned by advanced pruning", move_.to_usi_string()),
                     );
+                    let static_eval = all_search_state.static_eval;
+                    self.record_tree(|r| {
+                        r.record_pruned(move_, depth, alpha, beta, move_index, static_eval)
+                    });
                     continue; // Skip this move
                 }
             }
@@ -6915,6 +7391,7 @@ impl SearchEngine {
             }
 
             crate::debug_utils::start_timing(&format!("move_search_{}", move_index));
+            self.record_tree(|r| r.begin_child(move_, depth, alpha, beta, move_index));
             // Task 2.6: Pass current move as opponent_last_move to recursive call
             // Task 7.0.1: Pass IID move for explicit exemption from LMR
             // Task 7.0.3.4: Pass entry source for TT priority management
@@ -6938,6 +7415,7 @@ impl SearchEngine {
                 iid_move.as_ref(), // Task 7.0.1: Pass IID move for explicit exemption from LMR
                 entry_source,      // Task 7.0.3.4: Pass entry source for TT priority management
             );
+            self.record_tree(|r| r.end_child(score));
             crate::debug_utils::end_timing(&format!("move_search_{}", move_index), "NEGAMAX");
 
             // Restore board state by unmaking the move
@@ -7362,6 +7840,7 @@ impl SearchEngine {
 
         // Update statistics
         self.quiescence_stats.nodes_searched += 1;
+        self.clock.on_node();
         // Update seldepth (selective depth) - quiescence extends beyond normal depth
         // When we enter quiescence, depth is 0, so we've reached current_depth plies
         // Quiescence can extend deeper: current_depth + (max_quiescence_depth - depth)
@@ -7404,6 +7883,24 @@ impl SearchEngine {
             return score;
         }
 
+        // A side in check cannot stand pat; search its evasions instead
+        let quiescence_ply = recursion_depth.0;
+        if self.drop_quiescence_config.enabled
+            && self.drop_quiescence_config.enable_evasions
+            && board.is_king_in_check(player, captured_pieces)
+        {
+            return self.quiescence_evasions(
+                board,
+                captured_pieces,
+                player,
+                alpha,
+                beta,
+                start_time,
+                time_limit_ms,
+                depth,
+            );
+        }
+
         // Task 5.11: Extract TT best move as hint (if available)
         let mut tt_move_hint: Option<Move> = None;
         // Task 6.0: Extract stand-pat from TT if available
@@ -7540,7 +8037,8 @@ impl SearchEngine {
         }
 
         // crate::debug_utils::trace_log("QUIESCENCE", "Generating noisy moves");
-        let noisy_moves = self.generate_noisy_moves(board, player, captured_pieces);
+        let noisy_moves =
+            self.generate_noisy_moves_with_drops(board, player, captured_pieces, quiescence_ply);
         // crate::debug_utils::trace_log("QUIESCENCE", &format!("Found {} noisy moves",
         // noisy_moves.len()));
 
@@ -7669,7 +8167,12 @@ impl SearchEngine {
             // Apply pruning checks
             // Use adaptive pruning if enabled, otherwise use standard pruning
             // Adaptive pruning adjusts margins based on depth and total move count
-            let should_prune = if self.quiescence_config.enable_adaptive_pruning {
+            // Drops gain no material up front, so delta pruning never applies
+            // to the drops selected by drop-aware quiescence
+            let is_quiescence_drop = self.drop_quiescence_config.enabled && move_.from.is_none();
+            let should_prune = if is_quiescence_drop {
+                false
+            } else if self.quiescence_config.enable_adaptive_pruning {
                 self.should_prune_delta_adaptive(&move_, stand_pat, alpha, depth, total_move_count)
             } else {
                 self.should_prune_delta(&move_, stand_pat, alpha)
@@ -7699,6 +8202,10 @@ impl SearchEngine {
                 continue;
             }
 
+            if is_quiescence_drop {
+                self.drop_quiescence_stats.drops_searched += 1;
+            }
+
             // Use move unmaking instead of board cloning
             let move_info = board.make_move_with_info(&move_);
             let mut new_captured = captured_pieces.clone();
@@ -7802,6 +8309,9 @@ impl SearchEngine {
                     self.quiescence_stats.move_ordering_second_move_cutoffs += 1;
                 }
                 self.quiescence_stats.move_ordering_cutoffs += 1;
+                if is_quiescence_drop {
+                    self.drop_quiescence_stats.drop_cutoffs += 1;
+                }
 
                 // Task 7.6: Beta cutoff condition
                 //
@@ -7940,6 +8450,9 @@ impl SearchEngine {
     /// Check if search should stop due to time limit or stop flag
     /// Delegates to TimeManager (Task 1.8)
     fn should_stop(&mut self, start_time: &TimeSource, time_limit_ms: u32) -> bool {
+        if self.node_limit_reached() {
+            return true;
+        }
         self.time_manager.should_stop(
             start_time,
             time_limit_ms,
@@ -7951,6 +8464,9 @@ impl SearchEngine {
     /// Used when we must check time regardless of frequency (e.g., at depth
     /// boundaries)
     fn should_stop_force(&self, start_time: &TimeSource, time_limit_ms: u32) -> bool {
+        if self.node_limit_reached() {
+            return true;
+        }
         if let Some(flag) = &self.stop_flag {
             if flag.load(Ordering::Relaxed) {
                 return true;
@@ -7968,6 +8484,142 @@ impl SearchEngine {
         self.move_generator.generate_quiescence_moves(board, player, captured_pieces)
     }
 
+    /// Generate quiescence moves, selecting drops under the drop budget
+    ///
+    /// With drop-aware quiescence disabled this is [`Self::generate_noisy_moves`].
+    /// Otherwise board moves are generated without drops, and checking or
+    /// major-threatening drops are added only in the first quiescence plies,
+    /// after SEE filtering and within the per-node budget.
+    fn generate_noisy_moves_with_drops(
+        &mut self,
+        board: &BitboardBoard,
+        player: Player,
+        captured_pieces: &CapturedPieces,
+        quiescence_ply: u32,
+    ) -> Vec<Move> {
+        if !self.drop_quiescence_config.enabled {
+            return self.generate_noisy_moves(board, player, captured_pieces);
+        }
+
+        let mut moves =
+            self.move_generator
+                .generate_quiescence_board_moves(board, player, captured_pieces);
+        if self.drop_quiescence_config.drops_allowed_at(quiescence_ply) {
+            let candidates = self.move_generator.generate_quiescence_drops(
+                board,
+                player,
+                captured_pieces,
+                self.drop_quiescence_config.enable_checking_drops,
+                self.drop_quiescence_config.enable_threat_drops,
+            );
+            moves.extend(self.quiescence_helper.select_quiescence_drops(
+                &self.move_generator,
+                board,
+                captured_pieces,
+                candidates,
+                &self.drop_quiescence_config,
+                &mut self.drop_quiescence_stats,
+            ));
+        }
+        moves
+    }
+
+    /// Search check evasions inside quiescence search
+    ///
+    /// The side to move is in check, so standing pat is not an option: king
+    /// moves, captures of the checker and interpositions are searched, with
+    /// interposing drops limited by SEE and the interposition budget. No legal
+    /// evasion means mate.
+    fn quiescence_evasions(
+        &mut self,
+        board: &mut BitboardBoard,
+        captured_pieces: &CapturedPieces,
+        player: Player,
+        mut alpha: i32,
+        beta: i32,
+        start_time: &TimeSource,
+        time_limit_ms: u32,
+        depth: u8,
+    ) -> i32 {
+        self.drop_quiescence_stats.evasion_nodes += 1;
+
+        let legal_moves = self.move_generator.generate_legal_moves(board, player, captured_pieces);
+        if legal_moves.is_empty() {
+            self.drop_quiescence_stats.mates_found += 1;
+            return -100000;
+        }
+
+        let evasions = self.quiescence_helper.select_evasions(
+            &self.move_generator,
+            board,
+            captured_pieces,
+            legal_moves,
+            &self.drop_quiescence_config,
+            &mut self.drop_quiescence_stats,
+        );
+        let sorted_evasions =
+            self.sort_quiescence_moves_advanced(&evasions, board, captured_pieces, player, None);
+
+        let mut searched_any = false;
+        for move_ in &sorted_evasions {
+            if self.should_stop(start_time, time_limit_ms) {
+                break;
+            }
+
+            let is_drop = move_.from.is_none();
+            if is_drop {
+                self.drop_quiescence_stats.drops_searched += 1;
+                self.drop_quiescence_stats.interposition_drops_searched += 1;
+            }
+
+            let move_info = board.make_move_with_info(move_);
+            let mut new_captured = captured_pieces.clone();
+            if let Some(ref captured) = move_info.captured_piece {
+                new_captured.add_piece(captured.piece_type, player);
+            } else if is_drop {
+                new_captured.remove_piece(move_.piece_type, player);
+            }
+
+            let score = -self.quiescence_search(
+                board,
+                &new_captured,
+                player.opposite(),
+                beta.saturating_neg(),
+                alpha.saturating_neg(),
+                start_time,
+                time_limit_ms,
+                depth.saturating_sub(1),
+            );
+            board.unmake_move(&move_info);
+            searched_any = true;
+
+            if score >= beta {
+                if is_drop {
+                    self.drop_quiescence_stats.drop_cutoffs += 1;
+                }
+                return beta;
+            }
+            if score > alpha {
+                alpha = score;
+            }
+        }
+
+        if !searched_any {
+            // Stopped before any evasion was searched
+            return self.evaluator.evaluate_with_context(
+                board,
+                player,
+                captured_pieces,
+                depth,
+                false,
+                false,
+                false,
+                true,
+            );
+        }
+        alpha
+    }
+
     /// Sort quiescence moves using advanced move ordering
     ///
     /// Enhanced with:
@@ -8401,21 +9053,157 @@ impl SearchEngine {
     }
 
     pub fn clear(&mut self) {
-        self.transposition_table.clear();
+        self.tt_mut().clear();
         self.history_table = [[0; 9]; 9];
         self.killer_moves = [None, None];
         self.lmr_stats.reset();
     }
 
+    /// Forget everything earlier searches left behind (hash tables, ordering
+    /// heuristics and caches, statistics), so the next search depends only on
+    /// the position and the configuration
+    pub fn reset_search_state(&mut self) {
+        self.clear();
+        self.clear_quiescence_tt();
+        self.quiescence_tt_age = 0;
+        self.clear_eval_cache();
+        self.move_orderer.clear();
+        self.advanced_move_orderer.reset_stats();
+        self.tapered_search_enhancer.clear_cache();
+        self.search_statistics.reset();
+        self.reset_core_search_metrics();
+        self.reset_quiescence_stats();
+        self.reset_null_move_stats();
+        self.reset_iid_stats();
+        self.reset_aspiration_window_stats();
+        self.reset_pruning_statistics();
+        self.iid_overhead_history.clear();
+        self.previous_scores.clear();
+        self.best_move_effort = 0.0;
+    }
+
+    /// Hash of a position as used for repetition detection
+    pub fn position_hash(
+        &self,
+        board: &BitboardBoard,
+        player: Player,
+        captured_pieces: &CapturedPieces,
+    ) -> u64 {
+        self.hash_calculator.get_position_hash(board, player, captured_pieces)
+    }
+
+    /// Replace the repetition history with the positions of the game so far
+    pub fn set_game_history(&mut self, hashes: &[u64]) {
+        self.hash_calculator.clear_history();
+        for &hash in hashes {
+            self.hash_calculator.add_position_to_history(hash);
+        }
+    }
+
+    /// Set the starting array of the game, so phase estimates account for
+    /// the pieces a handicap removed
+    pub fn set_handicap(&mut self, handicap: Handicap) {
+        if self.handicap != handicap {
+            self.handicap = handicap;
+            self.evaluator.set_handicap(handicap);
+        }
+    }
+
+    /// Measure time limits on `clock` instead of the wall clock
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.clock = clock;
+    }
+
+    pub fn clock(&self) -> &Arc<dyn Clock> {
+        &self.clock
+    }
+
+    /// Share (0.0-1.0) of the last completed root search's nodes that went
+    /// into its best move; close to 1 when one move dominates
+    pub fn best_move_effort(&self) -> f64 {
+        self.best_move_effort
+    }
+
+    /// Start timing on this engine's clock
+    pub fn start_timer(&self) -> TimeSource {
+        TimeSource::start(&self.clock)
+    }
+
+    /// Game phase of a position (0 = endgame, `GAME_PHASE_MAX` = opening)
+    pub fn game_phase(&self, board: &BitboardBoard, captured_pieces: &CapturedPieces) -> i32 {
+        self.evaluator.calculate_game_phase(board, captured_pieces)
+    }
+
+    /// Draw every line that reaches `plies` plies from the root, for a game
+    /// with a move limit; `None` searches without one
+    pub fn set_plies_to_draw(&mut self, plies: Option<u32>) {
+        self.plies_to_draw = plies;
+    }
+
+    /// Score the legal root moves with a full-window search of `depth` plies
+    /// after each move (a MultiPV pass), best first. The pass ends when
+    /// `time_limit_ms` runs out or the search is stopped; moves not scored by
+    /// then are left out. Progress is not reported.
+    pub fn score_root_moves(
+        &mut self,
+        board: &BitboardBoard,
+        captured_pieces: &CapturedPieces,
+        player: Player,
+        depth: u8,
+        time_limit_ms: u32,
+    ) -> Vec<(Move, i32)> {
+        let start = TimeSource::start(&self.clock);
+        let reporter = std::mem::replace(&mut self.search_reporter, Arc::new(NullReporter));
+        let legal_moves = self.move_generator.generate_legal_moves(board, player, captured_pieces);
+        let mut scored = Vec::with_capacity(legal_moves.len());
+        for (index, mv) in legal_moves.iter().enumerate() {
+            let elapsed_ms = start.elapsed_ms();
+            if elapsed_ms >= time_limit_ms || self.stop_requested() {
+                break;
+            }
+            // Spread what is left over the moves still to score
+            let share_ms =
+                ((time_limit_ms - elapsed_ms) / (legal_moves.len() - index) as u32).max(1);
+            let mut child = board.clone();
+            let mut child_captured = captured_pieces.clone();
+            if let Some(captured) = child.make_move(mv) {
+                child_captured.add_piece(captured.piece_type, player);
+            } else if mv.from.is_none() {
+                child_captured.remove_piece(mv.piece_type, player);
+            }
+            let child_start = TimeSource::start(&self.clock);
+            let result = self.search_at_depth(
+                &mut child,
+                &child_captured,
+                player.opposite(),
+                depth,
+                share_ms,
+                MIN_SCORE,
+                MAX_SCORE,
+            );
+            // A search cut short has no reliable score, and the moves after
+            // it would get even less time
+            if child_start.elapsed_ms() >= share_ms || self.stop_requested() {
+                break;
+            }
+            if let Some((_, score)) = result {
+                scored.push((mv.clone(), -score));
+            }
+        }
+        self.search_reporter = reporter;
+        scored.sort_by(|a, b| b.1.cmp(&a.1));
+        scored
+    }
+
     #[cfg(test)]
     pub fn transposition_table_len(&self) -> usize {
-        self.transposition_table.size()
+        self.tt().size()
     }
 
     #[cfg(test)]
     pub fn transposition_table_capacity(&self) -> usize {
-        self.transposition_table.size() // ThreadSafeTranspositionTable doesn't
-                                        // expose capacity
+        self.tt().size() // ThreadSafeTranspositionTable doesn't
+                         // expose capacity
     }
 
     fn get_pv(
@@ -8426,7 +9214,7 @@ impl SearchEngine {
         _depth: u8,
     ) -> Vec<Move> {
         let mut pv = Vec::new();
-        let mut current_board = board.clone();
+        let mut current_board = board.clone_without_nnue();
         let mut current_captured = captured_pieces.clone();
         let mut current_player = player;
         let mut next_hash: Option<u64> = None;
@@ -8442,9 +9230,7 @@ impl SearchEngine {
                 &current_captured,
             );
             // Probe with depth=0 to accept entries from any search depth
-            if let Some(entry) =
-                self.transposition_table.probe_with_prefetch(position_hash, 0, next_hash)
-            {
+            if let Some(entry) = self.tt().probe_with_prefetch(position_hash, 0, next_hash) {
                 let _ = next_hash.take();
                 if let Some(move_) = &entry.best_move {
                     pv.push(move_.clone());
@@ -8484,7 +9270,7 @@ impl SearchEngine {
             if let Ok(tt) = shared_tt.try_read() {
                 TT_TRY_READ_SUCCESSES.fetch_add(1, Ordering::Relaxed);
                 let mut pv = Vec::new();
-                let mut current_board = board.clone();
+                let mut current_board = board.clone_without_nnue();
                 let mut current_captured = captured_pieces.clone();
                 let mut current_player = player;
                 let mut next_hash: Option<u64> = None;
@@ -8731,6 +9517,27 @@ impl SearchEngine {
     /// Reset quiescence statistics
     pub fn reset_quiescence_stats(&mut self) {
         self.quiescence_stats = QuiescenceStats::default();
+        self.drop_quiescence_stats.reset();
+    }
+
+    /// Get drop statistics for quiescence search
+    pub fn get_drop_quiescence_stats(&self) -> &DropQuiescenceStats {
+        &self.drop_quiescence_stats
+    }
+
+    /// Get the drop configuration for quiescence search
+    pub fn get_drop_quiescence_config(&self) -> &DropQuiescenceConfig {
+        &self.drop_quiescence_config
+    }
+
+    /// Update the drop configuration for quiescence search with validation
+    pub fn update_drop_quiescence_config(
+        &mut self,
+        config: DropQuiescenceConfig,
+    ) -> Result<(), String> {
+        config.validate()?;
+        self.drop_quiescence_config = config;
+        Ok(())
     }
 
     /// Get quiescence statistics
@@ -8781,7 +9588,8 @@ impl SearchEngine {
         // Reinitialize TT with new size
         const BYTES_PER_ENTRY: usize = 100;
         let new_capacity = size_mb * 1024 * 1024 / BYTES_PER_ENTRY;
-        self.quiescence_tt = HashMap::with_capacity(new_capacity);
+        self.quiescence_tt =
+            DeterministicHashMap::with_capacity_and_hasher(new_capacity, Default::default());
         Ok(())
     }
 
@@ -9942,8 +10750,8 @@ impl SearchEngine {
         }
 
         // Clear transposition table if it gets too large
-        if self.transposition_table.size() > 100_000 {
-            self.transposition_table.clear();
+        if self.tt().size() > 100_000 {
+            self.tt_mut().clear();
         }
     }
 
@@ -11303,6 +12111,13 @@ impl SearchEngine {
                 entry_source,        // Task 7.0.3.7: Propagate entry source through search
             );
 
+            self.record_tree(|r| {
+                r.update_current(|n| {
+                    n.reduction = reduction;
+                    n.reduced_score = Some(score);
+                })
+            });
+
             // Check if re-search is needed (with margin)
             // Task 7.0.6.3: Use position-type adaptive re-search margin
             let margin = if self.lmr_config.enable_position_type_margin {
@@ -11354,6 +12169,8 @@ impl SearchEngine {
                     ),
                 );
 
+                self.record_tree(|r| r.update_current(|n| n.researched = true));
+
                 // Re-search at full depth
                 // Task 2.6: Pass current move as opponent_last_move to recursive call
                 // Task 7.0.3.7: Main search path uses MainSearch entry source
@@ -12115,8 +12932,8 @@ impl SearchEngine {
         }
 
         // Clear transposition table if it gets too large
-        if self.transposition_table.size() > 100_000 {
-            self.transposition_table.clear();
+        if self.tt().size() > 100_000 {
+            self.tt_mut().clear();
         }
     }
 
@@ -13556,8 +14373,8 @@ impl SearchEngine {
         let avg_cutoff_index = ordering_effectiveness_metrics.average_cutoff_index;
 
         // Get transposition table metrics
-        let tt_stats = self.transposition_table.get_stats();
-        let tt_hit_rate = self.transposition_table.hit_rate() / 100.0; // Convert from percentage to ratio
+        let tt_stats = self.tt().get_stats();
+        let tt_hit_rate = self.tt().hit_rate() / 100.0; // Convert from percentage to ratio
                                                                        // Exact entry rate: approximate using hit rate (exact hits not directly
                                                                        // available)
         let exact_entry_rate = tt_hit_rate * 0.5; // Estimate: ~50% of hits are exact entries
@@ -13797,7 +14614,7 @@ impl SearchEngine {
         self.current_alpha = alpha;
         self.current_beta = beta;
         self.current_depth = depth;
-        self.search_start_time = Some(TimeSource::now());
+        self.search_start_time = Some(self.start_timer());
         self.current_best_move = None;
         self.current_best_score = i32::MIN;
     }
@@ -14167,23 +14984,40 @@ pub struct TroubleshootingReport {
 pub struct IterativeDeepening {
     max_depth: u8,
     time_limit_ms: u32,
-    _stop_flag: Option<Arc<AtomicBool>>,
+    stop_flag: Option<Arc<AtomicBool>>,
     // on_info removed - no longer using external callbacks
     /// Number of threads to use for parallel root search (1 = single-threaded)
     thread_count: usize,
     /// Optional parallel search engine for root move search
     parallel_engine: Option<ParallelSearchEngine>,
+    /// Configuration the parallel engine is built from on the first search,
+    /// once the search engine whose table it shares is known
+    parallel_config: Option<ParallelSearchConfig>,
+    /// Last result of every thread in the most recent Lazy SMP search
+    lazy_smp_results: Vec<LazySmpThreadResult>,
     parallel_min_depth: u8,
+    /// Receiver of `info` output (USI lines by default)
+    reporter: Arc<dyn SearchReporter>,
+    /// No new iteration starts once the completed ones searched this many
+    /// nodes
+    node_limit: Option<u64>,
+    /// Clock-based budget; `time_limit_ms` is then its maximum
+    time_plan: Option<TimePlan>,
 }
 impl IterativeDeepening {
     pub fn new(max_depth: u8, time_limit_ms: u32, stop_flag: Option<Arc<AtomicBool>>) -> Self {
         Self {
             max_depth,
             time_limit_ms,
-            _stop_flag: stop_flag,
+            stop_flag,
             thread_count: 1,
             parallel_engine: None,
+            parallel_config: None,
+            lazy_smp_results: Vec::new(),
             parallel_min_depth: 0,
+            reporter: default_reporter(),
+            node_limit: None,
+            time_plan: None,
         }
     }
 
@@ -14211,26 +15045,74 @@ impl IterativeDeepening {
             parallel_config.enable_parallel = false;
         }
         let parallel_min_depth = parallel_config.min_depth_parallel;
-        let parallel_engine = if threads > 1 && parallel_config.enable_parallel {
-            match ParallelSearchEngine::new_with_stop_flag(
-                parallel_config.clone(),
-                stop_flag.clone(),
-            ) {
-                Ok(engine) => Some(engine),
-                Err(_e) => None, // Fallback to single-threaded if thread pool creation fails
-            }
-        } else {
-            None
-        };
+        let parallel_config =
+            (threads > 1 && parallel_config.enable_parallel).then_some(parallel_config);
 
         Self {
             max_depth,
             time_limit_ms,
-            _stop_flag: stop_flag,
+            stop_flag,
             thread_count: threads,
-            parallel_engine,
+            parallel_engine: None,
+            parallel_config,
+            lazy_smp_results: Vec::new(),
             parallel_min_depth,
+            reporter: default_reporter(),
+            node_limit: None,
+            time_plan: None,
+        }
+    }
+
+    /// Report a root score that fell outside the aspiration window.
+    fn report_bound(&self, depth: u8, score: i32, bound: ScoreBound, elapsed_ms: u32, pv: String) {
+        let seldepth = GLOBAL_SELDEPTH.load(Ordering::Relaxed) as u8;
+        let nodes = GLOBAL_NODES_SEARCHED.load(Ordering::Relaxed);
+        let time_ms = elapsed_ms as u64;
+        self.reporter.report(SearchEvent::BoundUpdate(SearchInfo {
+            depth,
+            seldepth: seldepth.max(depth),
+            multipv: Some(1),
+            score,
+            bound,
+            time_ms,
+            nodes,
+            nps: if time_ms > 0 { nodes.saturating_mul(1000) / time_ms } else { 0 },
+            pv,
+        }));
+    }
+
+    /// Per-thread results of the last Lazy SMP search; empty when no Lazy SMP
+    /// search has run
+    pub fn lazy_smp_results(&self) -> &[LazySmpThreadResult] {
+        &self.lazy_smp_results
+    }
+
+    /// Send search output to `reporter` instead of the default USI output.
+    pub fn with_reporter(mut self, reporter: Arc<dyn SearchReporter>) -> Self {
+        if let Some(ref mut parallel_engine) = self.parallel_engine {
+            parallel_engine.set_reporter(reporter.clone());
         }
+        self.reporter = reporter;
+        self
+    }
+
+    /// Search about `node_limit` nodes: deepening stops once they are used
+    /// up, and an iteration after the first is cut off when it runs out.
+    /// `None` leaves the search limited by depth and time only.
+    pub fn with_node_limit(mut self, node_limit: Option<u64>) -> Self {
+        self.node_limit = node_limit;
+        self
+    }
+
+    /// Budget the search with `time_plan`: iterations stop around its
+    /// target time, which follows best-move stability and the score trend.
+    /// The plan's maximum replaces the time limit.
+    pub fn with_time_plan(mut self, time_plan: Option<TimePlan>) -> Self {
+        if let Some(ref plan) = time_plan {
+            self.time_limit_ms = plan.maximum_ms;
+        }
+        self.time_plan = time_plan;
+        self
     }
 
     pub fn search(
@@ -14246,10 +15128,42 @@ impl IterativeDeepening {
         );
         crate::debug_utils::start_timing("iterative_deepening_total");
 
+        // Workers and Lazy SMP helpers search into the engine's own table, so
+        // what they store is kept across moves and sized by the hash option
+        if let Some(config) = self.parallel_config.take() {
+            self.parallel_engine = match ParallelSearchEngine::new_with_shared_tt(
+                config,
+                search_engine.transposition_table_handle(),
+                self.stop_flag.clone(),
+            ) {
+                Ok(mut engine) => {
+                    engine.set_reporter(self.reporter.clone());
+                    Some(engine)
+                }
+                Err(_e) => None, // Fallback to single-threaded if thread pool creation fails
+            };
+        }
+
         // Task 1.0: Record start time for total search time tracking
-        let start_time = TimeSource::now();
+        let start_time = search_engine.start_timer();
         // Reset total search time at the start of a new search
         search_engine.iid_stats.total_search_time_ms = 0;
+        search_engine.set_search_reporter(self.reporter.clone());
+
+        // With NNUE on, search a copy of the root that carries the accumulator,
+        // so every move made below updates it incrementally
+        let nnue_root;
+        let board = match search_engine.evaluator.nnue_network() {
+            Some(network)
+                if search_engine.evaluator.is_using_nnue() && board.nnue_accumulator().is_none() =>
+            {
+                let mut attached = board.clone();
+                attached.attach_nnue(Arc::clone(network), captured_pieces);
+                nnue_root = attached;
+                &nnue_root
+            }
+            _ => board,
+        };
 
         let mut best_move: Option<Move> = None;
         let mut best_score = 0;
@@ -14353,15 +15267,9 @@ impl IterativeDeepening {
             }
         };
 
-        // For unlimited depth, use 60 seconds to allow much deeper searches
-        // This gives enough time to reach depths 30+ and profile what's taking so long
-        let search_time_limit = if effective_max_depth >= 100 {
-            // Unlimited depth: use 60 seconds (60000ms) to allow deep searches and
-            // profiling
-            60000u32
-        } else {
-            effective_time_limit
-        };
+        // Unlimited depth still honours the time limit: overriding it here
+        // made every clocked search think for a minute and lose on time
+        let search_time_limit = effective_time_limit;
         trace_log!(
             "ITERATIVE_DEEPENING",
             &format!(
@@ -14372,6 +15280,23 @@ impl IterativeDeepening {
                 effective_max_depth >= 100
             ),
         );
+        // Lazy SMP: helper threads run their own iterative deepening over the
+        // shared TT while this thread runs the main loop below
+        let lazy_smp_session = match self.parallel_engine {
+            Some(ref parallel_engine) if parallel_engine.is_lazy_smp() => {
+                Some(parallel_engine.start_lazy_smp(
+                    board,
+                    captured_pieces,
+                    player,
+                    effective_max_depth,
+                    search_time_limit,
+                ))
+            }
+            _ => None,
+        };
+        let mut completed_depth: u8 = 0;
+        let mut total_nodes: u64 = 0;
+
         trace_log!("ITERATIVE_DEEPENING", "Starting depth iteration loop");
 
         for depth in 1..=effective_max_depth {
@@ -14379,6 +15304,19 @@ impl IterativeDeepening {
             GLOBAL_NODES_SEARCHED.store(0, Ordering::Relaxed);
             // Task 8.4: Force time check at depth boundaries (use should_stop_force)
             search_engine.time_check_node_counter = 0; // Reset counter for new depth
+            // From depth 2 on an iteration is cut off once the node limit is
+            // used up; depth 1 always completes so there is a move
+            if depth > 1 {
+                search_engine
+                    .set_node_limit(self.node_limit.map(|limit| limit.saturating_sub(total_nodes)));
+            }
+            let depth_start_nodes = search_engine.total_nodes_searched();
+
+            // A `stop` ends the search once an iteration has produced a move
+            if depth > 1 && self.stop_flag.as_ref().is_some_and(|flag| flag.load(Ordering::Relaxed))
+            {
+                break;
+            }
 
             // Check time at start of each depth iteration
             // For unlimited depth, use a small buffer. For limited depth, use a larger
@@ -14396,8 +15334,9 @@ impl IterativeDeepening {
             // buffer.
             let time_buffer_ms = if self.max_depth >= 100 {
                 // Unlimited depth: only stop if we have less than 500ms remaining
-                // This allows the search to use almost all available time
-                500u32
+                // (a tenth of short budgets) This allows the search to use
+                // almost all available time
+                500u32.min(search_time_limit / 10)
             } else {
                 // Limited depth: use 20% or 2 seconds, whichever is larger
                 let percentage_buffer = (search_time_limit as f64 * 0.20) as u32;
@@ -14415,7 +15354,8 @@ impl IterativeDeepening {
                 self.max_depth >= 100
             );
 
-            if remaining_ms <= time_buffer_ms {
+            // Depth 1 always runs, so even a short budget yields a searched move
+            if depth > 1 && remaining_ms <= time_buffer_ms {
                 trace_log!(
                     "ITERATIVE_DEEPENING",
                     &format!(
@@ -14448,7 +15388,7 @@ impl IterativeDeepening {
             let elapsed_ms = start_time.elapsed_ms();
 
             // Calculate time budget for this depth (Task 4.5, 4.7)
-            let depth_start_time = TimeSource::now();
+            let depth_start_time = search_engine.start_timer();
 
             // Start periodic info message sender (similar to Stockfish/YaneuraOu)
             // Send info messages every ~1 second during search to keep UI responsive
@@ -14503,6 +15443,7 @@ impl IterativeDeepening {
                 Arc::new(std::sync::Mutex::new((None::<Move>, 0, String::new())))
             };
             let best_move_shared_clone = best_move_shared.clone();
+            let info_reporter = self.reporter.clone();
 
             // Spawn info sender thread that periodically sends updates
             let info_sender_handle = std::thread::spawn(move || {
@@ -14561,63 +15502,31 @@ impl IterativeDeepening {
                         // minimum requirement
                         let should_send = has_meaningful_score || has_pv;
 
-                        // Send info message only if we have valid search data (skip during silent
-                        // benches) CRITICAL: Only send if we have a real PV
-                        // (not just a single move) OR a non-zero score
-                        if std::env::var("SHOGI_SILENT_BENCH").is_err() && should_send {
-                            // Only send if we have a proper PV (multiple moves) OR a non-zero score
-                            // Don't send if we only have a single move with score 0
-                            if current_pv.is_empty() && current_score == 0 {
-                                continue; // Skip - no valid data
-                            }
-
-                            // Task: Fix score reporting for mate scores
-                            let mate_threshold = MAX_SCORE - 10000;
-                            let score_string = if current_score.abs() > mate_threshold {
-                                let moves_to_mate = if current_score > 0 {
-                                    (MAX_SCORE - current_score + 1) / 2
-                                } else {
-                                    -(MAX_SCORE + current_score + 1) / 2
-                                };
-                                format!("mate {}", moves_to_mate)
-                            } else {
-                                format!("cp {}", current_score)
-                            };
-
-                            let info_string = if !current_pv.is_empty() {
-                                format!(
-                                    "info depth {} seldepth {} score {} time {} nodes {} nps {} \
-                                     pv {}",
-                                    depth_clone,
-                                    seldepth,
-                                    score_string,
-                                    elapsed,
-                                    nodes,
-                                    nps,
-                                    current_pv
-                                )
+                        // Send info message only if we have valid search data
+                        if should_send {
+                            let pv = if !current_pv.is_empty() {
+                                current_pv
                             } else if let Some(ref mv) = current_move {
                                 // Only use single move as PV if score is non-zero
                                 if current_score == 0 {
                                     continue; // Skip - score is 0, don't send
                                 }
-                                format!(
-                                    "info depth {} seldepth {} score {} time {} nodes {} nps {} \
-                                     pv {}",
-                                    depth_clone,
-                                    seldepth,
-                                    score_string,
-                                    elapsed,
-                                    nodes,
-                                    nps,
-                                    mv.to_usi_string()
-                                )
+                                mv.to_usi_string()
                             } else {
                                 // Skip if we don't have valid data
                                 continue;
                             };
-                            println!("{}", info_string);
-                            let _ = std::io::Write::flush(&mut std::io::stdout());
+                            info_reporter.report(SearchEvent::PvUpdate(SearchInfo {
+                                depth: depth_clone,
+                                seldepth,
+                                multipv: None,
+                                score: current_score,
+                                bound: ScoreBound::Exact,
+                                time_ms: elapsed as u64,
+                                nodes,
+                                nps,
+                                pv,
+                            }));
                         }
 
                         last_info_time = std::time::Instant::now();
@@ -14763,7 +15672,7 @@ impl IterativeDeepening {
             );
 
             // Track depth iteration start time to detect if we're stuck
-            let depth_iteration_start = std::time::Instant::now();
+            let depth_iteration_start = search_engine.start_timer();
             let max_depth_iteration_time_ms = 30000u32; // Max 30 seconds per depth to prevent getting stuck
 
             loop {
@@ -14783,7 +15692,7 @@ impl IterativeDeepening {
                 }
 
                 // CRITICAL: Detect if this depth iteration is taking too long (stuck)
-                let depth_iteration_elapsed = depth_iteration_start.elapsed().as_millis() as u32;
+                let depth_iteration_elapsed = depth_iteration_start.elapsed_ms();
                 if depth_iteration_elapsed > max_depth_iteration_time_ms {
                     trace_log!(
                         "ASPIRATION_WINDOW",
@@ -14874,7 +15783,10 @@ impl IterativeDeepening {
                     }
                 }
 
-                let parallel_result = if self.thread_count > 1 && depth >= self.parallel_min_depth {
+                let parallel_result = if self.thread_count > 1
+                    && depth >= self.parallel_min_depth
+                    && lazy_smp_session.is_none()
+                {
                     if let Some(ref parallel_engine) = self.parallel_engine {
                         parallel_engine.search_root_moves(
                             board,
@@ -14955,6 +15867,13 @@ impl IterativeDeepening {
                                 .collect::<Vec<String>>()
                                 .join(" ")
                         };
+                        self.report_bound(
+                            depth,
+                            score,
+                            ScoreBound::Upper,
+                            start_time.elapsed_ms(),
+                            pv_string.clone(),
+                        );
                         update_shared_state(Some(move_clone), score, pv_string);
 
                         log_decision!(
@@ -14995,6 +15914,13 @@ impl IterativeDeepening {
                                 .collect::<Vec<String>>()
                                 .join(" ")
                         };
+                        self.report_bound(
+                            depth,
+                            score,
+                            ScoreBound::Lower,
+                            start_time.elapsed_ms(),
+                            pv_string.clone(),
+                        );
                         update_shared_state(Some(move_clone), score, pv_string);
 
                         log_decision!(
@@ -15200,6 +16126,7 @@ impl IterativeDeepening {
             }
 
             if let Some((mv_final, score)) = search_result {
+                completed_depth = depth;
                 // Ensure TT is flushed before building PV so all entries are visible
                 search_engine.flush_tt_buffer();
                 // Get seldepth (selective depth) - the maximum depth reached
@@ -15219,8 +16146,14 @@ impl IterativeDeepening {
                     pv.iter().map(|m| m.to_usi_string()).collect::<Vec<String>>().join(" ")
                 };
                 let time_searched = start_time.elapsed_ms();
-                // Use GLOBAL_NODES_SEARCHED for accurate node count across threads
-                let nodes_for_info = GLOBAL_NODES_SEARCHED.load(Ordering::Relaxed);
+                // Use GLOBAL_NODES_SEARCHED for accurate node count across threads;
+                // a single thread counts its own nodes, since the global counter
+                // also sees searches in other engines of the process
+                let nodes_for_info = if self.thread_count > 1 {
+                    GLOBAL_NODES_SEARCHED.load(Ordering::Relaxed)
+                } else {
+                    search_engine.total_nodes_searched() - depth_start_nodes
+                };
                 let nps = if time_searched > 0 {
                     nodes_for_info.saturating_mul(1000) / (time_searched as u64)
                 } else {
@@ -15244,19 +16177,18 @@ impl IterativeDeepening {
                         "Skipping info message: score is 0 and PV is empty",
                     );
                 } else {
-                    let info_string = format!(
-                        "info depth {} seldepth {} multipv 1 score cp {} time {} nodes {} nps {} \
-                         pv {}",
-                        depth, seldepth, score, time_searched, nodes_for_info, nps, pv_string
-                    );
-
-                    // Print the info message to stdout for USI protocol (skip during silent
-                    // benches)
-                    if std::env::var("SHOGI_SILENT_BENCH").is_err() {
-                        println!("{}", info_string);
-                        // Explicitly flush stdout to ensure info messages are sent immediately
-                        let _ = std::io::Write::flush(&mut std::io::stdout());
-                    }
+                    self.reporter.report(SearchEvent::IterationComplete(SearchInfo {
+                        depth,
+                        seldepth,
+                        multipv: Some(1),
+                        score,
+                        bound: ScoreBound::Exact,
+                        time_ms: time_searched as u64,
+                        nodes: nodes_for_info,
+                        nps,
+                        pv: pv_string,
+                    }));
+                    self.reporter.report(SearchEvent::Hashfull(search_engine.hashfull()));
                 }
 
                 // Only break early for extremely winning positions (king capture level)
@@ -15271,6 +16203,29 @@ impl IterativeDeepening {
                     );
                     break;
                 }
+
+                total_nodes += nodes_for_info;
+                if self.node_limit.is_some_and(|limit| total_nodes >= limit) {
+                    trace_log!(
+                        "ITERATIVE_DEEPENING",
+                        &format!("Node limit reached after depth {} ({} nodes)", depth, total_nodes),
+                    );
+                    break;
+                }
+                if let (Some(plan), Some(best)) = (self.time_plan.as_mut(), best_move.as_ref()) {
+                    plan.record_iteration(best, score, search_engine.best_move_effort());
+                    if !plan.should_continue(start_time.elapsed_ms()) {
+                        trace_log!(
+                            "ITERATIVE_DEEPENING",
+                            &format!(
+                                "Time plan target {}ms reached after depth {}",
+                                plan.target_ms(),
+                                depth
+                            ),
+                        );
+                        break;
+                    }
+                }
             } else {
                 crate::debug_utils::trace_log(
                     "ITERATIVE_DEEPENING",
@@ -15308,12 +16263,38 @@ impl IterativeDeepening {
             }
         }
 
+        search_engine.set_node_limit(None);
+
         // Task 6.0: Update IID performance measurements after search completes
         search_engine.update_iid_performance_measurements();
 
         // Print aggregated metrics (benches or manual on demand)
         maybe_print_search_metrics("iterative_deepening");
 
+        // Lazy SMP: stop the helpers and let depth/score voting pick the move
+        if let Some(session) = lazy_smp_session {
+            let main_result = best_move.clone().map(|m| (m, best_score, completed_depth));
+            let (voted, thread_results) = session.finish(main_result);
+            if let Some(voted) = voted {
+                if best_move.as_ref() != Some(&voted.best_move) {
+                    // Report the voted thread's score and depth so that the
+                    // last info line belongs to the move that is played
+                    let pv = search_engine.get_pv(board, captured_pieces, player, voted.depth);
+                    let pv_string = if pv.first() == Some(&voted.best_move) {
+                        pv.iter().map(Move::to_usi_string).collect::<Vec<String>>().join(" ")
+                    } else {
+                        voted.best_move.to_usi_string()
+                    };
+                    self.reporter.report(SearchEvent::IterationComplete(
+                        voted.search_info(pv_string, u64::from(start_time.elapsed_ms())),
+                    ));
+                }
+                best_move = Some(voted.best_move);
+                best_score = voted.score;
+            }
+            self.lazy_smp_results = thread_results;
+        }
+
         // Fallback: if we're in check and didn't find a move, just pick the first legal
         // move
         if is_in_check && best_move.is_none() && !legal_moves.is_empty() {
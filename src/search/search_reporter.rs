@@ -0,0 +1,240 @@
+//! Search output reporting.
+//!
+//! The search never writes to stdout itself; it sends [`SearchEvent`]s to a
+//! [`SearchReporter`] handed to `IterativeDeepening`. Three reporters are
+//! provided:
+//!
+//! - [`UsiReporter`] prints the events as USI `info` lines (the engine binary)
+//! - [`ChannelReporter`] forwards the events over an mpsc channel (library
+//!   users such as a GUI backend)
+//! - [`NullReporter`] discards them (tools such as the strength tester and the
+//!   benchmark)
+
+use crate::search::search_engine::MAX_SCORE;
+use std::io::Write;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+
+/// Scores beyond this magnitude are reported as mate distances.
+const MATE_REPORT_THRESHOLD: i32 = MAX_SCORE - 10000;
+
+/// Which side of the aspiration window a reported score lies on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScoreBound {
+    #[default]
+    Exact,
+    /// Fail high: the true score is at least the reported one.
+    Lower,
+    /// Fail low: the true score is at most the reported one.
+    Upper,
+}
+
+/// A snapshot of the search used for depth, PV and bound reports.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchInfo {
+    pub depth: u8,
+    pub seldepth: u8,
+    /// Reported as `multipv N` when present.
+    pub multipv: Option<u8>,
+    pub score: i32,
+    pub bound: ScoreBound,
+    pub time_ms: u64,
+    pub nodes: u64,
+    pub nps: u64,
+    /// Principal variation as space-separated USI moves.
+    pub pv: String,
+}
+
+impl SearchInfo {
+    /// Format as a USI `info` line.
+    pub fn to_usi(&self) -> String {
+        let multipv = self.multipv.map(|n| format!(" multipv {}", n)).unwrap_or_default();
+        let bound = match self.bound {
+            ScoreBound::Exact => "",
+            ScoreBound::Lower => " lowerbound",
+            ScoreBound::Upper => " upperbound",
+        };
+        format!(
+            "info depth {} seldepth {}{} score {}{} time {} nodes {} nps {} pv {}",
+            self.depth,
+            self.seldepth,
+            multipv,
+            format_usi_score(self.score),
+            bound,
+            self.time_ms,
+            self.nodes,
+            self.nps,
+            self.pv
+        )
+    }
+}
+
+/// Format a search score as `cp N` or `mate N`.
+pub fn format_usi_score(score: i32) -> String {
+    if score.abs() > MATE_REPORT_THRESHOLD {
+        let moves_to_mate =
+            if score > 0 { (MAX_SCORE - score + 1) / 2 } else { -(MAX_SCORE + score + 1) / 2 };
+        format!("mate {}", moves_to_mate)
+    } else {
+        format!("cp {}", score)
+    }
+}
+
+/// Something the search wants to tell the outside world.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchEvent {
+    /// An iteration of iterative deepening finished with an exact score.
+    IterationComplete(SearchInfo),
+    /// Periodic progress with the best line found so far.
+    PvUpdate(SearchInfo),
+    /// The root search failed outside the aspiration window.
+    BoundUpdate(SearchInfo),
+    /// The root move currently being searched (1-based `number`).
+    CurrentMove { depth: u8, mv: String, number: usize },
+    /// Transposition table occupancy in permille.
+    Hashfull(u32),
+    /// Free-form message (`info string`).
+    Message(String),
+}
+
+impl SearchEvent {
+    /// Format as a USI `info` line.
+    pub fn to_usi(&self) -> String {
+        match self {
+            SearchEvent::IterationComplete(info)
+            | SearchEvent::PvUpdate(info)
+            | SearchEvent::BoundUpdate(info) => info.to_usi(),
+            SearchEvent::CurrentMove { depth, mv, number } => {
+                format!("info depth {} currmove {} currmovenumber {}", depth, mv, number)
+            }
+            SearchEvent::Hashfull(permill) => format!("info hashfull {}", permill),
+            SearchEvent::Message(text) => format!("info string {}", text),
+        }
+    }
+}
+
+/// Receiver of search output.
+///
+/// Reporters are shared between the main search thread, the periodic info
+/// thread and parallel workers, so they must be `Send + Sync`.
+pub trait SearchReporter: Send + Sync {
+    fn report(&self, event: SearchEvent);
+}
+
+/// Prints events to stdout as USI `info` lines.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct UsiReporter;
+
+impl SearchReporter for UsiReporter {
+    fn report(&self, event: SearchEvent) {
+        let mut stdout = std::io::stdout().lock();
+        let _ = writeln!(stdout, "{}", event.to_usi());
+        let _ = stdout.flush();
+    }
+}
+
+/// Forwards events to an mpsc channel.
+///
+/// Events sent after the receiver is dropped are discarded.
+#[derive(Debug, Clone)]
+pub struct ChannelReporter {
+    sender: Sender<SearchEvent>,
+}
+
+impl ChannelReporter {
+    pub fn new(sender: Sender<SearchEvent>) -> Self {
+        Self { sender }
+    }
+
+    /// Create a reporter together with the receiving end of its channel.
+    pub fn channel() -> (Self, Receiver<SearchEvent>) {
+        let (sender, receiver) = mpsc::channel();
+        (Self::new(sender), receiver)
+    }
+}
+
+impl SearchReporter for ChannelReporter {
+    fn report(&self, event: SearchEvent) {
+        let _ = self.sender.send(event);
+    }
+}
+
+/// Discards all events.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NullReporter;
+
+impl SearchReporter for NullReporter {
+    fn report(&self, _event: SearchEvent) {}
+}
+
+/// Reporter used when none is configured: USI output, or nothing when
+/// `SHOGI_SILENT_BENCH` is set.
+pub fn default_reporter() -> Arc<dyn SearchReporter> {
+    if std::env::var("SHOGI_SILENT_BENCH").is_ok() {
+        Arc::new(NullReporter)
+    } else {
+        Arc::new(UsiReporter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_info() -> SearchInfo {
+        SearchInfo {
+            depth: 4,
+            seldepth: 7,
+            multipv: Some(1),
+            score: 35,
+            bound: ScoreBound::Exact,
+            time_ms: 120,
+            nodes: 5000,
+            nps: 41666,
+            pv: "7g7f 3c3d".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_usi_formatting() {
+        let info = sample_info();
+        assert_eq!(
+            info.to_usi(),
+            "info depth 4 seldepth 7 multipv 1 score cp 35 time 120 nodes 5000 nps 41666 pv 7g7f \
+             3c3d"
+        );
+
+        let progress = SearchInfo { multipv: None, bound: ScoreBound::Lower, ..info };
+        assert_eq!(
+            SearchEvent::BoundUpdate(progress).to_usi(),
+            "info depth 4 seldepth 7 score cp 35 lowerbound time 120 nodes 5000 nps 41666 pv 7g7f \
+             3c3d"
+        );
+        assert_eq!(SearchEvent::Hashfull(12).to_usi(), "info hashfull 12");
+        assert_eq!(
+            SearchEvent::CurrentMove { depth: 3, mv: "2g2f".to_string(), number: 2 }.to_usi(),
+            "info depth 3 currmove 2g2f currmovenumber 2"
+        );
+        assert_eq!(SearchEvent::Message("hi".to_string()).to_usi(), "info string hi");
+    }
+
+    #[test]
+    fn test_mate_scores() {
+        assert_eq!(format_usi_score(MAX_SCORE), "mate 0");
+        assert_eq!(format_usi_score(MAX_SCORE - 3), "mate 2");
+        assert_eq!(format_usi_score(-(MAX_SCORE - 3)), "mate -2");
+        assert_eq!(format_usi_score(-120), "cp -120");
+    }
+
+    #[test]
+    fn test_channel_reporter() {
+        let (reporter, receiver) = ChannelReporter::channel();
+        reporter.report(SearchEvent::Hashfull(5));
+        reporter.report(SearchEvent::IterationComplete(sample_info()));
+        assert_eq!(receiver.try_recv().unwrap(), SearchEvent::Hashfull(5));
+        assert_eq!(receiver.try_recv().unwrap(), SearchEvent::IterationComplete(sample_info()));
+        drop(receiver);
+        // Reporting into a closed channel is harmless
+        reporter.report(SearchEvent::Message("late".to_string()));
+    }
+}
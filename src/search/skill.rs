@@ -0,0 +1,248 @@
+//! Strength limiting for play against humans.
+//!
+//! A [`SkillLevel`] below the maximum weakens the engine in three ways:
+//!
+//! - the main search gets a node budget, so it sees less deeply
+//! - the best root moves are rescored with a shallow MultiPV pass and each
+//!   score gets Gaussian noise, as if the evaluation were less precise
+//! - the move is drawn from those candidates with a softmax at a given
+//!   temperature, after dropping every move that loses more than the level's
+//!   blunder margin
+//!
+//! The margin is what keeps the mistakes human-like: a weak level drifts into
+//! slightly worse moves instead of hanging pieces at random. All parameters
+//! follow one curve over a continuous strength from 0 to [`MAX_LEVEL`], so a
+//! target Elo (`LimitStrength`) maps between levels. `strength-tester
+//! calibrate` plays levels against each other to check the Elo curve.
+
+use crate::types::core::Move;
+use rand::Rng;
+
+/// Full strength; nothing is limited at this level
+pub const MAX_LEVEL: u8 = 20;
+
+/// Elo of level 0 on the calibration curve
+pub const MIN_ELO: u32 = 800;
+
+/// Elo of the full-strength engine on the calibration curve
+pub const MAX_ELO: u32 = MIN_ELO + ELO_PER_LEVEL * MAX_LEVEL as u32;
+
+/// Elo gained per skill level
+pub const ELO_PER_LEVEL: u32 = 100;
+
+/// Node budget of level 0; each level multiplies it by [`NODE_GROWTH`]
+const BASE_NODES: f64 = 300.0;
+const NODE_GROWTH: f64 = 1.45;
+
+/// Settings of the `SkillLevel`, `LimitStrength` and `Elo` options
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SkillLevel {
+    /// 0 (weakest) to [`MAX_LEVEL`] (full strength)
+    pub level: u8,
+    /// Use `elo` instead of `level`
+    pub limit_strength: bool,
+    /// Target rating on the calibration curve
+    pub elo: u32,
+}
+
+impl Default for SkillLevel {
+    fn default() -> Self {
+        Self { level: MAX_LEVEL, limit_strength: false, elo: MAX_ELO }
+    }
+}
+
+impl SkillLevel {
+    pub fn from_level(level: u8) -> Self {
+        Self { level: level.min(MAX_LEVEL), ..Self::default() }
+    }
+
+    /// Limit strength to `elo`, clamped to the calibrated range
+    pub fn from_elo(elo: u32) -> Self {
+        Self { limit_strength: true, elo: elo.clamp(MIN_ELO, MAX_ELO), ..Self::default() }
+    }
+
+    /// Rating of `level` on the calibration curve
+    pub fn elo_for_level(level: u8) -> u32 {
+        MIN_ELO + ELO_PER_LEVEL * u32::from(level.min(MAX_LEVEL))
+    }
+
+    /// Expected rating of these settings
+    pub fn expected_elo(&self) -> u32 {
+        if self.limit_strength {
+            self.elo.clamp(MIN_ELO, MAX_ELO)
+        } else {
+            Self::elo_for_level(self.level)
+        }
+    }
+
+    /// Whether any weakening applies
+    pub fn is_limited(&self) -> bool {
+        self.strength() < f64::from(MAX_LEVEL)
+    }
+
+    /// Continuous level in `0.0..=MAX_LEVEL`; a target Elo falls between
+    /// levels
+    fn strength(&self) -> f64 {
+        if self.limit_strength {
+            f64::from(self.expected_elo() - MIN_ELO) / f64::from(ELO_PER_LEVEL)
+        } else {
+            f64::from(self.level.min(MAX_LEVEL))
+        }
+    }
+
+    /// Levels below full strength
+    fn weakness(&self) -> f64 {
+        f64::from(MAX_LEVEL) - self.strength()
+    }
+
+    /// Node budget of the main search; no new iteration starts once it is
+    /// spent
+    pub fn node_limit(&self) -> Option<u64> {
+        self.is_limited()
+            .then(|| (BASE_NODES * NODE_GROWTH.powf(self.strength())) as u64)
+    }
+
+    /// Depth of the MultiPV pass that scores the candidate moves
+    pub fn candidate_depth(&self) -> u8 {
+        1 + (self.strength() / 7.0) as u8
+    }
+
+    /// Number of best root moves the pick is made from
+    pub fn candidate_count(&self) -> usize {
+        4 + (self.weakness() / 4.0) as usize
+    }
+
+    /// Standard deviation of the noise added to candidate scores
+    pub fn noise_cp(&self) -> f64 {
+        10.0 * self.weakness()
+    }
+
+    /// Softmax temperature: the score gap that makes a move e times less
+    /// likely
+    pub fn temperature_cp(&self) -> f64 {
+        10.0 + 8.0 * self.weakness()
+    }
+
+    /// Largest loss against the best candidate a pick may accept
+    pub fn max_loss_cp(&self) -> i32 {
+        60 + (35.0 * self.weakness()) as i32
+    }
+
+    /// Pick a move among `candidates` (root moves with their scores, best
+    /// first). The search's `best_move` is always kept as a top candidate.
+    /// At full strength this is `best_move`.
+    pub fn pick_move<R: Rng>(
+        &self,
+        best_move: &Move,
+        candidates: &[(Move, i32)],
+        rng: &mut R,
+    ) -> Move {
+        if !self.is_limited() || candidates.is_empty() {
+            return best_move.clone();
+        }
+
+        let top_score = candidates.iter().map(|(_, score)| *score).max().unwrap_or(0);
+        let mut pool: Vec<(Move, i32)> = candidates
+            .iter()
+            .filter(|(mv, _)| mv != best_move)
+            .take(self.candidate_count() - 1)
+            .filter(|(_, score)| *score >= top_score.saturating_sub(self.max_loss_cp()))
+            .cloned()
+            .collect();
+        pool.push((best_move.clone(), top_score));
+
+        let noise = self.noise_cp();
+        let noisy: Vec<f64> = pool
+            .iter()
+            .map(|(_, score)| f64::from(*score) + noise * standard_normal(rng))
+            .collect();
+        let max = noisy.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let temperature = self.temperature_cp();
+        let weights: Vec<f64> = noisy.iter().map(|s| ((s - max) / temperature).exp()).collect();
+
+        let mut target = rng.gen::<f64>() * weights.iter().sum::<f64>();
+        for ((mv, _), weight) in pool.iter().zip(&weights) {
+            if target < *weight {
+                return mv.clone();
+            }
+            target -= weight;
+        }
+        best_move.clone()
+    }
+}
+
+/// Standard normal sample (Box-Muller)
+fn standard_normal<R: Rng>(rng: &mut R) -> f64 {
+    let u1 = rng.gen::<f64>().max(f64::MIN_POSITIVE);
+    let u2 = rng.gen::<f64>();
+    (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::core::{PieceType, Player, Position};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn pawn_move(col: u8) -> Move {
+        Move::new_move(
+            Position::new(6, col),
+            Position::new(5, col),
+            PieceType::Pawn,
+            Player::Black,
+            false,
+        )
+    }
+
+    #[test]
+    fn test_skill_curve_is_monotonic() {
+        assert!(!SkillLevel::default().is_limited());
+        assert_eq!(SkillLevel::default().node_limit(), None);
+        for level in 1..MAX_LEVEL {
+            let weaker = SkillLevel::from_level(level - 1);
+            let stronger = SkillLevel::from_level(level);
+            assert!(weaker.node_limit() < stronger.node_limit());
+            assert!(weaker.noise_cp() > stronger.noise_cp());
+            assert!(weaker.temperature_cp() > stronger.temperature_cp());
+            assert!(weaker.max_loss_cp() > stronger.max_loss_cp());
+            assert!(weaker.candidate_depth() <= stronger.candidate_depth());
+        }
+
+        // A target Elo lands between the levels around it
+        let between = SkillLevel::from_elo(SkillLevel::elo_for_level(10) + ELO_PER_LEVEL / 2);
+        assert!(between.node_limit() > SkillLevel::from_level(10).node_limit());
+        assert!(between.node_limit() < SkillLevel::from_level(11).node_limit());
+        assert_eq!(SkillLevel::from_elo(100).expected_elo(), MIN_ELO);
+        assert!(!SkillLevel::from_elo(MAX_ELO).is_limited());
+    }
+
+    #[test]
+    fn test_pick_move_respects_blunder_margin() {
+        let best = pawn_move(0);
+        let candidates =
+            vec![(best.clone(), 50), (pawn_move(1), 40), (pawn_move(2), 30), (pawn_move(3), -900)];
+        let mut rng = StdRng::seed_from_u64(7);
+
+        let full = SkillLevel::default();
+        assert_eq!(full.pick_move(&best, &candidates, &mut rng), best);
+
+        let weak = SkillLevel::from_level(5);
+        let mut picked = std::collections::HashSet::new();
+        for _ in 0..200 {
+            let mv = weak.pick_move(&best, &candidates, &mut rng);
+            assert_ne!(mv, pawn_move(3), "a move losing 950cp is outside the margin");
+            picked.insert(mv.to_usi_string());
+        }
+        assert!(picked.len() > 1, "a weak level should sometimes deviate");
+
+        // The search's move stays a candidate even if the shallow pass
+        // scored it lower
+        let strong = SkillLevel::from_level(19);
+        let candidates = vec![(pawn_move(1), 300), (best.clone(), -400)];
+        let best_count = (0..100)
+            .filter(|_| strong.pick_move(&best, &candidates, &mut rng) == best)
+            .count();
+        assert!(best_count > 30);
+    }
+}
@@ -94,6 +94,9 @@ pub fn print_and_reset_search_metrics(tag: &str) {
 pub struct SearchStatistics {
     /// Total nodes searched in current search
     nodes_searched: u64,
+    /// Nodes searched by this engine since it was created or reset; unlike
+    /// [`GLOBAL_NODES_SEARCHED`] it never counts other engines' nodes
+    total_nodes_searched: u64,
     /// Core search metrics (TT hits, cutoffs, etc.)
     core_metrics: CoreSearchMetrics,
     /// Initiative/attack debt metrics (Task 3.4)
@@ -119,6 +122,7 @@ impl SearchStatistics {
     pub fn new() -> Self {
         Self {
             nodes_searched: 0,
+            total_nodes_searched: 0,
             core_metrics: CoreSearchMetrics::default(),
             initiative_metrics: InitiativeMetrics::default(),
         }
@@ -127,6 +131,7 @@ impl SearchStatistics {
     /// Increment nodes searched counter
     pub fn increment_nodes(&mut self) {
         self.nodes_searched += 1;
+        self.total_nodes_searched += 1;
         self.core_metrics.total_nodes += 1;
         GLOBAL_NODES_SEARCHED.fetch_add(1, Ordering::Relaxed);
     }
@@ -186,6 +191,11 @@ impl SearchStatistics {
         self.nodes_searched
     }
 
+    /// Get nodes searched by this engine since it was created or reset
+    pub fn get_total_nodes_searched(&self) -> u64 {
+        self.total_nodes_searched
+    }
+
     /// Get core search metrics
     pub fn get_core_metrics(&self) -> &CoreSearchMetrics {
         &self.core_metrics
@@ -210,6 +220,7 @@ impl SearchStatistics {
     /// Reset all statistics
     pub fn reset(&mut self) {
         self.nodes_searched = 0;
+        self.total_nodes_searched = 0;
         self.core_metrics = CoreSearchMetrics::default();
         self.initiative_metrics = InitiativeMetrics::default();
     }
@@ -65,15 +65,18 @@
 use crate::bitboards::BitboardBoard;
 use crate::opening_book::OpeningBook;
 use crate::search::cache_management::CacheManager;
+use crate::search::error_handling::TranspositionResult;
 use crate::search::replacement_policies::ReplacementDecision;
 use crate::search::replacement_policies::ReplacementPolicyHandler;
 use crate::search::transposition_config::TranspositionConfig;
+use crate::search::tt_persistence::{read_tt_file, write_tt_file, TtFileKind, TtFileRecord};
 use crate::search::zobrist::{RepetitionState, ZobristHasher};
 use crate::types::core::{Move, PieceType, Player, Position};
 use crate::types::search::EntrySource;
 use crate::types::search::TranspositionFlag;
 use crate::types::transposition::TranspositionEntry;
 use log::warn;
+use std::path::Path;
 use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 use std::sync::{Arc, LockResult, Mutex, MutexGuard, RwLock, RwLockWriteGuard};
 
@@ -784,6 +787,22 @@ impl ThreadSafeTranspositionTable {
         self.size
     }
 
+    /// Estimate table occupancy in permille from the first 1000 slots
+    ///
+    /// Used for the USI `hashfull` report; sampling keeps it cheap enough to
+    /// call once per iteration.
+    pub fn hashfull(&self) -> u32 {
+        let sample = self.entries.len().min(1000);
+        if sample == 0 {
+            return 0;
+        }
+        let used = self.entries[..sample]
+            .iter()
+            .filter(|entry| entry.packed_data.is_valid())
+            .count();
+        (used * 1000 / sample) as u32
+    }
+
     /// Get the number of lock buckets
     ///
     /// Returns the number of independent lock buckets used for parallel write
@@ -942,6 +961,38 @@ impl ThreadSafeTranspositionTable {
         }
     }
 
+    /// Snapshot all occupied slots as hash file records with the given tier.
+    pub(crate) fn persisted_records(&self, tier: u8) -> Vec<TtFileRecord> {
+        (0..self.entries.len())
+            .filter(|&index| self.entries[index].packed_data.is_valid())
+            .filter_map(|index| self.get_entry_at_index(index))
+            .map(|entry| TtFileRecord { entry, tier })
+            .collect()
+    }
+
+    /// Save all occupied entries to a hash file.
+    ///
+    /// Returns the number of entries written.
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> TranspositionResult<usize> {
+        let records = self.persisted_records(0);
+        write_tt_file(path, TtFileKind::ThreadSafe, &records)?;
+        Ok(records.len())
+    }
+
+    /// Load entries from a hash file written by `save_to_file` (or by the
+    /// hierarchical table) into this table.
+    ///
+    /// Entries go through the normal replacement policy, so a table smaller
+    /// than the saved one keeps the preferred entry of each slot. Files whose
+    /// Zobrist seed or entry layout differ from the running engine are
+    /// refused. Returns the number of entries read.
+    pub fn load_from_file<P: AsRef<Path>>(&self, path: P) -> TranspositionResult<usize> {
+        let (_, records) = read_tt_file(path)?;
+        let count = records.len();
+        self.store_batch(records.into_iter().map(|record| record.entry));
+        Ok(count)
+    }
+
     // Statistics increment methods
     fn increment_hits(&self) {
         if !self.statistics_enabled {
@@ -5,12 +5,136 @@
 //! `search_engine.rs` as part of Task 1.0: File Modularization and Structure
 //! Improvements.
 
+use crate::types::core::Move;
 use crate::types::search::{
     TimeAllocationStrategy, TimeBudgetStats, TimeManagementConfig, TimePressure,
     TimePressureThresholds,
 };
+use crate::types::GAME_PHASE_MAX;
 use crate::utils::time::TimeSource;
 
+/// Plies a game is expected to last when estimating moves to go
+const EXPECTED_GAME_PLIES: u32 = 160;
+
+/// Bounds on the estimated number of own moves left
+const MIN_MOVES_TO_GO: u32 = 12;
+const MAX_MOVES_TO_GO: u32 = 50;
+
+/// The maximum time is at most this many times the optimum
+const MAX_STRETCH: u32 = 4;
+
+/// No new iteration starts after this share of the target time: the next
+/// one would likely run past it
+const NEXT_ITERATION_SHARE: f64 = 0.6;
+
+/// Clock situation of the side to move
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MoveClock {
+    /// Main time left
+    pub remaining_ms: u32,
+    /// Fischer increment credited after this move
+    pub increment_ms: u32,
+    /// Byoyomi period available once the main time is spent
+    pub byoyomi_ms: u32,
+    /// Moves until the next time control, when the GUI sends it
+    pub moves_to_go: Option<u32>,
+    /// Plies played so far
+    pub ply: u32,
+    /// Game phase (0 = endgame, `GAME_PHASE_MAX` = opening)
+    pub phase: i32,
+}
+
+impl MoveClock {
+    /// Own moves the main time has to last: fewer as the game goes on and
+    /// as material comes off the board
+    pub fn estimated_moves_to_go(&self) -> u32 {
+        if let Some(moves_to_go) = self.moves_to_go {
+            return moves_to_go.max(1);
+        }
+        let by_ply = EXPECTED_GAME_PLIES.saturating_sub(self.ply) / 2;
+        let phase = f64::from(self.phase.clamp(0, GAME_PHASE_MAX)) / f64::from(GAME_PHASE_MAX);
+        let estimate = (f64::from(by_ply) * (0.6 + 0.4 * phase)) as u32;
+        estimate.clamp(MIN_MOVES_TO_GO, MAX_MOVES_TO_GO)
+    }
+}
+
+/// Time budget of one move: an optimum that stretches or shrinks with how
+/// the iterations go, and a maximum the search never passes
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimePlan {
+    pub optimum_ms: u32,
+    pub maximum_ms: u32,
+    previous: Option<(Move, i32)>,
+    /// Decaying count of best-move changes between iterations
+    best_move_changes: f64,
+    /// Factor from the score trend of the last iteration
+    score_trend: f64,
+    /// Share of the root nodes the best move took in the last iteration
+    best_move_effort: f64,
+}
+
+impl TimePlan {
+    /// Plan a move on `clock`, keeping `network_delay_ms` in reserve for
+    /// transmission lag
+    pub fn new(clock: &MoveClock, network_delay_ms: u32) -> Self {
+        let moves_to_go = clock.estimated_moves_to_go();
+        // Never plan past what is on the clock now: the increment only
+        // arrives after the move
+        let hard_limit =
+            (clock.remaining_ms + clock.byoyomi_ms).saturating_sub(network_delay_ms).max(1);
+        let time_pool = clock.remaining_ms + clock.increment_ms * (moves_to_go - 1);
+        let optimum = (time_pool / moves_to_go + clock.byoyomi_ms)
+            .saturating_sub(network_delay_ms)
+            .clamp(1, hard_limit);
+        let maximum = (optimum * MAX_STRETCH)
+            .min(optimum + clock.remaining_ms / 4)
+            .min(hard_limit)
+            .max(optimum);
+        Self {
+            optimum_ms: optimum,
+            maximum_ms: maximum,
+            previous: None,
+            best_move_changes: 0.0,
+            score_trend: 1.0,
+            best_move_effort: 0.0,
+        }
+    }
+
+    /// Record the result of a completed iteration
+    pub fn record_iteration(&mut self, best_move: &Move, score: i32, best_move_effort: f64) {
+        self.best_move_changes *= 0.5;
+        if let Some((previous_move, previous_score)) = &self.previous {
+            if previous_move != best_move {
+                self.best_move_changes += 1.0;
+            }
+            // A falling score asks for more time, a rising one for a little
+            // less
+            self.score_trend = (1.0 + f64::from(previous_score - score) / 300.0).clamp(0.85, 1.6);
+        }
+        self.best_move_effort = best_move_effort;
+        self.previous = Some((best_move.clone(), score));
+    }
+
+    /// Time this search should use given the iterations so far
+    pub fn target_ms(&self) -> u32 {
+        let instability = (1.0 + 0.6 * self.best_move_changes).min(2.5);
+        let dominance = if self.best_move_effort > 0.9 {
+            0.5
+        } else if self.best_move_effort > 0.75 {
+            0.75
+        } else {
+            1.0
+        };
+        let target = f64::from(self.optimum_ms) * instability * self.score_trend * dominance;
+        (target as u32).min(self.maximum_ms)
+    }
+
+    /// Whether another iteration should start after `elapsed_ms`
+    pub fn should_continue(&self, elapsed_ms: u32) -> bool {
+        f64::from(elapsed_ms) < f64::from(self.target_ms()) * NEXT_ITERATION_SHARE
+    }
+}
+
 /// Time management functionality for search engine
 #[derive(Debug, Clone)]
 pub struct TimeManager {
@@ -266,6 +390,8 @@ impl TimeManager {
 mod tests {
     use super::*;
     use crate::types::TimeAllocationStrategy;
+    use crate::utils::time::{Clock, VirtualClock};
+    use std::sync::Arc;
 
     #[test]
     fn test_time_pressure_calculation() {
@@ -283,6 +409,95 @@ mod tests {
         // demonstrates the API
     }
 
+    fn pawn_move(col: u8) -> Move {
+        use crate::types::core::{PieceType, Player, Position};
+        Move::new_move(
+            Position::new(6, col),
+            Position::new(5, col),
+            PieceType::Pawn,
+            Player::Black,
+            false,
+        )
+    }
+
+    #[test]
+    fn test_time_plan_budgets() {
+        let opening = MoveClock {
+            remaining_ms: 600_000,
+            phase: GAME_PHASE_MAX,
+            ..MoveClock::default()
+        };
+        let plan = TimePlan::new(&opening, 100);
+        assert_eq!(opening.estimated_moves_to_go(), 50);
+        assert_eq!(plan.optimum_ms, 600_000 / 50 - 100);
+        assert_eq!(plan.maximum_ms, plan.optimum_ms * MAX_STRETCH);
+
+        // Later and with less material the same clock buys longer moves
+        let endgame = MoveClock { ply: 120, phase: 0, ..opening };
+        assert!(endgame.estimated_moves_to_go() < 20);
+        assert!(TimePlan::new(&endgame, 100).optimum_ms > plan.optimum_ms * 2);
+
+        // Byoyomi is used in full, minus the network delay
+        let byoyomi = MoveClock { byoyomi_ms: 10_000, ..MoveClock::default() };
+        let plan = TimePlan::new(&byoyomi, 200);
+        assert_eq!((plan.optimum_ms, plan.maximum_ms), (9800, 9800));
+
+        // Living on the increment: never more than the clock holds now
+        let increment = MoveClock { remaining_ms: 1500, increment_ms: 3000, ..MoveClock::default() };
+        let plan = TimePlan::new(&increment, 100);
+        assert_eq!(plan.maximum_ms, 1400);
+        assert!(plan.optimum_ms <= plan.maximum_ms);
+        assert_eq!(
+            TimePlan::new(&MoveClock { moves_to_go: Some(1), ..increment }, 100).optimum_ms,
+            1400
+        );
+    }
+
+    #[test]
+    fn test_time_plan_reacts_to_iterations() {
+        let clock = MoveClock { remaining_ms: 300_000, phase: 128, ply: 40, ..MoveClock::default() };
+        let base = TimePlan::new(&clock, 0);
+
+        // A stable best move that takes almost all nodes stops early
+        let mut stable = base.clone();
+        for _ in 0..4 {
+            stable.record_iteration(&pawn_move(0), 50, 0.95);
+        }
+        assert!(stable.target_ms() < base.optimum_ms);
+
+        // Changing best moves and a falling score extend, up to the maximum
+        let mut unstable = base.clone();
+        for (i, score) in [50, -100, -250, -400].into_iter().enumerate() {
+            unstable.record_iteration(&pawn_move(i as u8), score, 0.3);
+        }
+        assert!(unstable.target_ms() > base.optimum_ms * 2);
+        assert!(unstable.target_ms() <= base.maximum_ms);
+        assert!(unstable.should_continue(base.optimum_ms));
+        assert!(!stable.should_continue(base.optimum_ms));
+    }
+
+    #[test]
+    fn test_should_stop_on_virtual_clock() {
+        let config = TimeManagementConfig { time_check_frequency: 4, ..Default::default() };
+        let mut manager = TimeManager::new(config, TimePressureThresholds::default());
+        let clock = Arc::new(VirtualClock::new(1000));
+        let start_time = TimeSource::start(&(clock.clone() as Arc<dyn Clock>));
+
+        // 1ms per node: the limit is passed at node 10, and seen at the next
+        // check
+        let stopped_at = (1..=100)
+            .find(|_| {
+                clock.on_node();
+                manager.should_stop(&start_time, 10, None)
+            })
+            .unwrap();
+        assert_eq!(stopped_at, 12);
+        assert_eq!(
+            manager.calculate_time_pressure_level(&start_time, 10),
+            TimePressure::from_remaining_time_percent(0.0, &TimePressureThresholds::default())
+        );
+    }
+
     #[test]
     fn test_time_budget_calculation() {
         let config = TimeManagementConfig {
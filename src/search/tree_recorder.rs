@@ -0,0 +1,831 @@
+//! Search tree recorder and offline inspector.
+//!
+//! When enabled on a `SearchEngine`, the recorder captures every move edge the
+//! main search explores (move, remaining depth, alpha/beta window, static
+//! evaluation, TT probe result, LMR reduction, quiescence extensions, pruning
+//! decision and returned score) up to a node and ply budget. The recorded tree
+//! is written as a compact little-endian binary file and can be loaded again
+//! with [`SearchTree`] to navigate by move path and explain individual
+//! decisions.
+//!
+//! Only the thread that owns the recorder is captured: YBWC siblings and Lazy
+//! SMP helpers run on their own engines. Auxiliary searches (null move,
+//! verification and IID) are not recorded either; their effect shows up as a
+//! decision on the node that triggered them.
+
+use crate::types::core::Move;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+/// File magic of a recorded search tree.
+const TREE_FILE_MAGIC: &[u8; 4] = b"YSTR";
+
+/// Current file format version.
+const TREE_FILE_VERSION: u16 = 1;
+
+/// Marker for "no parent" / "no value" in the binary format.
+const NONE_U32: u32 = u32::MAX;
+const NONE_I32: i32 = i32::MIN;
+
+/// Budget limits for the recorder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TreeRecorderConfig {
+    /// Maximum number of node records kept; further nodes are dropped.
+    pub max_nodes: usize,
+    /// Maximum ply from the root that is recorded.
+    pub max_ply: u8,
+}
+
+impl Default for TreeRecorderConfig {
+    fn default() -> Self {
+        Self { max_nodes: 200_000, max_ply: 16 }
+    }
+}
+
+/// Transposition table probe result at a node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TtProbe {
+    Miss,
+    Exact,
+    LowerBound,
+    UpperBound,
+}
+
+impl TtProbe {
+    fn to_u8(self) -> u8 {
+        match self {
+            TtProbe::Miss => 0,
+            TtProbe::Exact => 1,
+            TtProbe::LowerBound => 2,
+            TtProbe::UpperBound => 3,
+        }
+    }
+
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(TtProbe::Miss),
+            1 => Some(TtProbe::Exact),
+            2 => Some(TtProbe::LowerBound),
+            3 => Some(TtProbe::UpperBound),
+            _ => None,
+        }
+    }
+}
+
+/// How the search handled a node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeOutcome {
+    /// Children were generated and searched.
+    Searched,
+    /// Skipped by futility/delta pruning before being searched.
+    Pruned,
+    /// Returned directly from a TT entry.
+    TtCutoff,
+    /// Cut off by null move pruning (including mate threat and verification).
+    NullMoveCutoff,
+    /// Detected as a repetition draw.
+    Repetition,
+    /// Checkmate or stalemate.
+    NoLegalMoves,
+    /// Resolved by quiescence search.
+    Quiescence,
+    /// Abandoned because the search was stopped.
+    Stopped,
+}
+
+impl NodeOutcome {
+    /// Short lowercase name used by the inspector.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NodeOutcome::Searched => "searched",
+            NodeOutcome::Pruned => "pruned",
+            NodeOutcome::TtCutoff => "tt-cutoff",
+            NodeOutcome::NullMoveCutoff => "null-move-cutoff",
+            NodeOutcome::Repetition => "repetition",
+            NodeOutcome::NoLegalMoves => "no-legal-moves",
+            NodeOutcome::Quiescence => "quiescence",
+            NodeOutcome::Stopped => "stopped",
+        }
+    }
+
+    /// Parse a name produced by [`NodeOutcome::as_str`].
+    pub fn from_str(s: &str) -> Option<Self> {
+        [
+            NodeOutcome::Searched,
+            NodeOutcome::Pruned,
+            NodeOutcome::TtCutoff,
+            NodeOutcome::NullMoveCutoff,
+            NodeOutcome::Repetition,
+            NodeOutcome::NoLegalMoves,
+            NodeOutcome::Quiescence,
+            NodeOutcome::Stopped,
+        ]
+        .into_iter()
+        .find(|outcome| outcome.as_str() == s)
+    }
+
+    fn to_u8(self) -> u8 {
+        self as u8
+    }
+
+    fn from_u8(value: u8) -> Option<Self> {
+        [
+            NodeOutcome::Searched,
+            NodeOutcome::Pruned,
+            NodeOutcome::TtCutoff,
+            NodeOutcome::NullMoveCutoff,
+            NodeOutcome::Repetition,
+            NodeOutcome::NoLegalMoves,
+            NodeOutcome::Quiescence,
+            NodeOutcome::Stopped,
+        ]
+        .get(value as usize)
+        .copied()
+    }
+}
+
+/// A single recorded move edge and the node it leads to.
+///
+/// `alpha`, `beta`, `score` and `reduced_score` are from the perspective of
+/// the side that played `mv`; `static_eval` is from the perspective of the
+/// side to move in the resulting position, except for pruned moves where it
+/// is the parent's evaluation the pruning decision was based on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeNodeRecord {
+    /// Index of the parent record, `None` for root moves.
+    pub parent: Option<u32>,
+    /// Move in USI notation.
+    pub mv: String,
+    /// Root search pass (one per `search_at_depth` call).
+    pub pass: u16,
+    /// Iteration depth of the pass.
+    pub iteration: u8,
+    /// Ply from the root (1 for root moves).
+    pub ply: u8,
+    /// Remaining depth at the parent when the move was searched.
+    pub depth: u8,
+    /// 1-based index of the move in the parent's ordered move list.
+    pub move_index: u16,
+    pub alpha: i32,
+    pub beta: i32,
+    pub static_eval: Option<i32>,
+    pub tt_probe: TtProbe,
+    /// LMR reduction applied to the move (0 = full depth).
+    pub reduction: u8,
+    /// Score of the reduced null-window search, when LMR was applied.
+    pub reduced_score: Option<i32>,
+    /// Whether a reduced search was re-searched at full depth.
+    pub researched: bool,
+    /// Selective extensions applied by quiescence search below this node.
+    pub extension: u8,
+    pub outcome: NodeOutcome,
+    /// Final score returned for the move, `None` if never searched.
+    pub score: Option<i32>,
+}
+
+/// Records the tree explored by a `SearchEngine`.
+#[derive(Debug, Clone)]
+pub struct SearchTreeRecorder {
+    config: TreeRecorderConfig,
+    root_sfen: String,
+    nodes: Vec<TreeNodeRecord>,
+    /// Open nodes; `None` entries are beyond the budget and not recorded.
+    stack: Vec<Option<u32>>,
+    /// Nesting level of auxiliary searches during which recording is paused.
+    suspended: u32,
+    pass: u16,
+    iteration: u8,
+    dropped_nodes: u64,
+}
+
+impl SearchTreeRecorder {
+    pub fn new(config: TreeRecorderConfig) -> Self {
+        Self {
+            config,
+            root_sfen: String::new(),
+            nodes: Vec::new(),
+            stack: Vec::new(),
+            suspended: 0,
+            pass: 0,
+            iteration: 0,
+            dropped_nodes: 0,
+        }
+    }
+
+    pub fn config(&self) -> &TreeRecorderConfig {
+        &self.config
+    }
+
+    /// Number of records kept so far.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Nodes that were explored but not kept because of the budget.
+    pub fn dropped_nodes(&self) -> u64 {
+        self.dropped_nodes
+    }
+
+    /// Start a new root pass at `iteration` for the position `root_sfen`.
+    pub fn begin_pass(&mut self, root_sfen: &str, iteration: u8) {
+        if self.root_sfen.is_empty() {
+            self.root_sfen = root_sfen.to_string();
+        }
+        self.stack.clear();
+        self.suspended = 0;
+        self.pass = self.pass.wrapping_add(1);
+        self.iteration = iteration;
+    }
+
+    /// Pause recording for an auxiliary search.
+    pub fn suspend(&mut self) {
+        self.suspended += 1;
+    }
+
+    /// Resume recording after [`SearchTreeRecorder::suspend`].
+    pub fn resume(&mut self) {
+        self.suspended = self.suspended.saturating_sub(1);
+    }
+
+    fn is_active(&self) -> bool {
+        self.suspended == 0
+    }
+
+    /// Parent index for a new child, or `Err(())` when the parent is not
+    /// recorded.
+    fn current_parent(&self) -> Result<Option<u32>, ()> {
+        match self.stack.last() {
+            None => Ok(None),
+            Some(Some(id)) => Ok(Some(*id)),
+            Some(None) => Err(()),
+        }
+    }
+
+    fn push_record(
+        &mut self,
+        mv: &Move,
+        depth: u8,
+        alpha: i32,
+        beta: i32,
+        move_index: usize,
+        outcome: NodeOutcome,
+    ) -> Option<u32> {
+        let ply = self.stack.len() + 1;
+        let parent = match self.current_parent() {
+            Ok(parent) => parent,
+            Err(()) => {
+                self.dropped_nodes += 1;
+                return None;
+            }
+        };
+        if ply > self.config.max_ply as usize || self.nodes.len() >= self.config.max_nodes {
+            self.dropped_nodes += 1;
+            return None;
+        }
+        let id = self.nodes.len() as u32;
+        self.nodes.push(TreeNodeRecord {
+            parent,
+            mv: mv.to_usi_string(),
+            pass: self.pass,
+            iteration: self.iteration,
+            ply: ply as u8,
+            depth,
+            move_index: move_index.min(u16::MAX as usize) as u16,
+            alpha,
+            beta,
+            static_eval: None,
+            tt_probe: TtProbe::Miss,
+            reduction: 0,
+            reduced_score: None,
+            researched: false,
+            extension: 0,
+            outcome,
+            score: None,
+        });
+        Some(id)
+    }
+
+    /// Open a child node for `mv` before it is searched.
+    pub fn begin_child(&mut self, mv: &Move, depth: u8, alpha: i32, beta: i32, move_index: usize) {
+        if !self.is_active() {
+            return;
+        }
+        let id = self.push_record(mv, depth, alpha, beta, move_index, NodeOutcome::Searched);
+        self.stack.push(id);
+    }
+
+    /// Close the current child node with the score returned to its parent.
+    pub fn end_child(&mut self, score: i32) {
+        if !self.is_active() {
+            return;
+        }
+        if let Some(Some(id)) = self.stack.pop() {
+            self.nodes[id as usize].score = Some(score);
+        }
+    }
+
+    /// Record a move that was skipped by pruning without being searched.
+    pub fn record_pruned(
+        &mut self,
+        mv: &Move,
+        depth: u8,
+        alpha: i32,
+        beta: i32,
+        move_index: usize,
+        static_eval: i32,
+    ) {
+        if !self.is_active() {
+            return;
+        }
+        if let Some(id) = self.push_record(mv, depth, alpha, beta, move_index, NodeOutcome::Pruned)
+        {
+            // The pruning decision is taken on the parent's static evaluation
+            self.nodes[id as usize].static_eval = Some(static_eval);
+        }
+    }
+
+    /// Update the innermost open node, if it is recorded.
+    pub fn update_current(&mut self, update: impl FnOnce(&mut TreeNodeRecord)) {
+        if !self.is_active() {
+            return;
+        }
+        if let Some(Some(id)) = self.stack.last() {
+            update(&mut self.nodes[*id as usize]);
+        }
+    }
+
+    /// Finish recording and return the tree.
+    pub fn into_tree(self) -> SearchTree {
+        SearchTree::from_records(self.root_sfen, self.nodes)
+    }
+}
+
+/// A recorded search tree, loaded from a recorder or a file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchTree {
+    pub root_sfen: String,
+    pub nodes: Vec<TreeNodeRecord>,
+    children: Vec<Vec<u32>>,
+}
+
+impl SearchTree {
+    fn from_records(root_sfen: String, nodes: Vec<TreeNodeRecord>) -> Self {
+        let mut children = vec![Vec::new(); nodes.len()];
+        for (id, node) in nodes.iter().enumerate() {
+            if let Some(parent) = node.parent {
+                children[parent as usize].push(id as u32);
+            }
+        }
+        Self { root_sfen, nodes, children }
+    }
+
+    /// Last recorded pass, if any.
+    pub fn last_pass(&self) -> Option<u16> {
+        self.nodes.iter().map(|n| n.pass).max()
+    }
+
+    /// Last pass of the given iteration depth.
+    pub fn last_pass_of_iteration(&self, iteration: u8) -> Option<u16> {
+        self.nodes.iter().filter(|n| n.iteration == iteration).map(|n| n.pass).max()
+    }
+
+    /// Root moves searched in `pass`.
+    pub fn roots(&self, pass: u16) -> Vec<u32> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, n)| n.parent.is_none() && n.pass == pass)
+            .map(|(id, _)| id as u32)
+            .collect()
+    }
+
+    /// Recorded children of `id`.
+    ///
+    /// A node that was re-searched after LMR lists the children of both the
+    /// reduced and the full-depth search.
+    pub fn children(&self, id: u32) -> &[u32] {
+        &self.children[id as usize]
+    }
+
+    /// Follow a path of USI moves from the roots of `pass`.
+    ///
+    /// When a move appears several times below the same node (re-searches),
+    /// the last occurrence is used.
+    pub fn find_path(&self, pass: u16, path: &[&str]) -> Option<u32> {
+        let mut current: Option<u32> = None;
+        for mv in path {
+            let candidates = match current {
+                None => self.roots(pass),
+                Some(id) => self.children(id).to_vec(),
+            };
+            current =
+                Some(candidates.into_iter().rev().find(|c| self.nodes[*c as usize].mv == *mv)?);
+        }
+        current
+    }
+
+    /// Moves from the root to `id`.
+    pub fn path_to(&self, id: u32) -> Vec<String> {
+        let mut path = Vec::new();
+        let mut current = Some(id);
+        while let Some(node_id) = current {
+            let node = &self.nodes[node_id as usize];
+            path.push(node.mv.clone());
+            current = node.parent;
+        }
+        path.reverse();
+        path
+    }
+
+    /// One-line summary of a node.
+    pub fn summary(&self, id: u32) -> String {
+        let node = &self.nodes[id as usize];
+        let mut line = format!(
+            "#{:<3} {:<6} d={} [{}, {}] {}",
+            node.move_index,
+            node.mv,
+            node.depth,
+            format_score(node.alpha),
+            format_score(node.beta),
+            node.outcome.as_str()
+        );
+        if let Some(score) = node.score {
+            line.push_str(&format!(" score={}", format_score(score)));
+        }
+        if let Some(eval) = node.static_eval {
+            line.push_str(&format!(" eval={}", eval));
+        }
+        if node.tt_probe != TtProbe::Miss {
+            line.push_str(&format!(" tt={:?}", node.tt_probe));
+        }
+        if node.reduction > 0 {
+            line.push_str(&format!(" R={}", node.reduction));
+            if node.researched {
+                line.push_str(" re-searched");
+            }
+        }
+        if node.extension > 0 {
+            line.push_str(&format!(" ext={}", node.extension));
+        }
+        let children = self.children(id).len();
+        if children > 0 {
+            line.push_str(&format!(" children={}", children));
+        }
+        line
+    }
+
+    /// Human-readable explanation of how the search treated a node.
+    pub fn explain(&self, id: u32) -> String {
+        let node = &self.nodes[id as usize];
+        let mut lines = vec![format!(
+            "{} (move #{} at ply {}, remaining depth {}, window [{}, {}], iteration {})",
+            self.path_to(id).join(" "),
+            node.move_index,
+            node.ply,
+            node.depth,
+            format_score(node.alpha),
+            format_score(node.beta),
+            node.iteration
+        )];
+
+        match node.outcome {
+            NodeOutcome::Pruned => lines.push(format!(
+                "Pruned before search by futility/delta pruning: quiet move #{} at depth {} with \
+                 parent static eval {} against window [{}, {}].",
+                node.move_index,
+                node.depth,
+                node.static_eval.map(|e| e.to_string()).unwrap_or_else(|| "?".to_string()),
+                format_score(node.alpha),
+                format_score(node.beta)
+            )),
+            NodeOutcome::TtCutoff => lines.push(format!(
+                "Cut off by a {:?} transposition table entry before generating moves.",
+                node.tt_probe
+            )),
+            NodeOutcome::NullMoveCutoff => lines.push(
+                "Cut off by null move pruning: passing still failed high for the side to move."
+                    .to_string(),
+            ),
+            NodeOutcome::Repetition => {
+                lines.push("Scored as a repetition draw.".to_string());
+            }
+            NodeOutcome::NoLegalMoves => {
+                lines.push("No legal moves: checkmate or stalemate.".to_string())
+            }
+            NodeOutcome::Quiescence => lines.push(format!(
+                "Reached depth 0 and was resolved by quiescence search ({} selective \
+                 extensions).",
+                node.extension
+            )),
+            NodeOutcome::Stopped => {
+                lines.push("Search stopped before this node was completed.".to_string())
+            }
+            NodeOutcome::Searched => {
+                lines.push(format!("Searched with {} recorded children.", self.children(id).len()))
+            }
+        }
+
+        if node.reduction > 0 {
+            let reduced = node.reduced_score.map(format_score).unwrap_or_else(|| "?".to_string());
+            if node.researched {
+                lines.push(format!(
+                    "Late move reduction by {} ply; the reduced null-window search returned {} \
+                     above alpha {}, so the move was re-searched at full depth.",
+                    node.reduction,
+                    reduced,
+                    format_score(node.alpha)
+                ));
+            } else {
+                lines.push(format!(
+                    "Late move reduction by {} ply; the reduced null-window search returned {} \
+                     which did not beat alpha {} by the re-search margin, so the reduced result \
+                     was kept.",
+                    node.reduction,
+                    reduced,
+                    format_score(node.alpha)
+                ));
+            }
+        } else if node.outcome != NodeOutcome::Pruned && node.depth > 0 {
+            lines.push("Searched at full depth (no reduction).".to_string());
+        }
+
+        if let Some(eval) = node.static_eval {
+            if node.outcome != NodeOutcome::Pruned {
+                lines.push(format!("Static eval (side to move): {}", eval));
+            }
+        }
+        match node.score {
+            Some(score) => lines.push(format!("Returned score: {}", format_score(score))),
+            None if node.outcome != NodeOutcome::Pruned => {
+                lines.push("No score recorded.".to_string())
+            }
+            None => {}
+        }
+        lines.join("\n")
+    }
+
+    /// Write the tree in the compact binary format.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(TREE_FILE_MAGIC)?;
+        writer.write_all(&TREE_FILE_VERSION.to_le_bytes())?;
+        write_str(writer, &self.root_sfen)?;
+        writer.write_all(&(self.nodes.len() as u32).to_le_bytes())?;
+        for node in &self.nodes {
+            writer.write_all(&node.parent.unwrap_or(NONE_U32).to_le_bytes())?;
+            let mv = node.mv.as_bytes();
+            writer.write_all(&[mv.len().min(u8::MAX as usize) as u8])?;
+            writer.write_all(&mv[..mv.len().min(u8::MAX as usize)])?;
+            writer.write_all(&node.pass.to_le_bytes())?;
+            writer.write_all(&[node.iteration, node.ply, node.depth])?;
+            writer.write_all(&node.move_index.to_le_bytes())?;
+            writer.write_all(&node.alpha.to_le_bytes())?;
+            writer.write_all(&node.beta.to_le_bytes())?;
+            writer.write_all(&node.static_eval.unwrap_or(NONE_I32).to_le_bytes())?;
+            writer.write_all(&node.reduced_score.unwrap_or(NONE_I32).to_le_bytes())?;
+            writer.write_all(&node.score.unwrap_or(NONE_I32).to_le_bytes())?;
+            writer.write_all(&[
+                node.tt_probe.to_u8(),
+                node.reduction,
+                node.researched as u8,
+                node.extension,
+                node.outcome.to_u8(),
+            ])?;
+        }
+        Ok(())
+    }
+
+    /// Read a tree written by [`SearchTree::write_to`].
+    pub fn read_from<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != TREE_FILE_MAGIC {
+            return Err(invalid_data("not a search tree file"));
+        }
+        let version = read_u16(reader)?;
+        if version != TREE_FILE_VERSION {
+            return Err(invalid_data(&format!("unsupported search tree version {}", version)));
+        }
+        let root_sfen = read_str(reader)?;
+        let count = read_u32(reader)? as usize;
+        let mut nodes = Vec::with_capacity(count.min(1 << 20));
+        for id in 0..count {
+            let parent = match read_u32(reader)? {
+                NONE_U32 => None,
+                parent if (parent as usize) < id => Some(parent),
+                parent => {
+                    return Err(invalid_data(&format!("node {} has invalid parent {}", id, parent)))
+                }
+            };
+            let mv_len = read_u8(reader)? as usize;
+            let mut mv = vec![0u8; mv_len];
+            reader.read_exact(&mut mv)?;
+            let mv = String::from_utf8(mv).map_err(|_| invalid_data("move is not UTF-8"))?;
+            let pass = read_u16(reader)?;
+            let iteration = read_u8(reader)?;
+            let ply = read_u8(reader)?;
+            let depth = read_u8(reader)?;
+            let move_index = read_u16(reader)?;
+            let alpha = read_i32(reader)?;
+            let beta = read_i32(reader)?;
+            let static_eval = optional_i32(read_i32(reader)?);
+            let reduced_score = optional_i32(read_i32(reader)?);
+            let score = optional_i32(read_i32(reader)?);
+            let mut tail = [0u8; 5];
+            reader.read_exact(&mut tail)?;
+            nodes.push(TreeNodeRecord {
+                parent,
+                mv,
+                pass,
+                iteration,
+                ply,
+                depth,
+                move_index,
+                alpha,
+                beta,
+                static_eval,
+                tt_probe: TtProbe::from_u8(tail[0]).ok_or_else(|| invalid_data("bad TT probe"))?,
+                reduction: tail[1],
+                reduced_score,
+                researched: tail[2] != 0,
+                extension: tail[3],
+                outcome: NodeOutcome::from_u8(tail[4])
+                    .ok_or_else(|| invalid_data("bad outcome"))?,
+                score,
+            });
+        }
+        Ok(Self::from_records(root_sfen, nodes))
+    }
+
+    /// Save the tree to `path`.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_to(&mut writer)?;
+        writer.flush()
+    }
+
+    /// Load a tree from `path`.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::read_from(&mut BufReader::new(File::open(path)?))
+    }
+}
+
+/// Format a score, clamping the search sentinels to `-inf`/`+inf`.
+fn format_score(score: i32) -> String {
+    if score <= -1_000_000_000 {
+        "-inf".to_string()
+    } else if score >= 1_000_000_000 {
+        "+inf".to_string()
+    } else {
+        score.to_string()
+    }
+}
+
+fn optional_i32(value: i32) -> Option<i32> {
+    if value == NONE_I32 {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+fn write_str<W: Write>(writer: &mut W, s: &str) -> io::Result<()> {
+    let bytes = s.as_bytes();
+    let len = bytes.len().min(u16::MAX as usize);
+    writer.write_all(&(len as u16).to_le_bytes())?;
+    writer.write_all(&bytes[..len])
+}
+
+fn read_str<R: Read>(reader: &mut R) -> io::Result<String> {
+    let len = read_u16(reader)? as usize;
+    let mut bytes = vec![0u8; len];
+    reader.read_exact(&mut bytes)?;
+    String::from_utf8(bytes).map_err(|_| invalid_data("string is not UTF-8"))
+}
+
+fn read_u8<R: Read>(reader: &mut R) -> io::Result<u8> {
+    let mut buf = [0u8; 1];
+    reader.read_exact(&mut buf)?;
+    Ok(buf[0])
+}
+
+fn read_u16<R: Read>(reader: &mut R) -> io::Result<u16> {
+    let mut buf = [0u8; 2];
+    reader.read_exact(&mut buf)?;
+    Ok(u16::from_le_bytes(buf))
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> io::Result<u32> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_i32<R: Read>(reader: &mut R) -> io::Result<i32> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(i32::from_le_bytes(buf))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::core::{PieceType, Player, Position};
+
+    fn pawn_move(col: u8) -> Move {
+        Move::new_move(
+            Position::new(6, col),
+            Position::new(5, col),
+            PieceType::Pawn,
+            Player::Black,
+            false,
+        )
+    }
+
+    fn sample_tree() -> SearchTree {
+        let mut recorder = SearchTreeRecorder::new(TreeRecorderConfig::default());
+        recorder.begin_pass("startpos", 2);
+        recorder.begin_child(&pawn_move(2), 2, -100, 100, 1);
+        recorder.update_current(|n| n.static_eval = Some(10));
+        recorder.begin_child(&pawn_move(3), 1, -100, 100, 1);
+        recorder.update_current(|n| n.outcome = NodeOutcome::Quiescence);
+        recorder.end_child(-5);
+        recorder.record_pruned(&pawn_move(4), 1, -100, 5, 2, -300);
+        recorder.end_child(5);
+        recorder.begin_child(&pawn_move(6), 2, 5, 100, 2);
+        recorder.update_current(|n| {
+            n.reduction = 1;
+            n.reduced_score = Some(3);
+        });
+        recorder.end_child(3);
+        recorder.into_tree()
+    }
+
+    #[test]
+    fn test_recorder_builds_tree() {
+        let tree = sample_tree();
+        assert_eq!(tree.nodes.len(), 4);
+        let roots = tree.roots(1);
+        assert_eq!(roots.len(), 2);
+        assert_eq!(tree.children(roots[0]).len(), 2);
+        let node = tree.find_path(1, &[pawn_move(2).to_usi_string().as_str()]).unwrap();
+        assert_eq!(tree.nodes[node as usize].score, Some(5));
+        assert_eq!(tree.nodes[node as usize].static_eval, Some(10));
+    }
+
+    #[test]
+    fn test_round_trip_binary_format() {
+        let tree = sample_tree();
+        let mut bytes = Vec::new();
+        tree.write_to(&mut bytes).unwrap();
+        let loaded = SearchTree::read_from(&mut bytes.as_slice()).unwrap();
+        assert_eq!(loaded, tree);
+        assert!(SearchTree::read_from(&mut &b"NOPE"[..]).is_err());
+    }
+
+    #[test]
+    fn test_budget_limits_nodes_and_ply() {
+        let mut recorder =
+            SearchTreeRecorder::new(TreeRecorderConfig { max_nodes: 10, max_ply: 1 });
+        recorder.begin_pass("startpos", 3);
+        recorder.begin_child(&pawn_move(0), 3, -1, 1, 1);
+        recorder.begin_child(&pawn_move(1), 2, -1, 1, 1);
+        recorder.begin_child(&pawn_move(2), 1, -1, 1, 1);
+        recorder.end_child(0);
+        recorder.end_child(0);
+        recorder.end_child(0);
+        assert_eq!(recorder.node_count(), 1);
+        assert_eq!(recorder.dropped_nodes(), 2);
+    }
+
+    #[test]
+    fn test_suspended_searches_are_not_recorded() {
+        let mut recorder = SearchTreeRecorder::new(TreeRecorderConfig::default());
+        recorder.begin_pass("startpos", 1);
+        recorder.begin_child(&pawn_move(0), 1, -1, 1, 1);
+        recorder.suspend();
+        recorder.begin_child(&pawn_move(1), 0, -1, 1, 1);
+        recorder.end_child(7);
+        recorder.resume();
+        recorder.end_child(2);
+        let tree = recorder.into_tree();
+        assert_eq!(tree.nodes.len(), 1);
+        assert_eq!(tree.nodes[0].score, Some(2));
+    }
+
+    #[test]
+    fn test_explain_pruned_and_reduced_moves() {
+        let tree = sample_tree();
+        let first = tree.roots(1)[0];
+        let pruned = tree.children(first)[1];
+        assert!(tree.explain(pruned).contains("futility/delta pruning"));
+        let reduced = tree.roots(1)[1];
+        let text = tree.explain(reduced);
+        assert!(text.contains("reduction by 1 ply"));
+        assert!(text.contains("reduced result was kept"));
+    }
+}
@@ -0,0 +1,348 @@
+//! On-disk format for saving and loading transposition tables.
+//!
+//! A hash file starts with a fixed header followed by fixed-size entry
+//! records, all little-endian:
+//!
+//! | Field          | Size | Notes                                        |
+//! |----------------|------|----------------------------------------------|
+//! | magic          | 4    | `YTTH`                                       |
+//! | version        | 2    | [`TT_FILE_VERSION`]                          |
+//! | table kind     | 1    | 0 = thread-safe table, 1 = hierarchical      |
+//! | reserved       | 1    |                                              |
+//! | zobrist seed   | 8    | seed of the global `ZobristTable`            |
+//! | record size    | 2    | bytes per entry record                       |
+//! | record layout  | 2    | [`TT_RECORD_LAYOUT`]                         |
+//! | entry count    | 8    |                                              |
+//!
+//! Hash keys are only meaningful for the Zobrist keys they were computed
+//! with, so files written with a different seed or record layout are refused
+//! instead of silently filling the table with garbage.
+
+use crate::search::error_handling::{TranspositionError, TranspositionResult};
+use crate::search::zobrist::ZOBRIST_TABLE;
+use crate::types::core::{Move, PieceType, Player, Position};
+use crate::types::search::{EntrySource, TranspositionFlag};
+use crate::types::transposition::TranspositionEntry;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+/// File magic of a saved transposition table.
+pub const TT_FILE_MAGIC: &[u8; 4] = b"YTTH";
+
+/// Current header version.
+pub const TT_FILE_VERSION: u16 = 1;
+
+/// Identifier of the entry record layout written by this version.
+pub const TT_RECORD_LAYOUT: u16 = 1;
+
+/// Size in bytes of one entry record.
+pub const TT_RECORD_SIZE: u16 = 24;
+
+const HEADER_SIZE: usize = 28;
+const DROP_SQUARE: u8 = 0xFF;
+
+/// Which table wrote a hash file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TtFileKind {
+    ThreadSafe,
+    Hierarchical,
+}
+
+impl TtFileKind {
+    fn to_u8(self) -> u8 {
+        match self {
+            TtFileKind::ThreadSafe => 0,
+            TtFileKind::Hierarchical => 1,
+        }
+    }
+
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(TtFileKind::ThreadSafe),
+            1 => Some(TtFileKind::Hierarchical),
+            _ => None,
+        }
+    }
+}
+
+/// Header of a hash file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TtFileHeader {
+    pub version: u16,
+    pub kind: TtFileKind,
+    pub zobrist_seed: u64,
+    pub record_size: u16,
+    pub record_layout: u16,
+    pub entry_count: u64,
+}
+
+impl TtFileHeader {
+    /// Header for the current format and global Zobrist table.
+    pub fn current(kind: TtFileKind, entry_count: u64) -> Self {
+        Self {
+            version: TT_FILE_VERSION,
+            kind,
+            zobrist_seed: ZOBRIST_TABLE.get_seed(),
+            record_size: TT_RECORD_SIZE,
+            record_layout: TT_RECORD_LAYOUT,
+            entry_count,
+        }
+    }
+
+    /// Check that entries in this file can be used by the running engine.
+    pub fn validate(&self) -> TranspositionResult<()> {
+        if self.version != TT_FILE_VERSION {
+            return Err(TranspositionError::InvalidInput(format!(
+                "unsupported hash file version {} (expected {})",
+                self.version, TT_FILE_VERSION
+            )));
+        }
+        let seed = ZOBRIST_TABLE.get_seed();
+        if self.zobrist_seed != seed {
+            return Err(TranspositionError::InvalidInput(format!(
+                "hash file Zobrist seed {:#018x} does not match engine seed {:#018x}",
+                self.zobrist_seed, seed
+            )));
+        }
+        if self.record_size != TT_RECORD_SIZE || self.record_layout != TT_RECORD_LAYOUT {
+            return Err(TranspositionError::InvalidInput(format!(
+                "hash file entry layout {} ({} bytes) does not match layout {} ({} bytes)",
+                self.record_layout, self.record_size, TT_RECORD_LAYOUT, TT_RECORD_SIZE
+            )));
+        }
+        Ok(())
+    }
+
+    fn to_bytes(&self) -> [u8; HEADER_SIZE] {
+        let mut bytes = [0u8; HEADER_SIZE];
+        bytes[0..4].copy_from_slice(TT_FILE_MAGIC);
+        bytes[4..6].copy_from_slice(&self.version.to_le_bytes());
+        bytes[6] = self.kind.to_u8();
+        bytes[8..16].copy_from_slice(&self.zobrist_seed.to_le_bytes());
+        bytes[16..18].copy_from_slice(&self.record_size.to_le_bytes());
+        bytes[18..20].copy_from_slice(&self.record_layout.to_le_bytes());
+        bytes[20..28].copy_from_slice(&self.entry_count.to_le_bytes());
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8; HEADER_SIZE]) -> TranspositionResult<Self> {
+        if &bytes[0..4] != TT_FILE_MAGIC {
+            return Err(TranspositionError::InvalidInput("not a hash file".to_string()));
+        }
+        let kind = TtFileKind::from_u8(bytes[6]).ok_or_else(|| {
+            TranspositionError::DataCorruption(format!("unknown table kind {}", bytes[6]))
+        })?;
+        Ok(Self {
+            version: u16::from_le_bytes([bytes[4], bytes[5]]),
+            kind,
+            zobrist_seed: u64::from_le_bytes(bytes[8..16].try_into().unwrap()),
+            record_size: u16::from_le_bytes([bytes[16], bytes[17]]),
+            record_layout: u16::from_le_bytes([bytes[18], bytes[19]]),
+            entry_count: u64::from_le_bytes(bytes[20..28].try_into().unwrap()),
+        })
+    }
+}
+
+/// A saved entry together with the tier it came from (0 for single-level
+/// tables, 1/2 for the hierarchical L1/L2 tiers).
+#[derive(Debug, Clone)]
+pub struct TtFileRecord {
+    pub entry: TranspositionEntry,
+    pub tier: u8,
+}
+
+fn encode_move(mv: &Option<Move>) -> [u8; 4] {
+    match mv {
+        None => [0; 4],
+        Some(mv) => {
+            let from = mv.from.map(|p| p.to_index()).unwrap_or(DROP_SQUARE);
+            let mut flags = 0b1000u8;
+            if mv.is_capture {
+                flags |= 0b0001;
+            }
+            if mv.is_promotion {
+                flags |= 0b0010;
+            }
+            if mv.player == Player::White {
+                flags |= 0b0100;
+            }
+            [from, mv.to.to_index(), mv.piece_type.to_u8(), flags]
+        }
+    }
+}
+
+fn decode_move(bytes: [u8; 4]) -> TranspositionResult<Option<Move>> {
+    let [from, to, piece, flags] = bytes;
+    if flags & 0b1000 == 0 {
+        return Ok(None);
+    }
+    if to >= 81 || (from != DROP_SQUARE && from >= 81) || piece >= 14 {
+        return Err(TranspositionError::DataCorruption(format!("invalid move record {:?}", bytes)));
+    }
+    Ok(Some(Move {
+        from: if from == DROP_SQUARE { None } else { Some(Position::from_index(from)) },
+        to: Position::from_index(to),
+        piece_type: PieceType::from_u8(piece),
+        player: if flags & 0b0100 != 0 { Player::White } else { Player::Black },
+        is_promotion: flags & 0b0010 != 0,
+        is_capture: flags & 0b0001 != 0,
+        captured_piece: None,
+        gives_check: false,
+        is_recapture: false,
+    }))
+}
+
+fn encode_record(record: &TtFileRecord) -> [u8; TT_RECORD_SIZE as usize] {
+    let entry = &record.entry;
+    let mut bytes = [0u8; TT_RECORD_SIZE as usize];
+    bytes[0..8].copy_from_slice(&entry.hash_key.to_le_bytes());
+    bytes[8..12].copy_from_slice(&entry.score.to_le_bytes());
+    bytes[12..16].copy_from_slice(&entry.age.to_le_bytes());
+    bytes[16..20].copy_from_slice(&encode_move(&entry.best_move));
+    bytes[20] = entry.depth;
+    bytes[21] = match entry.flag {
+        TranspositionFlag::Exact => 0,
+        TranspositionFlag::LowerBound => 1,
+        TranspositionFlag::UpperBound => 2,
+    };
+    bytes[22] = entry.source.to_discriminant() as u8;
+    bytes[23] = record.tier;
+    bytes
+}
+
+fn decode_record(bytes: &[u8; TT_RECORD_SIZE as usize]) -> TranspositionResult<TtFileRecord> {
+    let flag = match bytes[21] {
+        0 => TranspositionFlag::Exact,
+        1 => TranspositionFlag::LowerBound,
+        2 => TranspositionFlag::UpperBound,
+        other => {
+            return Err(TranspositionError::DataCorruption(format!("invalid entry flag {}", other)))
+        }
+    };
+    Ok(TtFileRecord {
+        entry: TranspositionEntry {
+            score: i32::from_le_bytes(bytes[8..12].try_into().unwrap()),
+            depth: bytes[20],
+            flag,
+            best_move: decode_move(bytes[16..20].try_into().unwrap())?,
+            hash_key: u64::from_le_bytes(bytes[0..8].try_into().unwrap()),
+            age: u32::from_le_bytes(bytes[12..16].try_into().unwrap()),
+            source: EntrySource::from_discriminant(bytes[22] as u32),
+        },
+        tier: bytes[23],
+    })
+}
+
+fn io_error(context: &str, path: &Path, err: std::io::Error) -> TranspositionError {
+    TranspositionError::TableOperationFailed(format!("{} {}: {}", context, path.display(), err))
+}
+
+/// Write `records` to `path`, replacing any existing file.
+pub fn write_tt_file<P: AsRef<Path>>(
+    path: P,
+    kind: TtFileKind,
+    records: &[TtFileRecord],
+) -> TranspositionResult<()> {
+    let path = path.as_ref();
+    let file = File::create(path).map_err(|e| io_error("cannot create", path, e))?;
+    let mut writer = BufWriter::new(file);
+    let header = TtFileHeader::current(kind, records.len() as u64);
+    writer
+        .write_all(&header.to_bytes())
+        .map_err(|e| io_error("cannot write", path, e))?;
+    for record in records {
+        writer
+            .write_all(&encode_record(record))
+            .map_err(|e| io_error("cannot write", path, e))?;
+    }
+    writer.flush().map_err(|e| io_error("cannot write", path, e))
+}
+
+/// Read and validate a hash file, returning its header and records.
+pub fn read_tt_file<P: AsRef<Path>>(
+    path: P,
+) -> TranspositionResult<(TtFileHeader, Vec<TtFileRecord>)> {
+    let path = path.as_ref();
+    let file = File::open(path).map_err(|e| io_error("cannot open", path, e))?;
+    let mut reader = BufReader::new(file);
+
+    let mut header_bytes = [0u8; HEADER_SIZE];
+    reader
+        .read_exact(&mut header_bytes)
+        .map_err(|e| io_error("cannot read", path, e))?;
+    let header = TtFileHeader::from_bytes(&header_bytes)?;
+    header.validate()?;
+
+    let mut records = Vec::with_capacity(header.entry_count.min(1 << 24) as usize);
+    let mut record_bytes = [0u8; TT_RECORD_SIZE as usize];
+    for _ in 0..header.entry_count {
+        reader
+            .read_exact(&mut record_bytes)
+            .map_err(|e| io_error("truncated", path, e))?;
+        records.push(decode_record(&record_bytes)?);
+    }
+    Ok((header, records))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_record(hash_key: u64, tier: u8) -> TtFileRecord {
+        TtFileRecord {
+            entry: TranspositionEntry {
+                score: -321,
+                depth: 7,
+                flag: TranspositionFlag::LowerBound,
+                best_move: Some(Move::new_drop(
+                    PieceType::Silver,
+                    Position::new(4, 4),
+                    Player::White,
+                )),
+                hash_key,
+                age: 3,
+                source: EntrySource::MainSearch,
+            },
+            tier,
+        }
+    }
+
+    #[test]
+    fn test_record_round_trip() {
+        let record = sample_record(0xDEAD_BEEF, 2);
+        let decoded = decode_record(&encode_record(&record)).unwrap();
+        assert_eq!(decoded.entry.hash_key, record.entry.hash_key);
+        assert_eq!(decoded.entry.score, -321);
+        assert_eq!(decoded.entry.flag, TranspositionFlag::LowerBound);
+        assert_eq!(decoded.entry.best_move, record.entry.best_move);
+        assert_eq!(decoded.tier, 2);
+    }
+
+    #[test]
+    fn test_header_rejects_foreign_seed_and_layout() {
+        let header = TtFileHeader::current(TtFileKind::ThreadSafe, 0);
+        assert!(header.validate().is_ok());
+
+        let mut foreign_seed = header.clone();
+        foreign_seed.zobrist_seed ^= 1;
+        assert!(foreign_seed.validate().is_err());
+
+        let mut foreign_layout = header;
+        foreign_layout.record_size += 8;
+        assert!(foreign_layout.validate().is_err());
+    }
+
+    #[test]
+    fn test_file_round_trip() {
+        let path = std::env::temp_dir().join(format!("tt_persistence_{}.ytt", std::process::id()));
+        let records = vec![sample_record(1, 0), sample_record(2, 0)];
+        write_tt_file(&path, TtFileKind::ThreadSafe, &records).unwrap();
+        let (header, loaded) = read_tt_file(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(header.kind, TtFileKind::ThreadSafe);
+        assert_eq!(header.entry_count, 2);
+        let keys: Vec<u64> = loaded.iter().map(|r| r.entry.hash_key).collect();
+        assert_eq!(keys, vec![1, 2]);
+    }
+}
@@ -0,0 +1,807 @@
+//! Embeddable search session
+//!
+//! `EngineSession` wraps a `ShogiEngine` for use as a library: set a position
+//! (base SFEN plus the moves played since, so repetitions are known), start a
+//! search with `SearchLimits` on a worker thread, receive progress through a
+//! `SearchReporter`, stop or wait on the returned `SearchHandle`, and get a
+//! `SearchOutcome` with the best move, score, PV and statistics.
+//!
+//! ```no_run
+//! use shogi_engine::search::ChannelReporter;
+//! use shogi_engine::{EngineSession, SearchLimits};
+//! use std::sync::Arc;
+//!
+//! let session = EngineSession::new();
+//! let (reporter, events) = ChannelReporter::channel();
+//! session.set_reporter(Arc::new(reporter));
+//! session.set_position("startpos", &["7g7f", "3c3d"]).unwrap();
+//!
+//! let handle = session.start(SearchLimits::movetime(1000));
+//! for event in events.iter().take(3) {
+//!     println!("{}", event.to_usi());
+//! }
+//! let outcome = handle.wait();
+//! println!("{}", outcome.to_usi_bestmove());
+//! ```
+//!
+//! The USI front end, the analyzer and the strength tester are built on this
+//! API.
+
+use crate::search::time_management::MoveClock;
+use crate::search::{ScoreBound, SearchEvent, SearchInfo, SearchReporter};
+use crate::types::core::{Move, Player};
+use crate::types::GameResult;
+use crate::ShogiEngine;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// Time limit used for `go infinite` and depth-only searches
+pub const INFINITE_TIME_MS: u32 = 24 * 60 * 60 * 1000;
+
+/// Time used when neither a clock nor a depth is given
+const DEFAULT_MOVE_TIME_MS: u32 = 5000;
+
+/// Share of the remaining clock spent on one move
+const CLOCK_FRACTION: u32 = 40;
+
+/// Limits for one search
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchLimits {
+    /// Maximum depth; `None` uses the engine's `MaxDepth` setting
+    pub depth: Option<u8>,
+    /// Fixed time for this move
+    pub movetime_ms: Option<u32>,
+    /// Remaining clock of each side
+    pub btime_ms: u32,
+    pub wtime_ms: u32,
+    /// Byoyomi period; when set it is used as the move time
+    pub byoyomi_ms: u32,
+    /// Fischer increment of each side, added after every move
+    pub binc_ms: u32,
+    pub winc_ms: u32,
+    /// Search until stopped
+    pub infinite: bool,
+    /// Search the expected reply without a time limit until `ponderhit`;
+    /// the other limits apply from then on
+    pub ponder: bool,
+    /// Maximum nodes to search
+    pub nodes: Option<u64>,
+}
+
+impl SearchLimits {
+    /// Search to `depth` without a time limit
+    pub fn depth(depth: u8) -> Self {
+        Self { depth: Some(depth), ..Self::default() }
+    }
+
+    /// Search for a fixed time
+    pub fn movetime(movetime_ms: u32) -> Self {
+        Self { movetime_ms: Some(movetime_ms), ..Self::default() }
+    }
+
+    /// Search about `nodes` nodes without a time limit
+    pub fn nodes(nodes: u64) -> Self {
+        Self { nodes: Some(nodes), ..Self::default() }
+    }
+
+    /// Search until stopped
+    pub fn infinite() -> Self {
+        Self { infinite: true, ..Self::default() }
+    }
+
+    /// Add a fixed time limit
+    pub fn with_movetime(mut self, movetime_ms: u32) -> Self {
+        self.movetime_ms = Some(movetime_ms);
+        self
+    }
+
+    /// Parse the arguments of a USI `go` command; unknown tokens are ignored
+    pub fn from_usi_go(parts: &[&str]) -> Self {
+        let mut limits = Self::default();
+        let mut i = 0;
+        while i < parts.len() {
+            let value = parts.get(i + 1).and_then(|v| v.parse::<u32>().ok());
+            match (parts[i], value) {
+                ("btime", Some(v)) => limits.btime_ms = v,
+                ("wtime", Some(v)) => limits.wtime_ms = v,
+                ("byoyomi", Some(v)) => limits.byoyomi_ms = v,
+                ("binc", Some(v)) => limits.binc_ms = v,
+                ("winc", Some(v)) => limits.winc_ms = v,
+                ("movetime", Some(v)) => limits.movetime_ms = Some(v),
+                ("depth", Some(v)) => limits.depth = Some(v.min(u8::MAX as u32) as u8),
+                ("nodes", _) => match parts.get(i + 1).and_then(|v| v.parse::<u64>().ok()) {
+                    Some(nodes) => limits.nodes = Some(nodes),
+                    None => {
+                        i += 1;
+                        continue;
+                    }
+                },
+                ("infinite", _) => {
+                    limits.infinite = true;
+                    i += 1;
+                    continue;
+                }
+                ("ponder", _) => {
+                    limits.ponder = true;
+                    i += 1;
+                    continue;
+                }
+                _ => {
+                    i += 1;
+                    continue;
+                }
+            }
+            i += 2;
+        }
+        limits
+    }
+
+    /// Clock of `player` for planning a move, `None` when the limits are not
+    /// clock-based (fixed move time, infinite, depth only)
+    pub fn move_clock(&self, player: Player, ply: u32, phase: i32) -> Option<MoveClock> {
+        if self.infinite || self.movetime_ms.is_some() {
+            return None;
+        }
+        let (remaining_ms, increment_ms) = match player {
+            Player::Black => (self.btime_ms, self.binc_ms),
+            Player::White => (self.wtime_ms, self.winc_ms),
+        };
+        (remaining_ms > 0 || self.byoyomi_ms > 0).then_some(MoveClock {
+            remaining_ms,
+            increment_ms,
+            byoyomi_ms: self.byoyomi_ms,
+            moves_to_go: None,
+            ply,
+            phase,
+        })
+    }
+
+    /// Fixed share of the time to spend on this move for `player`, used when
+    /// no game state is available to plan with
+    pub fn time_budget_ms(&self, player: Player) -> u32 {
+        if self.infinite {
+            return INFINITE_TIME_MS;
+        }
+        if let Some(movetime) = self.movetime_ms {
+            return movetime;
+        }
+        if self.byoyomi_ms > 0 {
+            return self.byoyomi_ms;
+        }
+        let (clock, increment) = match player {
+            Player::Black => (self.btime_ms, self.binc_ms),
+            Player::White => (self.wtime_ms, self.winc_ms),
+        };
+        if clock > 0 {
+            // The increment is credited after the move, so never spend more
+            // than is on the clock now
+            (clock / CLOCK_FRACTION + increment).min(clock - clock / CLOCK_FRACTION)
+        } else if self.depth.is_some() || self.nodes.is_some() {
+            INFINITE_TIME_MS
+        } else {
+            DEFAULT_MOVE_TIME_MS
+        }
+    }
+}
+
+/// Result of a finished search
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchOutcome {
+    /// `None` when the side to move has no legal move
+    pub best_move: Option<Move>,
+    /// Expected reply, taken from the PV
+    pub ponder_move: Option<Move>,
+    pub score: i32,
+    pub bound: ScoreBound,
+    pub pv: Vec<Move>,
+    /// Last completed iteration (0 for book and tablebase moves)
+    pub depth: u8,
+    pub seldepth: u8,
+    /// Nodes of the completed iterations
+    pub nodes: u64,
+    pub time_ms: u64,
+    /// The score stayed below `ResignValue` for `ResignMoves` searches
+    pub resign: bool,
+}
+
+impl SearchOutcome {
+    /// The USI `bestmove` line for this result
+    pub fn to_usi_bestmove(&self) -> String {
+        match self.best_move {
+            Some(ref mv) if !self.resign => format!("bestmove {}", mv.to_usi_string()),
+            _ => "bestmove resign".to_string(),
+        }
+    }
+}
+
+/// A running search started by `EngineSession::start`
+pub struct SearchHandle {
+    stop_flag: Arc<AtomicBool>,
+    ponder: Option<Arc<PonderGate>>,
+    thread: JoinHandle<SearchOutcome>,
+}
+
+impl SearchHandle {
+    /// Ask the search to stop; `wait` then returns the best move so far
+    pub fn stop(&self) {
+        self.stop_flag.store(true, Ordering::Relaxed);
+        if let Some(ref ponder) = self.ponder {
+            ponder.release();
+        }
+    }
+
+    /// The expected move was played: a ponder search now runs on the time
+    /// its limits give
+    pub fn ponderhit(&self) {
+        if let Some(ref ponder) = self.ponder {
+            ponder.hit();
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.thread.is_finished()
+    }
+
+    /// Block until the search finishes
+    pub fn wait(self) -> SearchOutcome {
+        self.thread.join().unwrap_or_default()
+    }
+}
+
+/// State of a `go ponder` search, shared with `ponderhit` and `stop`. Its
+/// result is held back until one of them arrives.
+#[derive(Default)]
+struct PonderGate {
+    state: Mutex<PonderState>,
+    changed: Condvar,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum PonderState {
+    /// Searching the expected reply without a time limit
+    #[default]
+    Pondering,
+    /// The expected move was played; the move's time is running
+    Hit,
+    /// Stopped, or finished
+    Released,
+}
+
+impl PonderGate {
+    fn state(&self) -> MutexGuard<'_, PonderState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn hit(&self) {
+        let mut state = self.state();
+        if *state == PonderState::Pondering {
+            *state = PonderState::Hit;
+            self.changed.notify_all();
+        }
+    }
+
+    fn release(&self) {
+        *self.state() = PonderState::Released;
+        self.changed.notify_all();
+    }
+
+    /// Block until `ponderhit` or `stop`; true when the move was played
+    fn wait_for_opponent(&self) -> bool {
+        let state = self
+            .changed
+            .wait_while(self.state(), |state| *state == PonderState::Pondering)
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        *state == PonderState::Hit
+    }
+
+    /// Stop the search `move_time_ms` after `ponderhit`, unless it ends first
+    fn run_timer(&self, move_time_ms: u32, stop_flag: &AtomicBool) {
+        let state = self
+            .changed
+            .wait_while(self.state(), |state| *state == PonderState::Pondering)
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if *state != PonderState::Hit {
+            return;
+        }
+        let (state, timeout) = self
+            .changed
+            .wait_timeout_while(state, Duration::from_millis(move_time_ms.into()), |state| {
+                *state == PonderState::Hit
+            })
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if timeout.timed_out() && *state == PonderState::Hit {
+            stop_flag.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Library entry point: a `ShogiEngine` driven from a worker thread
+pub struct EngineSession {
+    engine: Arc<Mutex<ShogiEngine>>,
+    stop_flag: Arc<AtomicBool>,
+    /// Gate of the running `go ponder` search
+    ponder: Mutex<Option<Arc<PonderGate>>>,
+}
+
+impl Default for EngineSession {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EngineSession {
+    pub fn new() -> Self {
+        Self::from_engine(ShogiEngine::new())
+    }
+
+    pub fn from_engine(engine: ShogiEngine) -> Self {
+        let stop_flag = engine.stop_flag.clone();
+        Self { engine: Arc::new(Mutex::new(engine)), stop_flag, ponder: Mutex::new(None) }
+    }
+
+    /// Run `f` on the engine. A running search works on a copy of it, so this
+    /// does not wait for the search; changes apply from the next one. Only
+    /// calls that need the search engine's tables wait for them to be
+    /// released.
+    pub fn with_engine<R>(&self, f: impl FnOnce(&mut ShogiEngine) -> R) -> R {
+        f(&mut lock_engine(&self.engine))
+    }
+
+    /// Send search progress to `reporter`
+    pub fn set_reporter(&self, reporter: Arc<dyn SearchReporter>) {
+        self.with_engine(|engine| engine.set_search_reporter(reporter));
+    }
+
+    /// Set the position from `startpos`, `handicap <name>` or an SFEN plus
+    /// the moves played since
+    pub fn set_position(&self, position: &str, moves: &[&str]) -> Result<(), String> {
+        self.with_engine(|engine| engine.set_usi_position(position, moves))
+    }
+
+    /// Play a legal move in the current position
+    pub fn push_move(&self, usi_move: &str) -> Result<(), String> {
+        self.with_engine(|engine| engine.push_usi_move(usi_move))
+    }
+
+    pub fn is_game_over(&self) -> Option<GameResult> {
+        self.with_engine(|engine| engine.is_game_over())
+    }
+
+    /// Start searching the current position on a worker thread.
+    ///
+    /// The worker searches a copy of the engine, sharing only the stop flag,
+    /// the search tables and the ponder state with the session. Only one
+    /// search runs at a time: wait for the previous handle before starting
+    /// another.
+    pub fn start(&self, limits: SearchLimits) -> SearchHandle {
+        self.stop_flag.store(false, Ordering::Relaxed);
+        let ponder = limits.ponder.then(|| Arc::new(PonderGate::default()));
+        *lock_ponder(&self.ponder) = ponder.clone();
+
+        let session_engine = Arc::clone(&self.engine);
+        let mut engine = lock_engine(&self.engine).clone();
+        let stop_flag = Arc::clone(&self.stop_flag);
+        let gate = ponder.clone();
+        let thread = thread::spawn(move || {
+            let Some(gate) = gate else {
+                let outcome = engine.search_with_limits(&limits, Some(stop_flag));
+                lock_engine(&session_engine).losing_streak = engine.losing_streak;
+                return outcome;
+            };
+
+            let move_time_ms = engine.move_time_ms(&limits);
+            let timer = {
+                let gate = Arc::clone(&gate);
+                let stop_flag = Arc::clone(&stop_flag);
+                thread::spawn(move || gate.run_timer(move_time_ms, &stop_flag))
+            };
+            let pondering = SearchLimits { infinite: true, ..limits };
+            let outcome = engine.search_with_limits(&pondering, Some(stop_flag));
+            // USI allows no `bestmove` before `ponderhit` or `stop`
+            let played = gate.wait_for_opponent();
+            gate.release();
+            let _ = timer.join();
+            if played {
+                lock_engine(&session_engine).losing_streak = engine.losing_streak;
+            }
+            outcome
+        });
+        SearchHandle { stop_flag: Arc::clone(&self.stop_flag), ponder, thread }
+    }
+
+    /// Search and block until the result is available
+    pub fn search(&self, limits: SearchLimits) -> SearchOutcome {
+        self.start(limits).wait()
+    }
+
+    /// Stop the running search, if any
+    pub fn stop(&self) {
+        self.stop_flag.store(true, Ordering::Relaxed);
+        if let Some(ponder) = lock_ponder(&self.ponder).take() {
+            ponder.release();
+        }
+    }
+
+    /// The expected move was played: the running ponder search, if any, now
+    /// runs on the time its limits give
+    pub fn ponderhit(&self) {
+        if let Some(ref ponder) = *lock_ponder(&self.ponder) {
+            ponder.hit();
+        }
+    }
+}
+
+fn lock_engine(engine: &Mutex<ShogiEngine>) -> MutexGuard<'_, ShogiEngine> {
+    engine.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn lock_ponder(ponder: &Mutex<Option<Arc<PonderGate>>>) -> MutexGuard<'_, Option<Arc<PonderGate>>> {
+    ponder.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Reporter that forwards events and remembers what the result needs
+pub(crate) struct SearchRecorder {
+    inner: Arc<dyn SearchReporter>,
+    state: Mutex<RecordedSearch>,
+}
+
+#[derive(Default)]
+struct RecordedSearch {
+    last_complete: Option<SearchInfo>,
+    /// Fail-high/low of an iteration that did not complete
+    last_bound: Option<SearchInfo>,
+    nodes: u64,
+}
+
+impl SearchRecorder {
+    pub(crate) fn new(inner: Arc<dyn SearchReporter>) -> Self {
+        Self { inner, state: Mutex::new(RecordedSearch::default()) }
+    }
+
+    /// Build the outcome for `best_move` from the recorded iterations
+    pub(crate) fn outcome(
+        &self,
+        best_move: Option<Move>,
+        mut pv: Vec<Move>,
+        time_ms: u64,
+    ) -> SearchOutcome {
+        let state = self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let Some(best_move) = best_move else {
+            return SearchOutcome { time_ms, ..SearchOutcome::default() };
+        };
+        if pv.first() != Some(&best_move) {
+            pv = vec![best_move.clone()];
+        }
+
+        let best_usi = best_move.to_usi_string();
+        let starts_with_best =
+            |info: &&SearchInfo| info.pv.split_whitespace().next() == Some(best_usi.as_str());
+        let scored = state
+            .last_bound
+            .as_ref()
+            .filter(starts_with_best)
+            .or(state.last_complete.as_ref());
+        let (depth, seldepth) =
+            state.last_complete.as_ref().map_or((0, 0), |info| (info.depth, info.seldepth));
+
+        SearchOutcome {
+            ponder_move: pv.get(1).cloned(),
+            best_move: Some(best_move),
+            score: scored.map_or(0, |info| info.score),
+            bound: scored.map_or(ScoreBound::Exact, |info| info.bound),
+            pv,
+            depth,
+            seldepth,
+            nodes: state.nodes,
+            time_ms,
+            resign: false,
+        }
+    }
+}
+
+impl SearchReporter for SearchRecorder {
+    fn report(&self, event: SearchEvent) {
+        if let Ok(mut state) = self.state.lock() {
+            match event {
+                SearchEvent::IterationComplete(ref info) => {
+                    state.nodes += info.nodes;
+                    state.last_complete = Some(info.clone());
+                    state.last_bound = None;
+                }
+                SearchEvent::BoundUpdate(ref info) => state.last_bound = Some(info.clone()),
+                _ => {}
+            }
+        }
+        self.inner.report(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::opening_book::OpeningBook;
+    use crate::search::{ChannelReporter, NullReporter, SkillLevel};
+    use crate::types::Handicap;
+    use crate::utils::time::VirtualClock;
+
+    /// Virtual cost of one node: 100 nodes per second, so debug builds get
+    /// through whole games
+    const US_PER_NODE: u64 = 10_000;
+
+    /// Play the engine against itself from the start position on a virtual
+    /// clock, failing if a side flags. Returns the time spent on each move
+    /// and the time that was available for it.
+    fn play_clocked_game(
+        main_ms: u32,
+        byoyomi_ms: u32,
+        increment_ms: u32,
+        plies: usize,
+    ) -> Vec<(u32, u32)> {
+        let clock = Arc::new(VirtualClock::new(US_PER_NODE));
+        let session = EngineSession::new();
+        session.set_reporter(Arc::new(NullReporter));
+        session.with_engine(|engine| {
+            engine.set_clock(clock.clone());
+            engine.thread_count = 1;
+            engine.parallel_options.enable_parallel = false;
+            engine.sync_parallel_options();
+            engine.handle_setoption(&["name", "TimeCheckFrequency", "value", "1"]);
+        });
+        session.set_position("startpos", &[]).unwrap();
+
+        let mut remaining = [main_ms; 2];
+        let mut moves = Vec::new();
+        for ply in 0..plies {
+            if session.is_game_over().is_some() {
+                break;
+            }
+            let side = ply % 2;
+            let limits = SearchLimits {
+                btime_ms: remaining[0],
+                wtime_ms: remaining[1],
+                byoyomi_ms,
+                binc_ms: increment_ms,
+                winc_ms: increment_ms,
+                ..SearchLimits::default()
+            };
+            let start = clock.now_ms();
+            let outcome = session.search(limits);
+            let spent = (clock.now_ms() - start) as u32;
+            let available = remaining[side] + byoyomi_ms;
+            assert!(spent <= available, "ply {}: spent {}ms of {}ms", ply, spent, available);
+            moves.push((spent, available));
+
+            remaining[side] = remaining[side].saturating_sub(spent) + increment_ms;
+            let best_move = outcome.best_move.expect("the game is not over");
+            session.push_move(&best_move.to_usi_string()).unwrap();
+        }
+        moves
+    }
+
+    #[test]
+    fn test_usi_go_limits() {
+        let limits =
+            SearchLimits::from_usi_go(&["btime", "60000", "wtime", "30000", "byoyomi", "0"]);
+        assert_eq!(limits.time_budget_ms(Player::Black), 1500);
+        assert_eq!(limits.time_budget_ms(Player::White), 750);
+
+        let limits = SearchLimits::from_usi_go(&["byoyomi", "3000", "btime", "0"]);
+        assert_eq!(limits.time_budget_ms(Player::Black), 3000);
+
+        // The increment is added to the share of the clock, but never past
+        // what is left on it
+        let limits = SearchLimits::from_usi_go(&["btime", "40000", "binc", "2000"]);
+        assert_eq!(limits.time_budget_ms(Player::Black), 3000);
+        let limits = SearchLimits::from_usi_go(&["wtime", "400", "winc", "2000"]);
+        assert_eq!(limits.time_budget_ms(Player::White), 390);
+
+        let limits = SearchLimits::from_usi_go(&["depth", "4"]);
+        assert_eq!(limits.depth, Some(4));
+        assert_eq!(limits.time_budget_ms(Player::Black), INFINITE_TIME_MS);
+        let limits = SearchLimits::from_usi_go(&["nodes", "5000000000", "depth", "6"]);
+        assert_eq!((limits.nodes, limits.depth), (Some(5_000_000_000), Some(6)));
+        assert_eq!(SearchLimits::nodes(100).time_budget_ms(Player::White), INFINITE_TIME_MS);
+
+        // Only clock limits are planned
+        let limits = SearchLimits::from_usi_go(&["btime", "0", "wtime", "0", "byoyomi", "1000"]);
+        let clock = limits.move_clock(Player::White, 30, 200).unwrap();
+        assert_eq!((clock.remaining_ms, clock.byoyomi_ms, clock.ply), (0, 1000, 30));
+        assert_eq!(SearchLimits::movetime(500).move_clock(Player::Black, 0, 0), None);
+        assert_eq!(SearchLimits::depth(3).move_clock(Player::Black, 0, 0), None);
+
+        let limits = SearchLimits::from_usi_go(&["infinite"]);
+        assert!(limits.infinite);
+        let limits = SearchLimits::from_usi_go(&["ponder", "btime", "1000", "wtime", "2000"]);
+        assert!(limits.ponder && !limits.infinite);
+        assert_eq!(limits.btime_ms, 1000);
+        assert_eq!(SearchLimits::from_usi_go(&[]).time_budget_ms(Player::White), 5000);
+        assert_eq!(SearchLimits::from_usi_go(&["movetime", "250"]).movetime_ms, Some(250));
+    }
+
+    #[test]
+    fn test_session_search_outcome() {
+        let session = EngineSession::new();
+        session.set_reporter(Arc::new(NullReporter));
+        session.with_engine(|engine| engine.disable_tablebase());
+        session.set_position("sfen 4k4/9/4G4/9/9/9/9/9/4K4 b G 1", &[]).unwrap();
+
+        let outcome = session.search(SearchLimits::depth(2));
+        let best_move = outcome.best_move.clone().expect("a legal move exists");
+        assert_eq!(outcome.pv.first(), Some(&best_move));
+        assert_eq!(outcome.depth, 2);
+        assert!(outcome.nodes > 0);
+        assert!(outcome.to_usi_bestmove().starts_with("bestmove "));
+    }
+
+    #[test]
+    fn test_recorder_reports_voted_result() {
+        use crate::search::lazy_smp::LazySmpThreadResult;
+        use crate::types::core::{PieceType, Position};
+
+        let pawn_push = |col: u8| {
+            Move::new_move(
+                Position::new(6, col),
+                Position::new(5, col),
+                PieceType::Pawn,
+                Player::Black,
+                false,
+            )
+        };
+        let (main_move, voted_move) = (pawn_push(2), pawn_push(7));
+        let recorder = SearchRecorder::new(Arc::new(NullReporter));
+        recorder.report(SearchEvent::IterationComplete(SearchInfo {
+            depth: 8,
+            seldepth: 12,
+            multipv: Some(1),
+            score: 120,
+            bound: ScoreBound::Exact,
+            time_ms: 10,
+            nodes: 5000,
+            nps: 500_000,
+            pv: format!("{} 3c3d", main_move.to_usi_string()),
+        }));
+
+        // A helper searched deeper and the vote overrode the main thread
+        let voted = LazySmpThreadResult {
+            thread_id: 3,
+            best_move: voted_move.clone(),
+            score: 40,
+            depth: 10,
+            nodes: 7000,
+            tt_writes: 0,
+        };
+        recorder.report(SearchEvent::IterationComplete(
+            voted.search_info(format!("{} 8c8d", voted_move.to_usi_string()), 12),
+        ));
+
+        let outcome = recorder.outcome(Some(voted_move.clone()), vec![voted_move.clone()], 12);
+        assert_eq!(outcome.best_move, Some(voted_move));
+        assert_eq!((outcome.score, outcome.bound, outcome.depth), (40, ScoreBound::Exact, 10));
+        assert_eq!(outcome.nodes, 5000);
+    }
+
+    #[test]
+    fn test_session_usable_while_searching() {
+        let session = EngineSession::new();
+        session.set_reporter(Arc::new(NullReporter));
+        session.with_engine(|engine| {
+            engine.opening_book = Arc::new(Mutex::new(OpeningBook::new()));
+        });
+        session.set_position("startpos", &["7g7f", "3c3d"]).unwrap();
+
+        let handle = session.start(SearchLimits::infinite());
+        // The search runs on its own copy of the engine
+        assert_eq!(session.with_engine(|engine| engine.current_player()), Player::Black);
+        session.with_engine(|engine| engine.network_delay_ms = 0);
+        assert!(!handle.is_finished());
+        session.stop();
+        handle.wait();
+        assert_eq!(session.with_engine(|engine| engine.network_delay_ms), 0);
+    }
+
+    #[test]
+    fn test_session_ponder() {
+        let session = EngineSession::new();
+        let (reporter, events) = ChannelReporter::channel();
+        session.set_reporter(Arc::new(reporter));
+        session.with_engine(|engine| engine.disable_tablebase());
+        session.set_position("sfen 4k4/9/4G4/9/9/9/9/9/4K4 b G 1", &[]).unwrap();
+
+        // A ponder search that completes holds its result until `ponderhit`
+        let handle = session.start(SearchLimits { ponder: true, ..SearchLimits::depth(1) });
+        assert!(events.iter().any(|event| matches!(event, SearchEvent::IterationComplete(_))));
+        thread::sleep(Duration::from_millis(200));
+        assert!(!handle.is_finished());
+        session.ponderhit();
+        let outcome = handle.wait();
+        assert!(outcome.best_move.is_some());
+        assert_eq!(outcome.depth, 1);
+
+        // The move time only runs from `ponderhit`
+        let handle = session.start(SearchLimits { ponder: true, ..SearchLimits::movetime(100) });
+        thread::sleep(Duration::from_millis(300));
+        assert!(!handle.is_finished());
+        handle.ponderhit();
+        assert!(handle.wait().best_move.is_some());
+    }
+
+    #[test]
+    fn test_session_position_history() {
+        let session = EngineSession::new();
+        session.set_position("startpos", &["7g7f", "3c3d"]).unwrap();
+        assert!(session.push_move("8h2b+").is_ok());
+        assert!(session.push_move("8h2b+").is_err());
+        assert!(session.set_position("startpos", &["7g7x"]).is_err());
+        assert_eq!(session.with_engine(|engine| engine.game_history.len()), 1);
+    }
+
+    #[test]
+    fn test_session_handicap_position() {
+        let session = EngineSession::new();
+        session.set_position("handicap 2-piece", &["5a4b"]).unwrap();
+        assert_eq!(session.with_engine(|engine| engine.handicap()), Handicap::TwoPiece);
+        assert_eq!(session.with_engine(|engine| engine.current_player()), Player::Black);
+
+        let sfen = format!("sfen {}", Handicap::Lance.sfen());
+        session.set_position(&sfen, &[]).unwrap();
+        assert_eq!(session.with_engine(|engine| engine.handicap()), Handicap::Lance);
+        assert_eq!(session.with_engine(|engine| engine.current_player()), Player::White);
+        assert!(session.push_move("7g7f").is_err());
+
+        assert!(session.set_position("handicap queen", &[]).is_err());
+        session.set_position("startpos", &[]).unwrap();
+        assert_eq!(session.with_engine(|engine| engine.handicap()), Handicap::Even);
+    }
+
+    #[test]
+    fn test_session_skill_limit() {
+        let session = EngineSession::new();
+        session.set_reporter(Arc::new(NullReporter));
+        session
+            .set_position(
+                "sfen ln1gkgsnl/1r1s3b1/ppppppppp/9/9/2P6/PP1PPPPPP/1B5R1/LNSGKGSNL b - 3",
+                &[],
+            )
+            .unwrap();
+        session.with_engine(|engine| engine.set_skill(SkillLevel::from_level(0)));
+
+        // The node budget of level 0 ends the search long before depth 8
+        let outcome = session.search(SearchLimits::depth(8));
+        let best_move = outcome.best_move.expect("a legal move exists");
+        assert!(outcome.depth < 8);
+        assert!(session.push_move(&best_move.to_usi_string()).is_ok());
+    }
+
+    #[test]
+    fn test_clocked_game_sudden_death() {
+        let moves = play_clocked_game(20_000, 0, 0, 60);
+        let spent: u32 = moves.iter().map(|(spent, _)| spent).sum();
+        for (spent, available) in &moves {
+            assert!(*spent <= available / 10, "spent {}ms of {}ms on one move", spent, available);
+        }
+        // Thirty moves each use a good part of the clock without running low
+        assert!(spent > 10_000 && spent < 32_000, "spent {}ms in total", spent);
+    }
+
+    #[test]
+    fn test_clocked_game_byoyomi() {
+        let moves = play_clocked_game(0, 1000, 0, 40);
+        let searched: Vec<u32> =
+            moves.iter().map(|(spent, _)| *spent).filter(|spent| *spent > 0).collect();
+        assert!(!searched.is_empty());
+        // Book moves take no time; searched moves use most of the period
+        assert!(searched.iter().all(|spent| *spent >= 500), "{:?}", searched);
+    }
+
+    #[test]
+    fn test_clocked_game_fischer() {
+        let moves = play_clocked_game(2000, 0, 500, 60);
+        let late: Vec<u32> = moves.iter().skip(20).map(|(spent, _)| *spent).collect();
+        // Once the main time is gone the engine lives on its increment
+        // without flagging
+        assert!(late.iter().all(|spent| *spent <= 2000 + 500), "{:?}", late);
+        assert!(late.iter().sum::<u32>() >= late.len() as u32 * 250, "{:?}", late);
+    }
+}
@@ -1,19 +1,95 @@
 // Time utilities for standalone environments
 
-/// A time source for standalone environments
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::Instant;
+
+/// Source of the current time for search and time management
+///
+/// The search reads time only through a clock, so tests can substitute a
+/// [`VirtualClock`] whose time advances with the nodes searched instead of
+/// wall-clock time.
+pub trait Clock: Send + Sync + Debug {
+    /// Microseconds since an arbitrary fixed origin
+    fn now_us(&self) -> u64;
+
+    /// Called for every node the search visits
+    fn on_node(&self) {}
+}
+
+/// Wall-clock time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_us(&self) -> u64 {
+        static ORIGIN: OnceLock<Instant> = OnceLock::new();
+        ORIGIN.get_or_init(Instant::now).elapsed().as_micros() as u64
+    }
+}
+
+/// The shared wall clock
+pub fn system_clock() -> Arc<dyn Clock> {
+    static SYSTEM: OnceLock<Arc<dyn Clock>> = OnceLock::new();
+    SYSTEM.get_or_init(|| Arc::new(SystemClock)).clone()
+}
+
+/// Deterministic clock for tests: time moves only by a fixed cost per
+/// searched node and by explicit [`VirtualClock::advance_ms`] calls
+#[derive(Debug, Default)]
+pub struct VirtualClock {
+    now_us: AtomicU64,
+    us_per_node: u64,
+}
+
+impl VirtualClock {
+    /// A clock at time zero that advances `us_per_node` per node
+    pub fn new(us_per_node: u64) -> Self {
+        Self { now_us: AtomicU64::new(0), us_per_node }
+    }
+
+    /// Move the clock forward, e.g. for time spent outside the search
+    pub fn advance_ms(&self, ms: u64) {
+        self.now_us.fetch_add(ms * 1000, Ordering::Relaxed);
+    }
+
+    pub fn now_ms(&self) -> u64 {
+        self.now_us() / 1000
+    }
+}
+
+impl Clock for VirtualClock {
+    fn now_us(&self) -> u64 {
+        self.now_us.load(Ordering::Relaxed)
+    }
+
+    fn on_node(&self) {
+        self.now_us.fetch_add(self.us_per_node, Ordering::Relaxed);
+    }
+}
+
+/// A point in time on a [`Clock`] to measure elapsed time from
+#[derive(Debug, Clone)]
 pub struct TimeSource {
-    start_time: std::time::Instant,
+    clock: Arc<dyn Clock>,
+    start_us: u64,
 }
 
 impl TimeSource {
-    /// Create a new time source with the current time
+    /// Create a new time source with the current wall-clock time
     pub fn now() -> Self {
-        Self { start_time: std::time::Instant::now() }
+        Self::start(&system_clock())
+    }
+
+    /// Create a new time source with the current time of `clock`
+    pub fn start(clock: &Arc<dyn Clock>) -> Self {
+        Self { clock: Arc::clone(clock), start_us: clock.now_us() }
     }
 
     /// Get elapsed time in milliseconds
     pub fn elapsed_ms(&self) -> u32 {
-        self.start_time.elapsed().as_millis() as u32
+        (self.clock.now_us().saturating_sub(self.start_us) / 1000) as u32
     }
 
     /// Check if the time limit has been exceeded
@@ -14,7 +14,7 @@
 use super::feature_extractor::FeatureExtractor;
 use super::types::{GameRecord, GameResult, PositionFilter, TimeControl, TrainingPosition};
 use crate::{
-    types::{CapturedPieces, Move, PieceType, Player, Position},
+    types::{CapturedPieces, Handicap, Move, PieceType, Player, Position},
     BitboardBoard,
 };
 use serde_json;
@@ -101,10 +101,14 @@ impl DataProcessor {
             return positions;
         }
 
-        // Replay the game and extract positions
-        let mut board = BitboardBoard::new();
-        let mut captured_pieces = CapturedPieces::new();
-        let mut player = Player::White;
+        // Replay the game from its starting array and extract positions.
+        // Records label the first mover of an even game White; in a handicap
+        // game the handicap giver (上手) moves first instead.
+        let (mut board, _, mut captured_pieces) =
+            BitboardBoard::from_fen(game_record.handicap.sfen())
+                .expect("handicap SFENs are valid");
+        let mut player =
+            if game_record.handicap == Handicap::Even { Player::White } else { Player::Black };
         let mut move_number = 1;
 
         for (move_index, move_) in game_record.moves.iter().enumerate() {
@@ -284,12 +288,18 @@ impl DataProcessor {
             } else if line.starts_with("後手:") {
                 // Black player info
             } else if line.starts_with("手合割:") {
-                // Game type
+                let name = line.split_once(':').map(|(_, v)| v).unwrap_or("");
+                current_game.handicap = Handicap::from_str(name).unwrap_or_default();
             } else if line.starts_with("結果:") {
                 let result_str = &line[4..];
+                // Handicap records call the players 下手 (first) and 上手
                 current_game.result = match result_str {
-                    s if s.contains("先手") && s.contains("勝") => GameResult::WhiteWin,
-                    s if s.contains("後手") && s.contains("勝") => GameResult::BlackWin,
+                    s if (s.contains("先手") || s.contains("下手")) && s.contains("勝") => {
+                        GameResult::WhiteWin
+                    }
+                    s if (s.contains("後手") || s.contains("上手")) && s.contains("勝") => {
+                        GameResult::BlackWin
+                    }
                     _ => GameResult::Draw,
                 };
             } else if line.starts_with("まで") {
@@ -346,6 +356,9 @@ impl DataProcessor {
             // Parse CSA header
             if line.starts_with("N+") || line.starts_with("N-") {
                 // Player names
+            } else if line.starts_with("PI") {
+                // Initial position: even array minus the listed pieces
+                current_game.handicap = Handicap::from_csa_pi(line).unwrap_or_default();
             } else if line.starts_with("$") {
                 // Comments and metadata
             } else if line.starts_with("%") {
@@ -912,6 +925,46 @@ impl DataProcessor {
 
         Ok(positions)
     }
+
+    /// Load training positions from a packed self-play file (see
+    /// [`super::packed`]). The game length is not recorded, so the phase
+    /// treats 256 plies as a full game.
+    pub fn load_packed_training_data(&self, path: &str) -> Result<Vec<TrainingPosition>, String> {
+        let records = super::packed::read_packed_file(path)
+            .map_err(|e| format!("Failed to read packed data: {}", e))?;
+
+        let mut positions = Vec::with_capacity(records.len());
+        for record in records {
+            let unpacked =
+                record.position.unpack().map_err(|e| format!("Failed to unpack: {}", e))?;
+            let move_number = u32::from(record.ply);
+            if move_number < self.filter.min_move_number
+                || move_number > self.filter.max_move_number
+            {
+                continue;
+            }
+            let board = unpacked.to_board();
+            let player = unpacked.side_to_move;
+            let features = self.feature_extractor.extract_features(
+                &board,
+                player,
+                &unpacked.captured_pieces,
+            );
+            if self.feature_extractor.validate_features(&features).is_err() {
+                continue;
+            }
+            let game_phase = self.calculate_game_phase(move_number.min(256), 256);
+            positions.push(TrainingPosition::new(
+                features,
+                f64::from(record.result.signum()),
+                game_phase,
+                true,
+                move_number,
+                player,
+            ));
+        }
+        Ok(positions)
+    }
 }
 
 impl Clone for DataProcessor {
@@ -1302,4 +1355,19 @@ mod tests {
         let late_phase = processor.calculate_game_phase(45, 50);
         assert!(early_phase < late_phase);
     }
+
+    #[test]
+    fn test_game_record_handicap() {
+        let mut game_record = GameRecord::new(vec![], GameResult::Draw, TimeControl::new(600, 10));
+        game_record.handicap = Handicap::FourPiece;
+        let json = serde_json::to_string(&game_record).unwrap();
+        let loaded: GameRecord = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.handicap, Handicap::FourPiece);
+
+        // Records written before handicaps were tracked are even games
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value.as_object_mut().unwrap().remove("handicap");
+        let legacy: GameRecord = serde_json::from_value(value).unwrap();
+        assert_eq!(legacy.handicap, Handicap::Even);
+    }
 }
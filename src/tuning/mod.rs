@@ -18,11 +18,17 @@
 //!   genetic)
 //! - `validator.rs`: Validation framework and cross-validation
 //! - `performance.rs`: Performance monitoring and analysis
+//! - `packed.rs`: Compact binary records of positions, scores and results
+//! - `selfplay.rs`: Multi-threaded self-play generation of packed records
+//! - `nnue_trainer.rs`: CPU training of NNUE networks from packed records
 
 pub mod data_processor;
 pub mod feature_extractor;
+pub mod nnue_trainer;
 pub mod optimizer;
+pub mod packed;
 pub mod performance;
+pub mod selfplay;
 pub mod types;
 pub mod validator;
 
@@ -0,0 +1,1120 @@
+//! NNUE training on the CPU
+//!
+//! Trains the HalfKP network of [`crate::evaluation::nnue`] from packed
+//! self-play records ([`super::packed`]). Each record becomes a
+//! [`TrainingSample`] holding the sparse king-relative features of both
+//! perspectives. A floating-point copy of the network ([`FloatNetwork`])
+//! mirrors the quantised one layer for layer, so exporting only rounds the
+//! weights into the engine's file format.
+//!
+//! The loss is the squared error between win probabilities: the network's
+//! score and the target both go through `sigmoid(cp / score_scale)`, and the
+//! target blends the search score with the game result by `lambda`.
+//! Gradients of a batch are computed in parallel with rayon and applied
+//! with Adam: the dense layers through [`super::optimizer::AdamState`], the
+//! feature transformer lazily, row by row, since a batch only touches the
+//! rows of the features present in its positions. Checkpoints are written
+//! through [`TuningProfiler`] as [`super::performance::CheckpointData`]; the
+//! float weights and both optimizers' moments go to a binary state file
+//! next to it, framed like the engine's network file, so a resumed run
+//! continues the same optimisation.
+
+use super::optimizer::AdamState;
+use super::packed::{read_packed_file, PackError, PackedRecord};
+use super::performance::TuningProfiler;
+use super::types::{OptimizationMethod, PerformanceConfig};
+use crate::evaluation::nnue::features::{active_features, NUM_FEATURES};
+use crate::evaluation::nnue::network::{
+    read_u32, read_values, write_values, Checksum, MAX_LAYER_SIZE, OUTPUT_SCALE, QA, QW,
+};
+use crate::evaluation::nnue::{hand_counts, NnueError, NnueNetwork};
+use crate::types::core::{PieceType, Player};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Centipawns of one unit of the float network's output
+pub const CP_PER_OUTPUT: f64 = (QA * QW / OUTPUT_SCALE) as f64;
+
+/// Largest hidden or output weight that still fits an `i8` after scaling
+const MAX_HIDDEN_WEIGHT: f64 = i8::MAX as f64 / QW as f64;
+
+/// Largest feature transformer value that still fits an `i16` after scaling
+const MAX_FT_WEIGHT: f64 = i16::MAX as f64 / QA as f64;
+
+/// Samples per rayon task when computing a batch gradient
+const SAMPLES_PER_TASK: usize = 256;
+
+/// Magic number of checkpoint state files
+pub const CHECKPOINT_MAGIC: &[u8] = b"SHOGI_NNUE_CKPT1";
+
+/// Checkpoint state file format version
+pub const CHECKPOINT_VERSION: u32 = 2;
+
+/// Values encoded per write while streaming a checkpoint state file
+const CHECKPOINT_CHUNK: usize = 1 << 16;
+
+/// Errors that can occur while training
+#[derive(Debug, thiserror::Error)]
+pub enum NnueTrainerError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Failed to read training data: {0}")]
+    Pack(#[from] PackError),
+
+    #[error("Failed to export network: {0}")]
+    Nnue(#[from] NnueError),
+
+    #[error("Checkpoint holds {found} parameters, layer sizes need {expected}")]
+    CheckpointMismatch { expected: usize, found: usize },
+
+    #[error("Checkpoint has no trainer state file")]
+    MissingCheckpointState,
+
+    #[error("No usable training samples")]
+    NoSamples,
+}
+
+/// Settings of a training run
+#[derive(Debug, Clone)]
+pub struct NnueTrainerConfig {
+    /// Network layer sizes `(l1, l2, l3)`
+    pub layer_sizes: (usize, usize, usize),
+    pub epochs: usize,
+    pub batch_size: usize,
+    /// Adam parameters
+    pub learning_rate: f64,
+    pub beta1: f64,
+    pub beta2: f64,
+    pub epsilon: f64,
+    /// Weight of the search score in the target; the game result gets
+    /// `1 - lambda`
+    pub lambda: f64,
+    /// Centipawns per unit of the win-probability sigmoid
+    pub score_scale: f64,
+    /// Share of the samples held out to measure the validation loss
+    pub validation_split: f64,
+    /// Checkpoint directory; `None` writes no checkpoints
+    pub checkpoint_path: Option<String>,
+    /// Epochs between checkpoints
+    pub checkpoint_frequency: usize,
+    /// Seed of the initial weights and the shuffling
+    pub seed: u64,
+}
+
+impl Default for NnueTrainerConfig {
+    fn default() -> Self {
+        Self {
+            layer_sizes: (128, 32, 32),
+            epochs: 10,
+            batch_size: 16384,
+            learning_rate: 0.001,
+            beta1: 0.9,
+            beta2: 0.999,
+            epsilon: 1e-8,
+            lambda: 0.75,
+            score_scale: 400.0,
+            validation_split: 0.05,
+            checkpoint_path: None,
+            checkpoint_frequency: 1,
+            seed: 0,
+        }
+    }
+}
+
+impl NnueTrainerConfig {
+    /// The optimizer settings in the tuner's terms
+    pub fn optimization_method(&self) -> OptimizationMethod {
+        OptimizationMethod::Adam {
+            learning_rate: self.learning_rate,
+            beta1: self.beta1,
+            beta2: self.beta2,
+            epsilon: self.epsilon,
+        }
+    }
+
+    /// Target win probability of a sample
+    fn target(&self, sample: &TrainingSample) -> f64 {
+        let score = sigmoid(f64::from(sample.score) / self.score_scale);
+        let result = (f64::from(sample.result) + 1.0) / 2.0;
+        self.lambda * score + (1.0 - self.lambda) * result
+    }
+}
+
+/// One position as network input: the active features of the side to move
+/// and of its opponent, with the record's labels
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrainingSample {
+    pub us: Vec<u32>,
+    pub them: Vec<u32>,
+    /// Search score for the side to move, in centipawns
+    pub score: i16,
+    /// Game result for the side to move: 1 win, 0 draw, -1 loss
+    pub result: i8,
+}
+
+impl TrainingSample {
+    /// Features of a record; `None` when it does not unpack or a king is
+    /// missing
+    pub fn from_record(record: &PackedRecord) -> Option<Self> {
+        let position = record.position.unpack().ok()?;
+        let hands = hand_counts(&position.captured_pieces);
+        let king = |player: Player| {
+            position.squares.iter().position(|piece| {
+                matches!(piece, Some(piece) if piece.piece_type == PieceType::King
+                    && piece.player == player)
+            })
+        };
+        let features = |player: Player| -> Option<Vec<u32>> {
+            let features = active_features(player, king(player)?, &position.squares, &hands);
+            Some(features.into_iter().map(|feature| feature as u32).collect())
+        };
+        let us = position.side_to_move;
+        Some(Self {
+            us: features(us)?,
+            them: features(us.opposite())?,
+            score: record.score,
+            result: record.result,
+        })
+    }
+}
+
+/// Samples of all usable records
+pub fn samples_from_records(records: &[PackedRecord]) -> Vec<TrainingSample> {
+    records.par_iter().filter_map(TrainingSample::from_record).collect()
+}
+
+/// Samples of all usable records in packed files
+pub fn load_samples<P: AsRef<Path>>(paths: &[P]) -> Result<Vec<TrainingSample>, NnueTrainerError> {
+    let mut samples = Vec::new();
+    for path in paths {
+        samples.extend(samples_from_records(&read_packed_file(path)?));
+    }
+    Ok(samples)
+}
+
+/// Offsets of each layer in the flat parameter vector. The feature
+/// transformer weights come last, so every other parameter fits in a short
+/// dense prefix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Layout {
+    l1: usize,
+    l2: usize,
+    l3: usize,
+    ft_bias: usize,
+    l2_bias: usize,
+    l2_weights: usize,
+    l3_bias: usize,
+    l3_weights: usize,
+    out_bias: usize,
+    out_weights: usize,
+    ft_weights: usize,
+    len: usize,
+}
+
+impl Layout {
+    fn new((l1, l2, l3): (usize, usize, usize)) -> Self {
+        let ft_bias = 0;
+        let l2_bias = ft_bias + l1;
+        let l2_weights = l2_bias + l2;
+        let l3_bias = l2_weights + l2 * 2 * l1;
+        let l3_weights = l3_bias + l3;
+        let out_bias = l3_weights + l3 * l2;
+        let out_weights = out_bias + 1;
+        let ft_weights = out_weights + l3;
+        let len = ft_weights + NUM_FEATURES * l1;
+        Self {
+            l1,
+            l2,
+            l3,
+            ft_bias,
+            l2_bias,
+            l2_weights,
+            l3_bias,
+            l3_weights,
+            out_bias,
+            out_weights,
+            ft_weights,
+            len,
+        }
+    }
+
+    /// Length of the dense prefix before the feature transformer weights
+    fn dense_len(&self) -> usize {
+        self.ft_weights
+    }
+
+    fn ft_row(&self, feature: u32) -> std::ops::Range<usize> {
+        let start = self.ft_weights + feature as usize * self.l1;
+        start..start + self.l1
+    }
+}
+
+/// Activations of one forward pass
+struct Activations {
+    acc: [Vec<f64>; 2],
+    input: Vec<f64>,
+    z2: Vec<f64>,
+    h2: Vec<f64>,
+    z3: Vec<f64>,
+    h3: Vec<f64>,
+    output: f64,
+}
+
+/// Floating-point network with the layout of [`NnueNetwork`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FloatNetwork {
+    layout: Layout,
+    params: Vec<f64>,
+}
+
+impl FloatNetwork {
+    /// Network with random starting weights
+    pub fn new(layer_sizes: (usize, usize, usize), seed: u64) -> Self {
+        let layout = Layout::new(layer_sizes);
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut params = vec![0.0; layout.len];
+        let (l1, l2, l3) = layer_sizes;
+        let mut fill = |range: std::ops::Range<usize>, bound: f64| {
+            for value in &mut params[range] {
+                *value = rng.gen_range(-bound..=bound);
+            }
+        };
+        fill(layout.ft_weights..layout.len, 0.05);
+        fill(layout.l2_weights..layout.l3_bias, (1.0 / (2 * l1) as f64).sqrt());
+        fill(layout.l3_weights..layout.out_bias, (1.0 / l2 as f64).sqrt());
+        fill(layout.out_weights..layout.ft_weights, (1.0 / l3 as f64).sqrt());
+        params[layout.ft_bias..layout.l2_bias].fill(0.25);
+        Self { layout, params }
+    }
+
+    /// Network from a flat parameter vector in the order of [`Self::params`]
+    pub fn from_params(
+        layer_sizes: (usize, usize, usize),
+        params: Vec<f64>,
+    ) -> Result<Self, NnueTrainerError> {
+        let layout = Layout::new(layer_sizes);
+        if params.len() != layout.len {
+            return Err(NnueTrainerError::CheckpointMismatch {
+                expected: layout.len,
+                found: params.len(),
+            });
+        }
+        Ok(Self { layout, params })
+    }
+
+    pub fn layer_sizes(&self) -> (usize, usize, usize) {
+        (self.layout.l1, self.layout.l2, self.layout.l3)
+    }
+
+    pub fn params(&self) -> &[f64] {
+        &self.params
+    }
+
+    /// Score of a sample for the side to move, in centipawns
+    pub fn evaluate(&self, sample: &TrainingSample) -> f64 {
+        self.forward(sample).output * CP_PER_OUTPUT
+    }
+
+    fn forward(&self, sample: &TrainingSample) -> Activations {
+        let layout = &self.layout;
+        let p = &self.params;
+        let acc = [&sample.us, &sample.them].map(|features| {
+            let mut acc = p[layout.ft_bias..layout.l2_bias].to_vec();
+            for &feature in features {
+                for (value, weight) in acc.iter_mut().zip(&p[layout.ft_row(feature)]) {
+                    *value += weight;
+                }
+            }
+            acc
+        });
+        let input: Vec<f64> = acc.iter().flatten().map(|&value| value.clamp(0.0, 1.0)).collect();
+        let z2 = affine(
+            &p[layout.l2_weights..layout.l3_bias],
+            &p[layout.l2_bias..layout.l2_weights],
+            &input,
+        );
+        let h2: Vec<f64> = z2.iter().map(|&value| value.clamp(0.0, 1.0)).collect();
+        let z3 = affine(
+            &p[layout.l3_weights..layout.out_bias],
+            &p[layout.l3_bias..layout.l3_weights],
+            &h2,
+        );
+        let h3: Vec<f64> = z3.iter().map(|&value| value.clamp(0.0, 1.0)).collect();
+        let output = p[layout.out_bias]
+            + h3.iter()
+                .zip(&p[layout.out_weights..layout.ft_weights])
+                .map(|(h, w)| h * w)
+                .sum::<f64>();
+        Activations { acc, input, z2, h2, z3, h3, output }
+    }
+
+    /// Add the loss gradient of one sample to `gradient`, returning its loss
+    fn backward(
+        &self,
+        sample: &TrainingSample,
+        target: f64,
+        score_scale: f64,
+        gradient: &mut Gradient,
+    ) -> f64 {
+        let layout = &self.layout;
+        let p = &self.params;
+        let a = self.forward(sample);
+        let predicted = sigmoid(a.output * CP_PER_OUTPUT / score_scale);
+        let error = predicted - target;
+        let d_output = 2.0 * error * predicted * (1.0 - predicted) * CP_PER_OUTPUT / score_scale;
+
+        let dense = &mut gradient.dense;
+        dense[layout.out_bias] += d_output;
+        let mut d_z3 = vec![0.0; layout.l3];
+        for k in 0..layout.l3 {
+            dense[layout.out_weights + k] += d_output * a.h3[k];
+            if a.z3[k] > 0.0 && a.z3[k] < 1.0 {
+                d_z3[k] = d_output * p[layout.out_weights + k];
+            }
+        }
+
+        let mut d_h2 = vec![0.0; layout.l2];
+        for (k, &d) in d_z3.iter().enumerate() {
+            if d == 0.0 {
+                continue;
+            }
+            dense[layout.l3_bias + k] += d;
+            let row = layout.l3_weights + k * layout.l2;
+            for j in 0..layout.l2 {
+                dense[row + j] += d * a.h2[j];
+                d_h2[j] += d * p[row + j];
+            }
+        }
+
+        let width = 2 * layout.l1;
+        let mut d_input = vec![0.0; width];
+        for k in 0..layout.l2 {
+            if !(a.z2[k] > 0.0 && a.z2[k] < 1.0) || d_h2[k] == 0.0 {
+                continue;
+            }
+            let d = d_h2[k];
+            dense[layout.l2_bias + k] += d;
+            let row = layout.l2_weights + k * width;
+            for i in 0..width {
+                dense[row + i] += d * a.input[i];
+                d_input[i] += d * p[row + i];
+            }
+        }
+
+        for (side, features) in [&sample.us, &sample.them].into_iter().enumerate() {
+            let d_acc: Vec<f64> = (0..layout.l1)
+                .map(|i| {
+                    let value = a.acc[side][i];
+                    if value > 0.0 && value < 1.0 {
+                        d_input[side * layout.l1 + i]
+                    } else {
+                        0.0
+                    }
+                })
+                .collect();
+            if d_acc.iter().all(|&d| d == 0.0) {
+                continue;
+            }
+            for (bias, d) in dense[layout.ft_bias..layout.l2_bias].iter_mut().zip(&d_acc) {
+                *bias += d;
+            }
+            for &feature in features {
+                let row = gradient.ft_rows.entry(feature).or_insert_with(|| vec![0.0; layout.l1]);
+                for (value, d) in row.iter_mut().zip(&d_acc) {
+                    *value += d;
+                }
+            }
+        }
+        error * error
+    }
+
+    /// Keep every weight of the dense prefix inside the range its quantised
+    /// type can hold; the feature transformer rows are clamped as Adam
+    /// updates them
+    fn clamp_dense_to_quantisable(&mut self) {
+        let layout = self.layout;
+        for value in &mut self.params[layout.l2_weights..layout.l3_bias] {
+            *value = value.clamp(-MAX_HIDDEN_WEIGHT, MAX_HIDDEN_WEIGHT);
+        }
+        for value in &mut self.params[layout.l3_weights..layout.out_bias] {
+            *value = value.clamp(-MAX_HIDDEN_WEIGHT, MAX_HIDDEN_WEIGHT);
+        }
+        for value in &mut self.params[layout.out_weights..layout.ft_weights] {
+            *value = value.clamp(-MAX_HIDDEN_WEIGHT, MAX_HIDDEN_WEIGHT);
+        }
+        for value in &mut self.params[layout.ft_bias..layout.l2_bias] {
+            *value = value.clamp(-MAX_FT_WEIGHT, MAX_FT_WEIGHT);
+        }
+    }
+
+    /// Round the weights into the engine's quantised network
+    pub fn quantise(&self) -> NnueNetwork {
+        let layout = &self.layout;
+        let p = &self.params;
+        let qa = f64::from(QA);
+        let qw = f64::from(QW);
+        let i16s = |range: std::ops::Range<usize>| -> Vec<i16> {
+            p[range]
+                .iter()
+                .map(|&v| (v * qa).round().clamp(-32768.0, 32767.0) as i16)
+                .collect()
+        };
+        let i8s = |range: std::ops::Range<usize>| -> Vec<i8> {
+            p[range].iter().map(|&v| (v * qw).round().clamp(-127.0, 127.0) as i8).collect()
+        };
+        let biases = |range: std::ops::Range<usize>| -> Vec<i32> {
+            p[range].iter().map(|&v| (v * qa * qw).round() as i32).collect()
+        };
+        NnueNetwork::from_parts(
+            self.layer_sizes(),
+            i16s(layout.ft_bias..layout.l2_bias),
+            i16s(layout.ft_weights..layout.len),
+            biases(layout.l2_bias..layout.l2_weights),
+            i8s(layout.l2_weights..layout.l3_bias),
+            biases(layout.l3_bias..layout.l3_weights),
+            i8s(layout.l3_weights..layout.out_bias),
+            biases(layout.out_bias..layout.out_weights)[0],
+            i8s(layout.out_weights..layout.ft_weights),
+        )
+        .expect("layout matches the network's layer sizes")
+    }
+}
+
+/// Gradient of a group of samples: the dense prefix and the touched
+/// feature transformer rows
+struct Gradient {
+    dense: Vec<f64>,
+    ft_rows: HashMap<u32, Vec<f64>>,
+    loss: f64,
+}
+
+impl Gradient {
+    fn new(layout: &Layout) -> Self {
+        Self { dense: vec![0.0; layout.dense_len()], ft_rows: HashMap::new(), loss: 0.0 }
+    }
+
+    fn merge(mut self, other: Self) -> Self {
+        for (value, d) in self.dense.iter_mut().zip(&other.dense) {
+            *value += d;
+        }
+        for (feature, row) in other.ft_rows {
+            match self.ft_rows.entry(feature) {
+                std::collections::hash_map::Entry::Occupied(mut entry) => {
+                    for (value, d) in entry.get_mut().iter_mut().zip(&row) {
+                        *value += d;
+                    }
+                }
+                std::collections::hash_map::Entry::Vacant(entry) => {
+                    entry.insert(row);
+                }
+            }
+        }
+        self.loss += other.loss;
+        self
+    }
+}
+
+/// Losses after one epoch
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EpochReport {
+    /// Epochs completed, counting those before a resumed checkpoint
+    pub epoch: usize,
+    pub training_loss: f64,
+    /// `None` without held-out samples
+    pub validation_loss: Option<f64>,
+    pub elapsed: Duration,
+}
+
+/// Adam for the feature transformer weights, applied lazily as in
+/// TensorFlow's LazyAdam: a step updates the moments and weights of the rows
+/// its batch touched and leaves every other row alone. The moments are kept
+/// as `f32` to halve their footprint.
+struct LazyAdam {
+    m: Vec<f32>,
+    v: Vec<f32>,
+    beta1: f64,
+    beta2: f64,
+    epsilon: f64,
+    t: i32,
+    /// Bias corrections of the current step
+    m_correction: f64,
+    v_correction: f64,
+}
+
+impl LazyAdam {
+    fn new(len: usize, config: &NnueTrainerConfig) -> Self {
+        Self {
+            m: vec![0.0; len],
+            v: vec![0.0; len],
+            beta1: config.beta1,
+            beta2: config.beta2,
+            epsilon: config.epsilon,
+            t: 0,
+            m_correction: 1.0,
+            v_correction: 1.0,
+        }
+    }
+
+    /// Start the next step; the bias correction follows the global step
+    /// count, not the number of times a row was updated
+    fn next_step(&mut self) {
+        self.t += 1;
+        self.set_corrections();
+    }
+
+    fn set_corrections(&mut self) {
+        self.m_correction = 1.0 - self.beta1.powi(self.t);
+        self.v_correction = 1.0 - self.beta2.powi(self.t);
+    }
+
+    /// Restore the step count and moments of a checkpoint
+    fn restore(&mut self, t: i32, m: Vec<f32>, v: Vec<f32>) {
+        self.t = t;
+        self.m = m;
+        self.v = v;
+        self.set_corrections();
+    }
+
+    /// Update one row, whose moments start at `offset`, with `gradient *
+    /// scale`, keeping the weights inside the quantisable range
+    fn update_row(
+        &mut self,
+        offset: usize,
+        weights: &mut [f64],
+        gradient: &[f64],
+        scale: f64,
+        learning_rate: f64,
+    ) {
+        let moments = self.m[offset..].iter_mut().zip(&mut self.v[offset..]);
+        for ((weight, &d), (m, v)) in weights.iter_mut().zip(gradient).zip(moments) {
+            let g = d * scale;
+            let m_new = self.beta1 * f64::from(*m) + (1.0 - self.beta1) * g;
+            let v_new = self.beta2 * f64::from(*v) + (1.0 - self.beta2) * g * g;
+            *m = m_new as f32;
+            *v = v_new as f32;
+            let step =
+                (m_new / self.m_correction) / ((v_new / self.v_correction).sqrt() + self.epsilon);
+            *weight = (*weight - learning_rate * step).clamp(-MAX_FT_WEIGHT, MAX_FT_WEIGHT);
+        }
+    }
+}
+
+/// Trains a [`FloatNetwork`] with Adam
+pub struct NnueTrainer {
+    config: NnueTrainerConfig,
+    network: FloatNetwork,
+    /// Adam over the dense prefix of the parameters
+    adam: AdamState,
+    ft_adam: LazyAdam,
+    epoch: usize,
+    profiler: Option<TuningProfiler>,
+}
+
+impl NnueTrainer {
+    pub fn new(config: NnueTrainerConfig) -> Self {
+        let network = FloatNetwork::new(config.layer_sizes, config.seed);
+        Self::with_network(config, network, 0)
+    }
+
+    /// Continue from a checkpoint (`checkpoint_iter_<epoch>.json`) written
+    /// by an earlier run with the same layer sizes, restoring the weights
+    /// and both optimizers from its state file
+    pub fn resume<P: AsRef<Path>>(
+        config: NnueTrainerConfig,
+        checkpoint: P,
+    ) -> Result<Self, NnueTrainerError> {
+        let checkpoint = checkpoint.as_ref();
+        let data = TuningProfiler::load_checkpoint(checkpoint)?;
+        let state_file = data.state_file.ok_or(NnueTrainerError::MissingCheckpointState)?;
+        Self::load_state(config, checkpoint.with_file_name(state_file), data.iteration)
+    }
+
+    /// Write the weights and both optimizers' state to `path`: magic,
+    /// version and layer sizes, then the weights and the dense Adam moments
+    /// as little-endian `f64`, the feature transformer moments as `f32`, each
+    /// optimizer preceded by its step count, and an FNV-1a checksum
+    fn save_state(&self, path: &Path) -> Result<(), NnueTrainerError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(CHECKPOINT_MAGIC)?;
+        writer.write_all(&CHECKPOINT_VERSION.to_le_bytes())?;
+        let (l1, l2, l3) = self.network.layer_sizes();
+        for size in [l1, l2, l3] {
+            writer.write_all(&(size as u32).to_le_bytes())?;
+        }
+
+        let mut checksum = Checksum::default();
+        let (t, m, v) = self.adam.moments();
+        write_chunked(&mut writer, &self.network.params, &mut checksum, f64::to_le_bytes)?;
+        write_chunked(&mut writer, &[t as u64], &mut checksum, u64::to_le_bytes)?;
+        write_chunked(&mut writer, m, &mut checksum, f64::to_le_bytes)?;
+        write_chunked(&mut writer, v, &mut checksum, f64::to_le_bytes)?;
+        let ft_t = self.ft_adam.t as u64;
+        write_chunked(&mut writer, &[ft_t], &mut checksum, u64::to_le_bytes)?;
+        write_chunked(&mut writer, &self.ft_adam.m, &mut checksum, f32::to_le_bytes)?;
+        write_chunked(&mut writer, &self.ft_adam.v, &mut checksum, f32::to_le_bytes)?;
+        writer.write_all(&checksum.0.to_le_bytes())?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Trainer at `epoch` from a state file written by [`Self::save_state`]
+    fn load_state(
+        config: NnueTrainerConfig,
+        path: PathBuf,
+        epoch: usize,
+    ) -> Result<Self, NnueTrainerError> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut magic = [0u8; 16];
+        reader.read_exact(&mut magic)?;
+        if magic != CHECKPOINT_MAGIC {
+            return Err(NnueError::InvalidMagic.into());
+        }
+        let version = read_u32(&mut reader)?;
+        if version != CHECKPOINT_VERSION {
+            return Err(NnueError::VersionMismatch {
+                file_version: version,
+                supported_version: CHECKPOINT_VERSION,
+            }
+            .into());
+        }
+        let (l1, l2, l3) = (read_u32(&mut reader)?, read_u32(&mut reader)?, read_u32(&mut reader)?);
+        let valid = |size: u32| size > 0 && size <= MAX_LAYER_SIZE;
+        if !(valid(l1) && valid(l2) && valid(l3)) {
+            return Err(NnueError::InvalidLayerSizes { l1, l2, l3 }.into());
+        }
+        let layout = Layout::new((l1 as usize, l2 as usize, l3 as usize));
+        if (l1 as usize, l2 as usize, l3 as usize) != config.layer_sizes {
+            return Err(NnueTrainerError::CheckpointMismatch {
+                expected: Layout::new(config.layer_sizes).len,
+                found: layout.len,
+            });
+        }
+
+        let mut checksum = Checksum::default();
+        let params = read_chunked(&mut reader, layout.len, &mut checksum, f64::from_le_bytes)?;
+        let t = read_chunked(&mut reader, 1, &mut checksum, u64::from_le_bytes)?[0];
+        let dense_len = layout.dense_len();
+        let m = read_chunked(&mut reader, dense_len, &mut checksum, f64::from_le_bytes)?;
+        let v = read_chunked(&mut reader, dense_len, &mut checksum, f64::from_le_bytes)?;
+        let ft_t = read_chunked(&mut reader, 1, &mut checksum, u64::from_le_bytes)?[0];
+        let ft_len = layout.len - layout.ft_weights;
+        let ft_m = read_chunked(&mut reader, ft_len, &mut checksum, f32::from_le_bytes)?;
+        let ft_v = read_chunked(&mut reader, ft_len, &mut checksum, f32::from_le_bytes)?;
+        let mut stored = [0u8; 8];
+        reader.read_exact(&mut stored)?;
+        let file_checksum = u64::from_le_bytes(stored);
+        if file_checksum != checksum.0 {
+            return Err(NnueError::ChecksumMismatch {
+                file_checksum,
+                calculated_checksum: checksum.0,
+            }
+            .into());
+        }
+
+        let mut trainer = Self::with_network(config, FloatNetwork { layout, params }, epoch);
+        trainer.adam.restore_moments(t as usize, m, v);
+        trainer.ft_adam.restore(ft_t as i32, ft_m, ft_v);
+        Ok(trainer)
+    }
+
+    fn with_network(config: NnueTrainerConfig, network: FloatNetwork, epoch: usize) -> Self {
+        let layout = network.layout;
+        let adam = AdamState::new(layout.dense_len(), config.beta1, config.beta2, config.epsilon);
+        let ft_adam = LazyAdam::new(layout.len - layout.ft_weights, &config);
+        let profiler = config.checkpoint_path.as_ref().map(|path| {
+            TuningProfiler::new(PerformanceConfig {
+                checkpoint_path: Some(path.clone()),
+                checkpoint_frequency: config.checkpoint_frequency,
+                enable_logging: false,
+                max_iterations: Some(config.epochs),
+                ..PerformanceConfig::default()
+            })
+        });
+        Self { config, network, adam, ft_adam, epoch, profiler }
+    }
+
+    pub fn config(&self) -> &NnueTrainerConfig {
+        &self.config
+    }
+
+    pub fn network(&self) -> &FloatNetwork {
+        &self.network
+    }
+
+    /// Epochs completed so far
+    pub fn epoch(&self) -> usize {
+        self.epoch
+    }
+
+    /// Train until `config.epochs` epochs are complete, calling `on_epoch`
+    /// after each one
+    pub fn train(
+        &mut self,
+        samples: &[TrainingSample],
+        mut on_epoch: impl FnMut(&EpochReport),
+    ) -> Result<Vec<EpochReport>, NnueTrainerError> {
+        if samples.is_empty() {
+            return Err(NnueTrainerError::NoSamples);
+        }
+        let mut order: Vec<usize> = (0..samples.len()).collect();
+        order.shuffle(&mut StdRng::seed_from_u64(self.config.seed));
+        let held_out =
+            (samples.len() as f64 * self.config.validation_split.clamp(0.0, 0.5)) as usize;
+        let (validation, training) = order.split_at(held_out);
+        let mut training = training.to_vec();
+        if training.is_empty() {
+            return Err(NnueTrainerError::NoSamples);
+        }
+
+        let mut reports = Vec::new();
+        let start = Instant::now();
+        while self.epoch < self.config.epochs {
+            let mut rng = StdRng::seed_from_u64(self.config.seed ^ self.epoch as u64);
+            training.shuffle(&mut rng);
+            let mut loss = 0.0;
+            for batch in training.chunks(self.config.batch_size.max(1)) {
+                loss += self.step(samples, batch);
+            }
+            self.epoch += 1;
+            let report = EpochReport {
+                epoch: self.epoch,
+                training_loss: loss / training.len() as f64,
+                validation_loss: (!validation.is_empty())
+                    .then(|| self.loss(validation.iter().map(|&i| &samples[i]))),
+                elapsed: start.elapsed(),
+            };
+            self.checkpoint(&report)?;
+            on_epoch(&report);
+            reports.push(report);
+        }
+        Ok(reports)
+    }
+
+    /// Mean loss of `samples` under the current network
+    pub fn loss<'a>(&self, samples: impl Iterator<Item = &'a TrainingSample>) -> f64 {
+        let samples: Vec<&TrainingSample> = samples.collect();
+        if samples.is_empty() {
+            return 0.0;
+        }
+        let total: f64 = samples
+            .par_iter()
+            .map(|sample| {
+                let predicted = sigmoid(self.network.evaluate(sample) / self.config.score_scale);
+                (predicted - self.config.target(sample)).powi(2)
+            })
+            .sum();
+        total / samples.len() as f64
+    }
+
+    /// One Adam step on a batch; returns the batch's summed loss
+    fn step(&mut self, samples: &[TrainingSample], batch: &[usize]) -> f64 {
+        let layout = self.network.layout;
+        let network = &self.network;
+        let config = &self.config;
+        let gradient = batch
+            .par_chunks(SAMPLES_PER_TASK)
+            .map(|chunk| {
+                let mut gradient = Gradient::new(&layout);
+                for &index in chunk {
+                    let sample = &samples[index];
+                    gradient.loss += network.backward(
+                        sample,
+                        config.target(sample),
+                        config.score_scale,
+                        &mut gradient,
+                    );
+                }
+                gradient
+            })
+            .reduce(|| Gradient::new(&layout), Gradient::merge);
+
+        let scale = 1.0 / batch.len() as f64;
+        let dense: Vec<f64> = gradient.dense.iter().map(|d| d * scale).collect();
+        let dense_len = layout.dense_len();
+        self.adam
+            .update(&mut self.network.params[..dense_len], &dense, config.learning_rate);
+        self.network.clamp_dense_to_quantisable();
+
+        // Only the rows of features seen in the batch move
+        self.ft_adam.next_step();
+        for (&feature, row) in &gradient.ft_rows {
+            let range = layout.ft_row(feature);
+            let offset = range.start - layout.ft_weights;
+            let weights = &mut self.network.params[range];
+            self.ft_adam.update_row(offset, weights, row, scale, config.learning_rate);
+        }
+        gradient.loss
+    }
+
+    /// Record the epoch and, when a checkpoint is due, write
+    /// `checkpoint_iter_<epoch>.json` and its state file
+    /// `checkpoint_iter_<epoch>.state`
+    fn checkpoint(&mut self, report: &EpochReport) -> Result<(), NnueTrainerError> {
+        let Some(mut profiler) = self.profiler.take() else {
+            return Ok(());
+        };
+        profiler.record_iteration(report.epoch, report.training_loss, report.elapsed);
+        let frequency = self.config.checkpoint_frequency.max(1);
+        let due = report.epoch.is_multiple_of(frequency) || report.epoch == self.config.epochs;
+        let result = if due { self.write_checkpoint(&mut profiler, report) } else { Ok(()) };
+        self.profiler = Some(profiler);
+        result
+    }
+
+    fn write_checkpoint(
+        &self,
+        profiler: &mut TuningProfiler,
+        report: &EpochReport,
+    ) -> Result<(), NnueTrainerError> {
+        let state_file = format!("checkpoint_iter_{}.state", report.epoch);
+        self.save_state(&profiler.checkpoint_dir()?.join(&state_file))?;
+        profiler.create_checkpoint_with_state_file(
+            report.epoch,
+            report.validation_loss.unwrap_or(report.training_loss),
+            self.config.optimization_method(),
+            &state_file,
+        )?;
+        Ok(())
+    }
+
+    /// Quantise the network and write it in the engine's file format
+    pub fn export<P: AsRef<Path>>(&self, path: P) -> Result<NnueNetwork, NnueTrainerError> {
+        let network = self.network.quantise();
+        network.save(path)?;
+        Ok(network)
+    }
+}
+
+/// `weights * input + bias` for row-major `weights`
+fn affine(weights: &[f64], bias: &[f64], input: &[f64]) -> Vec<f64> {
+    weights
+        .chunks_exact(input.len())
+        .zip(bias)
+        .map(|(row, &bias)| bias + row.iter().zip(input).map(|(w, x)| w * x).sum::<f64>())
+        .collect()
+}
+
+/// Write `values` in chunks of [`CHECKPOINT_CHUNK`] to bound the buffer
+fn write_chunked<W: Write, T: Copy, const N: usize>(
+    writer: &mut W,
+    values: &[T],
+    checksum: &mut Checksum,
+    encode: fn(T) -> [u8; N],
+) -> Result<(), NnueError> {
+    for chunk in values.chunks(CHECKPOINT_CHUNK) {
+        write_values(writer, chunk, checksum, encode)?;
+    }
+    Ok(())
+}
+
+/// Read `count` values written by [`write_chunked`]
+fn read_chunked<R: Read, T, const N: usize>(
+    reader: &mut R,
+    count: usize,
+    checksum: &mut Checksum,
+    decode: fn([u8; N]) -> T,
+) -> Result<Vec<T>, NnueError> {
+    let mut values = Vec::with_capacity(count);
+    while values.len() < count {
+        let chunk = (count - values.len()).min(CHECKPOINT_CHUNK);
+        values.extend(read_values(reader, chunk, checksum, decode)?);
+    }
+    Ok(values)
+}
+
+fn sigmoid(x: f64) -> f64 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitboards::BitboardBoard;
+    use crate::tuning::packed::PackedPosition;
+
+    const SIZES: (usize, usize, usize) = (8, 4, 4);
+
+    fn samples() -> Vec<TrainingSample> {
+        let sfens = [
+            ("lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b - 1", 0, 0),
+            ("lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b R 1", 900, 1),
+            ("lnsgkgsnl/7b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w R 1", -900, -1),
+            ("ln1gkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b S 1", 400, 1),
+        ];
+        sfens
+            .iter()
+            .map(|&(sfen, score, result)| {
+                let (board, player, captured) = BitboardBoard::from_fen(sfen).unwrap();
+                let record = PackedRecord {
+                    position: PackedPosition::pack(&board, player, &captured).unwrap(),
+                    score,
+                    ply: 20,
+                    result,
+                };
+                TrainingSample::from_record(&record).unwrap()
+            })
+            .collect()
+    }
+
+    fn small_config() -> NnueTrainerConfig {
+        NnueTrainerConfig {
+            layer_sizes: SIZES,
+            epochs: 60,
+            batch_size: 4,
+            learning_rate: 0.01,
+            validation_split: 0.0,
+            ..NnueTrainerConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_samples_hold_both_perspectives() {
+        let samples = samples();
+        assert_eq!(samples[0].us.len(), samples[0].them.len());
+        assert_eq!(samples[0].us.len(), 38);
+        assert_eq!(samples[1].us.len(), 39);
+        assert!(samples[0].us.iter().all(|&f| (f as usize) < NUM_FEATURES));
+    }
+
+    #[test]
+    fn test_gradient_matches_finite_differences() {
+        let config = NnueTrainerConfig { lambda: 1.0, ..small_config() };
+        let sample = &samples()[1];
+        let target = config.target(sample);
+        let mut network = FloatNetwork::new(SIZES, 3);
+        let mut gradient = Gradient::new(&network.layout);
+        network.backward(sample, target, config.score_scale, &mut gradient);
+
+        let layout = network.layout;
+        let feature = sample.us[5];
+        let checks = [
+            (layout.out_bias, gradient.dense[layout.out_bias]),
+            (layout.out_weights + 1, gradient.dense[layout.out_weights + 1]),
+            (layout.l3_weights + 2, gradient.dense[layout.l3_weights + 2]),
+            (layout.l2_weights + 3, gradient.dense[layout.l2_weights + 3]),
+            (layout.ft_bias + 1, gradient.dense[layout.ft_bias + 1]),
+            (layout.ft_row(feature).start, gradient.ft_rows[&feature][0]),
+        ];
+        let loss = |network: &FloatNetwork| {
+            let predicted = sigmoid(network.evaluate(sample) / config.score_scale);
+            (predicted - target).powi(2)
+        };
+        for (index, analytic) in checks {
+            let h = 1e-6;
+            let original = network.params[index];
+            network.params[index] = original + h;
+            let up = loss(&network);
+            network.params[index] = original - h;
+            let down = loss(&network);
+            network.params[index] = original;
+            let numeric = (up - down) / (2.0 * h);
+            assert!(
+                (numeric - analytic).abs() <= 1e-6 + 1e-3 * numeric.abs(),
+                "parameter {index}: numeric {numeric}, analytic {analytic}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_training_reduces_loss_and_exports() {
+        let samples = samples();
+        let mut trainer = NnueTrainer::new(small_config());
+        let before = trainer.loss(samples.iter());
+        let reports = trainer.train(&samples, |_| {}).unwrap();
+        assert_eq!(reports.len(), 60);
+        assert!(trainer.loss(samples.iter()) < before);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("trained.nnue");
+        let exported = trainer.export(&path).unwrap();
+        assert_eq!(NnueNetwork::load(&path).unwrap(), exported);
+
+        // The quantised network scores like the float one
+        for sample in &samples {
+            let accumulate = |features: &[u32]| {
+                let mut acc = exported.ft_bias().to_vec();
+                for &feature in features {
+                    for (value, &weight) in acc.iter_mut().zip(exported.ft_row(feature as usize)) {
+                        *value += weight;
+                    }
+                }
+                acc
+            };
+            let quantised = exported.evaluate(&accumulate(&sample.us), &accumulate(&sample.them));
+            let float = trainer.network().evaluate(sample);
+            assert!((f64::from(quantised) - float).abs() < 40.0, "{quantised} vs {float}");
+        }
+    }
+
+    #[test]
+    fn test_checkpoint_resume() {
+        let samples = samples();
+        let dir = tempfile::tempdir().unwrap();
+        let checkpoints = format!("{}/", dir.path().display());
+        let config = NnueTrainerConfig {
+            epochs: 2,
+            checkpoint_path: Some(checkpoints.clone()),
+            ..small_config()
+        };
+        let mut trainer = NnueTrainer::new(config.clone());
+        trainer.train(&samples, |_| {}).unwrap();
+
+        let checkpoint = dir.path().join("checkpoint_iter_2.json");
+        let data = TuningProfiler::load_checkpoint(&checkpoint).unwrap();
+        assert_eq!(data.state_file.as_deref(), Some("checkpoint_iter_2.state"));
+        let mut resumed =
+            NnueTrainer::resume(NnueTrainerConfig { epochs: 3, ..config }, &checkpoint).unwrap();
+        assert_eq!(resumed.epoch(), 2);
+        assert_eq!(resumed.network().params(), trainer.network().params());
+
+        // With the optimizer state restored, the resumed run continues the
+        // same optimisation as one that never stopped
+        resumed.train(&samples, |_| {}).unwrap();
+        let mut uninterrupted = NnueTrainer::new(NnueTrainerConfig { epochs: 3, ..small_config() });
+        uninterrupted.train(&samples, |_| {}).unwrap();
+        assert_eq!(resumed.network().params(), uninterrupted.network().params());
+
+        let wrong_sizes = NnueTrainerConfig { layer_sizes: (4, 4, 4), ..small_config() };
+        assert!(matches!(
+            NnueTrainer::resume(wrong_sizes, &checkpoint),
+            Err(NnueTrainerError::CheckpointMismatch { .. })
+        ));
+
+        let state = dir.path().join("checkpoint_iter_2.state");
+        let mut bytes = std::fs::read(&state).unwrap();
+        bytes[40] ^= 1;
+        std::fs::write(&state, &bytes).unwrap();
+        assert!(matches!(
+            NnueTrainer::resume(small_config(), &checkpoint),
+            Err(NnueTrainerError::Nnue(NnueError::ChecksumMismatch { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_step_leaves_untouched_rows() {
+        let samples = samples();
+        let mut trainer = NnueTrainer::new(small_config());
+        let before = trainer.network().clone();
+        let layout = before.layout;
+        trainer.step(&samples, &[0, 1]);
+
+        let touched = |feature: u32| {
+            [&samples[0], &samples[1]]
+                .iter()
+                .any(|s| s.us.contains(&feature) || s.them.contains(&feature))
+        };
+        for feature in 0..NUM_FEATURES as u32 {
+            let range = layout.ft_row(feature);
+            let moved = trainer.network().params[range.clone()] != before.params[range];
+            assert_eq!(moved, touched(feature), "feature {feature}");
+        }
+    }
+}
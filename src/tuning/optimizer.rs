@@ -431,12 +431,12 @@ impl AdamState {
     /// * `beta2` - Exponential decay rate for second moment estimates
     ///   (typically 0.999)
     /// * `epsilon` - Small constant for numerical stability (typically 1e-8)
-    fn new(num_weights: usize, beta1: f64, beta2: f64, epsilon: f64) -> Self {
+    pub fn new(num_weights: usize, beta1: f64, beta2: f64, epsilon: f64) -> Self {
         Self { m: vec![0.0; num_weights], v: vec![0.0; num_weights], beta1, beta2, epsilon, t: 0 }
     }
 
     /// Update weights using Adam optimizer
-    fn update(&mut self, weights: &mut [f64], gradients: &[f64], learning_rate: f64) {
+    pub fn update(&mut self, weights: &mut [f64], gradients: &[f64], learning_rate: f64) {
         self.t += 1;
         let beta1_t = self.beta1.powi(self.t as i32);
         let beta2_t = self.beta2.powi(self.t as i32);
@@ -458,6 +458,23 @@ impl AdamState {
             weights[i] -= learning_rate * m_hat / (v_hat.sqrt() + self.epsilon);
         }
     }
+
+    /// Time step and moment estimates, for checkpoints
+    pub fn moments(&self) -> (usize, &[f64], &[f64]) {
+        (self.t, &self.m, &self.v)
+    }
+
+    /// Restore the time step and moment estimates saved from
+    /// [`Self::moments`]
+    ///
+    /// # Panics
+    /// If the moments do not have one entry per weight
+    pub fn restore_moments(&mut self, t: usize, m: Vec<f64>, v: Vec<f64>) {
+        assert!(m.len() == self.m.len() && v.len() == self.v.len(), "moment length mismatch");
+        self.t = t;
+        self.m = m;
+        self.v = v;
+    }
 }
 
 impl LineSearch {
@@ -0,0 +1,365 @@
+//! Packed training records
+//!
+//! Self-play data is written as a stream of fixed-size [`PackedRecord`]s with
+//! no header, so files from several runs can simply be concatenated. Each
+//! record holds a bit-packed position, the search score, the ply and the game
+//! result, all from the side to move's point of view:
+//!
+//! | Bytes | Field |
+//! |-------|-------|
+//! | 0-41  | bit-packed position |
+//! | 42-43 | score in centipawns, `i16` little endian |
+//! | 44-45 | ply, `u16` little endian |
+//! | 46    | result: 1 win, 0 draw, -1 loss |
+//! | 47    | reserved, 0 |
+//!
+//! The position packs, least significant bit first: the side to move (1
+//! bit), the occupancy of the 81 squares (81 bits), 5 bits per occupied
+//! square (4-bit piece type, 1-bit owner), then Black's and White's hands
+//! (pawns 5 bits, lances to golds 3 bits, bishops and rooks 2 bits). A
+//! position fits as long as no more than 40 pieces are on the board, which
+//! holds for every position reachable from a legal start.
+
+use crate::bitboards::BitboardBoard;
+use crate::types::board::CapturedPieces;
+use crate::types::core::{Piece, PieceType, Player, Position};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+/// Size of one packed record in bytes
+pub const PACKED_RECORD_SIZE: usize = 48;
+
+/// Size of a packed position in bytes
+pub const PACKED_POSITION_SIZE: usize = 42;
+
+/// Hand piece types in packing order, with the bits used for their count
+const HAND_FIELDS: [(PieceType, u32); 7] = [
+    (PieceType::Pawn, 5),
+    (PieceType::Lance, 3),
+    (PieceType::Knight, 3),
+    (PieceType::Silver, 3),
+    (PieceType::Gold, 3),
+    (PieceType::Bishop, 2),
+    (PieceType::Rook, 2),
+];
+
+/// Most pieces a packed position can hold on the board
+const MAX_BOARD_PIECES: usize = 40;
+
+/// Errors that can occur while packing, reading or writing records
+#[derive(Debug, thiserror::Error)]
+pub enum PackError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Position cannot be packed: {0}")]
+    Unpackable(&'static str),
+
+    #[error("Corrupt packed position: {0}")]
+    Corrupt(&'static str),
+}
+
+/// A bit-packed position
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PackedPosition([u8; PACKED_POSITION_SIZE]);
+
+/// A position unpacked from a record
+#[derive(Debug, Clone)]
+pub struct UnpackedPosition {
+    pub squares: [Option<Piece>; 81],
+    pub side_to_move: Player,
+    pub captured_pieces: CapturedPieces,
+}
+
+impl UnpackedPosition {
+    /// Build a board holding the unpacked pieces
+    pub fn to_board(&self) -> BitboardBoard {
+        let mut board = BitboardBoard::empty();
+        for (index, piece) in self.squares.iter().enumerate() {
+            if let Some(piece) = piece {
+                board.place_piece(*piece, Position::from_index(index as u8));
+            }
+        }
+        board.set_side_to_move(self.side_to_move);
+        board
+    }
+}
+
+impl PackedPosition {
+    /// Pack the position on `board` with `side_to_move` to play
+    pub fn pack(
+        board: &BitboardBoard,
+        side_to_move: Player,
+        captured_pieces: &CapturedPieces,
+    ) -> Result<Self, PackError> {
+        Self::pack_squares(board.squares(), side_to_move, captured_pieces)
+    }
+
+    /// Pack a position given square by square
+    pub fn pack_squares(
+        squares: &[Option<Piece>; 81],
+        side_to_move: Player,
+        captured_pieces: &CapturedPieces,
+    ) -> Result<Self, PackError> {
+        if squares.iter().flatten().count() > MAX_BOARD_PIECES {
+            return Err(PackError::Unpackable("more than 40 pieces on the board"));
+        }
+        let mut writer = BitWriter::default();
+        writer.write(u32::from(side_to_move == Player::White), 1);
+        for square in squares {
+            writer.write(u32::from(square.is_some()), 1);
+        }
+        for piece in squares.iter().flatten() {
+            writer.write(u32::from(piece.piece_type.to_u8()), 4);
+            writer.write(u32::from(piece.player == Player::White), 1);
+        }
+        for player in [Player::Black, Player::White] {
+            for (piece_type, bits) in HAND_FIELDS {
+                let count = captured_pieces.count(piece_type, player);
+                if count >= 1 << bits {
+                    return Err(PackError::Unpackable("too many pieces in hand"));
+                }
+                writer.write(count as u32, bits);
+            }
+        }
+        Ok(Self(writer.bytes))
+    }
+
+    pub fn unpack(&self) -> Result<UnpackedPosition, PackError> {
+        let mut reader = BitReader::new(&self.0);
+        let side_to_move = if reader.read(1) == 1 { Player::White } else { Player::Black };
+        let occupied: Vec<usize> = (0..81).filter(|_| reader.read(1) == 1).collect();
+        if occupied.len() > MAX_BOARD_PIECES {
+            return Err(PackError::Corrupt("more than 40 occupied squares"));
+        }
+        let mut squares = [None; 81];
+        for index in occupied {
+            let piece_type = reader.read(4) as u8;
+            if usize::from(piece_type) >= PieceType::COUNT {
+                return Err(PackError::Corrupt("invalid piece type"));
+            }
+            let player = if reader.read(1) == 1 { Player::White } else { Player::Black };
+            squares[index] = Some(Piece::new(PieceType::from_u8(piece_type), player));
+        }
+        let mut captured_pieces = CapturedPieces::new();
+        for player in [Player::Black, Player::White] {
+            for (piece_type, bits) in HAND_FIELDS {
+                for _ in 0..reader.read(bits) {
+                    captured_pieces.add_piece(piece_type, player);
+                }
+            }
+        }
+        Ok(UnpackedPosition { squares, side_to_move, captured_pieces })
+    }
+
+    pub fn as_bytes(&self) -> &[u8; PACKED_POSITION_SIZE] {
+        &self.0
+    }
+}
+
+/// One training sample: a position with its search score, ply and result
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PackedRecord {
+    pub position: PackedPosition,
+    /// Search score for the side to move, in centipawns
+    pub score: i16,
+    /// Plies played since the start of the game
+    pub ply: u16,
+    /// Game result for the side to move: 1 win, 0 draw, -1 loss
+    pub result: i8,
+}
+
+impl PackedRecord {
+    pub fn to_bytes(&self) -> [u8; PACKED_RECORD_SIZE] {
+        let mut bytes = [0u8; PACKED_RECORD_SIZE];
+        bytes[..PACKED_POSITION_SIZE].copy_from_slice(&self.position.0);
+        bytes[42..44].copy_from_slice(&self.score.to_le_bytes());
+        bytes[44..46].copy_from_slice(&self.ply.to_le_bytes());
+        bytes[46] = self.result as u8;
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8; PACKED_RECORD_SIZE]) -> Self {
+        let mut position = [0u8; PACKED_POSITION_SIZE];
+        position.copy_from_slice(&bytes[..PACKED_POSITION_SIZE]);
+        Self {
+            position: PackedPosition(position),
+            score: i16::from_le_bytes([bytes[42], bytes[43]]),
+            ply: u16::from_le_bytes([bytes[44], bytes[45]]),
+            result: bytes[46] as i8,
+        }
+    }
+
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&self.to_bytes())
+    }
+}
+
+/// Iterator over the records of a packed stream
+pub struct PackedReader<R: Read> {
+    reader: R,
+}
+
+impl<R: Read> PackedReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self { reader }
+    }
+}
+
+impl PackedReader<BufReader<File>> {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, PackError> {
+        Ok(Self::new(BufReader::new(File::open(path)?)))
+    }
+}
+
+impl<R: Read> Iterator for PackedReader<R> {
+    type Item = Result<PackedRecord, PackError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut bytes = [0u8; PACKED_RECORD_SIZE];
+        let mut filled = 0;
+        while filled < PACKED_RECORD_SIZE {
+            match self.reader.read(&mut bytes[filled..]) {
+                Ok(0) if filled == 0 => return None,
+                Ok(0) => {
+                    return Some(Err(PackError::Corrupt("stream ends inside a record")));
+                }
+                Ok(read) => filled += read,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Some(Err(err.into())),
+            }
+        }
+        Some(Ok(PackedRecord::from_bytes(&bytes)))
+    }
+}
+
+/// Read every record of a packed file
+pub fn read_packed_file<P: AsRef<Path>>(path: P) -> Result<Vec<PackedRecord>, PackError> {
+    PackedReader::open(path)?.collect()
+}
+
+/// Write records to a packed file, replacing it
+pub fn write_packed_file<P: AsRef<Path>>(
+    path: P,
+    records: &[PackedRecord],
+) -> Result<(), PackError> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    for record in records {
+        record.write_to(&mut writer)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+struct BitWriter {
+    bytes: [u8; PACKED_POSITION_SIZE],
+    bit: usize,
+}
+
+impl Default for BitWriter {
+    fn default() -> Self {
+        Self { bytes: [0; PACKED_POSITION_SIZE], bit: 0 }
+    }
+}
+
+impl BitWriter {
+    fn write(&mut self, value: u32, bits: u32) {
+        for i in 0..bits {
+            if value >> i & 1 == 1 {
+                self.bytes[self.bit / 8] |= 1 << (self.bit % 8);
+            }
+            self.bit += 1;
+        }
+    }
+}
+
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    bit: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, bit: 0 }
+    }
+
+    fn read(&mut self, bits: u32) -> u32 {
+        let mut value = 0;
+        for i in 0..bits {
+            let byte = self.bytes.get(self.bit / 8).copied().unwrap_or(0);
+            value |= u32::from(byte >> (self.bit % 8) & 1) << i;
+            self.bit += 1;
+        }
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evaluation::nnue::hand_counts;
+
+    const POSITIONS: [&str; 3] = [
+        "lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b - 1",
+        "ln1g3nl/1r1sk1gs1/p1pppp1pp/1p4p2/7P1/2P6/PPSPPPP1P/1B1K3R1/LN1G1GSNL w Bb 24",
+        "4k4/9/4P4/9/9/9/9/9/4K4 b RB2G2S2N2L9Prb2g2s2n2l8p 1",
+    ];
+
+    #[test]
+    fn test_positions_round_trip() {
+        for sfen in POSITIONS {
+            let (board, player, captured) = BitboardBoard::from_fen(sfen).unwrap();
+            let packed = PackedPosition::pack(&board, player, &captured).unwrap();
+            let unpacked = packed.unpack().unwrap();
+            assert_eq!(unpacked.side_to_move, player);
+            assert_eq!(&unpacked.squares, board.squares());
+            assert_eq!(unpacked.to_board().squares(), board.squares());
+            assert_eq!(hand_counts(&unpacked.captured_pieces), hand_counts(&captured));
+        }
+    }
+
+    #[test]
+    fn test_records_round_trip_through_a_file() {
+        let records: Vec<PackedRecord> = POSITIONS
+            .iter()
+            .enumerate()
+            .map(|(i, sfen)| {
+                let (board, player, captured) = BitboardBoard::from_fen(sfen).unwrap();
+                PackedRecord {
+                    position: PackedPosition::pack(&board, player, &captured).unwrap(),
+                    score: -1234 + i as i16,
+                    ply: 300 + i as u16,
+                    result: i as i8 - 1,
+                }
+            })
+            .collect();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("records.bin");
+        write_packed_file(&path, &records).unwrap();
+        assert_eq!(
+            std::fs::metadata(&path).unwrap().len(),
+            (records.len() * PACKED_RECORD_SIZE) as u64
+        );
+        assert_eq!(read_packed_file(&path).unwrap(), records);
+
+        let mut truncated = std::fs::read(&path).unwrap();
+        truncated.pop();
+        let results: Vec<_> = PackedReader::new(truncated.as_slice()).collect();
+        assert_eq!(results.len(), records.len());
+        assert!(matches!(results.last(), Some(Err(PackError::Corrupt(_)))));
+    }
+
+    #[test]
+    fn test_overfull_hand_is_rejected() {
+        let board = BitboardBoard::empty();
+        let mut captured = CapturedPieces::new();
+        for _ in 0..4 {
+            captured.add_piece(PieceType::Rook, Player::Black);
+        }
+        assert!(matches!(
+            PackedPosition::pack(&board, Player::Black, &captured),
+            Err(PackError::Unpackable(_))
+        ));
+    }
+}
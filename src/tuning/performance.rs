@@ -85,6 +85,10 @@ pub struct CheckpointData {
     /// Incremental learning state (if incremental learning is enabled)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub incremental_state: Option<IncrementalStateCheckpoint>,
+    /// File next to the checkpoint holding weights and optimizer state too
+    /// large for JSON; `weights` is empty when it is set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_file: Option<String>,
 }
 
 /// Incremental learning state for checkpointing
@@ -316,30 +320,55 @@ impl TuningProfiler {
             metrics: self.metrics.lock().unwrap().clone(),
             validation_results: None,
             incremental_state: incremental_state.map(|s| s.to_checkpoint()),
+            state_file: None,
         };
+        self.write_checkpoint(&checkpoint_data)
+    }
 
+    /// Create a checkpoint whose weights and optimizer state the caller has
+    /// written to `state_file`, a file in [`Self::checkpoint_dir`]
+    pub fn create_checkpoint_with_state_file(
+        &mut self,
+        iteration: usize,
+        error: f64,
+        optimization_method: OptimizationMethod,
+        state_file: &str,
+    ) -> Result<(), std::io::Error> {
+        let checkpoint_data = CheckpointData {
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs(),
+            iteration,
+            weights: Vec::new(),
+            current_error: error,
+            optimization_method,
+            metrics: self.metrics.lock().unwrap().clone(),
+            validation_results: None,
+            incremental_state: None,
+            state_file: Some(state_file.to_string()),
+        };
+        self.write_checkpoint(&checkpoint_data)
+    }
+
+    /// Directory checkpoints are written to; created if it doesn't exist
+    pub fn checkpoint_dir(&self) -> Result<&Path, std::io::Error> {
         // Use configured checkpoint path or default to "checkpoints/"
-        let checkpoint_dir_str = self
-            .config
-            .checkpoint_path
-            .as_ref()
-            .map(|s| s.as_str())
-            .unwrap_or("checkpoints/");
-        let checkpoint_dir = Path::new(checkpoint_dir_str);
-
-        // Create directory if it doesn't exist
+        let checkpoint_dir =
+            Path::new(self.config.checkpoint_path.as_deref().unwrap_or("checkpoints/"));
         if !checkpoint_dir.exists() {
             std::fs::create_dir_all(checkpoint_dir)?;
         }
+        Ok(checkpoint_dir)
+    }
 
+    fn write_checkpoint(&mut self, checkpoint_data: &CheckpointData) -> Result<(), std::io::Error> {
+        let iteration = checkpoint_data.iteration;
+        let checkpoint_dir = self.checkpoint_dir()?;
         let checkpoint_file = checkpoint_dir.join(format!("checkpoint_iter_{}.json", iteration));
         let file = File::create(checkpoint_file)?;
         let writer = BufWriter::new(file);
-        serde_json::to_writer_pretty(writer, &checkpoint_data)?;
+        serde_json::to_writer_pretty(writer, checkpoint_data)?;
+        info!("Checkpoint created at iteration {} in {}", iteration, checkpoint_dir.display());
 
         self.last_checkpoint = Some(Instant::now());
-        info!("Checkpoint created at iteration {} in {}", iteration, checkpoint_dir_str);
-
         Ok(())
     }
 
@@ -0,0 +1,364 @@
+//! Self-play training data generation
+//!
+//! Worker threads each own a [`ShogiEngine`] and play games against
+//! themselves from randomized openings: a line from the configured book
+//! followed by a few uniformly random legal moves. Every move is searched to
+//! a fixed depth or node count in reproducible mode, so a seed and game index
+//! always produce the same game, and the shared [`GameAdjudicator`] rules end
+//! each game.
+//!
+//! Quiet positions (side to move not in check, best move not a capture,
+//! score within `eval_limit`) are recorded with the search score and ply, and
+//! labelled with the final result once the game is over. Games are written
+//! as [`PackedRecord`]s in the order they finish.
+
+use super::packed::{PackError, PackedPosition, PackedRecord};
+use crate::adjudication::{AdjudicationConfig, GameAdjudicator};
+use crate::moves::MoveGenerator;
+use crate::opening_book::OpeningBook;
+use crate::search::{NullReporter, ReproducibleSearch};
+use crate::types::core::Player;
+use crate::{SearchLimits, ShogiEngine};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+
+/// Openings that end the game before the first search are redrawn this many
+/// times before the game is skipped
+const OPENING_ATTEMPTS: u32 = 16;
+
+/// Errors that can stop a self-play run
+#[derive(Debug, thiserror::Error)]
+pub enum SelfPlayError {
+    #[error("Failed to write records: {0}")]
+    Pack(#[from] PackError),
+
+    #[error("Engine setup failed: {0}")]
+    Engine(String),
+}
+
+/// Settings of a self-play run
+#[derive(Debug, Clone)]
+pub struct SelfPlayConfig {
+    /// Games to play
+    pub games: usize,
+    /// Worker threads, each playing whole games
+    pub threads: usize,
+    /// Search limits of every move: a depth, a node count or both
+    pub limits: SearchLimits,
+    /// USI positions to start from (`startpos moves ...`, `sfen ... moves
+    /// ...`); empty starts every game from the initial position
+    pub book_lines: Vec<String>,
+    /// Random legal moves played after the book line
+    pub random_plies_min: u32,
+    pub random_plies_max: u32,
+    /// Resign, draw and ply-limit rules ending the games
+    pub adjudication: AdjudicationConfig,
+    /// Positions before this ply are not recorded
+    pub min_ply: u32,
+    /// Positions whose score is at least this large are not recorded
+    pub eval_limit: i32,
+    /// Seed of the openings and the searches' random choices
+    pub seed: u64,
+    /// NNUE network to play with; `None` uses the classical evaluator
+    pub eval_file: Option<PathBuf>,
+}
+
+impl Default for SelfPlayConfig {
+    fn default() -> Self {
+        Self {
+            games: 100,
+            threads: std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get),
+            limits: SearchLimits::depth(6),
+            book_lines: Vec::new(),
+            random_plies_min: 8,
+            random_plies_max: 16,
+            adjudication: AdjudicationConfig::default(),
+            min_ply: 16,
+            eval_limit: 3000,
+            seed: 0,
+            eval_file: None,
+        }
+    }
+}
+
+/// Totals of a self-play run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SelfPlayStats {
+    pub games: usize,
+    pub positions: usize,
+    pub black_wins: usize,
+    pub white_wins: usize,
+    pub draws: usize,
+}
+
+impl SelfPlayStats {
+    fn add_game(&mut self, game: &SelfPlayGame) {
+        self.games += 1;
+        self.positions += game.records.len();
+        match game.winner {
+            Some(Player::Black) => self.black_wins += 1,
+            Some(Player::White) => self.white_wins += 1,
+            None => self.draws += 1,
+        }
+    }
+}
+
+/// One finished game
+#[derive(Debug, Clone)]
+pub struct SelfPlayGame {
+    /// Recorded positions, labelled with the result
+    pub records: Vec<PackedRecord>,
+    /// `None` for a draw
+    pub winner: Option<Player>,
+    /// Plies played, including the opening
+    pub plies: u32,
+}
+
+/// Plays self-play games and writes their positions
+pub struct SelfPlayGenerator {
+    config: SelfPlayConfig,
+}
+
+impl SelfPlayGenerator {
+    pub fn new(config: SelfPlayConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn config(&self) -> &SelfPlayConfig {
+        &self.config
+    }
+
+    /// Play all games and write their records to `writer`, calling
+    /// `on_game` with the running totals after each game
+    pub fn run<W: Write>(
+        &self,
+        writer: &mut W,
+        mut on_game: impl FnMut(&SelfPlayStats),
+    ) -> Result<SelfPlayStats, SelfPlayError> {
+        let next_game = AtomicUsize::new(0);
+        let stop = AtomicBool::new(false);
+        let (sender, receiver) = mpsc::channel::<Result<SelfPlayGame, SelfPlayError>>();
+        let threads = self.config.threads.clamp(1, self.config.games.max(1));
+
+        std::thread::scope(|scope| {
+            for _ in 0..threads {
+                let sender = sender.clone();
+                let next_game = &next_game;
+                let stop = &stop;
+                scope.spawn(move || {
+                    let mut engine = match self.create_engine() {
+                        Ok(engine) => engine,
+                        Err(err) => {
+                            let _ = sender.send(Err(err));
+                            return;
+                        }
+                    };
+                    while !stop.load(Ordering::Relaxed) {
+                        let index = next_game.fetch_add(1, Ordering::Relaxed);
+                        if index >= self.config.games {
+                            break;
+                        }
+                        if let Some(game) = self.play_game(&mut engine, index as u64) {
+                            if sender.send(Ok(game)).is_err() {
+                                break;
+                            }
+                        }
+                    }
+                });
+            }
+            drop(sender);
+
+            let mut stats = SelfPlayStats::default();
+            for game in receiver {
+                let written = game.and_then(|game| {
+                    for record in &game.records {
+                        record.write_to(writer).map_err(PackError::from)?;
+                    }
+                    Ok(game)
+                });
+                match written {
+                    Ok(game) => {
+                        stats.add_game(&game);
+                        on_game(&stats);
+                    }
+                    Err(err) => {
+                        stop.store(true, Ordering::Relaxed);
+                        return Err(err);
+                    }
+                }
+            }
+            writer.flush().map_err(PackError::from)?;
+            Ok(stats)
+        })
+    }
+
+    /// Engine of one worker: quiet, without a book, searching reproducibly
+    fn create_engine(&self) -> Result<ShogiEngine, SelfPlayError> {
+        let mut engine = ShogiEngine::new();
+        engine.opening_book = Arc::new(Mutex::new(OpeningBook::new()));
+        engine.set_search_reporter(Arc::new(NullReporter));
+        if let Some(path) = &self.config.eval_file {
+            engine.load_eval_file(&path.to_string_lossy()).map_err(SelfPlayError::Engine)?;
+        }
+        Ok(engine)
+    }
+
+    /// Play game `index`; `None` when no opening left a playable position
+    pub fn play_game(&self, engine: &mut ShogiEngine, index: u64) -> Option<SelfPlayGame> {
+        let seed = self.config.seed ^ index.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        let mut rng = StdRng::seed_from_u64(seed);
+        engine.set_reproducible(ReproducibleSearch::new(None).with_seed(seed));
+
+        let mut attempts = 0;
+        while !self.play_opening(engine, &mut rng) {
+            attempts += 1;
+            if attempts >= OPENING_ATTEMPTS {
+                return None;
+            }
+        }
+
+        let mut adjudicator = GameAdjudicator::new(self.config.adjudication.clone());
+        let mut pending: Vec<(PackedRecord, Player)> = Vec::new();
+        let winner = loop {
+            let player = engine.current_player();
+            if let Some(adjudication) =
+                adjudicator.check_position(engine.board(), engine.captured_pieces(), player)
+            {
+                break adjudication.winner;
+            }
+
+            let outcome = engine.search_with_limits(&self.config.limits, None);
+            let Some(best_move) = outcome.best_move else {
+                break None;
+            };
+            if let Some(adjudication) = adjudicator.check_score(player, outcome.score) {
+                break adjudication.winner;
+            }
+
+            let ply = engine.game_ply();
+            let quiet = !engine.board().is_king_in_check(player, engine.captured_pieces())
+                && engine.board().get_piece(best_move.to).is_none();
+            if ply >= self.config.min_ply && quiet && outcome.score.abs() < self.config.eval_limit {
+                if let Ok(position) =
+                    PackedPosition::pack(engine.board(), player, engine.captured_pieces())
+                {
+                    let record = PackedRecord {
+                        position,
+                        score: outcome.score.clamp(i16::MIN.into(), i16::MAX.into()) as i16,
+                        ply: ply.min(u16::MAX.into()) as u16,
+                        result: 0,
+                    };
+                    pending.push((record, player));
+                }
+            }
+
+            if !engine.apply_move(&best_move) {
+                break None;
+            }
+        };
+
+        let records = pending
+            .into_iter()
+            .map(|(mut record, player)| {
+                record.result = match winner {
+                    Some(winner) if winner == player => 1,
+                    Some(_) => -1,
+                    None => 0,
+                };
+                record
+            })
+            .collect();
+        Some(SelfPlayGame { records, winner, plies: engine.game_ply() })
+    }
+
+    /// Set up a random opening; `false` when it ended the game
+    fn play_opening(&self, engine: &mut ShogiEngine, rng: &mut StdRng) -> bool {
+        let line = self.config.book_lines.choose(rng).map_or("startpos", String::as_str);
+        let (position, moves) = match line.split_once(" moves ") {
+            Some((position, moves)) => (position, moves.split_whitespace().collect()),
+            None => (line, Vec::new()),
+        };
+        if engine.set_usi_position(position, &moves).is_err() {
+            return false;
+        }
+
+        let low = self.config.random_plies_min;
+        let high = self.config.random_plies_max.max(low);
+        let generator = MoveGenerator::new();
+        for _ in 0..rng.gen_range(low..=high) {
+            let moves = generator.generate_legal_moves(
+                engine.board(),
+                engine.current_player(),
+                engine.captured_pieces(),
+            );
+            let Some(mv) = moves.choose(rng) else {
+                return false;
+            };
+            if !engine.apply_move(mv) {
+                return false;
+            }
+        }
+        !generator
+            .generate_legal_moves(engine.board(), engine.current_player(), engine.captured_pieces())
+            .is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tuning::packed::{PackedReader, PACKED_RECORD_SIZE};
+
+    fn small_config() -> SelfPlayConfig {
+        SelfPlayConfig {
+            games: 3,
+            threads: 2,
+            limits: SearchLimits::depth(1),
+            book_lines: vec!["startpos moves 7g7f 3c3d".to_string()],
+            random_plies_min: 2,
+            random_plies_max: 4,
+            adjudication: AdjudicationConfig { max_plies: 24, ..AdjudicationConfig::default() },
+            min_ply: 0,
+            eval_limit: 3000,
+            seed: 7,
+            eval_file: None,
+        }
+    }
+
+    #[test]
+    fn test_generated_records_read_back() {
+        let mut buffer = Vec::new();
+        let stats = SelfPlayGenerator::new(small_config()).run(&mut buffer, |_| {}).unwrap();
+        assert_eq!(stats.games, 3);
+        assert_eq!(stats.black_wins + stats.white_wins + stats.draws, 3);
+        assert!(stats.positions > 0);
+        assert_eq!(buffer.len(), stats.positions * PACKED_RECORD_SIZE);
+
+        for record in PackedReader::new(buffer.as_slice()) {
+            let record = record.unwrap();
+            let position = record.position.unpack().unwrap();
+            let board = position.to_board();
+            assert!(!board.is_king_in_check(position.side_to_move, &position.captured_pieces));
+            assert!(i32::from(record.score).abs() < 3000);
+            assert!((-1..=1).contains(&record.result));
+            assert!(record.ply >= 2);
+        }
+    }
+
+    #[test]
+    fn test_games_are_reproducible() {
+        let generator = SelfPlayGenerator::new(small_config());
+        let mut first = generator.create_engine().unwrap();
+        let mut second = generator.create_engine().unwrap();
+        let a = generator.play_game(&mut first, 1).unwrap();
+        let b = generator.play_game(&mut second, 1).unwrap();
+        assert_eq!(a.records, b.records);
+        assert_eq!(a.winner, b.winner);
+        assert!(a.plies <= 24 + 2 + 4);
+    }
+}
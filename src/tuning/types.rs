@@ -5,6 +5,7 @@
 
 use crate::types::core::{Move, Player};
 use crate::types::evaluation::NUM_EVAL_FEATURES;
+use crate::types::handicap::Handicap;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -84,6 +85,9 @@ pub struct GameRecord {
     pub opening: Option<String>,
     /// Date the game was played (if available)
     pub date: Option<String>,
+    /// Starting array; in handicap games the handicap giver moves first
+    #[serde(default)]
+    pub handicap: Handicap,
     /// Additional metadata
     pub metadata: HashMap<String, String>,
 }
@@ -99,6 +103,7 @@ impl GameRecord {
             time_control,
             opening: None,
             date: None,
+            handicap: Handicap::Even,
             metadata: HashMap::new(),
         }
     }
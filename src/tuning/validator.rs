@@ -43,7 +43,8 @@ use super::types::{
     FoldResult, GameResult as TuningGameResult, MatchResult, OptimizationMethod, TrainingPosition,
     ValidationConfig, ValidationResults,
 };
-use crate::types::core::{Move, Player};
+use crate::adjudication::{Adjudication, AdjudicationConfig, GameAdjudicator};
+use crate::types::core::Player;
 // Note: GameResult is not yet extracted to a sub-module, using root import
 use crate::types::GameResult;
 use crate::ShogiEngine;
@@ -466,6 +467,14 @@ impl ShogiEngineGamePlayer {
             (GameResult::Draw, _) => TuningGameResult::Draw,
         }
     }
+
+    /// Result of an adjudicated game, with Black as player 1
+    fn adjudicated_result(&self, adjudication: Adjudication) -> TuningGameResult {
+        if self.verbose {
+            println!("Game over by {}: {:?}", adjudication.reason.as_str(), adjudication.winner);
+        }
+        Self::convert_game_result(adjudication.game_result(), Player::Black)
+    }
 }
 
 impl GamePlayer for ShogiEngineGamePlayer {
@@ -490,84 +499,45 @@ impl GamePlayer for ShogiEngineGamePlayer {
         // 2. Ability to configure engine with different evaluation weights per game
         // 3. Or use two separate engine instances with different configurations
 
-        let mut move_count = 0;
-        let mut consecutive_passes = 0;
-        let mut last_move: Option<Move> = None;
-        let mut current_player = Player::Black;
+        let mut adjudicator = GameAdjudicator::new(AdjudicationConfig {
+            max_plies: max_moves,
+            ..AdjudicationConfig::default()
+        });
 
         // Play the game (engine plays against itself)
         loop {
-            // Check if game is over
-            if let Some(result) = engine.is_game_over() {
-                if self.verbose {
-                    println!("Game over: {:?}", result);
-                }
-                // Convert result from Black's perspective (player1)
-                return Ok(Self::convert_game_result(result, Player::Black));
+            let current_player = engine.current_player();
+            if let Some(adjudication) = adjudicator.check_position(
+                engine.board(),
+                engine.captured_pieces(),
+                current_player,
+            ) {
+                return Ok(self.adjudicated_result(adjudication));
             }
 
-            // Get engine's best move
-            let best_move = engine.get_best_move(self.search_depth, time_per_move_ms, None);
-
-            match best_move {
-                Some(move_) => {
-                    if self.verbose && move_count < 10 {
-                        println!(
-                            "Move {}: {} ({:?})",
-                            move_count + 1,
-                            move_.to_usi_string(),
-                            current_player
-                        );
-                    }
-
-                    // Check if same move repeated (possible infinite loop)
-                    if last_move.as_ref().map(|m| m.to_usi_string()) == Some(move_.to_usi_string())
-                    {
-                        consecutive_passes += 1;
-                        if consecutive_passes >= 3 {
-                            if self.verbose {
-                                println!(
-                                    "Consecutive repeated moves detected, ending game as draw"
-                                );
-                            }
-                            return Ok(TuningGameResult::Draw);
-                        }
-                    } else {
-                        consecutive_passes = 0;
-                    }
-
-                    last_move = Some(move_.clone());
-
-                    // Apply the move to the engine
-                    if !engine.apply_move(&move_) {
-                        if self.verbose {
-                            println!(
-                                "Failed to apply move: {}, ending game",
-                                move_.to_usi_string()
-                            );
-                        }
-                        return Ok(TuningGameResult::Draw);
-                    }
+            let outcome = engine.search_position(self.search_depth, time_per_move_ms, None);
+            let Some(move_) = outcome.best_move else {
+                return Ok(TuningGameResult::Draw);
+            };
+            if let Some(adjudication) = adjudicator.check_score(current_player, outcome.score) {
+                return Ok(self.adjudicated_result(adjudication));
+            }
 
-                    // Switch turns
-                    current_player = current_player.opposite();
-                    move_count += 1;
+            if self.verbose && adjudicator.plies() < 10 {
+                println!(
+                    "Move {}: {} ({:?})",
+                    adjudicator.plies() + 1,
+                    move_.to_usi_string(),
+                    current_player
+                );
+            }
 
-                    // Safety limit to avoid infinite games
-                    if move_count >= max_moves {
-                        if self.verbose {
-                            println!("Maximum move limit reached, ending as draw");
-                        }
-                        return Ok(TuningGameResult::Draw);
-                    }
-                }
-                None => {
-                    // No legal moves - game ended
-                    if let Some(result) = engine.is_game_over() {
-                        return Ok(Self::convert_game_result(result, Player::Black));
-                    }
-                    return Ok(TuningGameResult::Draw);
+            // Apply the move to the engine
+            if !engine.apply_move(&move_) {
+                if self.verbose {
+                    println!("Failed to apply move: {}, ending game", move_.to_usi_string());
                 }
+                return Ok(TuningGameResult::Draw);
             }
         }
     }
@@ -6068,11 +6068,43 @@ impl RealTimePerformance {
     }
 }
 
+/// Algorithm used to distribute work across parallel search threads.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum ParallelSearchMode {
+    /// Split root moves across workers (optionally with YBWC sibling waits).
+    #[default]
+    Ybwc,
+    /// Every thread runs its own iterative deepening over the shared TT.
+    LazySmp,
+}
+
+impl ParallelSearchMode {
+    /// USI option value for this mode.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ParallelSearchMode::Ybwc => "YBWC",
+            ParallelSearchMode::LazySmp => "LazySMP",
+        }
+    }
+
+    /// Parse a USI option value (case-insensitive).
+    pub fn from_str(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "ybwc" => Some(ParallelSearchMode::Ybwc),
+            "lazysmp" | "lazy_smp" | "lazy-smp" => Some(ParallelSearchMode::LazySmp),
+            _ => None,
+        }
+    }
+}
+
 /// Parallel search configuration exposed to frontends and USI.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ParallelOptions {
     /// Enable or disable the parallel search engine entirely.
     pub enable_parallel: bool,
+    /// Parallel algorithm (root splitting/YBWC or Lazy SMP).
+    #[serde(default)]
+    pub search_mode: ParallelSearchMode,
     /// Minimum depth at which to fan out parallel workers.
     pub min_depth_parallel: u8,
     /// Hash size in MB allocated for each parallel worker.
@@ -6100,6 +6132,7 @@ impl Default for ParallelOptions {
     fn default() -> Self {
         Self {
             enable_parallel: true,
+            search_mode: ParallelSearchMode::Ybwc,
             min_depth_parallel: 4,
             hash_size_mb: 16,
             ybwc_enabled: false,
@@ -204,6 +204,20 @@ pub const DEVELOPMENT_MAJOR_PIECES_INDEX: usize = 1000;
 pub const DEVELOPMENT_MINOR_PIECES_INDEX: usize = 1001;
 pub const DEVELOPMENT_CASTLING_INDEX: usize = 1002;
 
+// Hand feature indices: one feature per piece held, by kind and count
+// (18 pawns, 4 lances/knights/silvers/golds, 2 bishops/rooks = 38 features),
+// then safe king-zone drop squares per kind held (7 features)
+pub const HAND_COUNT_FEATURES_START: usize = 1200;
+pub const HAND_COUNT_FEATURES: usize = 38;
+pub const DROP_POTENTIAL_FEATURES_START: usize = HAND_COUNT_FEATURES_START + HAND_COUNT_FEATURES;
+pub const DROP_POTENTIAL_FEATURES: usize = 7;
+
+// King-danger features: one per entry of the danger-to-score table, +1 when
+// the opponent's king sits in that bucket and -1 when ours does
+pub const KING_DANGER_FEATURES_START: usize =
+    DROP_POTENTIAL_FEATURES_START + DROP_POTENTIAL_FEATURES;
+pub const KING_DANGER_FEATURES: usize = 64;
+
 // ============================================================================
 // King Safety Configuration
 // ============================================================================
@@ -0,0 +1,247 @@
+//! Handicap Starting Positions
+//!
+//! Named handicap (駒落ち) setups. In a handicap game the stronger player
+//! (上手) plays White, gives up the listed pieces and moves first; the weaker
+//! player (下手) keeps the full Black array.
+
+use super::core::{PieceType, Player};
+use serde::{Deserialize, Serialize};
+
+/// Starting array of a game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+pub enum Handicap {
+    /// Even game (平手)
+    #[default]
+    Even,
+    /// Lance handicap (香落ち): White's 1a lance is removed
+    Lance,
+    /// Bishop handicap (角落ち)
+    Bishop,
+    /// Rook handicap (飛車落ち)
+    Rook,
+    /// Rook and lance handicap (飛香落ち)
+    RookLance,
+    /// Two-piece handicap (二枚落ち): rook and bishop
+    TwoPiece,
+    /// Four-piece handicap (四枚落ち): two-piece plus both lances
+    FourPiece,
+    /// Six-piece handicap (六枚落ち): four-piece plus both knights
+    SixPiece,
+    /// Eight-piece handicap (八枚落ち): six-piece plus both silvers
+    EightPiece,
+    /// Ten-piece handicap (十枚落ち): eight-piece plus both golds
+    TenPiece,
+}
+
+impl Handicap {
+    /// Every handicap, from the even game to the largest
+    pub const ALL: [Handicap; 10] = [
+        Handicap::Even,
+        Handicap::Lance,
+        Handicap::Bishop,
+        Handicap::Rook,
+        Handicap::RookLance,
+        Handicap::TwoPiece,
+        Handicap::FourPiece,
+        Handicap::SixPiece,
+        Handicap::EightPiece,
+        Handicap::TenPiece,
+    ];
+
+    /// Name accepted by `position handicap <name>`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Handicap::Even => "even",
+            Handicap::Lance => "lance",
+            Handicap::Bishop => "bishop",
+            Handicap::Rook => "rook",
+            Handicap::RookLance => "rook-lance",
+            Handicap::TwoPiece => "2-piece",
+            Handicap::FourPiece => "4-piece",
+            Handicap::SixPiece => "6-piece",
+            Handicap::EightPiece => "8-piece",
+            Handicap::TenPiece => "10-piece",
+        }
+    }
+
+    /// Name used in the `手合割` header of KIF records
+    pub fn kif_name(&self) -> &'static str {
+        match self {
+            Handicap::Even => "平手",
+            Handicap::Lance => "香落ち",
+            Handicap::Bishop => "角落ち",
+            Handicap::Rook => "飛車落ち",
+            Handicap::RookLance => "飛香落ち",
+            Handicap::TwoPiece => "二枚落ち",
+            Handicap::FourPiece => "四枚落ち",
+            Handicap::SixPiece => "六枚落ち",
+            Handicap::EightPiece => "八枚落ち",
+            Handicap::TenPiece => "十枚落ち",
+        }
+    }
+
+    /// Parse an English or KIF handicap name (case-insensitive, ignoring
+    /// `-`, `_`, `+` and spaces)
+    pub fn from_str(value: &str) -> Option<Self> {
+        let normalized: String = value
+            .trim()
+            .chars()
+            .filter(|c| !matches!(c, '-' | '_' | '+' | ' '))
+            .collect::<String>()
+            .to_lowercase();
+        match normalized.as_str() {
+            "even" | "hirate" | "startpos" | "平手" => Some(Handicap::Even),
+            "lance" | "kyo" | "香落ち" | "香落" => Some(Handicap::Lance),
+            "bishop" | "kaku" | "角落ち" | "角落" => Some(Handicap::Bishop),
+            "rook" | "hisha" | "飛車落ち" | "飛落ち" | "飛車落" => Some(Handicap::Rook),
+            "rooklance" | "hikyo" | "飛香落ち" | "飛香落" => Some(Handicap::RookLance),
+            "2piece" | "twopiece" | "二枚落ち" | "二枚落" => Some(Handicap::TwoPiece),
+            "4piece" | "fourpiece" | "四枚落ち" | "四枚落" => Some(Handicap::FourPiece),
+            "6piece" | "sixpiece" | "六枚落ち" | "六枚落" => Some(Handicap::SixPiece),
+            "8piece" | "eightpiece" | "八枚落ち" | "八枚落" => Some(Handicap::EightPiece),
+            "10piece" | "tenpiece" | "十枚落ち" | "十枚落" => Some(Handicap::TenPiece),
+            _ => None,
+        }
+    }
+
+    /// SFEN of the starting position
+    pub fn sfen(&self) -> &'static str {
+        match self {
+            Handicap::Even => "lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b - 1",
+            Handicap::Lance => "lnsgkgsn1/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1",
+            Handicap::Bishop => "lnsgkgsnl/1r7/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1",
+            Handicap::Rook => "lnsgkgsnl/7b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1",
+            Handicap::RookLance => "lnsgkgsn1/7b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1",
+            Handicap::TwoPiece => "lnsgkgsnl/9/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1",
+            Handicap::FourPiece => "1nsgkgsn1/9/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1",
+            Handicap::SixPiece => "2sgkgs2/9/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1",
+            Handicap::EightPiece => "3gkg3/9/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1",
+            Handicap::TenPiece => "4k4/9/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1",
+        }
+    }
+
+    /// Recognize a starting SFEN by its board and side to move; hands and
+    /// the move number are ignored
+    pub fn from_sfen(sfen: &str) -> Option<Self> {
+        let sfen = sfen.trim();
+        let sfen = sfen.strip_prefix("sfen ").unwrap_or(sfen);
+        let key = |s: &str| s.split_whitespace().take(2).collect::<Vec<_>>().join(" ");
+        let wanted = key(sfen);
+        Self::ALL.iter().copied().find(|handicap| key(handicap.sfen()) == wanted)
+    }
+
+    /// Side that makes the first move
+    pub fn first_player(&self) -> Player {
+        match self {
+            Handicap::Even => Player::Black,
+            _ => Player::White,
+        }
+    }
+
+    /// Pieces White gives up
+    pub fn removed_pieces(&self) -> &'static [PieceType] {
+        use PieceType::*;
+        match self {
+            Handicap::Even => &[],
+            Handicap::Lance => &[Lance],
+            Handicap::Bishop => &[Bishop],
+            Handicap::Rook => &[Rook],
+            Handicap::RookLance => &[Rook, Lance],
+            Handicap::TwoPiece => &[Rook, Bishop],
+            Handicap::FourPiece => &[Rook, Bishop, Lance, Lance],
+            Handicap::SixPiece => &[Rook, Bishop, Lance, Lance, Knight, Knight],
+            Handicap::EightPiece => &[Rook, Bishop, Lance, Lance, Knight, Knight, Silver, Silver],
+            Handicap::TenPiece => {
+                &[Rook, Bishop, Lance, Lance, Knight, Knight, Silver, Silver, Gold, Gold]
+            }
+        }
+    }
+
+    /// `PI` line of a CSA record: the squares emptied from the even array
+    pub fn csa_pi(&self) -> &'static str {
+        match self {
+            Handicap::Even => "PI",
+            Handicap::Lance => "PI11KY",
+            Handicap::Bishop => "PI22KA",
+            Handicap::Rook => "PI82HI",
+            Handicap::RookLance => "PI82HI11KY",
+            Handicap::TwoPiece => "PI82HI22KA",
+            Handicap::FourPiece => "PI82HI22KA91KY11KY",
+            Handicap::SixPiece => "PI82HI22KA91KY81KE21KE11KY",
+            Handicap::EightPiece => "PI82HI22KA91KY81KE71GI31GI21KE11KY",
+            Handicap::TenPiece => "PI82HI22KA91KY81KE71GI61KI41KI31GI21KE11KY",
+        }
+    }
+
+    /// Parse a CSA `PI` line; the removed squares may come in any order
+    pub fn from_csa_pi(line: &str) -> Option<Self> {
+        let squares = |line: &str| -> Option<Vec<String>> {
+            let body = line.trim().strip_prefix("PI")?;
+            if body.len() % 4 != 0 || !body.is_ascii() {
+                return None;
+            }
+            let mut squares: Vec<String> =
+                (0..body.len()).step_by(4).map(|i| body[i..i + 4].to_string()).collect();
+            squares.sort();
+            Some(squares)
+        };
+        let wanted = squares(line)?;
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|handicap| squares(handicap.csa_pi()).as_ref() == Some(&wanted))
+    }
+}
+
+impl std::fmt::Display for Handicap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitboards::BitboardBoard;
+    use crate::opening_book::OpeningBook;
+
+    fn piece_count(board: &BitboardBoard, player: Player) -> usize {
+        board.iter_pieces().filter(|(_, piece)| piece.player == player).count()
+    }
+
+    #[test]
+    fn test_handicap_sfens() {
+        let (even_board, _, _) = BitboardBoard::from_fen(Handicap::Even.sfen()).unwrap();
+        for handicap in Handicap::ALL {
+            let (board, player, captured) = BitboardBoard::from_fen(handicap.sfen()).unwrap();
+            assert_eq!(player, handicap.first_player(), "{}", handicap);
+            assert!(captured.white.is_empty() && captured.black.is_empty());
+            assert_eq!(piece_count(&board, Player::Black), piece_count(&even_board, Player::Black));
+            assert_eq!(
+                piece_count(&board, Player::White) + handicap.removed_pieces().len(),
+                piece_count(&even_board, Player::White),
+                "{}",
+                handicap
+            );
+            assert_eq!(Handicap::from_sfen(handicap.sfen()), Some(handicap));
+            assert_eq!(
+                OpeningBook::determine_player_from_fen(handicap.sfen()),
+                handicap.first_player()
+            );
+        }
+        assert_eq!(Handicap::from_sfen("4k4/9/9/9/9/9/9/9/4K4 b - 1"), None);
+    }
+
+    #[test]
+    fn test_handicap_names() {
+        for handicap in Handicap::ALL {
+            assert_eq!(Handicap::from_str(handicap.as_str()), Some(handicap));
+            assert_eq!(Handicap::from_str(handicap.kif_name()), Some(handicap));
+            assert_eq!(Handicap::from_csa_pi(handicap.csa_pi()), Some(handicap));
+        }
+        assert_eq!(Handicap::from_str("Two Piece"), Some(Handicap::TwoPiece));
+        assert_eq!(Handicap::from_str("rook+lance"), Some(Handicap::RookLance));
+        assert_eq!(Handicap::from_csa_pi("PI22KA82HI"), Some(Handicap::TwoPiece));
+        assert_eq!(Handicap::from_str("queen"), None);
+    }
+}
@@ -9,6 +9,7 @@
 //!
 //! - **`core`**: Core domain types (Player, PieceType, Position, Piece, Move)
 //! - **`board`**: Board representation types (CapturedPieces, GamePhase)
+//! - **`handicap`**: Handicap (駒落ち) starting positions
 //! - **`search`**: Search-related types (configs, stats, quiescence, null-move,
 //!   LMR, IID, etc.)
 //! - **`evaluation`**: Evaluation-related types (TaperedScore, feature indices,
@@ -54,21 +55,25 @@ pub use core::{Move, Piece, PieceType, Player, Position};
 pub mod board;
 pub use board::{CapturedPieces, GamePhase};
 
+// Handicap starting positions
+pub mod handicap;
+pub use handicap::Handicap;
+
 // Search-related types
 pub mod search;
 pub use search::{
     AdaptiveTuningConfig, AdaptiveTuningStats, AdvancedReductionConfig, AdvancedReductionStrategy,
     AspirationWindowConfig, AspirationWindowPlayingStyle, AspirationWindowStats,
-    ConditionalExemptionConfig, CoreSearchMetrics, DynamicReductionFormula, EntrySource,
-    EscapeMoveConfig, EscapeMoveStats, IIDBoardState, IIDConfig, IIDDepthStrategy,
-    IIDOverheadStats, IIDPreset, IIDStats, LMRConfig, LMRPhaseStats, LMRPlayingStyle, LMRStats,
-    MoveOrderingEffectivenessStats, MoveType, NullMoveConfig, NullMovePreset,
-    NullMoveReductionStrategy, NullMoveStats, PositionClassification, PositionClassificationConfig,
-    PositionClassificationStats, PositionComplexity, PruningDecision, PruningEffectiveness,
-    PruningFrequencyStats, PruningParameters, PruningStatistics, QuiescenceConfig, QuiescenceEntry,
-    QuiescenceStats, SearchPerformanceMetrics, SearchState, TTReplacementPolicy,
-    TimeAllocationStrategy, TimeBudgetStats, TimeManagementConfig, TranspositionFlag,
-    TuningAggressiveness, WindowSizeByPositionType,
+    ConditionalExemptionConfig, CoreSearchMetrics, DropQuiescenceConfig, DropQuiescenceStats,
+    DynamicReductionFormula, EntrySource, EscapeMoveConfig, EscapeMoveStats, IIDBoardState,
+    IIDConfig, IIDDepthStrategy, IIDOverheadStats, IIDPreset, IIDStats, LMRConfig, LMRPhaseStats,
+    LMRPlayingStyle, LMRStats, MoveOrderingEffectivenessStats, MoveType, NullMoveConfig,
+    NullMovePreset, NullMoveReductionStrategy, NullMoveStats, PositionClassification,
+    PositionClassificationConfig, PositionClassificationStats, PositionComplexity, PruningDecision,
+    PruningEffectiveness, PruningFrequencyStats, PruningParameters, PruningStatistics,
+    QuiescenceConfig, QuiescenceEntry, QuiescenceStats, SearchPerformanceMetrics, SearchState,
+    TTReplacementPolicy, TimeAllocationStrategy, TimeBudgetStats, TimeManagementConfig,
+    TranspositionFlag, TuningAggressiveness, WindowSizeByPositionType,
 };
 
 // Evaluation-related types
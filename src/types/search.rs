@@ -363,6 +363,130 @@ impl QuiescenceStats {
     }
 }
 
+/// Configuration for drop moves in quiescence search
+///
+/// Drops are the main tactical resource in shogi, but every hand piece can be
+/// dropped on dozens of squares, so quiescence only looks at a small,
+/// SEE-filtered subset near the quiescence root.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DropQuiescenceConfig {
+    /// Master switch for drop-aware quiescence
+    pub enabled: bool,
+    /// Consider checking drops
+    pub enable_checking_drops: bool,
+    /// Consider drops attacking an undefended rook, bishop, dragon or horse
+    pub enable_threat_drops: bool,
+    /// Search all evasions (including drop interpositions) when in check
+    /// instead of standing pat
+    pub enable_evasions: bool,
+    /// Quiescence plies (from the quiescence root) in which drops are tried
+    pub max_drop_plies: u8,
+    /// Maximum number of drops searched per quiescence node
+    pub max_drops_per_node: usize,
+    /// Maximum number of interposing drops searched per evasion node
+    pub max_interposition_drops: usize,
+    /// Drops whose static exchange evaluation falls below this are skipped
+    pub see_threshold: i32,
+}
+
+impl Default for DropQuiescenceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            enable_checking_drops: true,
+            enable_threat_drops: true,
+            enable_evasions: true,
+            max_drop_plies: 2,
+            max_drops_per_node: 4,
+            max_interposition_drops: 3,
+            see_threshold: 0,
+        }
+    }
+}
+
+impl DropQuiescenceConfig {
+    /// Validate the configuration parameters and return any errors
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_drop_plies > 8 {
+            return Err("max_drop_plies should not exceed 8".to_string());
+        }
+        if self.max_drops_per_node > 32 {
+            return Err("max_drops_per_node should not exceed 32".to_string());
+        }
+        if self.max_interposition_drops > 16 {
+            return Err("max_interposition_drops should not exceed 16".to_string());
+        }
+        if self.see_threshold.abs() > 2000 {
+            return Err("see_threshold should be within +/-2000".to_string());
+        }
+        Ok(())
+    }
+
+    /// Whether drops should be generated at the given quiescence ply
+    pub fn drops_allowed_at(&self, quiescence_ply: u32) -> bool {
+        self.enabled
+            && (self.enable_checking_drops || self.enable_threat_drops)
+            && self.max_drops_per_node > 0
+            && quiescence_ply < u32::from(self.max_drop_plies)
+    }
+}
+
+/// Statistics for drop moves in quiescence search, kept apart from
+/// [`QuiescenceStats`] so the cost of drops can be measured on its own
+#[derive(Debug, Clone, Default)]
+pub struct DropQuiescenceStats {
+    /// Checking drops found among the candidates
+    pub checking_drops_found: u64,
+    /// Drops attacking an undefended major piece found among the candidates
+    pub threat_drops_found: u64,
+    /// Drops rejected by static exchange evaluation
+    pub see_rejected: u64,
+    /// Drops dropped because the per-node budget was exhausted
+    pub budget_rejected: u64,
+    /// Drops actually searched (including interpositions)
+    pub drops_searched: u64,
+    /// Beta cutoffs produced by a drop
+    pub drop_cutoffs: u64,
+    /// Quiescence nodes searched as check evasions
+    pub evasion_nodes: u64,
+    /// Interposing drops searched at evasion nodes
+    pub interposition_drops_searched: u64,
+    /// Evasion nodes with no legal reply
+    pub mates_found: u64,
+}
+
+impl DropQuiescenceStats {
+    /// Reset all statistics to zero
+    pub fn reset(&mut self) {
+        *self = DropQuiescenceStats::default();
+    }
+
+    /// Percentage of searched drops that produced a beta cutoff
+    pub fn cutoff_rate(&self) -> f64 {
+        if self.drops_searched == 0 {
+            return 0.0;
+        }
+        (self.drop_cutoffs as f64 / self.drops_searched as f64) * 100.0
+    }
+
+    /// Get a summary string of the statistics
+    pub fn summary(&self) -> String {
+        format!(
+            "DropQuiescenceStats: checking={}, threats={}, searched={}, cutoffs={}, \
+             see_rejected={}, budget_rejected={}, evasion_nodes={}, interpositions={}, mates={}",
+            self.checking_drops_found,
+            self.threat_drops_found,
+            self.drops_searched,
+            self.drop_cutoffs,
+            self.see_rejected,
+            self.budget_rejected,
+            self.evasion_nodes,
+            self.interposition_drops_searched,
+            self.mates_found
+        )
+    }
+}
+
 // ============================================================================
 // Null Move Pruning Types
 // ============================================================================
@@ -2410,7 +2534,8 @@ impl CoreSearchMetrics {
 // search.rs) These types are still in all.rs but should be accessible via
 // types::search::
 pub use super::all::{
-    EngineConfig, EnginePreset, ParallelOptions, TimePressure, TimePressureThresholds,
+    EngineConfig, EnginePreset, ParallelOptions, ParallelSearchMode, TimePressure,
+    TimePressureThresholds,
 };
 
 // Note: Additional search-related types (SearchMetrics, ParallelSearchMetrics,
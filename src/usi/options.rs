@@ -0,0 +1,1481 @@
+//! USI option registry
+//!
+//! Every option the engine accepts is declared once in [`usi_options`] with
+//! its name, USI type, default, range, a getter and a setter. The `usi`
+//! option list, `setoption` parsing, value validation and the error messages
+//! are all generated from that table, so exposing another configuration field
+//! means adding one entry.
+//!
+//! Setters receive an already validated value and return the `info string`
+//! text to report (one line per `\n`), or an error message. Getters read the
+//! value the engine is running with; it is what `SaveConfig` writes.
+//!
+//! Values may come from a config file (see [`super::profile`]) or from
+//! `setoption`. The engine remembers where each changed value came from and
+//! applies the precedence defaults < config file < setoption.
+
+use super::profile;
+use crate::adjudication::AdjudicationConfig;
+use crate::evaluation::pst_loader::PieceSquareTablePreset;
+use crate::search::search_engine::SearchEngine;
+use crate::search::skill::{self, SkillLevel};
+use crate::search::ReproducibleSearch;
+use crate::types::all::TimeAllocationStrategy;
+use crate::types::{DropQuiescenceConfig, EngineConfig, ParallelOptions, ParallelSearchMode};
+use crate::ShogiEngine;
+use std::sync::{OnceLock, PoisonError};
+
+/// Result of a setter: the message to report, or an error
+pub type OptionResult = Result<String, String>;
+
+/// Where an option value came from, in increasing precedence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum OptionSource {
+    Default,
+    ConfigFile,
+    SetOption,
+}
+
+impl OptionSource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::ConfigFile => "config file",
+            Self::SetOption => "setoption",
+        }
+    }
+}
+
+/// The value an option was last set to and its source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OptionSetting {
+    pub value: String,
+    pub source: OptionSource,
+}
+
+/// Options that only alias another option and are recorded under its name
+const ALIASES: &[(&str, &str)] = &[("depth", "MaxDepth")];
+
+/// USI option type with its default, range, getter and setter. Getters read
+/// the value the engine is running with, which is what profiles save.
+#[derive(Clone, Copy)]
+pub enum UsiOptionKind {
+    Check {
+        default: bool,
+        get: fn(&ShogiEngine) -> bool,
+        set: fn(&mut ShogiEngine, bool) -> OptionResult,
+    },
+    Spin {
+        default: i64,
+        min: i64,
+        max: i64,
+        get: fn(&ShogiEngine) -> i64,
+        set: fn(&mut ShogiEngine, i64) -> OptionResult,
+    },
+    Combo {
+        default: &'static str,
+        vars: &'static [&'static str],
+        get: fn(&ShogiEngine) -> String,
+        set: fn(&mut ShogiEngine, &str) -> OptionResult,
+    },
+    String {
+        default: &'static str,
+        get: fn(&ShogiEngine) -> String,
+        set: fn(&mut ShogiEngine, &str) -> OptionResult,
+    },
+    Filename {
+        default: &'static str,
+        get: fn(&ShogiEngine) -> String,
+        set: fn(&mut ShogiEngine, &str) -> OptionResult,
+    },
+    /// Buttons take no value; an optional one is passed through
+    Button {
+        set: fn(&mut ShogiEngine, Option<&str>) -> OptionResult,
+    },
+}
+
+/// A declared USI option
+#[derive(Clone, Copy)]
+pub struct UsiOption {
+    pub name: &'static str,
+    pub kind: UsiOptionKind,
+}
+
+impl UsiOption {
+    const fn new(name: &'static str, kind: UsiOptionKind) -> Self {
+        Self { name, kind }
+    }
+
+    /// The `option name ...` line sent in reply to `usi`
+    pub fn to_usi(&self) -> String {
+        let spec = match self.kind {
+            UsiOptionKind::Check { default, .. } => format!("type check default {}", default),
+            UsiOptionKind::Spin { default, min, max, .. } => {
+                format!("type spin default {} min {} max {}", default, min, max)
+            }
+            UsiOptionKind::Combo { default, vars, .. } => {
+                let vars: Vec<String> = vars.iter().map(|v| format!("var {}", v)).collect();
+                format!("type combo default {} {}", default, vars.join(" "))
+            }
+            UsiOptionKind::String { default, .. } => {
+                format!("type string default {}", usi_string_default(default))
+            }
+            UsiOptionKind::Filename { default, .. } => {
+                format!("type filename default {}", usi_string_default(default))
+            }
+            UsiOptionKind::Button { .. } => "type button".to_string(),
+        };
+        format!("option name {} {}", self.name, spec)
+    }
+
+    /// Validate `value` against the declared type and range, then apply it
+    pub fn apply(&self, engine: &mut ShogiEngine, value: Option<&str>) -> OptionResult {
+        let value = value.map(str::trim);
+        match self.kind {
+            UsiOptionKind::Button { set } => set(engine, value.filter(|v| !v.is_empty())),
+            UsiOptionKind::Check { set, .. } => match value {
+                Some("true") => set(engine, true),
+                Some("false") => set(engine, false),
+                other => Err(format!(
+                    "Invalid value '{}' for {} (expected true or false)",
+                    other.unwrap_or(""),
+                    self.name
+                )),
+            },
+            UsiOptionKind::Spin { min, max, set, .. } => {
+                match value.and_then(|v| v.parse::<i64>().ok()) {
+                    Some(v) if (min..=max).contains(&v) => set(engine, v),
+                    _ => Err(format!(
+                        "{} must be between {} and {} (got '{}')",
+                        self.name,
+                        min,
+                        max,
+                        value.unwrap_or("")
+                    )),
+                }
+            }
+            UsiOptionKind::Combo { vars, set, .. } => match value {
+                Some(v) if vars.contains(&v) => set(engine, v),
+                other => Err(format!(
+                    "{} must be one of {} (got '{}')",
+                    self.name,
+                    vars.join(", "),
+                    other.unwrap_or("")
+                )),
+            },
+            UsiOptionKind::String { set, .. } | UsiOptionKind::Filename { set, .. } => {
+                let value = value.unwrap_or("");
+                set(engine, if value == "<empty>" { "" } else { value })
+            }
+        }
+    }
+
+    /// Default value as it would be passed to `setoption`; buttons have none
+    pub fn default_value(&self) -> Option<String> {
+        match self.kind {
+            UsiOptionKind::Check { default, .. } => Some(default.to_string()),
+            UsiOptionKind::Spin { default, .. } => Some(default.to_string()),
+            UsiOptionKind::Combo { default, .. }
+            | UsiOptionKind::String { default, .. }
+            | UsiOptionKind::Filename { default, .. } => Some(default.to_string()),
+            UsiOptionKind::Button { .. } => None,
+        }
+    }
+
+    /// Name the value is recorded under; aliases share their target's entry
+    fn setting_name(&self) -> &'static str {
+        ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == self.name)
+            .map_or(self.name, |(_, name)| name)
+    }
+
+    /// Value the engine is running with, as it would be passed to
+    /// `setoption`; buttons have none
+    pub fn live_value(&self, engine: &ShogiEngine) -> Option<String> {
+        match self.kind {
+            UsiOptionKind::Check { get, .. } => Some(get(engine).to_string()),
+            UsiOptionKind::Spin { get, .. } => Some(get(engine).to_string()),
+            UsiOptionKind::Combo { get, .. }
+            | UsiOptionKind::String { get, .. }
+            | UsiOptionKind::Filename { get, .. } => Some(get(engine)),
+            UsiOptionKind::Button { .. } => None,
+        }
+    }
+
+    /// Live value and where it was last set from; buttons have none
+    pub fn current_value(&self, engine: &ShogiEngine) -> Option<OptionSetting> {
+        let value = self.live_value(engine)?;
+        let source = engine
+            .option_settings
+            .get(self.setting_name())
+            .map_or(OptionSource::Default, |setting| setting.source);
+        Some(OptionSetting { value, source })
+    }
+
+    /// Apply `value` from `source` and remember it. Replacing a value from a
+    /// lower-precedence source is reported after the setter's message.
+    pub fn set(
+        &self,
+        engine: &mut ShogiEngine,
+        value: Option<&str>,
+        source: OptionSource,
+    ) -> OptionResult {
+        let message = self.apply(engine, value)?;
+        if matches!(self.kind, UsiOptionKind::Button { .. }) {
+            return Ok(message);
+        }
+
+        let value = match (self.kind, value.map(str::trim).unwrap_or("")) {
+            (UsiOptionKind::Spin { .. }, v) => v.parse::<i64>().unwrap_or_default().to_string(),
+            (_, "<empty>") => String::new(),
+            (_, v) => v.to_string(),
+        };
+        let name = self.setting_name();
+        let previous = engine.option_settings.insert(name, OptionSetting { value, source });
+        match previous {
+            Some(previous) if previous.source < source => Ok(format!(
+                "{}\n{} from {} overrides {} value {}",
+                message,
+                name,
+                source.as_str(),
+                previous.source.as_str(),
+                previous.value
+            )),
+            _ => Ok(message),
+        }
+    }
+}
+
+/// USI has no syntax for an empty default string
+fn usi_string_default(default: &str) -> &str {
+    if default.is_empty() {
+        "<empty>"
+    } else {
+        default
+    }
+}
+
+/// All options, in the order they are listed to the GUI
+pub fn usi_options() -> &'static [UsiOption] {
+    static OPTIONS: OnceLock<Vec<UsiOption>> = OnceLock::new();
+    OPTIONS.get_or_init(build_options)
+}
+
+/// Look up an option by its exact name
+pub fn find_usi_option(name: &str) -> Option<&'static UsiOption> {
+    usi_options().iter().find(|option| option.name == name)
+}
+
+/// Handle `setoption name <id> [value <x>]`
+pub fn apply_setoption(engine: &mut ShogiEngine, parts: &[&str]) -> Vec<String> {
+    if parts.first() != Some(&"name") || parts.len() < 2 {
+        return vec!["info string error Invalid setoption command: expected 'name <id>'".into()];
+    }
+    let value_index = parts.iter().position(|&p| p == "value");
+    let name = parts[1..value_index.unwrap_or(parts.len())].join(" ");
+    let value = value_index.map(|i| parts[i + 1..].join(" "));
+
+    let Some(option) = find_usi_option(&name) else {
+        return vec![format!("info string error Unknown option: {}", name)];
+    };
+    match option.set(engine, value.as_deref(), OptionSource::SetOption) {
+        Ok(message) => message
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| format!("info string {}", line))
+            .collect(),
+        Err(err) => vec![format!("info string error {}", err)],
+    }
+}
+
+fn enabled(on: bool) -> &'static str {
+    if on {
+        "Enabled"
+    } else {
+        "Disabled"
+    }
+}
+
+/// Apply `update` to the search engine configuration, validating the result
+fn update_search_config(
+    engine: &mut ShogiEngine,
+    update: impl FnOnce(&mut EngineConfig),
+) -> Result<(), String> {
+    let mut search_engine = engine
+        .search_engine
+        .lock()
+        .map_err(|_| "search engine unavailable".to_string())?;
+    let mut config = search_engine.get_engine_config();
+    update(&mut config);
+    search_engine.update_engine_config(config)
+}
+
+fn update_drop_quiescence(
+    engine: &mut ShogiEngine,
+    update: impl FnOnce(&mut DropQuiescenceConfig),
+) -> Result<(), String> {
+    let mut search_engine = engine
+        .search_engine
+        .lock()
+        .map_err(|_| "search engine unavailable".to_string())?;
+    let mut config = search_engine.get_drop_quiescence_config().clone();
+    update(&mut config);
+    search_engine.update_drop_quiescence_config(config)
+}
+
+/// Read from the search engine, recovering the lock if a search panicked
+fn with_search_engine<T>(engine: &ShogiEngine, read: impl FnOnce(&SearchEngine) -> T) -> T {
+    read(&engine.search_engine.lock().unwrap_or_else(PoisonError::into_inner))
+}
+
+fn search_config(engine: &ShogiEngine) -> EngineConfig {
+    with_search_engine(engine, SearchEngine::get_engine_config)
+}
+
+fn drop_quiescence(engine: &ShogiEngine) -> DropQuiescenceConfig {
+    with_search_engine(engine, |search_engine| search_engine.get_drop_quiescence_config().clone())
+}
+
+/// Value last set for an option that leaves no readable state behind, such
+/// as the path a file was loaded from
+fn recorded(engine: &ShogiEngine, name: &str) -> String {
+    engine.option_settings.get(name).map(|setting| setting.value.clone()).unwrap_or_default()
+}
+
+fn pst_preset_name(engine: &ShogiEngine) -> &'static str {
+    match engine.pst_config.preset {
+        PieceSquareTablePreset::Builtin => "Builtin",
+        PieceSquareTablePreset::Default => "Default",
+        PieceSquareTablePreset::Custom => "Custom",
+    }
+}
+
+fn time_allocation_name(engine: &ShogiEngine) -> &'static str {
+    match search_config(engine).time_management.allocation_strategy {
+        TimeAllocationStrategy::Equal => "Equal",
+        TimeAllocationStrategy::Exponential => "Exponential",
+        TimeAllocationStrategy::Adaptive => "Adaptive",
+    }
+}
+
+fn update_parallel(engine: &mut ShogiEngine, update: impl FnOnce(&mut ParallelOptions)) {
+    update(&mut engine.parallel_options);
+    engine.sync_parallel_options();
+}
+
+fn set_max_depth(engine: &mut ShogiEngine, label: &str, depth: i64) -> OptionResult {
+    engine.set_max_depth(depth as u8);
+    Ok(if depth == 0 {
+        format!("Set {} to 0 (unlimited/adaptive)", label)
+    } else {
+        format!("Set {} to {}", label, depth)
+    })
+}
+
+fn set_pst_preset(engine: &mut ShogiEngine, value: &str) -> OptionResult {
+    let preset = match value {
+        "Default" => PieceSquareTablePreset::Default,
+        "Custom" => PieceSquareTablePreset::Custom,
+        _ => PieceSquareTablePreset::Builtin,
+    };
+    let previous = engine.pst_config.clone();
+    if !matches!(preset, PieceSquareTablePreset::Custom) {
+        engine.pst_config.values_path = None;
+    }
+    engine.pst_config.preset = preset;
+    engine
+        .apply_pst_config()
+        .map(|()| format!("PST preset set to {}", value))
+        .map_err(|err| {
+            engine.pst_config = previous;
+            format!("Failed to apply PST preset '{}': {}", value, err)
+        })
+}
+
+fn set_pst_path(engine: &mut ShogiEngine, path: &str) -> OptionResult {
+    let previous = engine.pst_config.clone();
+    if path.is_empty() {
+        engine.pst_config.values_path = None;
+        if matches!(engine.pst_config.preset, PieceSquareTablePreset::Custom) {
+            engine.pst_config.preset = PieceSquareTablePreset::Builtin;
+        }
+    } else {
+        engine.pst_config.values_path = Some(path.to_string());
+        engine.pst_config.preset = PieceSquareTablePreset::Custom;
+    }
+    match engine.apply_pst_config() {
+        Ok(()) if path.is_empty() => {
+            Ok("Cleared PSTPath override; using built-in tables".to_string())
+        }
+        Ok(()) => Ok(format!("Loaded PST from path '{}'", path)),
+        Err(err) => {
+            engine.pst_config = previous;
+            Err(format!("Failed to load PST from '{}': {}", path, err))
+        }
+    }
+}
+
+fn hash_file_button(engine: &mut ShogiEngine, save: bool, value: Option<&str>) -> OptionResult {
+    let path = value.map_or_else(|| engine.hash_file.clone(), str::to_string);
+    if save {
+        engine
+            .save_hash(&path)
+            .map(|count| format!("Saved {} hash entries to {}", count, path))
+            .map_err(|err| format!("Failed to save hash to {}: {}", path, err))
+    } else {
+        engine
+            .load_hash(&path)
+            .map(|count| format!("Loaded {} hash entries from {}", count, path))
+            .map_err(|err| format!("Failed to load hash from {}: {}", path, err))
+    }
+}
+
+fn build_options() -> Vec<UsiOption> {
+    use UsiOptionKind::*;
+
+    // Defaults are whatever a freshly built search engine runs with
+    let search_engine = SearchEngine::new(None, 1);
+    let config = search_engine.get_engine_config();
+    let drops = search_engine.get_drop_quiescence_config().clone();
+    let parallel = ParallelOptions::default();
+    let threads = num_cpus::get().clamp(1, 32) as i64;
+
+    vec![
+        // Hash and threads
+        UsiOption::new(
+            "USI_Hash",
+            Spin {
+                default: 16,
+                min: 1,
+                max: 1024,
+                get: |engine| engine.hash_size_mb as i64,
+                set: |engine, size| {
+                    let size = size as usize;
+                    if let Ok(mut search_engine) = engine.search_engine.lock() {
+                        *search_engine = SearchEngine::new(Some(engine.stop_flag.clone()), size);
+                        engine.hash_size_mb = size;
+                        engine.parallel_options.hash_size_mb = size.min(512);
+                        search_engine.set_parallel_options(engine.parallel_options.clone());
+                    }
+                    engine.opening_book_prefilled = false;
+                    engine.maybe_prefill_opening_book();
+                    Ok(format!("Set USI_Hash to {} MB", size))
+                },
+            },
+        ),
+        UsiOption::new(
+            "USI_Threads",
+            Spin {
+                default: threads,
+                min: 1,
+                max: 32,
+                get: |engine| engine.thread_count as i64,
+                set: |engine, threads| {
+                    engine.thread_count = threads as usize;
+                    if engine.thread_count <= 1 {
+                        engine.parallel_options.enable_parallel = false;
+                    }
+                    engine.sync_parallel_options();
+                    engine.save_prefs();
+                    Ok(format!("Set USI_Threads to {}", engine.thread_count))
+                },
+            },
+        ),
+        UsiOption::new(
+            "HashFile",
+            String {
+                default: crate::DEFAULT_HASH_FILE,
+                get: |engine| engine.hash_file.clone(),
+                set: |engine, path| {
+                    engine.hash_file = path.to_string();
+                    Ok(format!("Set HashFile to {}", path))
+                },
+            },
+        ),
+        UsiOption::new(
+            "SaveHash",
+            Button { set: |engine, value| hash_file_button(engine, true, value) },
+        ),
+        UsiOption::new(
+            "LoadHash",
+            Button { set: |engine, value| hash_file_button(engine, false, value) },
+        ),
+        // Engine profiles
+        UsiOption::new(
+            "EngineConfigFile",
+            Filename {
+                default: "",
+                get: |engine| recorded(engine, "EngineConfigFile"),
+                set: |engine, path| {
+                    if path.is_empty() {
+                        Ok("Cleared EngineConfigFile".to_string())
+                    } else {
+                        profile::load_profile(engine, path)
+                    }
+                },
+            },
+        ),
+        UsiOption::new(
+            "SaveConfig",
+            Button {
+                set: |engine, value| {
+                    let path = match value {
+                        Some(path) => path.to_string(),
+                        None => engine
+                            .option_settings
+                            .get("EngineConfigFile")
+                            .map(|setting| setting.value.clone())
+                            .unwrap_or_default(),
+                    };
+                    if path.is_empty() {
+                        return Err(
+                            "SaveConfig needs a path: set EngineConfigFile or pass a value"
+                                .to_string(),
+                        );
+                    }
+                    let count = profile::save_profile(engine, &path)?;
+                    Ok(format!("Saved {} options to {}", count, path))
+                },
+            },
+        ),
+        // Depth: MaxDepth, and the legacy `depth` alias
+        UsiOption::new(
+            "MaxDepth",
+            Spin {
+                default: 0,
+                min: 0,
+                max: 100,
+                get: |engine| i64::from(engine.depth),
+                set: |engine, depth| set_max_depth(engine, "MaxDepth", depth),
+            },
+        ),
+        UsiOption::new(
+            "depth",
+            Spin {
+                default: 0,
+                min: 0,
+                max: 100,
+                get: |engine| i64::from(engine.depth),
+                set: |engine, depth| set_max_depth(engine, "depth", depth),
+            },
+        ),
+        // Strength limiting
+        UsiOption::new(
+            "SkillLevel",
+            Spin {
+                default: skill::MAX_LEVEL as i64,
+                min: 0,
+                max: skill::MAX_LEVEL as i64,
+                get: |engine| i64::from(engine.skill().level),
+                set: |engine, level| {
+                    let skill = SkillLevel { level: level as u8, ..engine.skill() };
+                    engine.set_skill(skill);
+                    Ok(format!("Set SkillLevel to {}", level))
+                },
+            },
+        ),
+        UsiOption::new(
+            "LimitStrength",
+            Check {
+                default: false,
+                get: |engine| engine.skill().limit_strength,
+                set: |engine, on| {
+                    let skill = SkillLevel { limit_strength: on, ..engine.skill() };
+                    engine.set_skill(skill);
+                    Ok(format!("{} strength limit (Elo {})", enabled(on), skill.elo))
+                },
+            },
+        ),
+        UsiOption::new(
+            "Elo",
+            Spin {
+                default: skill::MAX_ELO as i64,
+                min: skill::MIN_ELO as i64,
+                max: skill::MAX_ELO as i64,
+                get: |engine| i64::from(engine.skill().elo),
+                set: |engine, elo| {
+                    let skill = SkillLevel { elo: elo as u32, ..engine.skill() };
+                    engine.set_skill(skill);
+                    Ok(format!("Set Elo to {}", elo))
+                },
+            },
+        ),
+        // Reproducible search
+        UsiOption::new(
+            "Reproducible",
+            Check {
+                default: false,
+                get: |engine| engine.reproducible().enabled,
+                set: |engine, on| {
+                    let reproducible = ReproducibleSearch { enabled: on, ..engine.reproducible() };
+                    engine.set_reproducible(reproducible);
+                    Ok(format!("{} reproducible search", enabled(on)))
+                },
+            },
+        ),
+        UsiOption::new(
+            "ReproducibleNodes",
+            Spin {
+                default: 0,
+                min: 0,
+                max: 1_000_000_000_000,
+                get: |engine| engine.reproducible().node_limit.unwrap_or(0) as i64,
+                set: |engine, nodes| {
+                    let node_limit = (nodes > 0).then_some(nodes as u64);
+                    let reproducible = ReproducibleSearch { node_limit, ..engine.reproducible() };
+                    engine.set_reproducible(reproducible);
+                    Ok(format!("Set ReproducibleNodes to {} (0 = depth limit only)", nodes))
+                },
+            },
+        ),
+        UsiOption::new(
+            "RandomSeed",
+            Spin {
+                default: 0,
+                min: 0,
+                max: u32::MAX as i64,
+                get: |engine| engine.reproducible().seed as i64,
+                set: |engine, seed| {
+                    let reproducible =
+                        ReproducibleSearch { seed: seed as u64, ..engine.reproducible() };
+                    engine.set_reproducible(reproducible);
+                    Ok(format!("Set RandomSeed to {}", seed))
+                },
+            },
+        ),
+        // Game management
+        UsiOption::new(
+            "ResignValue",
+            Spin {
+                default: 0,
+                min: 0,
+                max: 100_000,
+                get: |engine| i64::from(engine.adjudication().resign_score),
+                set: |engine, value| {
+                    let adjudication = AdjudicationConfig {
+                        resign_score: value as i32,
+                        ..engine.adjudication().clone()
+                    };
+                    engine.set_adjudication(adjudication);
+                    Ok(format!("Set ResignValue to {} (0 = never resign)", value))
+                },
+            },
+        ),
+        UsiOption::new(
+            "ResignMoves",
+            Spin {
+                default: 3,
+                min: 1,
+                max: 100,
+                get: |engine| i64::from(engine.adjudication().resign_moves),
+                set: |engine, moves| {
+                    let adjudication = AdjudicationConfig {
+                        resign_moves: moves as u32,
+                        ..engine.adjudication().clone()
+                    };
+                    engine.set_adjudication(adjudication);
+                    Ok(format!("Set ResignMoves to {}", moves))
+                },
+            },
+        ),
+        UsiOption::new(
+            "MaxMovesToDraw",
+            Spin {
+                default: 0,
+                min: 0,
+                max: 100_000,
+                get: |engine| i64::from(engine.adjudication().max_plies),
+                set: |engine, plies| {
+                    let adjudication = AdjudicationConfig {
+                        max_plies: plies as u32,
+                        ..engine.adjudication().clone()
+                    };
+                    engine.set_adjudication(adjudication);
+                    Ok(format!("Set MaxMovesToDraw to {} (0 = no limit)", plies))
+                },
+            },
+        ),
+        // Evaluation
+        UsiOption::new(
+            "PSTPreset",
+            Combo {
+                default: "Builtin",
+                vars: &["Builtin", "Default", "Custom"],
+                get: |engine| pst_preset_name(engine).to_string(),
+                set: set_pst_preset,
+            },
+        ),
+        UsiOption::new(
+            "PSTPath",
+            Filename {
+                default: "",
+                get: |engine| engine.pst_config.values_path.clone().unwrap_or_default(),
+                set: set_pst_path,
+            },
+        ),
+        UsiOption::new(
+            "EvalFile",
+            Filename {
+                default: "",
+                get: |engine| recorded(engine, "EvalFile"),
+                set: |engine, path| {
+                    engine
+                        .load_eval_file(path)
+                        .map_err(|err| format!("Failed to load NNUE from '{}': {}", path, err))?;
+                    if path.is_empty() {
+                        Ok("Unloaded NNUE network; using classical evaluation".to_string())
+                    } else {
+                        Ok(format!("Loaded NNUE network from '{}'", path))
+                    }
+                },
+            },
+        ),
+        UsiOption::new(
+            "KingDangerFile",
+            Filename {
+                default: "",
+                get: |engine| recorded(engine, "KingDangerFile"),
+                set: |engine, path| {
+                    engine.load_king_danger_file(path).map_err(|err| {
+                        format!("Failed to load king danger from '{}': {}", path, err)
+                    })?;
+                    if path.is_empty() {
+                        Ok("Using the built-in king danger table".to_string())
+                    } else {
+                        Ok(format!("Loaded king danger from '{}'", path))
+                    }
+                },
+            },
+        ),
+        UsiOption::new(
+            "UseNNUE",
+            Check {
+                default: true,
+                get: |engine| with_search_engine(engine, |s| s.get_evaluator().use_nnue()),
+                set: |engine, on| {
+                    engine.set_use_nnue(on)?;
+                    Ok(format!("{} NNUE evaluation", enabled(on)))
+                },
+            },
+        ),
+        UsiOption::new(
+            "EnableTablebase",
+            Check {
+                default: true,
+                get: |engine| engine.is_tablebase_enabled(),
+                set: |engine, on| {
+                    if on {
+                        engine.enable_tablebase();
+                    } else {
+                        engine.disable_tablebase();
+                    }
+                    Ok(format!("{} tablebase", enabled(on)))
+                },
+            },
+        ),
+        // Opening book
+        UsiOption::new(
+            "PrefillOpeningBook",
+            Check {
+                default: config.prefill_opening_book,
+                get: |engine| search_config(engine).prefill_opening_book,
+                set: |engine, on| {
+                    update_search_config(engine, |c| c.prefill_opening_book = on)?;
+                    if on {
+                        engine.opening_book_prefilled = false;
+                        engine.maybe_prefill_opening_book();
+                    }
+                    Ok(format!("{} opening book prefill", enabled(on)))
+                },
+            },
+        ),
+        UsiOption::new(
+            "OpeningBookPrefillDepth",
+            Spin {
+                default: config.opening_book_prefill_depth as i64,
+                min: 1,
+                max: 64,
+                get: |engine| i64::from(search_config(engine).opening_book_prefill_depth),
+                set: |engine, depth| {
+                    update_search_config(engine, |c| c.opening_book_prefill_depth = depth as u8)?;
+                    engine.opening_book_prefilled = false;
+                    engine.maybe_prefill_opening_book();
+                    Ok(format!("Set opening book prefill depth to {}", depth))
+                },
+            },
+        ),
+        // Quiescence
+        UsiOption::new(
+            "QuiescenceDepth",
+            Spin {
+                default: config.quiescence.max_depth as i64,
+                min: 1,
+                max: 20,
+                get: |engine| i64::from(search_config(engine).quiescence.max_depth),
+                set: |engine, depth| {
+                    update_search_config(engine, |c| c.quiescence.max_depth = depth as u8)?;
+                    Ok(format!("Set quiescence max_depth to {}", depth))
+                },
+            },
+        ),
+        UsiOption::new(
+            "QuiescenceDeltaPruning",
+            Check {
+                default: config.quiescence.enable_delta_pruning,
+                get: |engine| search_config(engine).quiescence.enable_delta_pruning,
+                set: |engine, on| {
+                    update_search_config(engine, |c| c.quiescence.enable_delta_pruning = on)?;
+                    Ok(format!("{} quiescence delta pruning", enabled(on)))
+                },
+            },
+        ),
+        UsiOption::new(
+            "QuiescenceFutilityPruning",
+            Check {
+                default: config.quiescence.enable_futility_pruning,
+                get: |engine| search_config(engine).quiescence.enable_futility_pruning,
+                set: |engine, on| {
+                    update_search_config(engine, |c| c.quiescence.enable_futility_pruning = on)?;
+                    Ok(format!("{} quiescence futility pruning", enabled(on)))
+                },
+            },
+        ),
+        UsiOption::new(
+            "DropQuiescence",
+            Check {
+                default: drops.enabled,
+                get: |engine| drop_quiescence(engine).enabled,
+                set: |engine, on| {
+                    update_drop_quiescence(engine, |c| c.enabled = on)?;
+                    Ok(format!("{} drop-aware quiescence", enabled(on)))
+                },
+            },
+        ),
+        UsiOption::new(
+            "DropQuiescenceMaxDrops",
+            Spin {
+                default: drops.max_drops_per_node as i64,
+                min: 0,
+                max: 32,
+                get: |engine| drop_quiescence(engine).max_drops_per_node as i64,
+                set: |engine, count| {
+                    update_drop_quiescence(engine, |c| c.max_drops_per_node = count as usize)?;
+                    Ok(format!("Set quiescence drops per node to {}", count))
+                },
+            },
+        ),
+        UsiOption::new(
+            "DropQuiescenceSEEThreshold",
+            Spin {
+                default: drops.see_threshold as i64,
+                min: -2000,
+                max: 2000,
+                get: |engine| i64::from(drop_quiescence(engine).see_threshold),
+                set: |engine, threshold| {
+                    update_drop_quiescence(engine, |c| c.see_threshold = threshold as i32)?;
+                    Ok(format!("Set quiescence drop SEE threshold to {}", threshold))
+                },
+            },
+        ),
+        // Null move
+        UsiOption::new(
+            "EnableNullMove",
+            Check {
+                default: config.null_move.enabled,
+                get: |engine| search_config(engine).null_move.enabled,
+                set: |engine, on| {
+                    update_search_config(engine, |c| c.null_move.enabled = on)?;
+                    Ok(format!("{} null-move pruning", enabled(on)))
+                },
+            },
+        ),
+        UsiOption::new(
+            "NullMoveMinDepth",
+            Spin {
+                default: config.null_move.min_depth as i64,
+                min: 1,
+                max: 10,
+                get: |engine| i64::from(search_config(engine).null_move.min_depth),
+                set: |engine, depth| {
+                    update_search_config(engine, |c| c.null_move.min_depth = depth as u8)?;
+                    Ok(format!("Set null-move min_depth to {}", depth))
+                },
+            },
+        ),
+        UsiOption::new(
+            "NullMoveReduction",
+            Spin {
+                default: config.null_move.reduction_factor as i64,
+                min: 1,
+                max: 5,
+                get: |engine| i64::from(search_config(engine).null_move.reduction_factor),
+                set: |engine, reduction| {
+                    update_search_config(engine, |c| {
+                        c.null_move.reduction_factor = reduction as u8
+                    })?;
+                    Ok(format!("Set null-move reduction to {}", reduction))
+                },
+            },
+        ),
+        // Late move reductions
+        UsiOption::new(
+            "EnableLMR",
+            Check {
+                default: config.lmr.enabled,
+                get: |engine| search_config(engine).lmr.enabled,
+                set: |engine, on| {
+                    update_search_config(engine, |c| c.lmr.enabled = on)?;
+                    Ok(format!("{} late move reduction", enabled(on)))
+                },
+            },
+        ),
+        UsiOption::new(
+            "LMRMinDepth",
+            Spin {
+                default: config.lmr.min_depth as i64,
+                min: 1,
+                max: 15,
+                get: |engine| i64::from(search_config(engine).lmr.min_depth),
+                set: |engine, depth| {
+                    update_search_config(engine, |c| c.lmr.min_depth = depth as u8)?;
+                    Ok(format!("Set LMR min_depth to {}", depth))
+                },
+            },
+        ),
+        UsiOption::new(
+            "LMRMinMoveIndex",
+            Spin {
+                default: config.lmr.min_move_index as i64,
+                min: 1,
+                max: 20,
+                get: |engine| i64::from(search_config(engine).lmr.min_move_index),
+                set: |engine, index| {
+                    update_search_config(engine, |c| c.lmr.min_move_index = index as u8)?;
+                    Ok(format!("Set LMR min_move_index to {}", index))
+                },
+            },
+        ),
+        UsiOption::new(
+            "LMRMaxReduction",
+            Spin {
+                default: config.lmr.max_reduction as i64,
+                min: 1,
+                max: 8,
+                get: |engine| i64::from(search_config(engine).lmr.max_reduction),
+                set: |engine, reduction| {
+                    update_search_config(engine, |c| c.lmr.max_reduction = reduction as u8)?;
+                    Ok(format!("Set LMR max_reduction to {}", reduction))
+                },
+            },
+        ),
+        // Internal iterative deepening
+        UsiOption::new(
+            "EnableIID",
+            Check {
+                default: config.iid.enabled,
+                get: |engine| search_config(engine).iid.enabled,
+                set: |engine, on| {
+                    update_search_config(engine, |c| c.iid.enabled = on)?;
+                    Ok(format!("{} internal iterative deepening", enabled(on)))
+                },
+            },
+        ),
+        UsiOption::new(
+            "IIDMinDepth",
+            Spin {
+                default: config.iid.min_depth as i64,
+                min: 2,
+                max: 15,
+                get: |engine| i64::from(search_config(engine).iid.min_depth),
+                set: |engine, depth| {
+                    update_search_config(engine, |c| c.iid.min_depth = depth as u8)?;
+                    Ok(format!("Set IID min_depth to {}", depth))
+                },
+            },
+        ),
+        // Aspiration windows
+        UsiOption::new(
+            "EnableAspirationWindows",
+            Check {
+                default: config.aspiration_windows.enabled,
+                get: |engine| search_config(engine).aspiration_windows.enabled,
+                set: |engine, on| {
+                    update_search_config(engine, |c| c.aspiration_windows.enabled = on)?;
+                    Ok(format!("{} aspiration windows", enabled(on)))
+                },
+            },
+        ),
+        UsiOption::new(
+            "AspirationWindowSize",
+            Spin {
+                default: config.aspiration_windows.base_window_size as i64,
+                min: 10,
+                max: 500,
+                get: |engine| i64::from(search_config(engine).aspiration_windows.base_window_size),
+                set: |engine, size| {
+                    update_search_config(engine, |c| {
+                        c.aspiration_windows.base_window_size = size as i32
+                    })?;
+                    Ok(format!("Set aspiration window size to {}", size))
+                },
+            },
+        ),
+        UsiOption::new(
+            "EnablePositionTypeTracking",
+            Check {
+                default: config.aspiration_windows.enable_position_type_tracking,
+                get: |engine| {
+                    search_config(engine).aspiration_windows.enable_position_type_tracking
+                },
+                set: |engine, on| {
+                    update_search_config(engine, |c| {
+                        c.aspiration_windows.enable_position_type_tracking = on
+                    })?;
+                    Ok(format!("{} position type tracking", enabled(on)))
+                },
+            },
+        ),
+        // Time management
+        UsiOption::new(
+            "TimeCheckFrequency",
+            Spin {
+                default: config.time_management.time_check_frequency as i64,
+                min: 1,
+                max: 100_000,
+                get: |engine| i64::from(search_config(engine).time_management.time_check_frequency),
+                set: |engine, frequency| {
+                    update_search_config(engine, |c| {
+                        c.time_management.time_check_frequency = frequency as u32
+                    })?;
+                    Ok(format!("Set time check frequency to {} nodes", frequency))
+                },
+            },
+        ),
+        UsiOption::new(
+            "TimeSafetyMargin",
+            Spin {
+                default: config.time_management.absolute_safety_margin_ms as i64,
+                min: 0,
+                max: 10_000,
+                get: |engine| {
+                    i64::from(search_config(engine).time_management.absolute_safety_margin_ms)
+                },
+                set: |engine, margin| {
+                    update_search_config(engine, |c| {
+                        c.time_management.absolute_safety_margin_ms = margin as u32
+                    })?;
+                    Ok(format!("Set time safety margin to {}ms", margin))
+                },
+            },
+        ),
+        UsiOption::new(
+            "NetworkDelay",
+            Spin {
+                default: crate::DEFAULT_NETWORK_DELAY_MS as i64,
+                min: 0,
+                max: 10_000,
+                get: |engine| i64::from(engine.network_delay_ms),
+                set: |engine, delay| {
+                    engine.set_network_delay(delay as u32);
+                    Ok(format!("Set NetworkDelay to {}ms", delay))
+                },
+            },
+        ),
+        UsiOption::new(
+            "TimeAllocationStrategy",
+            Combo {
+                default: "Adaptive",
+                vars: &["Equal", "Exponential", "Adaptive"],
+                get: |engine| time_allocation_name(engine).to_string(),
+                set: |engine, value| {
+                    let strategy = match value {
+                        "Equal" => TimeAllocationStrategy::Equal,
+                        "Exponential" => TimeAllocationStrategy::Exponential,
+                        _ => TimeAllocationStrategy::Adaptive,
+                    };
+                    update_search_config(engine, |c| {
+                        c.time_management.allocation_strategy = strategy
+                    })?;
+                    Ok(format!("Set time allocation strategy to {}", value))
+                },
+            },
+        ),
+        UsiOption::new(
+            "EnableTimeBudget",
+            Check {
+                default: config.time_management.enable_time_budget,
+                get: |engine| search_config(engine).time_management.enable_time_budget,
+                set: |engine, on| {
+                    update_search_config(engine, |c| c.time_management.enable_time_budget = on)?;
+                    Ok(format!("{} time budget allocation", enabled(on)))
+                },
+            },
+        ),
+        UsiOption::new(
+            "EnableCheckOptimization",
+            Check {
+                default: config.time_management.enable_check_optimization,
+                get: |engine| search_config(engine).time_management.enable_check_optimization,
+                set: |engine, on| {
+                    update_search_config(engine, |c| {
+                        c.time_management.enable_check_optimization = on
+                    })?;
+                    Ok(format!("{} check position optimization", enabled(on)))
+                },
+            },
+        ),
+        // Parallel search
+        UsiOption::new(
+            "ParallelEnable",
+            Check {
+                default: threads > 1,
+                get: |engine| engine.parallel_options.enable_parallel,
+                set: |engine, on| {
+                    update_parallel(engine, |p| p.enable_parallel = on);
+                    Ok(format!("{} parallel search", enabled(on)))
+                },
+            },
+        ),
+        UsiOption::new(
+            "ParallelHash",
+            Spin {
+                default: 16,
+                min: 1,
+                max: 512,
+                get: |engine| engine.parallel_options.hash_size_mb as i64,
+                set: |engine, size| {
+                    update_parallel(engine, |p| p.hash_size_mb = size as usize);
+                    Ok(format!("Set ParallelHash to {} MB", size))
+                },
+            },
+        ),
+        UsiOption::new(
+            "ParallelMinDepth",
+            Spin {
+                default: parallel.min_depth_parallel as i64,
+                min: 0,
+                max: 32,
+                get: |engine| i64::from(engine.parallel_options.min_depth_parallel),
+                set: |engine, depth| {
+                    update_parallel(engine, |p| p.min_depth_parallel = depth as u8);
+                    Ok(format!("Set ParallelMinDepth to {}", depth))
+                },
+            },
+        ),
+        UsiOption::new(
+            "ParallelMetrics",
+            Check {
+                default: parallel.enable_metrics,
+                get: |engine| engine.parallel_options.enable_metrics,
+                set: |engine, on| {
+                    update_parallel(engine, |p| p.enable_metrics = on);
+                    Ok(format!("{} parallel work metrics", enabled(on)))
+                },
+            },
+        ),
+        UsiOption::new(
+            "ParallelMode",
+            Combo {
+                default: parallel.search_mode.as_str(),
+                vars: &["YBWC", "LazySMP"],
+                get: |engine| engine.parallel_options.search_mode.as_str().to_string(),
+                set: |engine, value| {
+                    let mode = ParallelSearchMode::from_str(value)
+                        .ok_or_else(|| format!("Invalid ParallelMode '{}'", value))?;
+                    update_parallel(engine, |p| p.search_mode = mode);
+                    Ok(format!("Set ParallelMode to {}", mode.as_str()))
+                },
+            },
+        ),
+        UsiOption::new(
+            "YBWCEnable",
+            Check {
+                default: parallel.ybwc_enabled,
+                get: |engine| engine.parallel_options.ybwc_enabled,
+                set: |engine, on| {
+                    update_parallel(engine, |p| p.ybwc_enabled = on);
+                    Ok(format!("{} YBWC", enabled(on)))
+                },
+            },
+        ),
+        UsiOption::new(
+            "YBWCMinDepth",
+            Spin {
+                default: parallel.ybwc_min_depth as i64,
+                min: 0,
+                max: 32,
+                get: |engine| i64::from(engine.parallel_options.ybwc_min_depth),
+                set: |engine, depth| {
+                    update_parallel(engine, |p| p.ybwc_min_depth = depth as u8);
+                    Ok(format!("Set YBWCMinDepth to {}", depth))
+                },
+            },
+        ),
+        UsiOption::new(
+            "YBWCMinBranch",
+            Spin {
+                default: parallel.ybwc_min_branch as i64,
+                min: 1,
+                max: 256,
+                get: |engine| engine.parallel_options.ybwc_min_branch as i64,
+                set: |engine, branch| {
+                    update_parallel(engine, |p| p.ybwc_min_branch = branch as usize);
+                    Ok(format!("Set YBWCMinBranch to {}", branch))
+                },
+            },
+        ),
+        UsiOption::new(
+            "YBWCMaxSiblings",
+            Spin {
+                default: parallel.ybwc_max_siblings as i64,
+                min: 1,
+                max: 256,
+                get: |engine| engine.parallel_options.ybwc_max_siblings as i64,
+                set: |engine, max| {
+                    update_parallel(engine, |p| p.ybwc_max_siblings = max as usize);
+                    Ok(format!("Set YBWCMaxSiblings to {}", max))
+                },
+            },
+        ),
+        UsiOption::new(
+            "YBWCScalingShallow",
+            Spin {
+                default: parallel.ybwc_shallow_divisor as i64,
+                min: 1,
+                max: 32,
+                get: |engine| engine.parallel_options.ybwc_shallow_divisor as i64,
+                set: |engine, divisor| {
+                    update_parallel(engine, |p| p.ybwc_shallow_divisor = divisor as usize);
+                    Ok(format!("Set YBWCScalingShallow to {}", divisor))
+                },
+            },
+        ),
+        UsiOption::new(
+            "YBWCScalingMid",
+            Spin {
+                default: parallel.ybwc_mid_divisor as i64,
+                min: 1,
+                max: 32,
+                get: |engine| engine.parallel_options.ybwc_mid_divisor as i64,
+                set: |engine, divisor| {
+                    update_parallel(engine, |p| p.ybwc_mid_divisor = divisor as usize);
+                    Ok(format!("Set YBWCScalingMid to {}", divisor))
+                },
+            },
+        ),
+        UsiOption::new(
+            "YBWCScalingDeep",
+            Spin {
+                default: parallel.ybwc_deep_divisor as i64,
+                min: 1,
+                max: 32,
+                get: |engine| engine.parallel_options.ybwc_deep_divisor as i64,
+                set: |engine, divisor| {
+                    update_parallel(engine, |p| p.ybwc_deep_divisor = divisor as usize);
+                    Ok(format!("Set YBWCScalingDeep to {}", divisor))
+                },
+            },
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_option_names_are_unique() {
+        let mut names = HashSet::new();
+        for option in usi_options() {
+            assert!(names.insert(option.name), "duplicate option {}", option.name);
+        }
+    }
+
+    #[test]
+    fn test_usi_lines() {
+        let line = |name| find_usi_option(name).unwrap().to_usi();
+        assert_eq!(line("MaxDepth"), "option name MaxDepth type spin default 0 min 0 max 100");
+        assert_eq!(line("SaveHash"), "option name SaveHash type button");
+        assert_eq!(line("PSTPath"), "option name PSTPath type filename default <empty>");
+        assert_eq!(
+            line("TimeAllocationStrategy"),
+            "option name TimeAllocationStrategy type combo default Adaptive var Equal var \
+             Exponential var Adaptive"
+        );
+    }
+
+    #[test]
+    fn test_declared_defaults_match_engine() {
+        let engine = ShogiEngine::new();
+        let search_engine = engine.search_engine.lock().unwrap();
+        let config = search_engine.get_engine_config();
+        let spin = |name| match find_usi_option(name).unwrap().kind {
+            UsiOptionKind::Spin { default, .. } => default,
+            _ => panic!("{} is not a spin option", name),
+        };
+        let check = |name| match find_usi_option(name).unwrap().kind {
+            UsiOptionKind::Check { default, .. } => default,
+            _ => panic!("{} is not a check option", name),
+        };
+        assert_eq!(spin("QuiescenceDepth"), config.quiescence.max_depth as i64);
+        assert_eq!(spin("NullMoveMinDepth"), config.null_move.min_depth as i64);
+        assert_eq!(spin("LMRMinDepth"), config.lmr.min_depth as i64);
+        assert_eq!(spin("IIDMinDepth"), config.iid.min_depth as i64);
+        assert_eq!(spin("AspirationWindowSize"), config.aspiration_windows.base_window_size as i64);
+        assert_eq!(spin("TimeCheckFrequency"), config.time_management.time_check_frequency as i64);
+        assert_eq!(check("EnableNullMove"), config.null_move.enabled);
+        assert_eq!(check("EnableLMR"), config.lmr.enabled);
+        assert_eq!(check("EnableIID"), config.iid.enabled);
+        assert_eq!(check("PrefillOpeningBook"), config.prefill_opening_book);
+        assert_eq!(check("EnableTablebase"), engine.is_tablebase_enabled());
+        assert_eq!(spin("ParallelHash"), engine.parallel_options.hash_size_mb as i64);
+    }
+
+    #[test]
+    fn test_fresh_engine_runs_with_declared_defaults() {
+        let prefs = tempfile::tempdir().unwrap();
+        let engine = ShogiEngine::with_prefs_path(prefs.path().join("engine_prefs.json"));
+        for option in usi_options() {
+            assert_eq!(option.live_value(&engine), option.default_value(), "{}", option.name);
+        }
+    }
+
+    #[test]
+    fn test_setoption_dispatch_and_validation() {
+        let mut engine = ShogiEngine::new();
+        assert_eq!(
+            apply_setoption(&mut engine, &["name", "NullMoveMinDepth", "value", "4"]),
+            vec!["info string Set null-move min_depth to 4"]
+        );
+        let config = engine.search_engine.lock().unwrap().get_engine_config();
+        assert_eq!(config.null_move.min_depth, 4);
+
+        // Later options must not reset earlier ones
+        apply_setoption(&mut engine, &["name", "EnableLMR", "value", "false"]);
+        apply_setoption(&mut engine, &["name", "IIDMinDepth", "value", "6"]);
+        let config = engine.search_engine.lock().unwrap().get_engine_config();
+        assert_eq!(config.null_move.min_depth, 4);
+        assert!(!config.lmr.enabled);
+        assert_eq!(config.iid.min_depth, 6);
+
+        assert_eq!(
+            apply_setoption(&mut engine, &["name", "NullMoveMinDepth", "value", "11"]),
+            vec!["info string error NullMoveMinDepth must be between 1 and 10 (got '11')"]
+        );
+        assert_eq!(
+            apply_setoption(&mut engine, &["name", "EnableLMR", "value", "yes"]),
+            vec!["info string error Invalid value 'yes' for EnableLMR (expected true or false)"]
+        );
+        assert_eq!(
+            apply_setoption(&mut engine, &["name", "ParallelMode", "value", "Other"]),
+            vec!["info string error ParallelMode must be one of YBWC, LazySMP (got 'Other')"]
+        );
+        assert_eq!(
+            apply_setoption(&mut engine, &["name", "NoSuchOption", "value", "1"]),
+            vec!["info string error Unknown option: NoSuchOption"]
+        );
+
+        apply_setoption(&mut engine, &["name", "HashFile", "value", "my", "analysis.hash"]);
+        assert_eq!(engine.hash_file, "my analysis.hash");
+        apply_setoption(&mut engine, &["name", "MaxDepth", "value", "7"]);
+        assert_eq!(engine.depth, 7);
+    }
+
+    #[test]
+    fn test_strength_options() {
+        let mut engine = ShogiEngine::new();
+        assert!(!engine.skill().is_limited());
+        apply_setoption(&mut engine, &["name", "SkillLevel", "value", "5"]);
+        assert_eq!(engine.skill().expected_elo(), SkillLevel::elo_for_level(5));
+
+        // LimitStrength switches from the level to the target Elo
+        apply_setoption(&mut engine, &["name", "Elo", "value", "1650"]);
+        assert_eq!(engine.skill().expected_elo(), SkillLevel::elo_for_level(5));
+        apply_setoption(&mut engine, &["name", "LimitStrength", "value", "true"]);
+        assert_eq!(engine.skill().expected_elo(), 1650);
+        assert!(engine.skill().is_limited());
+        assert_eq!(
+            apply_setoption(&mut engine, &["name", "Elo", "value", "100"]),
+            vec!["info string error Elo must be between 800 and 2800 (got '100')"]
+        );
+    }
+
+    #[test]
+    fn test_game_management_options() {
+        let mut engine = ShogiEngine::new();
+        assert_eq!(engine.adjudication().resign_score, 0);
+        assert_eq!(engine.adjudication().max_plies, 0);
+        apply_setoption(&mut engine, &["name", "ResignValue", "value", "2500"]);
+        apply_setoption(&mut engine, &["name", "ResignMoves", "value", "4"]);
+        apply_setoption(&mut engine, &["name", "MaxMovesToDraw", "value", "256"]);
+        let adjudication = engine.adjudication();
+        assert_eq!(
+            (adjudication.resign_score, adjudication.resign_moves, adjudication.max_plies),
+            (2500, 4, 256)
+        );
+        assert!(!adjudication.should_resign(-2600, 3));
+        assert!(adjudication.should_resign(-2600, 4));
+    }
+
+    #[test]
+    fn test_reproducible_options() {
+        let mut engine = ShogiEngine::new();
+        assert!(!engine.reproducible().enabled);
+        apply_setoption(&mut engine, &["name", "ReproducibleNodes", "value", "50000"]);
+        apply_setoption(&mut engine, &["name", "RandomSeed", "value", "7"]);
+        assert!(!engine.reproducible().enabled);
+        apply_setoption(&mut engine, &["name", "Reproducible", "value", "true"]);
+        assert_eq!(engine.reproducible(), ReproducibleSearch::new(Some(50_000)).with_seed(7));
+        apply_setoption(&mut engine, &["name", "ReproducibleNodes", "value", "0"]);
+        assert_eq!(engine.reproducible().node_limit, None);
+    }
+
+    #[test]
+    fn test_nnue_options() {
+        use crate::evaluation::nnue::NnueNetwork;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("small.nnue");
+        NnueNetwork::random(8, 4, 4, 1).save(&path).unwrap();
+        let path = path.to_str().unwrap();
+
+        let mut engine = ShogiEngine::new();
+        assert!(!engine.is_using_nnue());
+        apply_setoption(&mut engine, &["name", "EvalFile", "value", path]);
+        assert!(engine.is_using_nnue());
+        apply_setoption(&mut engine, &["name", "UseNNUE", "value", "false"]);
+        assert!(!engine.is_using_nnue());
+        apply_setoption(&mut engine, &["name", "UseNNUE", "value", "true"]);
+        assert!(engine.is_using_nnue());
+
+        let missing = dir.path().join("missing.nnue");
+        let reply =
+            apply_setoption(&mut engine, &["name", "EvalFile", "value", missing.to_str().unwrap()]);
+        assert!(reply[0].starts_with("info string error Failed to load NNUE"), "{:?}", reply);
+        assert!(engine.is_using_nnue(), "a failed load keeps the previous network");
+
+        apply_setoption(&mut engine, &["name", "EvalFile", "value", "<empty>"]);
+        assert!(!engine.is_using_nnue());
+    }
+
+    #[test]
+    fn test_king_danger_file_option() {
+        use crate::evaluation::king_safety::KingDangerConfig;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("king_danger.json");
+        let tuned = KingDangerConfig { board_check_weight: 9, ..KingDangerConfig::default() };
+        std::fs::write(&path, serde_json::to_string(&tuned).unwrap()).unwrap();
+        let king_danger = |engine: &ShogiEngine| {
+            with_search_engine(engine, |s| s.get_evaluator().king_danger_config().cloned())
+        };
+
+        let mut engine = ShogiEngine::new();
+        apply_setoption(&mut engine, &["name", "KingDangerFile", "value", path.to_str().unwrap()]);
+        assert_eq!(king_danger(&engine), Some(tuned));
+        apply_setoption(&mut engine, &["name", "KingDangerFile", "value", "<empty>"]);
+        assert_eq!(king_danger(&engine), Some(KingDangerConfig::default()));
+    }
+}
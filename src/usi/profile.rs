@@ -0,0 +1,317 @@
+//! Engine profiles
+//!
+//! A profile is a TOML, JSON or YAML file mapping USI option names to values,
+//! so a GUI does not have to send every `setoption` itself:
+//!
+//! ```toml
+//! USI_Hash = 256
+//! MaxDepth = 12
+//! EnableNullMove = false
+//! TimeAllocationStrategy = "Exponential"
+//! ```
+//!
+//! A file written by `config::EngineConfig::to_file` (nested `search`,
+//! `evaluation`, `time_management` and `simd` tables) is accepted too, as
+//! are the preset names `performance` and `memory_optimized`. Their fields
+//! are mapped onto the options that control them; fields without an option
+//! are listed as not applied.
+//!
+//! Profiles are loaded with `usi-engine --config <path>` or the
+//! `EngineConfigFile` option and written by the `SaveConfig` button. Values
+//! go through the option registry, so they are validated exactly like
+//! `setoption`. Precedence is defaults < config file < setoption: loading a
+//! file never replaces a value the GUI has set, and every decision is
+//! reported with `info string`.
+
+use super::options::{usi_options, OptionSource, UsiOption, UsiOptionKind};
+use crate::config::{read_config_file, write_config_file, EngineConfig};
+use crate::types::search::TimeAllocationStrategy;
+use crate::ShogiEngine;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A single option value as written in a profile
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ProfileValue {
+    Bool(bool),
+    Int(i64),
+    Text(String),
+}
+
+impl fmt::Display for ProfileValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bool(value) => write!(f, "{}", value),
+            Self::Int(value) => write!(f, "{}", value),
+            Self::Text(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// Option name to value
+pub type EngineProfile = BTreeMap<String, ProfileValue>;
+
+/// Options written to a profile: buttons, the profile path itself and
+/// aliases are left out
+fn in_profile(option: &UsiOption) -> bool {
+    !matches!(option.kind, UsiOptionKind::Button { .. })
+        && !matches!(option.name, "EngineConfigFile" | "depth")
+}
+
+/// `EngineConfig` fields that have no USI option and are not applied
+const UNMAPPED_ENGINE_CONFIG_FIELDS: &str =
+    "evaluation, transposition, simd and the remaining search and time_management fields";
+
+/// Option values equivalent to `config`. The parallel settings are not
+/// serialized, so they are only taken from presets (`with_parallel`).
+fn engine_config_profile(config: &EngineConfig, with_parallel: bool) -> EngineProfile {
+    let time = &config.time_management;
+    let strategy = match time.allocation_strategy {
+        TimeAllocationStrategy::Equal => "Equal",
+        TimeAllocationStrategy::Exponential => "Exponential",
+        TimeAllocationStrategy::Adaptive => "Adaptive",
+    };
+    let mut profile = EngineProfile::from([
+        ("MaxDepth".to_string(), ProfileValue::Int(i64::from(config.search.max_depth))),
+        ("TimeAllocationStrategy".to_string(), ProfileValue::Text(strategy.to_string())),
+        ("EnableTimeBudget".to_string(), ProfileValue::Bool(time.enable_time_budget)),
+        ("EnableCheckOptimization".to_string(), ProfileValue::Bool(time.enable_check_optimization)),
+        ("TimeCheckFrequency".to_string(), ProfileValue::Int(i64::from(time.time_check_frequency))),
+        (
+            "TimeSafetyMargin".to_string(),
+            ProfileValue::Int(i64::from(time.absolute_safety_margin_ms)),
+        ),
+    ]);
+    if with_parallel {
+        let parallel = &config.parallel;
+        profile.insert("USI_Threads".into(), ProfileValue::Int(parallel.num_threads as i64));
+        profile.insert("ParallelEnable".into(), ProfileValue::Bool(parallel.enable_parallel));
+        profile.insert("ParallelHash".into(), ProfileValue::Int(parallel.hash_size_mb as i64));
+    }
+    profile
+}
+
+/// Read `path` as an option profile or an `EngineConfig`, telling them apart
+/// by the nested tables only an `EngineConfig` has. Returns the option
+/// values and whether they came from an `EngineConfig`.
+fn read_profile(path: &str) -> Result<(EngineProfile, bool), String> {
+    let preset = match path {
+        "performance" => Some(EngineConfig::performance()),
+        "memory_optimized" => Some(EngineConfig::memory_optimized()),
+        _ => None,
+    };
+    if let Some(config) = preset {
+        return Ok((engine_config_profile(&config, true), true));
+    }
+
+    let value: serde_json::Value = read_config_file(path).map_err(|e| e.to_string())?;
+    let nested = value
+        .as_object()
+        .is_some_and(|table| table.values().any(serde_json::Value::is_object));
+    if !nested {
+        let profile = serde_json::from_value(value).map_err(|e| format!("{}: {}", path, e))?;
+        return Ok((profile, false));
+    }
+    let config: EngineConfig =
+        serde_json::from_value(value).map_err(|e| format!("{}: {}", path, e))?;
+    config.validate().map_err(|e| e.to_string())?;
+    Ok((engine_config_profile(&config, false), true))
+}
+
+/// Load the profile at `path` (or a preset name) into `engine`, returning the
+/// report lines
+pub fn load_profile(engine: &mut ShogiEngine, path: &str) -> Result<String, String> {
+    let (mut profile, engine_config) = read_profile(path)?;
+    let mut lines = Vec::new();
+    let mut applied = 0;
+
+    // Registry order, so related options are applied the way a GUI sends them
+    for option in usi_options() {
+        let Some(value) = profile.remove(option.name) else {
+            continue;
+        };
+        if matches!(option.kind, UsiOptionKind::Button { .. }) || option.name == "EngineConfigFile"
+        {
+            lines.push(format!("error {} cannot be set from a config file", option.name));
+            continue;
+        }
+        let current = option.current_value(engine);
+        if let Some(current) = current.filter(|c| c.source == OptionSource::SetOption) {
+            lines.push(format!(
+                "{} keeps setoption value {} over config file value {}",
+                option.name, current.value, value
+            ));
+            continue;
+        }
+
+        let value = value.to_string();
+        match option.set(engine, Some(&value), OptionSource::ConfigFile) {
+            Ok(_) => {
+                applied += 1;
+                lines.push(format!("{} = {} from config file", option.name, value));
+            }
+            Err(err) => lines.push(format!("error {}", err)),
+        }
+    }
+    for name in profile.keys() {
+        lines.push(format!("error Unknown option in config file: {}", name));
+    }
+    if engine_config {
+        lines.push(format!(
+            "EngineConfig fields without a USI option not applied: {}",
+            UNMAPPED_ENGINE_CONFIG_FIELDS
+        ));
+    }
+
+    lines.push(format!(
+        "Loaded {} options from {} (precedence: defaults < config file < setoption)",
+        applied, path
+    ));
+    Ok(lines.join("\n"))
+}
+
+/// Value the engine is running with for every profile option, typed as in
+/// the registry
+pub fn effective_profile(engine: &ShogiEngine) -> EngineProfile {
+    usi_options()
+        .iter()
+        .filter(|option| in_profile(option))
+        .filter_map(|option| {
+            let value = match option.kind {
+                UsiOptionKind::Check { get, .. } => ProfileValue::Bool(get(engine)),
+                UsiOptionKind::Spin { get, .. } => ProfileValue::Int(get(engine)),
+                UsiOptionKind::Combo { get, .. }
+                | UsiOptionKind::String { get, .. }
+                | UsiOptionKind::Filename { get, .. } => ProfileValue::Text(get(engine)),
+                UsiOptionKind::Button { .. } => return None,
+            };
+            Some((option.name.to_string(), value))
+        })
+        .collect()
+}
+
+/// Write the effective configuration to `path`, returning the option count
+pub fn save_profile(engine: &ShogiEngine, path: &str) -> Result<usize, String> {
+    let profile = effective_profile(engine);
+    write_config_file(path, &profile).map_err(|e| e.to_string())?;
+    Ok(profile.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::usi::options::{apply_setoption, find_usi_option};
+
+    fn temp_path(name: &str) -> String {
+        std::env::temp_dir()
+            .join(format!("yggdrasil_profile_{}_{}", std::process::id(), name))
+            .to_string_lossy()
+            .to_string()
+    }
+
+    /// Loading USI_Threads persists preferences; keep them out of the user's
+    /// config directory
+    fn engine_with_prefs(dir: &tempfile::TempDir) -> ShogiEngine {
+        ShogiEngine::with_prefs_path(dir.path().join("engine_prefs.json"))
+    }
+
+    #[test]
+    fn test_profile_round_trip_in_every_format() {
+        let prefs = tempfile::tempdir().unwrap();
+        for extension in ["toml", "json", "yaml"] {
+            let path = temp_path(&format!("round_trip.{}", extension));
+            let mut engine = engine_with_prefs(&prefs);
+            apply_setoption(&mut engine, &["name", "MaxDepth", "value", "9"]);
+            apply_setoption(&mut engine, &["name", "EnableLMR", "value", "false"]);
+            apply_setoption(&mut engine, &["name", "TimeAllocationStrategy", "value", "Equal"]);
+            let saved = apply_setoption(&mut engine, &["name", "SaveConfig", "value", &path]);
+            assert!(saved[0].starts_with("info string Saved"), "{:?}", saved);
+
+            let mut loaded = engine_with_prefs(&prefs);
+            let lines = apply_setoption(&mut loaded, &["name", "EngineConfigFile", "value", &path]);
+            let _ = std::fs::remove_file(&path);
+            assert!(!lines.iter().any(|line| line.contains("error")), "{:?}", lines);
+            assert_eq!(effective_profile(&loaded), effective_profile(&engine));
+            assert_eq!(loaded.depth, 9);
+        }
+    }
+
+    #[test]
+    fn test_engine_config_files_and_presets_load() {
+        let prefs = tempfile::tempdir().unwrap();
+        for extension in ["toml", "json", "yaml"] {
+            let path = temp_path(&format!("engine_config.{}", extension));
+            EngineConfig::performance().to_file(&path).unwrap();
+            let mut engine = engine_with_prefs(&prefs);
+            apply_setoption(&mut engine, &["name", "EnableTimeBudget", "value", "false"]);
+
+            let lines = apply_setoption(&mut engine, &["name", "EngineConfigFile", "value", &path]);
+            let _ = std::fs::remove_file(&path);
+            assert!(!lines.iter().any(|line| line.contains("error")), "{:?}", lines);
+            assert!(lines.contains(&"info string MaxDepth = 30 from config file".to_string()));
+            assert!(lines.contains(
+                &"info string EnableTimeBudget keeps setoption value false over config file value true"
+                    .to_string()
+            ));
+            let profile = effective_profile(&engine);
+            assert_eq!(profile["TimeAllocationStrategy"], ProfileValue::Text("Exponential".into()));
+            assert_eq!(profile["EnableTimeBudget"], ProfileValue::Bool(false));
+            assert_eq!(engine.depth, 30);
+        }
+
+        // Presets also carry the parallel settings, which files cannot
+        let mut engine = engine_with_prefs(&prefs);
+        let lines = apply_setoption(
+            &mut engine,
+            &["name", "EngineConfigFile", "value", "memory_optimized"],
+        );
+        assert!(!lines.iter().any(|line| line.contains("error")), "{:?}", lines);
+        let profile = effective_profile(&engine);
+        assert_eq!(profile["MaxDepth"], ProfileValue::Int(15));
+        assert_eq!(profile["USI_Threads"], ProfileValue::Int(1));
+        assert_eq!(profile["ParallelHash"], ProfileValue::Int(8));
+    }
+
+    #[test]
+    fn test_profile_saves_live_engine_state() {
+        let prefs = tempfile::tempdir().unwrap();
+        std::fs::write(prefs.path().join("engine_prefs.json"), r#"{"thread_count": 3}"#).unwrap();
+        let mut engine = engine_with_prefs(&prefs);
+        apply_setoption(&mut engine, &["name", "USI_Hash", "value", "32"]);
+        engine.set_max_depth(5);
+
+        // Restored and directly set values are saved, not the registry defaults
+        let profile = effective_profile(&engine);
+        assert_eq!(profile["USI_Threads"], ProfileValue::Int(3));
+        assert_eq!(profile["USI_Hash"], ProfileValue::Int(32));
+        assert_eq!(profile["MaxDepth"], ProfileValue::Int(5));
+        let threads = find_usi_option("USI_Threads").unwrap().current_value(&engine).unwrap();
+        assert_eq!((threads.value.as_str(), threads.source), ("3", OptionSource::Default));
+    }
+
+    #[test]
+    fn test_setoption_takes_precedence_over_config_file() {
+        let path = temp_path("precedence.toml");
+        std::fs::write(&path, "MaxDepth = 12\nNullMoveMinDepth = 4\nBogus = 1\n").unwrap();
+        let mut engine = ShogiEngine::new();
+        apply_setoption(&mut engine, &["name", "MaxDepth", "value", "6"]);
+
+        let lines = apply_setoption(&mut engine, &["name", "EngineConfigFile", "value", &path]);
+        let _ = std::fs::remove_file(&path);
+        assert!(lines.contains(
+            &"info string MaxDepth keeps setoption value 6 over config file value 12".to_string()
+        ));
+        assert!(lines.contains(&"info string NullMoveMinDepth = 4 from config file".to_string()));
+        assert!(lines.contains(&"info string error Unknown option in config file: Bogus".into()));
+        assert_eq!(engine.depth, 6);
+
+        // A later setoption wins over the file and says so
+        let lines = apply_setoption(&mut engine, &["name", "NullMoveMinDepth", "value", "5"]);
+        assert_eq!(
+            lines.last().unwrap(),
+            "info string NullMoveMinDepth from setoption overrides config file value 4"
+        );
+    }
+}
@@ -1,5 +1,17 @@
 //! Common small helpers centralized for reuse.
 
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::BuildHasherDefault;
+
+/// `HashMap` with a fixed hasher, so its iteration order depends only on
+/// what was inserted
+///
+/// The default `RandomState` seeds every map differently. Search caches that
+/// evict whichever entry iteration yields first use this map, so the same
+/// search evicts the same entries on every run.
+pub type DeterministicHashMap<K, V> = HashMap<K, V, BuildHasherDefault<DefaultHasher>>;
+
 /// Saturating clamp for i32.
 #[inline]
 pub fn clamp_i32(value: i32, min: i32, max: i32) -> i32 {
@@ -1,7 +1,7 @@
 //! Time utilities: re-exports common time sources and provides convenience
 //! helpers.
 
-pub use crate::time_utils::TimeSource;
+pub use crate::time_utils::{system_clock, Clock, SystemClock, TimeSource, VirtualClock};
 
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
@@ -35,6 +35,7 @@ impl Stopwatch {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::Arc;
     #[test]
     fn stopwatch_measures_time() {
         let sw = Stopwatch::start();
@@ -42,6 +43,20 @@ mod tests {
         assert!(sw.elapsed() >= Duration::from_millis(5));
     }
 
+    #[test]
+    fn virtual_clock_advances_by_nodes() {
+        let virtual_clock = Arc::new(VirtualClock::new(250));
+        let clock: Arc<dyn Clock> = virtual_clock.clone();
+        let start = TimeSource::start(&clock);
+        for _ in 0..8 {
+            clock.on_node();
+        }
+        assert_eq!(start.elapsed_ms(), 2);
+        virtual_clock.advance_ms(100);
+        assert_eq!(start.elapsed_ms(), 102);
+        assert!(start.has_exceeded_limit(102));
+    }
+
     #[test]
     fn current_time_ms_monotonic_nonzero() {
         let a = current_time_ms();
@@ -296,6 +296,11 @@ impl WeightManager {
         self.weights.is_some()
     }
 
+    /// Get the loaded weights
+    pub fn weights(&self) -> Option<&[f64]> {
+        self.weights.as_deref()
+    }
+
     /// Get weight metadata
     pub fn get_metadata(&self) -> Option<&WeightFileHeader> {
         self.metadata.as_ref()
@@ -526,4 +531,24 @@ mod tests {
         let magic = WeightManager::get_magic_bytes();
         assert_eq!(&magic[..WEIGHT_FILE_MAGIC.len()], WEIGHT_FILE_MAGIC);
     }
+
+    #[test]
+    fn test_tuned_weights_set_the_king_danger_table() {
+        use crate::evaluation::PositionEvaluator;
+        use crate::types::evaluation::{TaperedScore, KING_DANGER_FEATURES_START};
+
+        let temp_dir = tempdir().unwrap();
+        let weight_file = temp_dir.path().join("tuned.json");
+        let mut weights = vec![1.0; NUM_EVAL_FEATURES];
+        weights[KING_DANGER_FEATURES_START + 4] = 321.0;
+        let mut manager = WeightManager::new();
+        manager.weights = Some(weights);
+        manager.save_weights(&weight_file, "adam".to_string(), 0.1, 10).unwrap();
+
+        let mut evaluator = PositionEvaluator::new();
+        evaluator.load_tuned_weights(&weight_file).unwrap();
+        let king_danger = evaluator.king_danger_config().unwrap();
+        assert_eq!(king_danger.score(4), TaperedScore::new_tapered(321, 160));
+        assert_eq!(king_danger.score(5), TaperedScore::new_tapered(1, 0));
+    }
 }
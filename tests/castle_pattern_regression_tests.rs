@@ -1,6 +1,6 @@
 use shogi_engine::bitboards::BitboardBoard;
 use shogi_engine::evaluation::castle_fixtures::{
-    castle_fixtures, CastleFixture, CastleFixtureTheme,
+    castle_fixtures, CastleFixture, CastleFixtureTheme, CastleType,
 };
 use shogi_engine::evaluation::castles::CastleRecognizer;
 use shogi_engine::evaluation::king_safety::KingSafetyEvaluator;
@@ -237,3 +237,63 @@ fn test_castle_cache_effectiveness() {
     assert_eq!(stats_after_second.hits, 1);
     assert_eq!(stats_after_second.misses, 1);
 }
+
+#[test]
+fn test_extended_castles_recognised_by_name() {
+    let recognizer = CastleRecognizer::new();
+    let fixtures = castle_fixtures();
+    let extended = [
+        CastleType::SilverCrown,
+        CastleType::DiamondMino,
+        CastleType::LeftMino,
+        CastleType::Boat,
+        CastleType::Elmo,
+        CastleType::Nakahara,
+        CastleType::Bonanza,
+    ];
+
+    for castle_type in extended {
+        let expected = castle_type.pattern_name();
+        let mut canonical_quality = None;
+
+        for fixture in fixtures.iter().filter(|f| f.castle_type == castle_type) {
+            let (board, king_pos) = (fixture.builder)(fixture.player);
+            let evaluation = recognizer.evaluate_castle(&board, fixture.player, king_pos);
+            assert_castle_quality(fixture, evaluation);
+
+            match fixture.theme {
+                CastleFixtureTheme::Canonical | CastleFixtureTheme::Mirrored => {
+                    assert_eq!(
+                        evaluation.matched_pattern,
+                        Some(expected),
+                        "{} should be recognised as {} (quality {})",
+                        fixture.name,
+                        expected,
+                        evaluation.quality
+                    );
+                    canonical_quality.get_or_insert(evaluation.quality);
+                }
+                CastleFixtureTheme::Partial => {
+                    let canonical = canonical_quality.expect("canonical fixture listed first");
+                    assert!(
+                        evaluation.quality < canonical,
+                        "{} quality {} should be below canonical {}",
+                        fixture.name,
+                        evaluation.quality,
+                        canonical
+                    );
+                }
+                CastleFixtureTheme::Broken => {
+                    assert!(
+                        evaluation.matched_pattern.is_none(),
+                        "{} should not match",
+                        fixture.name
+                    );
+                }
+                CastleFixtureTheme::Attacked => {}
+            }
+        }
+
+        assert!(canonical_quality.is_some(), "missing canonical fixture for {}", expected);
+    }
+}
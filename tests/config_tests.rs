@@ -1,7 +1,7 @@
 /// Tests for configuration system, including SIMD runtime flags
 ///
 /// # Task 4.0 (Task 4.9)
-use shogi_engine::config::{EngineConfig, SimdConfig};
+use shogi_engine::config::{ConfigFormat, EngineConfig, SimdConfig};
 
 #[test]
 fn test_simd_config_default() {
@@ -117,5 +117,35 @@ fn test_engine_config_simd_serialization() {
     }
 }
 
+#[test]
+fn test_config_format_from_extension() {
+    assert_eq!(ConfigFormat::from_path("engine.toml"), ConfigFormat::Toml);
+    assert_eq!(ConfigFormat::from_path("engine.YAML"), ConfigFormat::Yaml);
+    assert_eq!(ConfigFormat::from_path("engine.yml"), ConfigFormat::Yaml);
+    assert_eq!(ConfigFormat::from_path("engine.json"), ConfigFormat::Json);
+    assert_eq!(ConfigFormat::from_path("engine"), ConfigFormat::Json);
+}
 
-
+#[test]
+fn test_engine_config_file_round_trip_in_every_format() {
+    let mut config = EngineConfig::memory_optimized();
+    config.search.max_depth = 11;
+
+    for extension in ["json", "toml", "yaml"] {
+        let path = std::env::temp_dir().join(format!(
+            "engine_config_{}.{}",
+            std::process::id(),
+            extension
+        ));
+        config.to_file(&path).expect("Should save");
+        let loaded = EngineConfig::from_file(&path).expect("Should load");
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(loaded.search, config.search, "{}", extension);
+        assert_eq!(loaded.simd, config.simd, "{}", extension);
+        assert_eq!(
+            loaded.time_management.allocation_strategy,
+            config.time_management.allocation_strategy
+        );
+    }
+}
@@ -0,0 +1,36 @@
+//! SaveHash / LoadHash round trip over the table a multi-threaded search
+//! writes to.
+
+use shogi_engine::bitboards::BitboardBoard;
+use shogi_engine::search::search_engine::{IterativeDeepening, SearchEngine};
+use shogi_engine::search::ParallelSearchConfig;
+use shogi_engine::types::{CapturedPieces, ParallelSearchMode, Player};
+
+const THREADS: usize = 3;
+
+#[test]
+fn test_parallel_search_table_survives_save_and_load() {
+    let board = BitboardBoard::new();
+    let captured = CapturedPieces::new();
+    let mut engine = SearchEngine::new(None, 16);
+
+    let mut config = ParallelSearchConfig::new(THREADS);
+    config.search_mode = ParallelSearchMode::LazySmp;
+    let mut search = IterativeDeepening::new_with_threads(3, 30_000, None, THREADS, config);
+    search.search(&mut engine, &board, &captured, Player::Black).expect("a move");
+    assert!(search.lazy_smp_results().iter().any(|result| result.tt_writes > 0));
+
+    let path =
+        std::env::temp_dir().join(format!("parallel_hash_round_trip_{}.ytt", std::process::id()));
+    let saved = engine.save_transposition_table(&path).unwrap();
+    let mut restored = SearchEngine::new(None, 16);
+    let loaded = restored.load_transposition_table(&path);
+    std::fs::remove_file(&path).ok();
+    assert!(saved > 0);
+    assert_eq!(loaded.unwrap(), saved);
+
+    // The restored table answers the same probes
+    let pv = engine.get_pv_for_reporting(&board, &captured, Player::Black, 3);
+    assert!(!pv.is_empty());
+    assert_eq!(restored.get_pv_for_reporting(&board, &captured, Player::Black, 3), pv);
+}
@@ -11,6 +11,7 @@ fn king_safety_only() -> PositionFeatureEvaluator {
         enable_mobility: false,
         enable_center_control: false,
         enable_development: false,
+        ..PositionFeatureConfig::default()
     };
     PositionFeatureEvaluator::with_config(config)
 }
@@ -22,6 +23,7 @@ fn pawn_structure_only() -> PositionFeatureEvaluator {
         enable_mobility: false,
         enable_center_control: false,
         enable_development: false,
+        ..PositionFeatureConfig::default()
     };
     PositionFeatureEvaluator::with_config(config)
 }
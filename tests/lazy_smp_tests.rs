@@ -0,0 +1,53 @@
+//! Integration tests for Lazy SMP: helper threads search into the engine's own
+//! transposition table, which outlives the search.
+
+use shogi_engine::bitboards::BitboardBoard;
+use shogi_engine::search::search_engine::{IterativeDeepening, SearchEngine};
+use shogi_engine::search::ParallelSearchConfig;
+use shogi_engine::types::{CapturedPieces, ParallelSearchMode, Player};
+
+const THREADS: usize = 3;
+
+fn lazy_smp_search(depth: u8) -> IterativeDeepening {
+    let mut config = ParallelSearchConfig::new(THREADS);
+    config.search_mode = ParallelSearchMode::LazySmp;
+    IterativeDeepening::new_with_threads(depth, 30_000, None, THREADS, config)
+}
+
+#[test]
+fn test_lazy_smp_helpers_write_to_the_engine_table() {
+    let board = BitboardBoard::new();
+    let captured = CapturedPieces::new();
+    let mut engine = SearchEngine::new(None, 16);
+    let table = engine.transposition_table_handle();
+
+    let mut search = lazy_smp_search(3);
+    assert!(search.search(&mut engine, &board, &captured, Player::Black).is_some());
+
+    let helpers: Vec<_> =
+        search.lazy_smp_results().iter().filter(|result| result.thread_id > 0).collect();
+    assert!(!helpers.is_empty(), "no helper completed an iteration");
+    assert!(helpers.iter().all(|helper| helper.tt_writes > 0));
+
+    // Every helper write landed in the engine's table
+    let helper_writes: u64 = helpers.iter().map(|helper| helper.tt_writes).sum();
+    let stores = table.read().unwrap().get_stats().stores;
+    assert!(stores >= helper_writes, "{} stores, {} helper writes", stores, helper_writes);
+}
+
+#[test]
+fn test_lazy_smp_table_is_kept_across_searches() {
+    let board = BitboardBoard::new();
+    let captured = CapturedPieces::new();
+    let mut engine = SearchEngine::new(None, 16);
+    let table = engine.transposition_table_handle();
+
+    lazy_smp_search(3).search(&mut engine, &board, &captured, Player::Black);
+    let stores = table.read().unwrap().get_stats().stores;
+    assert!(stores > 0);
+
+    // The next move's search starts from the same, already filled table
+    lazy_smp_search(3).search(&mut engine, &board, &captured, Player::Black);
+    assert!(std::sync::Arc::ptr_eq(&table, &engine.transposition_table_handle()));
+    assert!(table.read().unwrap().get_stats().stores > stores);
+}
@@ -23,6 +23,20 @@ fn loads_builtin_value_set_from_path() {
     assert_eq!(evaluator.value_set().id, "research");
 }
 
+#[test]
+fn builtin_files_match_presets() {
+    for (file, preset) in [
+        ("research.json", MaterialValueSet::research()),
+        ("classic.json", MaterialValueSet::classic()),
+    ] {
+        let loaded = MaterialValueSet::from_path(resources_dir().join(file)).expect(file);
+        assert_eq!(loaded.board_values, preset.board_values, "{}", file);
+        assert_eq!(loaded.hand_values, preset.hand_values, "{}", file);
+        assert_eq!(loaded.hand_count_values, preset.hand_count_values, "{}", file);
+        assert_eq!(loaded.drop_potential, preset.drop_potential, "{}", file);
+    }
+}
+
 #[test]
 fn loads_custom_json_value_set() {
     let mut custom_set = MaterialValueSet::classic();
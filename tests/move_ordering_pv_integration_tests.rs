@@ -9,6 +9,7 @@ use shogi_engine::bitboards::BitboardBoard;
 use shogi_engine::search::move_ordering::{MoveOrdering, OrderingWeights};
 use shogi_engine::search::{ThreadSafeTranspositionTable, ThreadSafetyMode, TranspositionConfig};
 use shogi_engine::types::*;
+use std::sync::{Arc, RwLock};
 
 #[cfg(test)]
 mod integration_tests {
@@ -37,14 +38,14 @@ mod integration_tests {
     fn test_pv_move_integration_with_transposition_table() {
         // Create transposition table
         let config = TranspositionConfig::default();
-        let mut tt = ThreadSafeTranspositionTable::with_thread_mode(
+        let tt = Arc::new(RwLock::new(ThreadSafeTranspositionTable::with_thread_mode(
             config,
             ThreadSafetyMode::SingleThreaded,
-        );
+        )));
 
         // Create move orderer
         let mut orderer = MoveOrdering::new();
-        orderer.set_transposition_table(&tt);
+        orderer.set_transposition_table(tt.clone());
 
         // Create test position
         let board = BitboardBoard::new();
@@ -96,14 +97,14 @@ mod integration_tests {
     fn test_pv_move_storage_and_retrieval() {
         // Create transposition table
         let config = TranspositionConfig::default();
-        let mut tt = ThreadSafeTranspositionTable::with_thread_mode(
+        let tt = Arc::new(RwLock::new(ThreadSafeTranspositionTable::with_thread_mode(
             config,
             ThreadSafetyMode::SingleThreaded,
-        );
+        )));
 
         // Create move orderer
         let mut orderer = MoveOrdering::new();
-        orderer.set_transposition_table(&tt);
+        orderer.set_transposition_table(tt.clone());
 
         // Create test position
         let board = BitboardBoard::new();
@@ -143,14 +144,14 @@ mod integration_tests {
     fn test_pv_move_prioritization_in_ordering() {
         // Create transposition table
         let config = TranspositionConfig::default();
-        let mut tt = ThreadSafeTranspositionTable::with_thread_mode(
+        let tt = Arc::new(RwLock::new(ThreadSafeTranspositionTable::with_thread_mode(
             config,
             ThreadSafetyMode::SingleThreaded,
-        );
+        )));
 
         // Create move orderer
         let mut orderer = MoveOrdering::new();
-        orderer.set_transposition_table(&tt);
+        orderer.set_transposition_table(tt.clone());
 
         // Create test position
         let board = BitboardBoard::new();
@@ -208,14 +209,14 @@ mod integration_tests {
     fn test_pv_move_cache_effectiveness() {
         // Create transposition table
         let config = TranspositionConfig::default();
-        let mut tt = ThreadSafeTranspositionTable::with_thread_mode(
+        let tt = Arc::new(RwLock::new(ThreadSafeTranspositionTable::with_thread_mode(
             config,
             ThreadSafetyMode::SingleThreaded,
-        );
+        )));
 
         // Create move orderer
         let mut orderer = MoveOrdering::new();
-        orderer.set_transposition_table(&tt);
+        orderer.set_transposition_table(tt.clone());
 
         // Create test position
         let board = BitboardBoard::new();
@@ -252,14 +253,14 @@ mod integration_tests {
     fn test_pv_move_clear_functionality() {
         // Create transposition table
         let config = TranspositionConfig::default();
-        let mut tt = ThreadSafeTranspositionTable::with_thread_mode(
+        let tt = Arc::new(RwLock::new(ThreadSafeTranspositionTable::with_thread_mode(
             config,
             ThreadSafetyMode::SingleThreaded,
-        );
+        )));
 
         // Create move orderer
         let mut orderer = MoveOrdering::new();
-        orderer.set_transposition_table(&tt);
+        orderer.set_transposition_table(tt.clone());
 
         // Create test position
         let board = BitboardBoard::new();
@@ -297,14 +298,14 @@ mod integration_tests {
     fn test_pv_move_with_different_positions() {
         // Create transposition table
         let config = TranspositionConfig::default();
-        let mut tt = ThreadSafeTranspositionTable::with_thread_mode(
+        let tt = Arc::new(RwLock::new(ThreadSafeTranspositionTable::with_thread_mode(
             config,
             ThreadSafetyMode::SingleThreaded,
-        );
+        )));
 
         // Create move orderer
         let mut orderer = MoveOrdering::new();
-        orderer.set_transposition_table(&tt);
+        orderer.set_transposition_table(tt.clone());
 
         let captured_pieces = CapturedPieces::new();
         let player = Player::Black;
@@ -349,14 +350,14 @@ mod integration_tests {
     fn test_pv_move_statistics_accuracy() {
         // Create transposition table
         let config = TranspositionConfig::default();
-        let mut tt = ThreadSafeTranspositionTable::with_thread_mode(
+        let tt = Arc::new(RwLock::new(ThreadSafeTranspositionTable::with_thread_mode(
             config,
             ThreadSafetyMode::SingleThreaded,
-        );
+        )));
 
         // Create move orderer
         let mut orderer = MoveOrdering::new();
-        orderer.set_transposition_table(&tt);
+        orderer.set_transposition_table(tt.clone());
 
         // Create test position
         let board = BitboardBoard::new();
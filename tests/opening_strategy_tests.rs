@@ -0,0 +1,70 @@
+//! Integration tests for the opening strategy classifier and its consumers
+//! (opening book tagging and strategy-aware evaluation weights).
+
+use shogi_engine::bitboards::BitboardBoard;
+use shogi_engine::evaluation::integration::{IntegratedEvaluationConfig, IntegratedEvaluator};
+use shogi_engine::evaluation::opening_strategy::{classify_opening, OpeningFamily};
+use shogi_engine::opening_book::{BookMove, OpeningBook, PositionEntry};
+use shogi_engine::types::{PieceType, Position};
+
+const START: &str = "lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b - 1";
+const SHIKENBISHA: &str = "lnsgkgsnl/1r5b1/ppppppppp/9/9/2P6/PP1PPPPPP/1B1R5/LNSGKGSNL w - 4";
+const GOKIGEN: &str = "lnsgkgsnl/1r5b1/ppppppppp/9/4P4/2P6/PP1P1PPPP/1B2R4/LNSGKGSNL w - 6";
+
+fn pawn_push() -> BookMove {
+    BookMove::new(
+        Some(Position::new(6, 6)),
+        Position::new(5, 6),
+        PieceType::Pawn,
+        false,
+        false,
+        500,
+        0,
+    )
+}
+
+#[test]
+fn test_book_entries_are_tagged_by_strategy() {
+    let entry = PositionEntry::new(SHIKENBISHA.to_string(), vec![pawn_push()]);
+    let classification = entry.classify_strategy().expect("valid FEN");
+    assert_eq!(classification.family, OpeningFamily::Shikenbisha);
+
+    let broken = PositionEntry::new("not a fen".to_string(), vec![pawn_push()]);
+    assert!(broken.classify_strategy().is_none());
+}
+
+#[test]
+fn test_book_strategy_distribution() {
+    let mut book = OpeningBook::new();
+    book.add_position(START.to_string(), vec![pawn_push()]);
+    book.add_position(SHIKENBISHA.to_string(), vec![pawn_push()]);
+    book.add_position(GOKIGEN.to_string(), vec![pawn_push()]);
+
+    let distribution = book.strategy_distribution();
+    assert_eq!(distribution.get(&OpeningFamily::Undetermined), Some(&1));
+    assert_eq!(distribution.get(&OpeningFamily::Shikenbisha), Some(&1));
+    assert_eq!(distribution.get(&OpeningFamily::GokigenNakabisha), Some(&1));
+    assert_eq!(distribution.values().sum::<usize>(), 3);
+}
+
+#[test]
+fn test_strategy_weights_change_evaluation_only_when_enabled() {
+    let (board, player, captured) = BitboardBoard::from_fen(GOKIGEN).unwrap();
+    assert_eq!(classify_opening(&board, &captured).family, OpeningFamily::GokigenNakabisha);
+
+    let mut plain = IntegratedEvaluator::with_config(IntegratedEvaluationConfig::default());
+    let mut config = IntegratedEvaluationConfig::default();
+    config.enable_strategy_weights = true;
+    let mut adapted = IntegratedEvaluator::with_config(config);
+
+    let baseline = plain.evaluate(&board, player, &captured).score;
+    let scaled = adapted.evaluate(&board, player, &captured).score;
+    assert_ne!(baseline, scaled);
+
+    // The start position is undetermined, so its weights are left untouched
+    let (start, player, captured) = BitboardBoard::from_fen(START).unwrap();
+    assert_eq!(
+        plain.evaluate(&start, player, &captured).score,
+        adapted.evaluate(&start, player, &captured).score
+    );
+}
@@ -14,6 +14,7 @@ fn position_feature_toggles_skip_computation_and_statistics() {
         enable_mobility: false,
         enable_center_control: false,
         enable_development: false,
+        ..PositionFeatureConfig::default()
     };
 
     let mut evaluator = PositionFeatureEvaluator::with_config(config);
@@ -68,6 +69,7 @@ fn integrated_evaluator_respects_position_feature_weights() {
         enable_mobility: false,
         enable_center_control: false,
         enable_development: false,
+        ..PositionFeatureConfig::default()
     };
     config.weights = EvaluationWeights {
         material_weight: 0.0,
@@ -179,6 +181,7 @@ fn combined_position_features_midgame_snapshot_responds_to_toggles() {
         enable_mobility: true,
         enable_center_control: true,
         enable_development: true,
+        ..PositionFeatureConfig::default()
     };
     base_config.enable_phase_cache = false;
     base_config.enable_eval_cache = false;
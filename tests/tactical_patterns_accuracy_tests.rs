@@ -13,6 +13,7 @@ fn forks_only_config() -> TacticalConfig {
         enable_discovered_attacks: false,
         enable_knight_forks: false,
         enable_back_rank_threats: false,
+        enable_pawn_tactics: false,
         ..TacticalConfig::default()
     }
 }
@@ -25,6 +26,7 @@ fn back_rank_only_config() -> TacticalConfig {
         enable_discovered_attacks: false,
         enable_knight_forks: false,
         enable_back_rank_threats: true,
+        enable_pawn_tactics: false,
         ..TacticalConfig::default()
     }
 }
@@ -37,6 +39,7 @@ fn pins_only_config() -> TacticalConfig {
         enable_discovered_attacks: false,
         enable_knight_forks: false,
         enable_back_rank_threats: false,
+        enable_pawn_tactics: false,
         ..TacticalConfig::default()
     }
 }
@@ -49,6 +52,7 @@ fn skewers_only_config() -> TacticalConfig {
         enable_discovered_attacks: false,
         enable_knight_forks: false,
         enable_back_rank_threats: false,
+        enable_pawn_tactics: false,
         ..TacticalConfig::default()
     }
 }
@@ -61,10 +65,40 @@ fn discovered_only_config() -> TacticalConfig {
         enable_discovered_attacks: true,
         enable_knight_forks: false,
         enable_back_rank_threats: false,
+        enable_pawn_tactics: false,
         ..TacticalConfig::default()
     }
 }
 
+fn pawn_tactics_only_config() -> TacticalConfig {
+    TacticalConfig {
+        enable_forks: false,
+        enable_pins: false,
+        enable_skewers: false,
+        enable_discovered_attacks: false,
+        enable_knight_forks: false,
+        enable_back_rank_threats: false,
+        enable_pawn_tactics: true,
+        ..TacticalConfig::default()
+    }
+}
+
+/// Black pawns on every file except `open_col`, so pawn drops are only legal
+/// there (nifu)
+fn pawn_wall_except(board: &mut BitboardBoard, open_col: u8) {
+    for col in (0..9).filter(|&col| col != open_col) {
+        board.place_piece(Piece::new(PieceType::Pawn, Player::Black), Position::new(6, col));
+    }
+}
+
+fn pawns_in_hand(count: usize) -> CapturedPieces {
+    let mut captured = CapturedPieces::new();
+    for _ in 0..count {
+        captured.add_piece(PieceType::Pawn, Player::Black);
+    }
+    captured
+}
+
 #[test]
 fn forks_respect_blockers_and_line_of_sight() {
     let mut board = BitboardBoard::empty();
@@ -315,3 +349,75 @@ fn telemetry_includes_tactical_snapshot() {
         "Expected tactical telemetry to reflect recorded evaluations"
     );
 }
+
+#[test]
+fn hanging_pawn_drop_respects_nifu() {
+    let mut board = BitboardBoard::empty();
+    board.place_piece(Piece::new(PieceType::King, Player::White), Position::new(0, 4));
+    pawn_wall_except(&mut board, 0);
+
+    let mut recognizer = TacticalPatternRecognizer::with_config(pawn_tactics_only_config());
+    let open_file = recognizer.evaluate_tactics(&board, Player::Black, &pawns_in_hand(1));
+    assert!(open_file.mg > 0, "A pawn dropped on the open file threatens to promote");
+
+    board.place_piece(Piece::new(PieceType::Pawn, Player::Black), Position::new(6, 0));
+    let all_files = recognizer.evaluate_tactics(&board, Player::Black, &pawns_in_hand(1));
+    assert_eq!(all_files.mg, 0, "No pawn can be dropped once every file holds one");
+}
+
+#[test]
+fn tapping_pawn_attacks_piece_that_cannot_take_it() {
+    let mut board = BitboardBoard::empty();
+    board.place_piece(Piece::new(PieceType::King, Player::White), Position::new(0, 0));
+    pawn_wall_except(&mut board, 4);
+    let mut recognizer = TacticalPatternRecognizer::with_config(pawn_tactics_only_config());
+    let without_target = recognizer.evaluate_tactics(&board, Player::Black, &pawns_in_hand(1));
+
+    // A bishop cannot take a pawn dropped straight in front of it
+    board.place_piece(Piece::new(PieceType::Bishop, Player::White), Position::new(3, 4));
+    let tapped = recognizer.evaluate_tactics(&board, Player::Black, &pawns_in_hand(1));
+    assert!(
+        tapped.mg > without_target.mg,
+        "Tapping the bishop should add to the hanging-pawn threat ({} vs {})",
+        tapped.mg,
+        without_target.mg
+    );
+}
+
+#[test]
+fn joining_pawn_needs_pawns_to_follow_up() {
+    let mut board = BitboardBoard::empty();
+    board.place_piece(Piece::new(PieceType::King, Player::White), Position::new(0, 0));
+    pawn_wall_except(&mut board, 4);
+    // White's pawn has been drawn forward on the fifth file after an exchange
+    board.place_piece(Piece::new(PieceType::Pawn, Player::White), Position::new(4, 4));
+    board.place_piece(Piece::new(PieceType::Gold, Player::White), Position::new(3, 3));
+
+    let mut recognizer = TacticalPatternRecognizer::with_config(pawn_tactics_only_config());
+    let single = recognizer.evaluate_tactics(&board, Player::Black, &pawns_in_hand(1));
+    let several = recognizer.evaluate_tactics(&board, Player::Black, &pawns_in_hand(3));
+    assert!(
+        several.mg > single.mg,
+        "Joining pawns need pawns in hand to keep going ({} vs {})",
+        several.mg,
+        single.mg
+    );
+}
+
+#[test]
+fn pawn_sacrifice_opens_file_for_rook() {
+    let mut board = BitboardBoard::empty();
+    board.place_piece(Piece::new(PieceType::King, Player::White), Position::new(0, 4));
+    board.place_piece(Piece::new(PieceType::Pawn, Player::White), Position::new(3, 7));
+    board.place_piece(Piece::new(PieceType::Pawn, Player::Black), Position::new(5, 7));
+
+    let mut recognizer = TacticalPatternRecognizer::with_config(pawn_tactics_only_config());
+    let captured = CapturedPieces::new();
+    let unbacked = recognizer.evaluate_tactics(&board, Player::Black, &captured);
+    assert_eq!(unbacked.mg, 0, "Without a rook behind, the pawn push opens nothing");
+
+    board.place_piece(Piece::new(PieceType::Rook, Player::Black), Position::new(7, 7));
+    let backed = recognizer.evaluate_tactics(&board, Player::Black, &captured);
+    assert!(backed.mg > 0, "The pawn push opens the rook's file");
+    assert!(recognizer.stats().snapshot().pawn_tactics_found >= 1);
+}